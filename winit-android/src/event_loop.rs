@@ -1,11 +1,13 @@
 use std::cell::Cell;
-use std::fmt;
 use std::hash::Hash;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
+use std::{fmt, mem};
 
-use android_activity::input::{InputEvent, KeyAction, Keycode, MotionAction};
+use android_activity::input::{
+    ImeOptions, InputEvent, InputType, KeyAction, Keycode, MotionAction, TextInputAction,
+};
 use android_activity::{
     AndroidApp, AndroidAppWaker, ConfigurationRef, InputStatus, MainEvent, Rect,
 };
@@ -13,13 +15,15 @@ use dpi::{PhysicalInsets, PhysicalPosition, PhysicalSize, Position, Size};
 use tracing::{debug, trace, warn};
 use winit_core::application::ApplicationHandler;
 use winit_core::cursor::{Cursor, CustomCursor, CustomCursorSource};
-use winit_core::error::{EventLoopError, NotSupportedError, RequestError};
-use winit_core::event::{self, DeviceId, FingerId, Force, StartCause, SurfaceSizeWriter};
+use winit_core::error::{CreateWindowError, EventLoopError, NotSupportedError, RequestError};
+use winit_core::event::{
+    self, DeviceId, FingerId, Force, StartCause, SurfaceSizeWriter, WaitCancelReason,
+};
 use winit_core::event_loop::pump_events::PumpStatus;
 use winit_core::event_loop::{
-    ActiveEventLoop as RootActiveEventLoop, ControlFlow, DeviceEvents,
+    ActiveEventLoop as RootActiveEventLoop, Backend, ControlFlow, DeviceEvents,
     EventLoopProxy as CoreEventLoopProxy, EventLoopProxyProvider,
-    OwnedDisplayHandle as CoreOwnedDisplayHandle,
+    OwnedDisplayHandle as CoreOwnedDisplayHandle, PendingWakeUps,
 };
 use winit_core::monitor::{Fullscreen, MonitorHandle as CoreMonitorHandle};
 use winit_core::window::{
@@ -29,6 +33,7 @@ use winit_core::window::{
 };
 
 use crate::keycodes;
+use crate::lifecycle::SurfaceLifecycle;
 
 static HAS_FOCUS: AtomicBool = AtomicBool::new(true);
 
@@ -106,6 +111,9 @@ pub struct EventLoop {
     loop_running: bool, // Dispatched `NewEvents<Init>`
     running: bool,
     pending_redraw: bool,
+    lifecycle: SurfaceLifecycle,
+    /// The scale factor last reported to the application, to detect changes.
+    scale_factor: f64,
     cause: StartCause,
     primary_pointer: Option<FingerId>,
     ignore_volume_keys: bool,
@@ -150,7 +158,7 @@ impl EventLoop {
             window_target: ActiveEventLoop {
                 app: android_app.clone(),
                 control_flow: Cell::new(ControlFlow::default()),
-                exit: Cell::new(false),
+                exit: Cell::new(None),
                 redraw_requester: RedrawRequester::new(&redraw_flag, android_app.create_waker()),
                 event_loop_proxy,
             },
@@ -158,6 +166,8 @@ impl EventLoop {
             loop_running: false,
             running: false,
             pending_redraw: false,
+            lifecycle: SurfaceLifecycle::default(),
+            scale_factor: scale_factor(android_app),
             cause: StartCause::Init,
             ignore_volume_keys: attributes.ignore_volume_keys,
             combining_accent: None,
@@ -187,8 +197,12 @@ impl EventLoop {
             match event {
                 MainEvent::InitWindow { .. } => {
                     app.can_create_surfaces(&self.window_target);
+                    if let Some(surface_destroyed) = self.lifecycle.init_window() {
+                        app.resumed(self.window_target(), surface_destroyed);
+                    }
                 },
                 MainEvent::TerminateWindow { .. } => {
+                    self.lifecycle.terminate_window();
                     app.destroy_surfaces(&self.window_target);
                 },
                 MainEvent::WindowResized { .. } => resized = true,
@@ -198,24 +212,31 @@ impl EventLoop {
                 },
                 MainEvent::GainedFocus => {
                     HAS_FOCUS.store(true, Ordering::Relaxed);
-                    let event = event::WindowEvent::Focused(true);
+                    let event = event::WindowEvent::Focused {
+                        focused: true,
+                        via: event::FocusSource::Unknown,
+                    };
                     app.window_event(&self.window_target, GLOBAL_WINDOW, event);
                 },
                 MainEvent::LostFocus => {
                     HAS_FOCUS.store(false, Ordering::Relaxed);
-                    let event = event::WindowEvent::Focused(false);
+                    let event = event::WindowEvent::Focused {
+                        focused: false,
+                        via: event::FocusSource::Unknown,
+                    };
                     app.window_event(&self.window_target, GLOBAL_WINDOW, event);
                 },
                 MainEvent::ConfigChanged { .. } => {
-                    let old_scale_factor = scale_factor(&self.android_app);
                     let scale_factor = scale_factor(&self.android_app);
-                    if (scale_factor - old_scale_factor).abs() < f64::EPSILON {
+                    let old_scale_factor = mem::replace(&mut self.scale_factor, scale_factor);
+                    if (scale_factor - old_scale_factor).abs() >= f64::EPSILON {
                         let new_surface_size = Arc::new(Mutex::new(screen_size(&self.android_app)));
                         let event = event::WindowEvent::ScaleFactorChanged {
                             surface_size_writer: SurfaceSizeWriter::new(Arc::downgrade(
                                 &new_surface_size,
                             )),
                             scale_factor,
+                            old_scale_factor,
                         };
 
                         app.window_event(&self.window_target, GLOBAL_WINDOW, event);
@@ -225,7 +246,9 @@ impl EventLoop {
                     app.memory_warning(&self.window_target);
                 },
                 MainEvent::Start => {
-                    app.resumed(self.window_target());
+                    if let Some(surface_destroyed) = self.lifecycle.start() {
+                        app.resumed(self.window_target(), surface_destroyed);
+                    }
                 },
                 MainEvent::Resume { .. } => {
                     debug!("App Resumed - is running");
@@ -243,7 +266,9 @@ impl EventLoop {
                     self.running = false;
                 },
                 MainEvent::Stop => {
-                    app.suspended(self.window_target());
+                    if let Some(surface_destroyed) = self.lifecycle.stop() {
+                        app.suspended(self.window_target(), surface_destroyed);
+                    }
                 },
                 MainEvent::Destroy => {
                     // XXX: maybe exit mainloop to drop things before being
@@ -283,6 +308,9 @@ impl EventLoop {
 
         if self.window_target.event_loop_proxy.wake_up.swap(false, Ordering::Relaxed) {
             app.proxy_wake_up(&self.window_target);
+            for key in self.window_target.event_loop_proxy.pending_wake_ups.take() {
+                app.proxy_wake_up_coalesced(&self.window_target, key);
+            }
         }
 
         if self.running {
@@ -383,6 +411,7 @@ impl EventLoop {
                                     android_activity::input::ToolType::Mouse => continue,
                                     _ => event::ButtonSource::Unknown(0),
                                 },
+                                click_count: None,
                             };
                             app.window_event(&self.window_target, GLOBAL_WINDOW, event);
                         },
@@ -426,6 +455,7 @@ impl EventLoop {
                                         android_activity::input::ToolType::Mouse => continue,
                                         _ => event::ButtonSource::Unknown(0),
                                     },
+                                    click_count: None,
                                 };
                                 app.window_event(&self.window_target, GLOBAL_WINDOW, event);
                             }
@@ -491,6 +521,7 @@ impl EventLoop {
                                 text: text.clone(),
                                 text_with_all_modifiers: text,
                                 key_without_modifiers: keycodes::to_logical(key_char, keycode),
+                                composed_length: None,
                             },
                             is_synthetic: false,
                         };
@@ -514,11 +545,8 @@ impl EventLoop {
         self.window_target.clear_exit();
         loop {
             match self.pump_app_events(None, &mut app) {
-                PumpStatus::Exit(0) => {
-                    break Ok(());
-                },
                 PumpStatus::Exit(code) => {
-                    break Err(EventLoopError::ExitFailure(code));
+                    break EventLoopError::exit_result(code);
                 },
                 _ => {
                     continue;
@@ -540,6 +568,7 @@ impl EventLoop {
             // than once
             self.pending_redraw = false;
             self.cause = StartCause::Init;
+            self.window_target.clear_exit();
 
             // run the initial loop iteration
             self.single_iteration(None, &mut app);
@@ -550,10 +579,10 @@ impl EventLoop {
         if !self.exiting() {
             self.poll_events_with_timeout(timeout, &mut app);
         }
-        if self.exiting() {
+        if let Some(code) = self.window_target.exit.get() {
             self.loop_running = false;
 
-            PumpStatus::Exit(0)
+            PumpStatus::Exit(code)
         } else {
             PumpStatus::Continue
         }
@@ -575,13 +604,7 @@ impl EventLoop {
             // If we already have work to do then we don't want to block on the next poll
             Some(Duration::ZERO)
         } else {
-            let control_flow_timeout = match self.control_flow() {
-                ControlFlow::Wait => None,
-                ControlFlow::Poll => Some(Duration::ZERO),
-                ControlFlow::WaitUntil(wait_deadline) => {
-                    Some(wait_deadline.saturating_duration_since(start))
-                },
-            };
+            let control_flow_timeout = self.control_flow().timeout(start);
 
             min_timeout(control_flow_timeout, timeout)
         };
@@ -617,12 +640,20 @@ impl EventLoop {
                 },
             }
 
-            self.cause = match self.control_flow() {
-                ControlFlow::Poll => StartCause::Poll,
-                ControlFlow::Wait => StartCause::WaitCancelled { start, requested_resume: None },
+            self.cause = match self.control_flow().effective(start) {
+                ControlFlow::Poll | ControlFlow::PollUntil(_) => StartCause::Poll,
+                ControlFlow::Wait => StartCause::WaitCancelled {
+                    start,
+                    requested_resume: None,
+                    reason: WaitCancelReason::Unknown,
+                },
                 ControlFlow::WaitUntil(deadline) => {
                     if Instant::now() < deadline {
-                        StartCause::WaitCancelled { start, requested_resume: Some(deadline) }
+                        StartCause::WaitCancelled {
+                            start,
+                            requested_resume: Some(deadline),
+                            reason: WaitCancelReason::Unknown,
+                        }
                     } else {
                         StartCause::ResumeTimeReached { start, requested_resume: deadline }
                     }
@@ -644,6 +675,7 @@ impl EventLoop {
 
 pub struct EventLoopProxy {
     wake_up: AtomicBool,
+    pending_wake_ups: PendingWakeUps,
     waker: AndroidAppWaker,
 }
 
@@ -655,7 +687,7 @@ impl fmt::Debug for EventLoopProxy {
 
 impl EventLoopProxy {
     fn new(waker: AndroidAppWaker) -> Self {
-        Self { wake_up: AtomicBool::new(false), waker }
+        Self { wake_up: AtomicBool::new(false), pending_wake_ups: PendingWakeUps::new(), waker }
     }
 }
 
@@ -664,20 +696,26 @@ impl EventLoopProxyProvider for EventLoopProxy {
         self.wake_up.store(true, Ordering::Relaxed);
         self.waker.wake();
     }
+
+    fn wake_up_coalesced(&self, key: u64) {
+        if self.pending_wake_ups.insert(key) {
+            self.wake_up();
+        }
+    }
 }
 
 #[derive(Debug)]
 pub struct ActiveEventLoop {
     pub(crate) app: AndroidApp,
     control_flow: Cell<ControlFlow>,
-    exit: Cell<bool>,
+    exit: Cell<Option<i32>>,
     redraw_requester: RedrawRequester,
     event_loop_proxy: Arc<EventLoopProxy>,
 }
 
 impl ActiveEventLoop {
     fn clear_exit(&self) {
-        self.exit.set(false);
+        self.exit.set(None);
     }
 }
 
@@ -689,7 +727,8 @@ impl RootActiveEventLoop for ActiveEventLoop {
     fn create_window(
         &self,
         window_attributes: WindowAttributes,
-    ) -> Result<Box<dyn CoreWindow>, RequestError> {
+    ) -> Result<Box<dyn CoreWindow>, CreateWindowError> {
+        window_attributes.validate()?;
         Ok(Box::new(Window::new(self, window_attributes)?))
     }
 
@@ -723,11 +762,15 @@ impl RootActiveEventLoop for ActiveEventLoop {
     }
 
     fn exit(&self) {
-        self.exit.set(true)
+        self.exit.set(Some(0))
+    }
+
+    fn exit_with_code(&self, code: i32) {
+        self.exit.set(Some(code))
     }
 
     fn exiting(&self) -> bool {
-        self.exit.get()
+        self.exit.get().is_some()
     }
 
     fn owned_display_handle(&self) -> CoreOwnedDisplayHandle {
@@ -737,6 +780,10 @@ impl RootActiveEventLoop for ActiveEventLoop {
     fn rwh_06_handle(&self) -> &dyn rwh_06::HasDisplayHandle {
         self
     }
+
+    fn backend(&self) -> Option<Backend> {
+        Some(Backend::Android)
+    }
 }
 
 impl rwh_06::HasDisplayHandle for ActiveEventLoop {
@@ -788,6 +835,40 @@ impl Window {
         self.app.content_rect()
     }
 
+    /// Pick the soft keyboard for the IME purpose, only supported with `GameActivity`.
+    fn set_ime_editor_info(&self, purpose: ImePurpose) {
+        let input_type = match purpose {
+            ImePurpose::Password => {
+                InputType::TYPE_CLASS_TEXT | InputType::TYPE_TEXT_VARIATION_PASSWORD
+            },
+            ImePurpose::Terminal => {
+                InputType::TYPE_CLASS_TEXT | InputType::TYPE_TEXT_FLAG_NO_SUGGESTIONS
+            },
+            ImePurpose::Number => {
+                InputType::TYPE_CLASS_NUMBER
+                    | InputType::TYPE_NUMBER_FLAG_SIGNED
+                    | InputType::TYPE_NUMBER_FLAG_DECIMAL
+            },
+            ImePurpose::Phone => InputType::TYPE_CLASS_PHONE,
+            ImePurpose::Url => InputType::TYPE_CLASS_TEXT | InputType::TYPE_TEXT_VARIATION_URI,
+            ImePurpose::Email => {
+                InputType::TYPE_CLASS_TEXT | InputType::TYPE_TEXT_VARIATION_EMAIL_ADDRESS
+            },
+            ImePurpose::Pin => {
+                InputType::TYPE_CLASS_NUMBER | InputType::TYPE_NUMBER_VARIATION_PASSWORD
+            },
+            ImePurpose::Date => {
+                InputType::TYPE_CLASS_DATETIME | InputType::TYPE_DATETIME_VARIATION_DATE
+            },
+            ImePurpose::Time => {
+                InputType::TYPE_CLASS_DATETIME | InputType::TYPE_DATETIME_VARIATION_TIME
+            },
+            ImePurpose::DateTime => InputType::TYPE_CLASS_DATETIME,
+            _ => InputType::TYPE_CLASS_TEXT,
+        };
+        self.app.set_ime_editor_info(input_type, TextInputAction::Unspecified, ImeOptions::empty());
+    }
+
     // Allow the usage of HasRawWindowHandle inside this function
     #[allow(deprecated)]
     fn raw_window_handle_rwh_06(&self) -> Result<rwh_06::RawWindowHandle, rwh_06::HandleError> {
@@ -910,7 +991,7 @@ impl CoreWindow for Window {
     fn set_enabled_buttons(&self, _buttons: WindowButtons) {}
 
     fn enabled_buttons(&self) -> WindowButtons {
-        WindowButtons::all()
+        WindowButtons::default()
     }
 
     fn set_minimized(&self, _minimized: bool) {}
@@ -943,23 +1024,35 @@ impl CoreWindow for Window {
 
     fn set_window_icon(&self, _window_icon: Option<winit_core::icon::Icon>) {}
 
+    fn window_icon(&self) -> Option<winit_core::icon::Icon> {
+        None
+    }
+
     fn set_ime_cursor_area(&self, _position: Position, _size: Size) {}
 
     fn request_ime_update(&self, request: ImeRequest) -> Result<(), ImeRequestError> {
         let mut current_caps = self.ime_capabilities.lock().unwrap();
         match request {
             ImeRequest::Enable(enable) => {
-                let (capabilities, _) = enable.into_raw();
+                let (capabilities, request_data) = enable.into_raw();
                 if current_caps.is_some() {
                     return Err(ImeRequestError::AlreadyEnabled);
                 }
-                *current_caps = Some(capabilities);
+                // Only the purpose is applied, through the editor info.
+                *current_caps =
+                    Some(capabilities.intersection(ImeCapabilities::new().with_hint_and_purpose()));
+                if let Some((_, purpose)) = request_data.hint_and_purpose {
+                    self.set_ime_editor_info(purpose);
+                }
                 self.app.show_soft_input(true);
             },
-            ImeRequest::Update(_) => {
+            ImeRequest::Update(request_data) => {
                 if current_caps.is_none() {
                     return Err(ImeRequestError::NotEnabled);
                 }
+                if let Some((_, purpose)) = request_data.hint_and_purpose {
+                    self.set_ime_editor_info(purpose);
+                }
             },
             ImeRequest::Disable => {
                 *current_caps = None;
@@ -974,8 +1067,6 @@ impl CoreWindow for Window {
         *self.ime_capabilities.lock().unwrap()
     }
 
-    fn set_ime_purpose(&self, _purpose: ImePurpose) {}
-
     fn focus_window(&self) {}
 
     fn request_user_attention(&self, _request_type: Option<window::UserAttentionType>) {}
@@ -1015,6 +1106,10 @@ impl CoreWindow for Window {
 
     fn set_content_protected(&self, _protected: bool) {}
 
+    fn is_content_protected(&self) -> bool {
+        false
+    }
+
     fn has_focus(&self) -> bool {
         HAS_FOCUS.load(Ordering::Relaxed)
     }
@@ -73,6 +73,7 @@
 
 mod event_loop;
 mod keycodes;
+mod lifecycle;
 
 use winit_core::event_loop::ActiveEventLoop as CoreActiveEventLoop;
 use winit_core::window::Window as CoreWindow;
@@ -0,0 +1,145 @@
+use std::mem;
+
+/// Orders the surface lifecycle callbacks around the activity's lifecycle.
+///
+/// `onStart` usually comes before the native window is created, so `resumed` is deferred until
+/// `can_create_surfaces` was called, like on other platforms.
+#[derive(Debug)]
+pub(crate) struct SurfaceLifecycle {
+    /// Whether the native window exists, i.e. `can_create_surfaces` was called.
+    has_surface: bool,
+    /// `resumed` waiting for the native window, to be called after `can_create_surfaces`.
+    pending_resume: bool,
+    /// Whether the native window was destroyed since the last `resumed`.
+    surface_destroyed: bool,
+}
+
+impl Default for SurfaceLifecycle {
+    fn default() -> Self {
+        Self { has_surface: false, pending_resume: false, surface_destroyed: true }
+    }
+}
+
+impl SurfaceLifecycle {
+    /// The native window was created, `can_create_surfaces` has to be called.
+    ///
+    /// Returns `surface_destroyed` if `resumed` has to be called afterwards.
+    pub fn init_window(&mut self) -> Option<bool> {
+        self.has_surface = true;
+        self.pending_resume.then(|| self.resume())
+    }
+
+    /// The native window was destroyed, `destroy_surfaces` has to be called.
+    pub fn terminate_window(&mut self) {
+        self.has_surface = false;
+        self.surface_destroyed = true;
+    }
+
+    /// The activity was started.
+    ///
+    /// Returns `surface_destroyed` if `resumed` has to be called now.
+    pub fn start(&mut self) -> Option<bool> {
+        if self.has_surface {
+            Some(self.resume())
+        } else {
+            self.pending_resume = true;
+            None
+        }
+    }
+
+    /// The activity was stopped.
+    ///
+    /// Returns `surface_destroyed` if `suspended` has to be called, which isn't the case when the
+    /// application never saw it was resumed.
+    pub fn stop(&mut self) -> Option<bool> {
+        (!mem::take(&mut self.pending_resume)).then_some(!self.has_surface)
+    }
+
+    fn resume(&mut self) -> bool {
+        self.pending_resume = false;
+        mem::take(&mut self.surface_destroyed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy)]
+    enum MainEvent {
+        InitWindow,
+        TerminateWindow,
+        Start,
+        Stop,
+    }
+
+    /// Drive the lifecycle like the event loop does, recording the callbacks it makes.
+    fn callbacks(events: &[MainEvent]) -> Vec<String> {
+        let mut lifecycle = SurfaceLifecycle::default();
+        let mut calls = Vec::new();
+        for event in events {
+            match event {
+                MainEvent::InitWindow => {
+                    calls.push("can_create_surfaces".to_owned());
+                    if let Some(surface_destroyed) = lifecycle.init_window() {
+                        calls.push(format!("resumed({surface_destroyed})"));
+                    }
+                },
+                MainEvent::TerminateWindow => {
+                    lifecycle.terminate_window();
+                    calls.push("destroy_surfaces".to_owned());
+                },
+                MainEvent::Start => {
+                    if let Some(surface_destroyed) = lifecycle.start() {
+                        calls.push(format!("resumed({surface_destroyed})"));
+                    }
+                },
+                MainEvent::Stop => {
+                    if let Some(surface_destroyed) = lifecycle.stop() {
+                        calls.push(format!("suspended({surface_destroyed})"));
+                    }
+                },
+            }
+        }
+        calls
+    }
+
+    #[test]
+    fn resumed_waits_for_the_native_window() {
+        use MainEvent::*;
+
+        assert_eq!(callbacks(&[Start, InitWindow, Stop, TerminateWindow, Start, InitWindow]), [
+            "can_create_surfaces",
+            "resumed(true)",
+            "suspended(false)",
+            "destroy_surfaces",
+            "can_create_surfaces",
+            "resumed(true)",
+        ]);
+    }
+
+    #[test]
+    fn resumed_keeps_the_native_window() {
+        use MainEvent::*;
+
+        // The window outlives the stop, so it wasn't destroyed when resuming.
+        assert_eq!(callbacks(&[InitWindow, Start, Stop, Start, Stop, TerminateWindow]), [
+            "can_create_surfaces",
+            "resumed(true)",
+            "suspended(false)",
+            "resumed(false)",
+            "suspended(false)",
+            "destroy_surfaces",
+        ]);
+    }
+
+    #[test]
+    fn stop_before_the_native_window_skips_suspended() {
+        use MainEvent::*;
+
+        assert_eq!(callbacks(&[Start, Stop, InitWindow, TerminateWindow, Start, Stop]), [
+            "can_create_surfaces",
+            "destroy_surfaces",
+        ]);
+    }
+}
@@ -113,28 +113,23 @@ fn maybe_dispatch_device_event(app_state: &Rc<AppState>, event: &NSEvent) {
 
             if delta_x != 0.0 || delta_y != 0.0 {
                 app_state.maybe_queue_with_handler(move |app, event_loop| {
-                    app.device_event(event_loop, None, DeviceEvent::PointerMotion {
-                        delta: (delta_x, delta_y),
-                    });
+                    let event = DeviceEvent::PointerMotion { delta: (delta_x, delta_y) };
+                    app.device_event_with_window(event_loop, None, event, None);
                 });
             }
         },
         NSEventType::LeftMouseDown | NSEventType::RightMouseDown | NSEventType::OtherMouseDown => {
             let button = event.buttonNumber() as u32;
             app_state.maybe_queue_with_handler(move |app, event_loop| {
-                app.device_event(event_loop, None, DeviceEvent::Button {
-                    button,
-                    state: ElementState::Pressed,
-                });
+                let event = DeviceEvent::Button { button, state: ElementState::Pressed };
+                app.device_event_with_window(event_loop, None, event, None);
             });
         },
         NSEventType::LeftMouseUp | NSEventType::RightMouseUp | NSEventType::OtherMouseUp => {
             let button = event.buttonNumber() as u32;
             app_state.maybe_queue_with_handler(move |app, event_loop| {
-                app.device_event(event_loop, None, DeviceEvent::Button {
-                    button,
-                    state: ElementState::Released,
-                });
+                let event = DeviceEvent::Button { button, state: ElementState::Released };
+                app.device_event_with_window(event_loop, None, event, None);
             });
         },
         _ => (),
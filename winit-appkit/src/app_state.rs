@@ -1,9 +1,9 @@
 use std::cell::{Cell, OnceCell, RefCell};
 use std::collections::HashMap;
-use std::mem;
 use std::rc::Rc;
 use std::sync::Arc;
 use std::time::Instant;
+use std::{fmt, mem};
 
 use dispatch2::MainThreadBound;
 use objc2::MainThreadMarker;
@@ -16,7 +16,7 @@ use winit_common::core_foundation::{EventLoopProxy, MainRunLoop};
 use winit_common::event_handler::EventHandler;
 use winit_core::application::ApplicationHandler;
 use winit_core::data_transfer::DataTransferId;
-use winit_core::event::{StartCause, WindowEvent};
+use winit_core::event::{StartCause, WaitCancelReason, WindowEvent};
 use winit_core::event_loop::{ControlFlow, DndAction};
 use winit_core::window::WindowId;
 
@@ -46,17 +46,29 @@ pub(super) struct AppState {
     /// Whether an `EventLoop` is currently running.
     is_running: Cell<bool>,
     /// Whether the user has requested the event loop to exit.
-    exit: Cell<bool>,
+    exit: Cell<Option<i32>>,
     control_flow: Cell<ControlFlow>,
     waker: RefCell<EventLoopWaker>,
     start_time: Cell<Option<Instant>>,
     wait_timeout: Cell<Option<Instant>>,
     pending_redraw: RefCell<Vec<WindowId>>,
+    /// Events that arrived while the handler was set but before `NewEvents(Init)` was dispatched,
+    /// e.g. input that the OS queued while launching.
+    queued_before_init: RefCell<Vec<QueuedEvent>>,
     windows: RefCell<HashMap<WindowId, MainThreadBound<Weak<WindowDelegate>>>>,
     // NOTE: This is strongly referenced by our `NSWindowDelegate` and our `NSView` subclass, and
     // as such should be careful to not add fields that, in turn, strongly reference those.
 }
 
+/// An event that arrived before `NewEvents(Init)`, see [`AppState::maybe_queue_with_handler`].
+struct QueuedEvent(Box<dyn FnOnce(&mut dyn ApplicationHandler, &ActiveEventLoop)>);
+
+impl fmt::Debug for QueuedEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("QueuedEvent").finish_non_exhaustive()
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct DragState {
     pub id: DataTransferId,
@@ -76,7 +88,13 @@ impl AppState {
         activate_ignoring_other_apps: bool,
     ) -> Option<Rc<Self>> {
         let event_loop_proxy = Arc::new(EventLoopProxy::new(mtm, move || {
-            Self::get(mtm).with_handler(|app, event_loop| app.proxy_wake_up(event_loop));
+            let this = Self::get(mtm);
+            this.with_handler(|app, event_loop| {
+                app.proxy_wake_up(event_loop);
+                for key in this.event_loop_proxy.take_pending_wake_ups() {
+                    app.proxy_wake_up_coalesced(event_loop, key);
+                }
+            });
         }));
 
         let this = Rc::new(Self {
@@ -95,13 +113,14 @@ impl AppState {
             stop_on_redraw: Cell::new(false),
             is_launched: Cell::new(false),
             is_running: Cell::new(false),
-            exit: Cell::new(false),
+            exit: Cell::new(None),
             control_flow: Cell::new(ControlFlow::default()),
             waker: RefCell::new(EventLoopWaker::new()),
             start_time: Cell::new(None),
             wait_timeout: Cell::new(None),
             windows: Default::default(),
             pending_redraw: RefCell::new(vec![]),
+            queued_before_init: RefCell::new(vec![]),
         });
 
         GLOBAL.get(mtm).set(this.clone()).ok().and(Some(this))
@@ -253,15 +272,19 @@ impl AppState {
         self.is_running.get()
     }
 
-    pub fn exit(&self) {
-        self.exit.set(true)
+    pub fn exit(&self, code: i32) {
+        self.exit.set(Some(code))
     }
 
     pub fn clear_exit(&self) {
-        self.exit.set(false)
+        self.exit.set(None)
     }
 
     pub fn exiting(&self) -> bool {
+        self.exit.get().is_some()
+    }
+
+    pub fn exit_code(&self) -> Option<i32> {
         self.exit.get()
     }
 
@@ -310,7 +333,10 @@ impl AppState {
         // However, it is not documented which actions do this, and which ones are done immediately,
         // so to make sure that we don't encounter re-entrancy issues, we first check if we're
         // currently handling another event, and if we are, we queue the event instead.
-        if !self.event_handler.in_use() {
+        if self.event_handler.ready() && !self.is_running() {
+            // The application must see `NewEvents(Init)` before any other event.
+            self.queued_before_init.borrow_mut().push(QueuedEvent(Box::new(callback)));
+        } else if !self.event_handler.in_use() {
             self.with_handler(callback);
         } else {
             tracing::debug!("had to queue event since another is currently being handled");
@@ -336,6 +362,10 @@ impl AppState {
         // NB: For consistency all platforms must call `can_create_surfaces` even though macOS
         // applications don't themselves have a formal surface destroy/create lifecycle.
         self.with_handler(|app, event_loop| app.can_create_surfaces(event_loop));
+
+        for event in self.queued_before_init.take() {
+            self.with_handler(event.0);
+        }
     }
 
     // Called by RunLoopObserver after finishing waiting for new events
@@ -352,14 +382,22 @@ impl AppState {
         }
 
         let start = self.start_time.get().unwrap();
-        let cause = match self.control_flow() {
-            ControlFlow::Poll => StartCause::Poll,
-            ControlFlow::Wait => StartCause::WaitCancelled { start, requested_resume: None },
+        let cause = match self.control_flow().effective(start) {
+            ControlFlow::Poll | ControlFlow::PollUntil(_) => StartCause::Poll,
+            ControlFlow::Wait => StartCause::WaitCancelled {
+                start,
+                requested_resume: None,
+                reason: WaitCancelReason::Unknown,
+            },
             ControlFlow::WaitUntil(requested_resume) => {
                 if Instant::now() >= requested_resume {
                     StartCause::ResumeTimeReached { start, requested_resume }
                 } else {
-                    StartCause::WaitCancelled { start, requested_resume: Some(requested_resume) }
+                    StartCause::WaitCancelled {
+                        start,
+                        requested_resume: Some(requested_resume),
+                        reason: WaitCancelReason::Unknown,
+                    }
                 }
             },
         };
@@ -397,11 +435,8 @@ impl AppState {
         }
         self.start_time.set(Some(Instant::now()));
         let wait_timeout = self.wait_timeout.get(); // configured by pump_events
-        let app_timeout = match self.control_flow() {
-            ControlFlow::Wait => None,
-            ControlFlow::Poll => Some(Instant::now()),
-            ControlFlow::WaitUntil(instant) => Some(instant),
-        };
+        let now = Instant::now();
+        let app_timeout = self.control_flow().timeout(now).map(|timeout| now + timeout);
         self.waker.borrow_mut().start_at(min_timeout(wait_timeout, app_timeout));
     }
 
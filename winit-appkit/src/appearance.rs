@@ -0,0 +1,101 @@
+use std::ffi::c_void;
+use std::ptr;
+use std::rc::{Rc, Weak};
+
+use objc2::rc::Retained;
+use objc2::runtime::AnyObject;
+use objc2::{DefinedClass, MainThreadMarker, MainThreadOnly, define_class, msg_send};
+use objc2_app_kit::{NSAppearance, NSApplication};
+use objc2_foundation::{
+    NSDictionary, NSKeyValueChangeKey, NSKeyValueChangeNewKey, NSKeyValueChangeOldKey,
+    NSKeyValueObservingOptions, NSObject, NSObjectNSKeyValueObserverRegistration, NSObjectProtocol,
+    NSString, ns_string,
+};
+use tracing::{debug_span, trace};
+
+use super::app_state::AppState;
+use super::window_delegate::appearance_to_theme;
+
+define_class!(
+    /// Observes the effective appearance of the application, to report system theme changes
+    /// independently of any window.
+    #[unsafe(super(NSObject))]
+    #[thread_kind = MainThreadOnly]
+    #[name = "WinitAppearanceObserver"]
+    #[ivars = Weak<AppState>]
+    pub(crate) struct AppearanceObserver;
+
+    unsafe impl NSObjectProtocol for AppearanceObserver {}
+
+    /// Key-Value Observing
+    impl AppearanceObserver {
+        #[unsafe(method(observeValueForKeyPath:ofObject:change:context:))]
+        fn observe_value(
+            &self,
+            key_path: Option<&NSString>,
+            _object: Option<&AnyObject>,
+            change: Option<&NSDictionary<NSKeyValueChangeKey, AnyObject>>,
+            _context: *mut c_void,
+        ) {
+            let _entered = debug_span!("observeValueForKeyPath:ofObject:change:context:").entered();
+            if key_path != Some(ns_string!("effectiveAppearance")) {
+                panic!("unknown observed keypath {key_path:?}");
+            }
+
+            let change =
+                change.expect("requested a change dictionary in `addObserver`, but none was provided");
+            let old = change
+                .objectForKey(unsafe { NSKeyValueChangeOldKey })
+                .expect("requested change dictionary did not contain `NSKeyValueChangeOldKey`");
+            let new = change
+                .objectForKey(unsafe { NSKeyValueChangeNewKey })
+                .expect("requested change dictionary did not contain `NSKeyValueChangeNewKey`");
+
+            // The value of `effectiveAppearance` is `NSAppearance`
+            let old = old.downcast::<NSAppearance>().unwrap();
+            let new = new.downcast::<NSAppearance>().unwrap();
+
+            trace!(old = %old.name(), new = %new.name(), "application effectiveAppearance changed");
+
+            // The appearance may change on other parameters, such as the level of contrast.
+            let old = appearance_to_theme(&old);
+            let new = appearance_to_theme(&new);
+            if old == new {
+                return;
+            }
+
+            if let Some(app_state) = self.ivars().upgrade() {
+                app_state.maybe_queue_with_handler(move |app, event_loop| {
+                    app.system_theme_changed(event_loop, new)
+                });
+            }
+        }
+    }
+);
+
+impl AppearanceObserver {
+    /// Start observing the effective appearance of the application.
+    pub(crate) fn new(app_state: &Rc<AppState>, mtm: MainThreadMarker) -> Retained<Self> {
+        let this = Self::alloc(mtm).set_ivars(Rc::downgrade(app_state));
+        let this: Retained<Self> = unsafe { msg_send![super(this), init] };
+
+        // SAFETY: The observer is un-registered in the `Drop` of the observer.
+        unsafe {
+            NSApplication::sharedApplication(mtm).addObserver_forKeyPath_options_context(
+                &this,
+                ns_string!("effectiveAppearance"),
+                NSKeyValueObservingOptions::New | NSKeyValueObservingOptions::Old,
+                ptr::null_mut(),
+            )
+        };
+
+        this
+    }
+}
+
+impl Drop for AppearanceObserver {
+    fn drop(&mut self) {
+        let app = NSApplication::sharedApplication(self.mtm());
+        unsafe { app.removeObserver_forKeyPath(self, ns_string!("effectiveAppearance")) };
+    }
+}
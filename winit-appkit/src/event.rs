@@ -151,6 +151,7 @@ pub(crate) fn create_key_event(ns_event: &NSEvent, is_press: bool, is_repeat: bo
         text,
         text_with_all_modifiers,
         key_without_modifiers,
+        composed_length: None,
     }
 }
 
@@ -328,7 +329,7 @@ pub(super) fn event_mods(event: &NSEvent) -> Modifiers {
     pressed_mods.set(ModifiersKeys::LMETA, flags.contains(NX_DEVICELCMDKEYMASK));
     pressed_mods.set(ModifiersKeys::RMETA, flags.contains(NX_DEVICERCMDKEYMASK));
 
-    Modifiers::new(state, pressed_mods)
+    Modifiers::new(state, pressed_mods).with_other_mods_released()
 }
 
 pub(super) fn dummy_event() -> Option<Retained<NSEvent>> {
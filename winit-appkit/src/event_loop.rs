@@ -8,7 +8,7 @@ use objc2::runtime::ProtocolObject;
 use objc2::{AnyThread, MainThreadMarker, available};
 use objc2_app_kit::{
     NSApplication, NSApplicationActivationPolicy, NSApplicationDidFinishLaunchingNotification,
-    NSApplicationWillTerminateNotification, NSDraggingItem, NSWindow,
+    NSApplicationWillTerminateNotification, NSDraggingItem, NSEvent, NSWindow,
 };
 use objc2_core_foundation::{
     CFIndex, CFRunLoopActivity, CGPoint, CGRect, CGSize, kCFRunLoopCommonModes,
@@ -23,19 +23,20 @@ use winit_core::cursor::{CustomCursor as CoreCustomCursor, CustomCursorSource};
 use winit_core::data_transfer::{
     DataTransfer, DataTransferId, DataTransferSend, SendData, TransferType, TypeHint,
 };
-use winit_core::error::{EventLoopError, RequestError};
+use winit_core::error::{CreateWindowError, EventLoopError, RequestError};
 use winit_core::event::WindowEvent;
 use winit_core::event_loop::pump_events::PumpStatus;
 use winit_core::event_loop::{
-    ActiveEventLoop as RootActiveEventLoop, AsyncRequestSerial, ControlFlow, DeviceEvents,
+    ActiveEventLoop as RootActiveEventLoop, AsyncRequestSerial, Backend, ControlFlow, DeviceEvents,
     DndAction, DragIcon, EventLoopProxy as CoreEventLoopProxy,
-    OwnedDisplayHandle as CoreOwnedDisplayHandle,
+    OwnedDisplayHandle as CoreOwnedDisplayHandle, PointerConfig,
 };
 use winit_core::monitor::MonitorHandle as CoreMonitorHandle;
 use winit_core::window::{Theme, WindowId};
 
 use super::app::override_send_event;
 use super::app_state::AppState;
+use super::appearance::AppearanceObserver;
 use super::cursor::CustomCursor;
 use super::event::dummy_event;
 use super::monitor;
@@ -76,7 +77,8 @@ impl RootActiveEventLoop for ActiveEventLoop {
     fn create_window(
         &self,
         window_attributes: winit_core::window::WindowAttributes,
-    ) -> Result<Box<dyn winit_core::window::Window>, RequestError> {
+    ) -> Result<Box<dyn winit_core::window::Window>, CreateWindowError> {
+        window_attributes.validate()?;
         Ok(Box::new(Window::new(self, window_attributes)?))
     }
 
@@ -102,6 +104,12 @@ impl RootActiveEventLoop for ActiveEventLoop {
 
     fn listen_device_events(&self, _allowed: DeviceEvents) {}
 
+    fn system_pointer_config(&self) -> PointerConfig {
+        let mut config = PointerConfig::default();
+        config.double_click_interval = Duration::from_secs_f64(NSEvent::doubleClickInterval());
+        config
+    }
+
     fn system_theme(&self) -> Option<Theme> {
         let app = NSApplication::sharedApplication(self.mtm);
 
@@ -122,7 +130,11 @@ impl RootActiveEventLoop for ActiveEventLoop {
     }
 
     fn exit(&self) {
-        self.app_state.exit()
+        self.app_state.exit(0)
+    }
+
+    fn exit_with_code(&self, code: i32) {
+        self.app_state.exit(code)
     }
 
     fn exiting(&self) -> bool {
@@ -137,6 +149,10 @@ impl RootActiveEventLoop for ActiveEventLoop {
         self
     }
 
+    fn backend(&self) -> Option<Backend> {
+        Some(Backend::AppKit)
+    }
+
     fn fetch_data_transfer(
         &self,
         id: DataTransferId,
@@ -338,6 +354,9 @@ pub struct EventLoop {
     _did_finish_launching_observer: Retained<ProtocolObject<dyn NSObjectProtocol>>,
     _will_terminate_observer: Retained<ProtocolObject<dyn NSObjectProtocol>>,
 
+    /// Reports system theme changes, the dark appearance was introduced in macOS 10.14.
+    _appearance_observer: Option<Retained<AppearanceObserver>>,
+
     _tracing_observers: Option<(MainRunLoopObserver, MainRunLoopObserver)>,
     _before_waiting_observer: MainRunLoopObserver,
     _after_waiting_observer: MainRunLoopObserver,
@@ -410,6 +429,9 @@ impl EventLoop {
             },
         );
 
+        let _appearance_observer =
+            available!(macos = 10.14).then(|| AppearanceObserver::new(&app_state, mtm));
+
         let main_loop = MainRunLoop::get(mtm);
         let mode = unsafe { kCFRunLoopCommonModes }.unwrap();
 
@@ -449,6 +471,7 @@ impl EventLoop {
             window_target: ActiveEventLoop { app_state, mtm },
             _did_finish_launching_observer,
             _will_terminate_observer,
+            _appearance_observer,
             _tracing_observers,
             _before_waiting_observer,
             _after_waiting_observer,
@@ -489,7 +512,7 @@ impl EventLoop {
             })
         });
 
-        Ok(())
+        self.app_state.exit_code().map_or(Ok(()), EventLoopError::exit_result)
     }
 
     pub fn pump_app_events<A: ApplicationHandler>(
@@ -513,7 +536,9 @@ impl EventLoop {
                     // Even though the application may have been launched, it's possible we aren't
                     // running if the `EventLoop` was run before and has since
                     // exited. This indicates that we just starting to re-run
-                    // the same `EventLoop` again.
+                    // the same `EventLoop` again, the exit code of the previous run doesn't carry
+                    // over.
+                    self.app_state.clear_exit();
                     self.app_state.set_is_running(true);
                     self.app_state.dispatch_init_events();
                 } else {
@@ -540,9 +565,9 @@ impl EventLoop {
                     self.app.run();
                 }
 
-                if self.app_state.exiting() {
+                if let Some(code) = self.app_state.exit_code() {
                     self.app_state.internal_exit();
-                    PumpStatus::Exit(0)
+                    PumpStatus::Exit(code)
                 } else {
                     PumpStatus::Continue
                 }
@@ -70,6 +70,7 @@ mod util;
 
 mod app;
 mod app_state;
+mod appearance;
 mod cursor;
 mod dnd;
 mod event;
@@ -83,6 +84,7 @@ mod window;
 mod window_delegate;
 
 use std::os::raw::c_void;
+use std::path::PathBuf;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -188,6 +190,55 @@ pub trait WindowExtMacOS {
 
     /// Getter for the [`WindowExtMacOS::set_unified_titlebar`].
     fn unified_titlebar(&self) -> bool;
+
+    /// Sets the raw `NSWindow.level` of the window, for levels [`WindowLevel`] can't express.
+    ///
+    /// Common levels, from the `CGWindowLevel` constants, are:
+    ///
+    /// - `0`: `kCGNormalWindowLevel`, same as [`WindowLevel::Normal`].
+    /// - `3`: `kCGFloatingWindowLevel`, same as [`WindowLevel::AlwaysOnTop`].
+    /// - `8`: `kCGModalPanelWindowLevel`.
+    /// - `24`: `kCGMainMenuWindowLevel`.
+    /// - `25`: `kCGStatusWindowLevel`.
+    /// - `101`: `kCGPopUpMenuWindowLevel`.
+    /// - `1000`: `kCGScreenSaverWindowLevel`.
+    ///
+    /// The level is overridden by [`Window::set_window_level`], and by fullscreen transitions.
+    ///
+    /// <https://developer.apple.com/documentation/appkit/nswindow/level>
+    ///
+    /// [`WindowLevel`]: winit_core::window::WindowLevel
+    /// [`WindowLevel::Normal`]: winit_core::window::WindowLevel::Normal
+    /// [`WindowLevel::AlwaysOnTop`]: winit_core::window::WindowLevel::AlwaysOnTop
+    fn set_window_level_raw(&self, level: i64);
+
+    /// Getter for the [`WindowExtMacOS::set_window_level_raw`].
+    fn window_level_raw(&self) -> i64;
+
+    /// Sets the secondary text displayed below the window's title.
+    ///
+    /// An empty string removes the subtitle. Has no effect before macOS 11.
+    ///
+    /// <https://developer.apple.com/documentation/appkit/nswindow/subtitle>
+    fn set_subtitle(&self, subtitle: &str);
+
+    /// Getter for the [`WindowExtMacOS::set_subtitle`].
+    fn subtitle(&self) -> String;
+
+    /// Sets the file the window represents, or clears it with `None`.
+    ///
+    /// The titlebar then shows the file's icon next to the title. Users can drag that proxy icon
+    /// to move or copy the file elsewhere, e.g. into Finder or another application, and
+    /// ⌘-click the title to pop up a menu with the path to the file.
+    ///
+    /// Paths that can't be converted to a file URL, such as empty paths, clear the represented
+    /// file.
+    ///
+    /// <https://developer.apple.com/documentation/appkit/nswindow/representedurl>
+    fn set_represented_filename(&self, path: Option<PathBuf>);
+
+    /// Getter for the [`WindowExtMacOS::set_represented_filename`].
+    fn represented_filename(&self) -> Option<PathBuf>;
 }
 
 impl WindowExtMacOS for dyn Window + '_ {
@@ -298,6 +349,42 @@ impl WindowExtMacOS for dyn Window + '_ {
         let window = self.cast_ref::<AppKitWindow>().unwrap();
         window.maybe_wait_on_main(|w| w.unified_titlebar())
     }
+
+    #[inline]
+    fn set_window_level_raw(&self, level: i64) {
+        let window = self.cast_ref::<AppKitWindow>().unwrap();
+        window.maybe_wait_on_main(|w| w.set_window_level_raw(level))
+    }
+
+    #[inline]
+    fn window_level_raw(&self) -> i64 {
+        let window = self.cast_ref::<AppKitWindow>().unwrap();
+        window.maybe_wait_on_main(|w| w.window_level_raw())
+    }
+
+    #[inline]
+    fn set_subtitle(&self, subtitle: &str) {
+        let window = self.cast_ref::<AppKitWindow>().unwrap();
+        window.maybe_wait_on_main(|w| w.set_subtitle(subtitle))
+    }
+
+    #[inline]
+    fn subtitle(&self) -> String {
+        let window = self.cast_ref::<AppKitWindow>().unwrap();
+        window.maybe_wait_on_main(|w| w.subtitle())
+    }
+
+    #[inline]
+    fn set_represented_filename(&self, path: Option<PathBuf>) {
+        let window = self.cast_ref::<AppKitWindow>().unwrap();
+        window.maybe_wait_on_main(move |w| w.set_represented_filename(path))
+    }
+
+    #[inline]
+    fn represented_filename(&self) -> Option<PathBuf> {
+        let window = self.cast_ref::<AppKitWindow>().unwrap();
+        window.maybe_wait_on_main(|w| w.represented_filename())
+    }
 }
 
 /// Corresponds to `NSApplicationActivationPolicy`.
@@ -13,7 +13,8 @@ use objc2_app_kit::NSScreen;
 use objc2_core_foundation::{CFArray, CFRetained, CFUUID};
 use objc2_core_graphics::{
     CGDirectDisplayID, CGDisplayBounds, CGDisplayCopyAllDisplayModes, CGDisplayCopyDisplayMode,
-    CGDisplayMode, CGDisplayModelNumber, CGGetActiveDisplayList, CGMainDisplayID,
+    CGDisplayIsBuiltin, CGDisplayMode, CGDisplayModelNumber, CGDisplayScreenSize,
+    CGGetActiveDisplayList, CGMainDisplayID,
 };
 use objc2_core_video::{CVDisplayLink, CVTimeFlags, kCVReturnSuccess};
 use objc2_foundation::{NSNumber, NSPoint, NSRect, ns_string};
@@ -231,6 +232,17 @@ impl MonitorHandleProvider for MonitorHandle {
     fn video_modes(&self) -> Box<dyn Iterator<Item = VideoMode>> {
         Box::new(self.video_mode_handles().map(|mode| mode.mode))
     }
+
+    fn physical_size_mm(&self) -> Option<(u32, u32)> {
+        // Returns a zero size if the display is invalid or has no EDID information.
+        let size = CGDisplayScreenSize(self.display_id());
+        (size.width > 0.0 && size.height > 0.0)
+            .then(|| (size.width.round() as u32, size.height.round() as u32))
+    }
+
+    fn is_builtin(&self) -> Option<bool> {
+        Some(CGDisplayIsBuiltin(self.display_id()))
+    }
 }
 
 pub fn available_monitors() -> VecDeque<MonitorHandle> {
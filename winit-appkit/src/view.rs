@@ -13,6 +13,7 @@ use objc2_app_kit::{
     NSViewLayerContentsRedrawPolicy, NSWindow,
 };
 use objc2_core_foundation::CGRect;
+use objc2_core_graphics::{CGAssociateMouseAndMouseCursorPosition, CGWarpMouseCursorPosition};
 use objc2_foundation::{
     NSArray, NSAttributedString, NSAttributedStringKey, NSCopying, NSMutableAttributedString,
     NSNotFound, NSObject, NSPoint, NSRange, NSRect, NSSize, NSString, NSUInteger,
@@ -31,6 +32,8 @@ use super::event::{
     code_to_key, code_to_location, create_key_event, event_mods, lalt_pressed, ralt_pressed,
     scancode_to_physicalkey,
 };
+use super::monitor::flip_window_screen_coordinates;
+use super::util::cgerr;
 use super::window::window_id;
 use crate::OptionAsAlt;
 
@@ -142,6 +145,9 @@ pub struct ViewState {
 
     /// The state of the `Option` as `Alt`.
     option_as_alt: Cell<OptionAsAlt>,
+
+    /// The position of the confined cursor in the view, which moves it itself.
+    confined_cursor: Cell<Option<NSPoint>>,
 }
 
 define_class!(
@@ -280,7 +286,8 @@ define_class!(
             // Notify IME is active if application still doesn't know it.
             if self.ivars().ime_state.get() == ImeState::Disabled {
                 *self.ivars().input_source.borrow_mut() = self.current_input_source();
-                self.queue_event(WindowEvent::Ime(Ime::Enabled));
+                let capabilities = self.ime_capabilities().unwrap_or_default();
+                self.queue_event(WindowEvent::Ime(Ime::Enabled(capabilities)));
             }
 
             if self.hasMarkedText() {
@@ -309,7 +316,7 @@ define_class!(
             };
 
             // Send WindowEvent for updating marked text
-            self.queue_event(WindowEvent::Ime(Ime::Preedit(string, cursor_range)));
+            self.queue_event(WindowEvent::Ime(Ime::preedit(string, cursor_range)));
         }
 
         #[unsafe(method(unmarkText))]
@@ -390,7 +397,7 @@ define_class!(
             // Commit only if we have marked text.
             if self.hasMarkedText() && self.is_ime_enabled() && !is_control {
                 self.queue_event(WindowEvent::Ime(Ime::Preedit(String::new(), None)));
-                self.queue_event(WindowEvent::Ime(Ime::Commit(string)));
+                self.queue_event(WindowEvent::Ime(Ime::Commit(string, None)));
                 self.ivars().ime_state.set(ImeState::Committed);
             }
         }
@@ -698,12 +705,16 @@ define_class!(
                 },
             };
 
+            let momentum = !event.momentumPhase().is_empty();
+
             self.update_modifiers(event, false);
 
+            let window_id = window_id(&self.window());
             self.ivars().app_state.maybe_queue_with_handler(move |app, event_loop| {
-                app.device_event(event_loop, None, DeviceEvent::MouseWheel { delta })
+                let event = DeviceEvent::MouseWheel { delta };
+                app.device_event_with_window(event_loop, None, event, Some(window_id))
             });
-            self.queue_event(WindowEvent::MouseWheel { device_id: None, delta, phase });
+            self.queue_event(WindowEvent::MouseWheel { device_id: None, delta, phase, momentum });
         }
 
         #[unsafe(method(magnifyWithEvent:))]
@@ -810,6 +821,7 @@ impl WinitView {
             marked_text: Default::default(),
             accepts_first_mouse,
             option_as_alt: Cell::new(option_as_alt),
+            confined_cursor: Cell::new(None),
         });
         let this: Retained<Self> = unsafe { msg_send![super(this), init] };
         *this.ivars().input_source.borrow_mut() = this.current_input_source();
@@ -973,6 +985,23 @@ impl WinitView {
         *self.ivars().marked_text.borrow_mut() = NSMutableAttributedString::new();
     }
 
+    /// Drop a pending dead key.
+    ///
+    /// While IME is enabled, the text input system holds a pending dead key as the marked text of
+    /// the input context, so discarding it resets the dead key state, like clicking away from a
+    /// text field does. Without IME, the dead key state lives in the system and can't be reset.
+    pub(super) fn reset_dead_keys(&self) {
+        if let Some(input_context) = self.inputContext() {
+            input_context.discardMarkedText();
+        }
+
+        if self.ivars().ime_state.get() == ImeState::Preedit {
+            *self.ivars().marked_text.borrow_mut() = NSMutableAttributedString::new();
+            self.ivars().ime_state.set(ImeState::Ground);
+            self.queue_event(WindowEvent::Ime(Ime::Preedit(String::new(), None)));
+        }
+    }
+
     pub(super) fn ime_capabilities(&self) -> Option<ImeCapabilities> {
         self.ivars().ime_capabilities.get()
     }
@@ -1042,6 +1071,7 @@ impl WinitView {
                     text: None,
                     text_with_all_modifiers: None,
                     key_without_modifiers: logical_key.clone(),
+                    composed_length: None,
                 };
 
                 let location_mask = ModLocationMask::from_location(event.location);
@@ -1161,10 +1191,28 @@ impl WinitView {
             state: button_state,
             position,
             button: button.into(),
+            click_count: u32::try_from(event.clickCount()).ok().filter(|&count| count > 0),
         });
     }
 
     fn mouse_motion(&self, event: &NSEvent) {
+        if let Some(point) = self.ivars().confined_cursor.get() {
+            // The cursor is dissociated from the mouse, move it with the mouse deltas instead.
+            let point = self
+                .clamp_to_bounds(NSPoint::new(point.x + event.deltaX(), point.y + event.deltaY()));
+            self.ivars().confined_cursor.set(Some(point));
+            self.warp_cursor(point);
+
+            self.update_modifiers(event, false);
+            self.queue_event(WindowEvent::PointerMoved {
+                device_id: None,
+                primary: true,
+                position: LogicalPosition::new(point.x, point.y).to_physical(self.scale_factor()),
+                source: PointerSource::Mouse,
+            });
+            return;
+        }
+
         let view_point = self.mouse_view_point(event);
         let frame = self.frame();
 
@@ -1190,6 +1238,39 @@ impl WinitView {
         });
     }
 
+    /// Start or stop confining the cursor to the view, starting from the current cursor position.
+    ///
+    /// The cursor must be dissociated from the mouse while confined.
+    pub(super) fn set_cursor_confined(&self, confined: bool) {
+        let point = confined.then(|| {
+            let window_point = self.window().mouseLocationOutsideOfEventStream();
+            self.clamp_to_bounds(self.convertPoint_fromView(window_point, None))
+        });
+        self.ivars().confined_cursor.set(point);
+        if let Some(point) = point {
+            self.warp_cursor(point);
+        }
+    }
+
+    fn clamp_to_bounds(&self, point: NSPoint) -> NSPoint {
+        let size = self.frame().size;
+        NSPoint::new(point.x.clamp(0.0, size.width), point.y.clamp(0.0, size.height))
+    }
+
+    /// Move the confined cursor to a point in the view.
+    fn warp_cursor(&self, point: NSPoint) {
+        let window_point = self.convertPoint_toView(point, None);
+        let screen_point = self.window().convertPointToScreen(window_point);
+        let point = flip_window_screen_coordinates(NSRect::new(screen_point, NSSize::ZERO));
+        // Dissociating the cursor again right after warping it avoids the delay macOS adds to the
+        // mouse events following a warp.
+        let result = cgerr(CGWarpMouseCursorPosition(point))
+            .and_then(|_| cgerr(CGAssociateMouseAndMouseCursorPosition(false)));
+        if let Err(err) = result {
+            tracing::warn!("failed to move the confined cursor: {err}");
+        }
+    }
+
     fn mouse_view_point(&self, event: &NSEvent) -> LogicalPosition<f64> {
         let window_point = event.locationInWindow();
         let view_point = self.convertPoint_fromView(window_point, None);
@@ -3,7 +3,7 @@
 use std::sync::Arc;
 
 use dispatch2::MainThreadBound;
-use dpi::{Position, Size};
+use dpi::{LogicalPosition, Position, Size};
 use objc2::rc::{Retained, autoreleasepool};
 use objc2::{MainThreadMarker, Message, define_class};
 use objc2_app_kit::{NSPanel, NSResponder, NSWindow};
@@ -123,10 +123,29 @@ impl CoreWindow for Window {
         self.maybe_wait_on_main(|delegate| delegate.outer_position())
     }
 
+    fn surface_outer_position(&self) -> Result<dpi::PhysicalPosition<i32>, RequestError> {
+        self.maybe_wait_on_main(|delegate| delegate.surface_outer_position())
+    }
+
     fn set_outer_position(&self, position: Position) {
         self.maybe_wait_on_main(|delegate| delegate.set_outer_position(position));
     }
 
+    fn set_position_on_monitor(&self, monitor: &CoreMonitorHandle, position: Position) {
+        let Some(origin) = monitor.position() else {
+            tracing::warn!("Can't position window on a monitor without a known position");
+            return;
+        };
+
+        // The desktop is laid out in points, which `set_outer_position` would convert a physical
+        // position to with the window's scale factor rather than the monitor's.
+        let scale_factor = monitor.scale_factor();
+        let origin = origin.to_logical::<f64>(scale_factor);
+        let offset = position.to_logical::<f64>(scale_factor);
+        let position = LogicalPosition::new(origin.x + offset.x, origin.y + offset.y);
+        self.set_outer_position(position.into());
+    }
+
     fn surface_size(&self) -> dpi::PhysicalSize<u32> {
         self.maybe_wait_on_main(|delegate| delegate.surface_size())
     }
@@ -235,6 +254,10 @@ impl CoreWindow for Window {
         self.maybe_wait_on_main(|delegate| delegate.set_window_icon(window_icon));
     }
 
+    fn window_icon(&self) -> Option<Icon> {
+        None
+    }
+
     fn request_ime_update(&self, request: ImeRequest) -> Result<(), ImeRequestError> {
         self.maybe_wait_on_main(|delegate| delegate.request_ime_update(request))
     }
@@ -267,6 +290,10 @@ impl CoreWindow for Window {
         self.maybe_wait_on_main(|delegate| delegate.set_content_protected(protected));
     }
 
+    fn is_content_protected(&self) -> bool {
+        self.maybe_wait_on_main(|delegate| delegate.is_content_protected())
+    }
+
     fn title(&self) -> String {
         self.maybe_wait_on_main(|delegate| delegate.title())
     }
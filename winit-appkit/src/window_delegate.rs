@@ -2,6 +2,7 @@
 use std::cell::{Cell, RefCell};
 use std::collections::VecDeque;
 use std::ffi::c_void;
+use std::path::PathBuf;
 use std::ptr;
 use std::rc::Rc;
 use std::sync::{Arc, Mutex};
@@ -40,14 +41,14 @@ use objc2_foundation::{
     NSArray, NSDictionary, NSEdgeInsets, NSKeyValueChangeKey, NSKeyValueChangeNewKey,
     NSKeyValueChangeOldKey, NSKeyValueObservingOptions, NSNotificationCenter, NSObject,
     NSObjectNSDelayedPerforming, NSObjectNSKeyValueObserverRegistration, NSObjectProtocol, NSPoint,
-    NSRect, NSSize, NSString, ns_string,
+    NSRect, NSSize, NSString, NSURL, ns_string,
 };
 use tracing::{debug_span, trace, warn};
 use winit_common::core_foundation::MainRunLoop;
 use winit_core::cursor::Cursor;
 use winit_core::data_transfer::DataTransferId;
 use winit_core::error::{NotSupportedError, RequestError};
-use winit_core::event::{SurfaceSizeWriter, WindowEvent};
+use winit_core::event::{FocusSource, SurfaceSizeWriter, WindowEvent};
 use winit_core::icon::Icon;
 use winit_core::monitor::{Fullscreen, MonitorHandle as CoreMonitorHandle, MonitorHandleProvider};
 use winit_core::window::{
@@ -112,6 +113,8 @@ pub(crate) struct State {
     is_simple_fullscreen: Cell<bool>,
     saved_style: Cell<Option<NSWindowStyleMask>>,
     is_borderless_game: Cell<bool>,
+    /// The grab requested with `set_cursor_grab`, released while the window isn't focused.
+    cursor_grab: Cell<CursorGrabMode>,
 }
 
 define_class!(
@@ -178,21 +181,24 @@ define_class!(
             if scale_factor == self.ivars().previous_scale_factor.get() {
                 return;
             };
-            self.ivars().previous_scale_factor.set(scale_factor);
+            let old_scale_factor = self.ivars().previous_scale_factor.replace(scale_factor);
 
             let mtm = MainThreadMarker::from(self);
             let this = self.retain();
             MainRunLoop::get(mtm).queue_closure(move || {
-                this.handle_scale_factor_changed(scale_factor);
+                this.handle_scale_factor_changed(scale_factor, old_scale_factor);
             });
         }
 
         #[unsafe(method(windowDidBecomeKey:))]
         fn window_did_become_key(&self, _: Option<&AnyObject>) {
             let _entered = debug_span!("windowDidBecomeKey:").entered();
-            // TODO: center the cursor if the window had mouse grab when it
-            // lost focus
-            self.queue_event(WindowEvent::Focused(true));
+            if self.ivars().cursor_grab.get() != CursorGrabMode::None {
+                if let Err(err) = self.apply_cursor_grab(true) {
+                    warn!("failed to re-apply the cursor grab: {err}");
+                }
+            }
+            self.queue_event(WindowEvent::Focused { focused: true, via: FocusSource::Unknown });
         }
 
         #[unsafe(method(windowDidResignKey:))]
@@ -207,7 +213,14 @@ define_class!(
             // a synthetic ModifiersChanged event when we lose focus.
             self.view().reset_modifiers();
 
-            self.queue_event(WindowEvent::Focused(false));
+            // Give the cursor back to the other applications.
+            if self.ivars().cursor_grab.get() != CursorGrabMode::None {
+                if let Err(err) = self.apply_cursor_grab(false) {
+                    warn!("failed to release the cursor grab: {err}");
+                }
+            }
+
+            self.queue_event(WindowEvent::Focused { focused: false, via: FocusSource::Unknown });
         }
 
         /// Invoked when before enter fullscreen
@@ -946,6 +959,7 @@ impl WindowDelegate {
             is_simple_fullscreen: Cell::new(false),
             saved_style: Cell::new(None),
             is_borderless_game: Cell::new(macos_attrs.borderless_game),
+            cursor_grab: Cell::new(CursorGrabMode::None),
         });
         let delegate: Retained<WindowDelegate> = unsafe { msg_send![super(delegate), init] };
 
@@ -1039,13 +1053,14 @@ impl WindowDelegate {
         });
     }
 
-    fn handle_scale_factor_changed(&self, scale_factor: CGFloat) {
+    fn handle_scale_factor_changed(&self, scale_factor: CGFloat, old_scale_factor: CGFloat) {
         let window = self.window();
 
         let suggested_size = self.view().surface_size();
         let new_surface_size = Arc::new(Mutex::new(suggested_size));
         self.queue_event(WindowEvent::ScaleFactorChanged {
             scale_factor,
+            old_scale_factor,
             surface_size_writer: SurfaceSizeWriter::new(Arc::downgrade(&new_surface_size)),
         });
         let physical_size = *new_surface_size.lock().unwrap();
@@ -1150,6 +1165,12 @@ impl WindowDelegate {
         Ok(LogicalPosition::new(position.x, position.y).to_physical(self.scale_factor()))
     }
 
+    pub fn surface_outer_position(&self) -> Result<PhysicalPosition<i32>, RequestError> {
+        let content_rect = self.window().contentRectForFrameRect(self.window().frame());
+        let position = flip_window_screen_coordinates(content_rect);
+        Ok(LogicalPosition::new(position.x, position.y).to_physical(self.scale_factor()))
+    }
+
     pub fn surface_position(&self) -> PhysicalPosition<i32> {
         // The calculation here is a bit awkward because we've gotta reconcile the
         // different origins (Winit prefers top-left vs. NSWindow's bottom-left),
@@ -1221,6 +1242,7 @@ impl WindowDelegate {
 
     #[inline]
     pub fn request_surface_size(&self, size: Size) -> Option<PhysicalSize<u32>> {
+        let size = winit_core::window::clamp_surface_size(size);
         let scale_factor = self.scale_factor();
         let size = size.to_logical(scale_factor);
         self.window().setContentSize(NSSize::new(size.width, size.height));
@@ -1370,15 +1392,20 @@ impl WindowDelegate {
     pub fn set_cursor(&self, cursor: Cursor) {
         let view = self.view();
 
-        let cursor = match cursor {
-            Cursor::Icon(icon) => cursor_from_icon(icon),
+        // Use the first cursor in the fallback chain that can be shown.
+        let cursor = cursor.candidates().find_map(|cursor| match cursor {
+            Cursor::Icon(icon) => Some(cursor_from_icon(*icon)),
             Cursor::Custom(cursor) => match cursor.cast_ref::<CustomCursor>() {
-                Some(cursor) => cursor.0.clone(),
+                Some(cursor) => Some(cursor.0.clone()),
                 None => {
                     tracing::error!("unrecognized cursor passed to macOS backend");
-                    return;
+                    None
                 },
             },
+            Cursor::Fallback(_) => unreachable!("fallback chains are flattened"),
+        });
+        let Some(cursor) = cursor else {
+            return;
         };
 
         if view.cursor_icon() == cursor {
@@ -1391,17 +1418,19 @@ impl WindowDelegate {
 
     #[inline]
     pub fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<(), RequestError> {
-        let associate_mouse_cursor = match mode {
-            CursorGrabMode::Locked => false,
-            CursorGrabMode::None => true,
-            CursorGrabMode::Confined => {
-                return Err(NotSupportedError::new("confined cursor is not supported").into());
-            },
-        };
-
-        // TODO: Do this for real https://stackoverflow.com/a/40922095/5435443
-        cgerr(CGAssociateMouseAndMouseCursorPosition(associate_mouse_cursor))?;
-
+        self.ivars().cursor_grab.set(mode);
+        self.apply_cursor_grab(self.window().isKeyWindow())
+    }
+
+    /// Apply the requested cursor grab, or release it when the window isn't focused.
+    ///
+    /// Both grabs dissociate the cursor from the mouse, so the cursor doesn't move on its own.
+    /// A locked cursor stays in place, while a confined cursor is moved by the view with the mouse
+    /// deltas, clamped to its bounds.
+    fn apply_cursor_grab(&self, focused: bool) -> Result<(), RequestError> {
+        let mode = if focused { self.ivars().cursor_grab.get() } else { CursorGrabMode::None };
+        cgerr(CGAssociateMouseAndMouseCursorPosition(mode == CursorGrabMode::None))?;
+        self.view().set_cursor_confined(mode == CursorGrabMode::Confined);
         Ok(())
     }
 
@@ -1429,7 +1458,15 @@ impl WindowDelegate {
             y: window_position.y + cursor_position.y,
         };
         cgerr(CGWarpMouseCursorPosition(point))?;
-        cgerr(CGAssociateMouseAndMouseCursorPosition(true))?;
+        // Associating the cursor right after warping it avoids the delay macOS adds to the mouse
+        // events following a warp.
+        let grabbed = self.ivars().cursor_grab.get() != CursorGrabMode::None;
+        let grabbed = grabbed && self.window().isKeyWindow();
+        cgerr(CGAssociateMouseAndMouseCursorPosition(!grabbed))?;
+        if grabbed && self.ivars().cursor_grab.get() == CursorGrabMode::Confined {
+            // Continue confining the cursor from where it was moved.
+            self.view().set_cursor_confined(true);
+        }
 
         Ok(())
     }
@@ -1566,6 +1603,10 @@ impl WindowDelegate {
 
     #[inline]
     pub(crate) fn set_fullscreen(&self, fullscreen: Option<Fullscreen>) {
+        if !winit_core::monitor::validate_fullscreen(fullscreen.as_ref()) {
+            return;
+        }
+
         let mtm = MainThreadMarker::from(self);
         let app = NSApplication::sharedApplication(mtm);
 
@@ -1817,6 +1858,7 @@ impl WindowDelegate {
     }
 
     pub fn request_ime_update(&self, request: ImeRequest) -> Result<(), ImeRequestError> {
+        let request = request.to_physical(self.scale_factor());
         let current_caps = self.view().ime_capabilities();
         let request_data = match request {
             ImeRequest::Enable(enable) => {
@@ -1824,6 +1866,9 @@ impl WindowDelegate {
                 if current_caps.is_some() {
                     return Err(ImeRequestError::AlreadyEnabled);
                 }
+                // Only the position of the candidate window is supported.
+                let capabilities =
+                    capabilities.intersection(ImeCapabilities::new().with_cursor_area());
                 self.view().enable_ime(capabilities);
                 request_data
             },
@@ -1966,12 +2011,16 @@ impl WindowDelegate {
         })
     }
 
+    pub fn is_content_protected(&self) -> bool {
+        self.window().sharingType() == NSWindowSharingType::None
+    }
+
     pub fn title(&self) -> String {
         self.window().title().to_string()
     }
 
     pub fn reset_dead_keys(&self) {
-        // (Artur) I couldn't find a way to implement this.
+        self.view().reset_dead_keys();
     }
 }
 
@@ -2171,6 +2220,33 @@ impl WindowExtMacOS for WindowDelegate {
 
         window.toolbar().is_some() && window.toolbarStyle() == NSWindowToolbarStyle::Unified
     }
+
+    fn set_window_level_raw(&self, level: i64) {
+        self.window().setLevel(level as NSWindowLevel);
+    }
+
+    fn window_level_raw(&self) -> i64 {
+        self.window().level() as i64
+    }
+
+    fn set_subtitle(&self, subtitle: &str) {
+        if available!(macos = 11.0) {
+            self.window().setSubtitle(&NSString::from_str(subtitle));
+        }
+    }
+
+    fn subtitle(&self) -> String {
+        if available!(macos = 11.0) { self.window().subtitle().to_string() } else { String::new() }
+    }
+
+    fn set_represented_filename(&self, path: Option<PathBuf>) {
+        let url = path.and_then(NSURL::from_file_path);
+        self.window().setRepresentedURL(url.as_deref());
+    }
+
+    fn represented_filename(&self) -> Option<PathBuf> {
+        self.window().representedURL().and_then(|url| url.to_file_path())
+    }
 }
 
 const DEFAULT_STANDARD_FRAME: NSRect =
@@ -2196,9 +2272,10 @@ pub fn appearance_to_theme(appearance: &NSAppearance) -> Theme {
 }
 
 fn theme_to_appearance(theme: Option<Theme>) -> Option<Retained<NSAppearance>> {
-    let appearance = match theme? {
-        Theme::Light => unsafe { NSAppearance::appearanceNamed(NSAppearanceNameAqua) },
-        Theme::Dark => NSAppearance::appearanceNamed(dark_appearance_name()),
+    let appearance = if theme?.is_dark() {
+        NSAppearance::appearanceNamed(dark_appearance_name())
+    } else {
+        unsafe { NSAppearance::appearanceNamed(NSAppearanceNameAqua) }
     };
     if let Some(appearance) = appearance {
         Some(appearance)
@@ -1,3 +1,4 @@
+use std::collections::HashSet;
 use std::os::raw::c_void;
 use std::sync::Arc;
 
@@ -5,7 +6,7 @@ use objc2::MainThreadMarker;
 use objc2_core_foundation::{
     CFIndex, CFRetained, CFRunLoop, CFRunLoopSource, CFRunLoopSourceContext, kCFRunLoopCommonModes,
 };
-use winit_core::event_loop::EventLoopProxyProvider;
+use winit_core::event_loop::{EventLoopProxyProvider, PendingWakeUps};
 
 /// A waker that signals a `CFRunLoopSource` on the main thread.
 ///
@@ -13,11 +14,12 @@ use winit_core::event_loop::EventLoopProxyProvider;
 /// atomic around that we check on each iteration of the event loop).
 ///
 /// See <https://developer.apple.com/documentation/corefoundation/cfrunloopsource?language=objc>.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone)]
 pub struct EventLoopProxy {
     source: CFRetained<CFRunLoopSource>,
     /// Cached value of `CFRunLoopGetMain`.
     main_loop: CFRetained<CFRunLoop>,
+    pending_wake_ups: Arc<PendingWakeUps>,
 }
 
 // FIXME(madsmtm): Mark `CFRunLoopSource` + `CFRunLoop` as `Send` + `Sync`.
@@ -27,7 +29,10 @@ unsafe impl Sync for EventLoopProxy {}
 impl EventLoopProxy {
     /// Create a new proxy, registering it to be performed on the main thread.
     ///
-    /// The provided closure should call `proxy_wake_up` on the application.
+    /// The provided closure should call `proxy_wake_up` on the application, followed by
+    /// `proxy_wake_up_coalesced` for each key in [`take_pending_wake_ups()`].
+    ///
+    /// [`take_pending_wake_ups()`]: Self::take_pending_wake_ups
     pub fn new<F: Fn() + 'static>(mtm: MainThreadMarker, signaller: F) -> Self {
         // We use an `Arc` here to make sure that the reference-counting of the signal container is
         // atomic (`Retained`/`CFRetained` would be valid alternatives too).
@@ -94,7 +99,12 @@ impl EventLoopProxy {
         let main_loop = CFRunLoop::main().unwrap();
         unsafe { main_loop.add_source(Some(&source), kCFRunLoopCommonModes) };
 
-        Self { source, main_loop }
+        Self { source, main_loop, pending_wake_ups: Arc::new(PendingWakeUps::new()) }
+    }
+
+    /// Take the keys passed to `wake_up_coalesced` since this was last called.
+    pub fn take_pending_wake_ups(&self) -> HashSet<u64> {
+        self.pending_wake_ups.take()
     }
 
     // FIXME(madsmtm): Use this on macOS too.
@@ -119,4 +129,10 @@ impl EventLoopProxyProvider for EventLoopProxy {
         // main loop may be sleeping (and `CFRunLoopSourceSignal` won't wake it).
         self.main_loop.wake_up();
     }
+
+    fn wake_up_coalesced(&self, key: u64) {
+        if self.pending_wake_ups.insert(key) {
+            self.wake_up();
+        }
+    }
 }
@@ -25,7 +25,7 @@ use keymap::XkbKeymap;
 #[cfg(feature = "x11")]
 pub use keymap::raw_keycode_to_physicalkey;
 pub use keymap::{physicalkey_to_scancode, scancode_to_physicalkey};
-pub use state::XkbState;
+pub use state::{ModifiersKeysState, XkbState};
 
 // TODO: Wire this up without using a static `AtomicBool`.
 static RESET_DEAD_KEYS: AtomicBool = AtomicBool::new(false);
@@ -204,6 +204,7 @@ impl KeyContext<'_> {
             repeat,
             text_with_all_modifiers,
             key_without_modifiers,
+            composed_length: None,
         }
     }
 
@@ -4,6 +4,8 @@ use std::os::raw::c_char;
 use std::ptr::NonNull;
 
 use smol_str::SmolStr;
+use winit_core::event::{ElementState, Modifiers};
+use winit_core::keyboard::{KeyCode, ModifiersKeys, PhysicalKey};
 #[cfg(feature = "x11")]
 use x11_dl::xlib_xcb::xcb_connection_t;
 use xkbcommon_dl::{
@@ -12,7 +14,7 @@ use xkbcommon_dl::{
 
 #[cfg(feature = "x11")]
 use super::XKBXH;
-use super::keymap::XkbKeymap;
+use super::keymap::{XkbKeymap, raw_keycode_to_physicalkey};
 use super::{XKBH, make_string_with};
 
 #[derive(Debug)]
@@ -188,3 +190,76 @@ impl From<ModifiersState> for winit_core::keyboard::ModifiersState {
         to_mods
     }
 }
+
+/// The modifier keys held down, tracked from the key events.
+///
+/// XKB only reports the logical state of the modifiers, this tells which of their keys are pressed
+/// and which are released.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ModifiersKeysState {
+    pressed: ModifiersKeys,
+}
+
+impl ModifiersKeysState {
+    /// Update the pressed keys with the event of the raw X11-style `keycode`.
+    ///
+    /// Returns whether the pressed keys changed, which they don't for other keys or repeats.
+    pub fn update(&mut self, keycode: xkb_keycode_t, state: ElementState) -> bool {
+        let key = match raw_keycode_to_physicalkey(keycode) {
+            PhysicalKey::Code(KeyCode::ShiftLeft) => ModifiersKeys::LSHIFT,
+            PhysicalKey::Code(KeyCode::ShiftRight) => ModifiersKeys::RSHIFT,
+            PhysicalKey::Code(KeyCode::ControlLeft) => ModifiersKeys::LCONTROL,
+            PhysicalKey::Code(KeyCode::ControlRight) => ModifiersKeys::RCONTROL,
+            PhysicalKey::Code(KeyCode::AltLeft) => ModifiersKeys::LALT,
+            PhysicalKey::Code(KeyCode::AltRight) => ModifiersKeys::RALT,
+            PhysicalKey::Code(KeyCode::MetaLeft) => ModifiersKeys::LMETA,
+            PhysicalKey::Code(KeyCode::MetaRight) => ModifiersKeys::RMETA,
+            _ => return false,
+        };
+
+        let pressed = self.pressed;
+        self.pressed.set(key, state == ElementState::Pressed);
+        self.pressed != pressed
+    }
+
+    /// Release all the keys, e.g. when the keyboard focus is lost.
+    pub fn clear(&mut self) {
+        self.pressed = ModifiersKeys::empty();
+    }
+
+    /// The modifiers with the logical `state`, along with the state of their keys.
+    pub fn modifiers(&self, state: winit_core::keyboard::ModifiersState) -> Modifiers {
+        Modifiers::new(state, self.pressed).with_other_mods_released()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use winit_core::keyboard::{ModifiersKeyState, ModifiersState};
+
+    use super::*;
+
+    // The X11-style keycodes of the keys.
+    const SHIFT_LEFT: u32 = 42 + 8;
+    const SHIFT_RIGHT: u32 = 54 + 8;
+    const KEY_A: u32 = 30 + 8;
+
+    #[test]
+    fn modifiers_keys_follow_key_events() {
+        let mut keys = ModifiersKeysState::default();
+        assert!(!keys.update(KEY_A, ElementState::Pressed));
+
+        assert!(keys.update(SHIFT_LEFT, ElementState::Pressed));
+        assert!(!keys.update(SHIFT_LEFT, ElementState::Pressed));
+        assert!(keys.update(SHIFT_RIGHT, ElementState::Pressed));
+        assert!(keys.update(SHIFT_LEFT, ElementState::Released));
+        let mods = keys.modifiers(ModifiersState::SHIFT);
+        assert_eq!(mods.lshift_state(), ModifiersKeyState::Released);
+        assert_eq!(mods.rshift_state(), ModifiersKeyState::Pressed);
+        assert_eq!(mods.lcontrol_state(), ModifiersKeyState::Released);
+
+        keys.clear();
+        let mods = keys.modifiers(ModifiersState::empty());
+        assert_eq!(mods.rshift_state(), ModifiersKeyState::Released);
+    }
+}
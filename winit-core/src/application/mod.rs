@@ -2,7 +2,7 @@
 
 use crate::event::{DeviceEvent, DeviceId, StartCause, WindowEvent};
 use crate::event_loop::ActiveEventLoop;
-use crate::window::WindowId;
+use crate::window::{Theme, WindowId};
 
 pub mod macos;
 
@@ -14,6 +14,9 @@ pub trait ApplicationHandler {
     /// events, such as updating frame timing information for benchmarking or checking the
     /// [`StartCause`] to see if a timer set by
     /// [`ControlFlow::WaitUntil`][crate::event_loop::ControlFlow::WaitUntil] has elapsed.
+    ///
+    /// The first call of a run always has [`StartCause::Init`], before any other method of the
+    /// handler is called.
     fn new_events(&mut self, event_loop: &dyn ActiveEventLoop, cause: StartCause) {
         let _ = (event_loop, cause);
     }
@@ -22,6 +25,13 @@ pub trait ApplicationHandler {
     ///
     /// See [`suspended()`][Self::suspended].
     ///
+    /// `surface_destroyed` tells whether the native surface was destroyed since the previous
+    /// [`resumed()`], so render surfaces created before must be recreated. It's `true` for the
+    /// first [`resumed()`], and whenever a backend can't tell.
+    ///
+    /// On Android, iOS and Web, the first [`resumed()`] is preceded by [`can_create_surfaces()`].
+    /// On Android, so is the first [`resumed()`] following a [`destroy_surfaces()`].
+    ///
     /// ## Platform-specific
     ///
     /// ### iOS
@@ -30,6 +40,8 @@ pub trait ApplicationHandler {
     /// callback which means the application is about to transition from the inactive to active
     /// state (according to the [iOS application lifecycle]).
     ///
+    /// `surface_destroyed` is always `true`.
+    ///
     /// [`applicationDidBecomeActive`]: https://developer.apple.com/documentation/uikit/uiapplicationdelegate/1622956-applicationdidbecomeactive
     /// [iOS application lifecycle]: https://developer.apple.com/documentation/uikit/app_and_environment/managing_your_app_s_life_cycle
     ///
@@ -40,6 +52,8 @@ pub trait ApplicationHandler {
     /// that stores a complete snapshot of a page (including the JavaScript heap) as the user is
     /// navigating away.
     ///
+    /// The canvas is restored along with the page, so `surface_destroyed` is always `false`.
+    ///
     /// [`pageshow`]: https://developer.mozilla.org/en-US/docs/Web/API/Window/pageshow_event
     /// [`bfcache`]: https://web.dev/bfcache/
     ///
@@ -47,8 +61,13 @@ pub trait ApplicationHandler {
     ///
     /// On Android, the [`resumed()`] method is called when the `Activity` is (again, if after a
     /// prior [`suspended()`]) being displayed to the user. This is a good place to begin drawing
-    /// visual elements, running animations, etc. It is driven by Android's [`onStart()`] method.
+    /// visual elements, running animations, etc. It is driven by Android's [`onStart()`] method,
+    /// but is delayed until the native window was created when [`onStart()`] comes first.
+    ///
+    /// `surface_destroyed` is `true` if the [`NativeWindow`] was destroyed since the previous
+    /// [`resumed()`], i.e. [`destroy_surfaces()`] was called in between.
     ///
+    /// [`NativeWindow`]: https://developer.android.com/ndk/reference/group/a-native-window
     /// [`onStart()`]: https://developer.android.com/reference/android/app/Activity#onStart()
     ///
     /// ### Others
@@ -57,8 +76,10 @@ pub trait ApplicationHandler {
     ///
     /// [`resumed()`]: Self::resumed()
     /// [`suspended()`]: Self::suspended()
-    fn resumed(&mut self, event_loop: &dyn ActiveEventLoop) {
-        let _ = event_loop;
+    /// [`can_create_surfaces()`]: Self::can_create_surfaces()
+    /// [`destroy_surfaces()`]: Self::destroy_surfaces()
+    fn resumed(&mut self, event_loop: &dyn ActiveEventLoop, surface_destroyed: bool) {
+        let _ = (event_loop, surface_destroyed);
     }
 
     /// Emitted from the point onwards the application should create render surfaces.
@@ -75,6 +96,11 @@ pub trait ApplicationHandler {
     /// surface destroy/create lifecycle. For systems without a surface destroy/create lifecycle the
     /// [`can_create_surfaces()`] event is always emitted after the [`StartCause::Init`] event.
     ///
+    /// On Android, [`can_create_surfaces()`] is called exactly once before the first [`resumed()`],
+    /// and again before the next [`resumed()`] after [`destroy_surfaces()`]. iOS and Web call it
+    /// once at startup, before any [`resumed()`], and the other platforms never call
+    /// [`resumed()`].
+    ///
     /// Applications should be able to gracefully handle back-to-back [`can_create_surfaces()`] and
     /// [`destroy_surfaces()`] calls.
     ///
@@ -100,6 +126,7 @@ pub trait ApplicationHandler {
     ///
     /// [`can_create_surfaces()`]: Self::can_create_surfaces()
     /// [`destroy_surfaces()`]: Self::destroy_surfaces()
+    /// [`resumed()`]: Self::resumed()
     fn can_create_surfaces(&mut self, event_loop: &dyn ActiveEventLoop);
 
     /// Called after a wake up is requested using [`EventLoopProxy::wake_up()`].
@@ -191,6 +218,18 @@ pub trait ApplicationHandler {
         let _ = event_loop;
     }
 
+    /// Called for each key passed to [`EventLoopProxy::wake_up_coalesced()`] since the keys were
+    /// last delivered.
+    ///
+    /// Keys requested several times before being delivered are only delivered once, and keys are
+    /// delivered in no particular order. They're delivered right after [`proxy_wake_up()`].
+    ///
+    /// [`EventLoopProxy::wake_up_coalesced()`]: crate::event_loop::EventLoopProxy::wake_up_coalesced
+    /// [`proxy_wake_up()`]: Self::proxy_wake_up()
+    fn proxy_wake_up_coalesced(&mut self, event_loop: &dyn ActiveEventLoop, key: u64) {
+        let _ = (event_loop, key);
+    }
+
     /// Emitted when the OS sends an event to a winit window.
     fn window_event(
         &mut self,
@@ -199,6 +238,31 @@ pub trait ApplicationHandler {
         event: WindowEvent,
     );
 
+    /// Emitted with the events the OS sent to a winit window within a single frame.
+    ///
+    /// Backends that naturally receive events in batches deliver them with this method instead of
+    /// [`window_event`][Self::window_event], so applications implementing it can e.g. run their
+    /// layout logic once per frame. The events are in the order they would have been delivered to
+    /// `window_event` one by one.
+    ///
+    /// The default implementation forwards each event to `window_event`.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** Consecutive events of the same window sent in one iteration of the event loop are
+    ///   batched.
+    /// - **Other platforms:** Events are always delivered one by one to `window_event`.
+    fn window_events(
+        &mut self,
+        event_loop: &dyn ActiveEventLoop,
+        window_id: WindowId,
+        events: &[WindowEvent],
+    ) {
+        for event in events {
+            self.window_event(event_loop, window_id, event.clone());
+        }
+    }
+
     /// Emitted when the OS sends an event to a device.
     ///
     /// Whether device events are delivered depends on the backend in use.
@@ -211,6 +275,33 @@ pub trait ApplicationHandler {
         let _ = (event_loop, device_id, event);
     }
 
+    /// Emitted when the OS sends an event to a device, with the window it most likely targets.
+    ///
+    /// The `window_id` is the window that was focused when the event was received, and `None`
+    /// when no window was focused or the backend can't tell. It's only a hint to help applications
+    /// with multiple windows route raw input: device events aren't associated with any window, and
+    /// the focus may have changed by the time the event is delivered.
+    ///
+    /// All device events are delivered with this method, the default implementation forwards them
+    /// to [`device_event`][Self::device_event].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows / X11:** The window with the keyboard focus is reported.
+    /// - **macOS:** The window is only reported for mouse wheel events.
+    /// - **Orbital:** The window receiving the relative pointer motion is reported.
+    /// - **Wayland / Web:** The window is always `None`.
+    fn device_event_with_window(
+        &mut self,
+        event_loop: &dyn ActiveEventLoop,
+        device_id: Option<DeviceId>,
+        event: DeviceEvent,
+        window_id: Option<WindowId>,
+    ) {
+        let _ = window_id;
+        self.device_event(event_loop, device_id, event);
+    }
+
     /// Emitted when the event loop is about to block and wait for new events.
     ///
     /// Most applications shouldn't need to hook into this event since there is no real relationship
@@ -230,6 +321,10 @@ pub trait ApplicationHandler {
     ///
     /// See [`resumed()`][Self::resumed].
     ///
+    /// `surface_destroyed` tells whether the native surface was already destroyed when the
+    /// application was suspended. It's `true` whenever a backend can't tell. When it's `false`,
+    /// [`destroy_surfaces()`] may still follow while the application is suspended.
+    ///
     /// ## Platform-specific
     ///
     /// ### iOS
@@ -238,6 +333,8 @@ pub trait ApplicationHandler {
     /// [`applicationWillResignActive`] callback which means that the application is about to
     /// transition from the active to inactive state (according to the [iOS application lifecycle]).
     ///
+    /// `surface_destroyed` is always `true`.
+    ///
     /// [`applicationWillResignActive`]: https://developer.apple.com/documentation/uikit/uiapplicationdelegate/1622950-applicationwillresignactive
     /// [iOS application lifecycle]: https://developer.apple.com/documentation/uikit/app_and_environment/managing_your_app_s_life_cycle
     ///
@@ -248,6 +345,8 @@ pub trait ApplicationHandler {
     /// stores a complete snapshot of a page (including the JavaScript heap) as the user is
     /// navigating away.
     ///
+    /// The canvas is stored along with the page, so `surface_destroyed` is always `false`.
+    ///
     /// [`pagehide`]: https://developer.mozilla.org/en-US/docs/Web/API/Window/pagehide_event
     /// [`bfcache`]: https://web.dev/bfcache/
     ///
@@ -259,6 +358,9 @@ pub trait ApplicationHandler {
     ///
     /// After this event the application either receives [`resumed()`] again, or will exit.
     ///
+    /// The native window is usually destroyed right before [`onStop()`], in which case
+    /// `surface_destroyed` is `true`.
+    ///
     /// [`onStop()`]: https://developer.android.com/reference/android/app/Activity#onStop()
     ///
     /// ### Others
@@ -267,8 +369,9 @@ pub trait ApplicationHandler {
     ///
     /// [`resumed()`]: Self::resumed()
     /// [`suspended()`]: Self::suspended()
-    fn suspended(&mut self, event_loop: &dyn ActiveEventLoop) {
-        let _ = event_loop;
+    /// [`destroy_surfaces()`]: Self::destroy_surfaces()
+    fn suspended(&mut self, event_loop: &dyn ActiveEventLoop, surface_destroyed: bool) {
+        let _ = (event_loop, surface_destroyed);
     }
 
     /// Emitted when the application must destroy its render surfaces.
@@ -336,6 +439,25 @@ pub trait ApplicationHandler {
         let _ = event_loop;
     }
 
+    /// Emitted when the system theme changed.
+    ///
+    /// This is reported independently of any window, so the theme can be followed before a window
+    /// was created. [`ActiveEventLoop::system_theme`] returns the current theme. Windows which
+    /// follow the system theme additionally receive [`WindowEvent::ThemeChanged`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Read from the dark mode setting of apps and high contrast mode.
+    /// - **macOS:** Read from the application's effective appearance.
+    /// - **Web:** Read from the `prefers-color-scheme` media query.
+    /// - **Wayland / X11:** Unsupported, the freedesktop appearance portal isn't read.
+    /// - **iOS / Android / Orbital:** Unsupported.
+    ///
+    /// [`ActiveEventLoop::system_theme`]: crate::event_loop::ActiveEventLoop::system_theme
+    fn system_theme_changed(&mut self, event_loop: &dyn ActiveEventLoop, theme: Theme) {
+        let _ = (event_loop, theme);
+    }
+
     /// The macOS-specific handler.
     ///
     /// The return value from this should not change at runtime.
@@ -353,8 +475,8 @@ impl<A: ?Sized + ApplicationHandler> ApplicationHandler for &mut A {
     }
 
     #[inline]
-    fn resumed(&mut self, event_loop: &dyn ActiveEventLoop) {
-        (**self).resumed(event_loop);
+    fn resumed(&mut self, event_loop: &dyn ActiveEventLoop, surface_destroyed: bool) {
+        (**self).resumed(event_loop, surface_destroyed);
     }
 
     #[inline]
@@ -367,6 +489,11 @@ impl<A: ?Sized + ApplicationHandler> ApplicationHandler for &mut A {
         (**self).proxy_wake_up(event_loop);
     }
 
+    #[inline]
+    fn proxy_wake_up_coalesced(&mut self, event_loop: &dyn ActiveEventLoop, key: u64) {
+        (**self).proxy_wake_up_coalesced(event_loop, key);
+    }
+
     #[inline]
     fn window_event(
         &mut self,
@@ -377,6 +504,16 @@ impl<A: ?Sized + ApplicationHandler> ApplicationHandler for &mut A {
         (**self).window_event(event_loop, window_id, event);
     }
 
+    #[inline]
+    fn window_events(
+        &mut self,
+        event_loop: &dyn ActiveEventLoop,
+        window_id: WindowId,
+        events: &[WindowEvent],
+    ) {
+        (**self).window_events(event_loop, window_id, events);
+    }
+
     #[inline]
     fn device_event(
         &mut self,
@@ -387,14 +524,25 @@ impl<A: ?Sized + ApplicationHandler> ApplicationHandler for &mut A {
         (**self).device_event(event_loop, device_id, event);
     }
 
+    #[inline]
+    fn device_event_with_window(
+        &mut self,
+        event_loop: &dyn ActiveEventLoop,
+        device_id: Option<DeviceId>,
+        event: DeviceEvent,
+        window_id: Option<WindowId>,
+    ) {
+        (**self).device_event_with_window(event_loop, device_id, event, window_id);
+    }
+
     #[inline]
     fn about_to_wait(&mut self, event_loop: &dyn ActiveEventLoop) {
         (**self).about_to_wait(event_loop);
     }
 
     #[inline]
-    fn suspended(&mut self, event_loop: &dyn ActiveEventLoop) {
-        (**self).suspended(event_loop);
+    fn suspended(&mut self, event_loop: &dyn ActiveEventLoop, surface_destroyed: bool) {
+        (**self).suspended(event_loop, surface_destroyed);
     }
 
     #[inline]
@@ -407,6 +555,11 @@ impl<A: ?Sized + ApplicationHandler> ApplicationHandler for &mut A {
         (**self).memory_warning(event_loop);
     }
 
+    #[inline]
+    fn system_theme_changed(&mut self, event_loop: &dyn ActiveEventLoop, theme: Theme) {
+        (**self).system_theme_changed(event_loop, theme);
+    }
+
     #[inline]
     fn macos_handler(&mut self) -> Option<&mut dyn macos::ApplicationHandlerExtMacOS> {
         (**self).macos_handler()
@@ -421,8 +574,8 @@ impl<A: ?Sized + ApplicationHandler> ApplicationHandler for Box<A> {
     }
 
     #[inline]
-    fn resumed(&mut self, event_loop: &dyn ActiveEventLoop) {
-        (**self).resumed(event_loop);
+    fn resumed(&mut self, event_loop: &dyn ActiveEventLoop, surface_destroyed: bool) {
+        (**self).resumed(event_loop, surface_destroyed);
     }
 
     #[inline]
@@ -435,6 +588,11 @@ impl<A: ?Sized + ApplicationHandler> ApplicationHandler for Box<A> {
         (**self).proxy_wake_up(event_loop);
     }
 
+    #[inline]
+    fn proxy_wake_up_coalesced(&mut self, event_loop: &dyn ActiveEventLoop, key: u64) {
+        (**self).proxy_wake_up_coalesced(event_loop, key);
+    }
+
     #[inline]
     fn window_event(
         &mut self,
@@ -445,6 +603,16 @@ impl<A: ?Sized + ApplicationHandler> ApplicationHandler for Box<A> {
         (**self).window_event(event_loop, window_id, event);
     }
 
+    #[inline]
+    fn window_events(
+        &mut self,
+        event_loop: &dyn ActiveEventLoop,
+        window_id: WindowId,
+        events: &[WindowEvent],
+    ) {
+        (**self).window_events(event_loop, window_id, events);
+    }
+
     #[inline]
     fn device_event(
         &mut self,
@@ -455,14 +623,25 @@ impl<A: ?Sized + ApplicationHandler> ApplicationHandler for Box<A> {
         (**self).device_event(event_loop, device_id, event);
     }
 
+    #[inline]
+    fn device_event_with_window(
+        &mut self,
+        event_loop: &dyn ActiveEventLoop,
+        device_id: Option<DeviceId>,
+        event: DeviceEvent,
+        window_id: Option<WindowId>,
+    ) {
+        (**self).device_event_with_window(event_loop, device_id, event, window_id);
+    }
+
     #[inline]
     fn about_to_wait(&mut self, event_loop: &dyn ActiveEventLoop) {
         (**self).about_to_wait(event_loop);
     }
 
     #[inline]
-    fn suspended(&mut self, event_loop: &dyn ActiveEventLoop) {
-        (**self).suspended(event_loop);
+    fn suspended(&mut self, event_loop: &dyn ActiveEventLoop, surface_destroyed: bool) {
+        (**self).suspended(event_loop, surface_destroyed);
     }
 
     #[inline]
@@ -475,6 +654,11 @@ impl<A: ?Sized + ApplicationHandler> ApplicationHandler for Box<A> {
         (**self).memory_warning(event_loop);
     }
 
+    #[inline]
+    fn system_theme_changed(&mut self, event_loop: &dyn ActiveEventLoop, theme: Theme) {
+        (**self).system_theme_changed(event_loop, theme);
+    }
+
     #[inline]
     fn macos_handler(&mut self) -> Option<&mut dyn macos::ApplicationHandlerExtMacOS> {
         (**self).macos_handler()
@@ -20,6 +20,65 @@ const PIXEL_SIZE: usize = 4;
 pub enum Cursor {
     Icon(CursorIcon),
     Custom(CustomCursor),
+    /// An ordered list of cursors, where each entry is only used if none of the entries before
+    /// it can be shown.
+    ///
+    /// An empty list leaves the cursor unchanged. See [`Cursor::with_fallback`] for more details.
+    Fallback(Vec<Cursor>),
+}
+
+impl Cursor {
+    /// Creates a cursor that shows `primary`, or `fallback` if `primary` can't be shown.
+    ///
+    /// This is useful for custom cursors, which may fail to load in some environments, e.g. when
+    /// the image can't be decoded on Web, or when an icon is missing from the X11 cursor theme.
+    /// Fallback chains passed as either argument are flattened, so calls can be nested to build
+    /// longer chains.
+    ///
+    /// The final fallback should always be a named [`CursorIcon`] like [`CursorIcon::Default`],
+    /// which every backend is guaranteed to be able to show.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use winit_core::cursor::{Cursor, CursorIcon, CustomCursor};
+    /// # use winit_core::window::Window;
+    /// # fn scope(window: &dyn Window, custom_cursor: CustomCursor) {
+    /// window.set_cursor(Cursor::with_fallback(
+    ///     Cursor::with_fallback(custom_cursor, CursorIcon::Grab),
+    ///     CursorIcon::Default,
+    /// ));
+    /// # }
+    /// ```
+    pub fn with_fallback(primary: impl Into<Cursor>, fallback: impl Into<Cursor>) -> Self {
+        let mut cursors = Vec::new();
+        for cursor in [primary.into(), fallback.into()] {
+            match cursor {
+                Self::Fallback(chain) => cursors.extend(chain),
+                cursor => cursors.push(cursor),
+            }
+        }
+
+        Self::Fallback(cursors)
+    }
+
+    /// Returns the cursors to try in order of preference.
+    ///
+    /// Nested fallback chains are flattened, so this never yields a [`Cursor::Fallback`].
+    pub fn candidates(&self) -> impl Iterator<Item = &Cursor> {
+        let mut candidates = Vec::new();
+        self.push_candidates(&mut candidates);
+        candidates.into_iter()
+    }
+
+    fn push_candidates<'a>(&'a self, candidates: &mut Vec<&'a Cursor>) {
+        match self {
+            Self::Fallback(chain) => {
+                chain.iter().for_each(|cursor| cursor.push_candidates(candidates))
+            },
+            cursor => candidates.push(cursor),
+        }
+    }
 }
 
 impl Default for Cursor {
@@ -334,3 +393,24 @@ impl CursorAnimation {
         (self.duration, self.cursors)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Cursor, CursorIcon};
+
+    #[test]
+    fn fallback_chain_is_flattened() {
+        let cursor = Cursor::with_fallback(
+            Cursor::with_fallback(CursorIcon::Grab, CursorIcon::Pointer),
+            Cursor::Fallback(vec![Cursor::Fallback(vec![CursorIcon::Default.into()])]),
+        );
+
+        assert_eq!(cursor.candidates().collect::<Vec<_>>(), [
+            &Cursor::Icon(CursorIcon::Grab),
+            &Cursor::Icon(CursorIcon::Pointer),
+            &Cursor::Icon(CursorIcon::Default),
+        ]);
+        assert_eq!(Cursor::Fallback(Vec::new()).candidates().count(), 0);
+        assert_eq!(Cursor::Icon(CursorIcon::Wait).candidates().count(), 1);
+    }
+}
@@ -1,6 +1,8 @@
 use std::error::Error;
 use std::fmt::{self, Display};
 
+use crate::monitor::Fullscreen;
+
 /// A general error that may occur while running or creating
 /// the event loop.
 #[derive(Debug)]
@@ -16,6 +18,16 @@ pub enum EventLoopError {
     NotSupported(NotSupportedError),
 }
 
+impl EventLoopError {
+    /// The result of running an event loop that exited with `code`.
+    ///
+    /// A non-zero code is an [`EventLoopError::ExitFailure`].
+    #[doc(hidden)]
+    pub fn exit_result(code: i32) -> Result<(), Self> {
+        if code == 0 { Ok(()) } else { Err(Self::ExitFailure(code)) }
+    }
+}
+
 impl fmt::Display for EventLoopError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -90,6 +102,98 @@ impl From<OsError> for RequestError {
     }
 }
 
+/// An error that may occur while creating a window.
+///
+/// See [`ActiveEventLoop::create_window`].
+///
+/// [`ActiveEventLoop::create_window`]: crate::event_loop::ActiveEventLoop::create_window
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum CreateWindowError {
+    /// The parent window handle is not one of the platform.
+    UnsupportedParentWindow,
+    /// The requested surface size is zero in at least one dimension.
+    InvalidSurfaceSize,
+    /// The monitor requested for fullscreen isn't available, e.g. because it was disconnected.
+    ///
+    /// Creating the window without [`WindowAttributes::fullscreen`] may succeed.
+    ///
+    /// [`WindowAttributes::fullscreen`]: crate::window::WindowAttributes::fullscreen
+    MonitorUnavailable(Fullscreen),
+    /// Creating a window with the requested attributes is not supported.
+    NotSupported(NotSupportedError),
+    /// The request to create the window was ignored by the operating system.
+    Ignored,
+    /// Got unspecified OS specific error while creating the window.
+    Os(OsError),
+}
+
+impl Display for CreateWindowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnsupportedParentWindow => write!(f, "The parent window is not supported"),
+            Self::InvalidSurfaceSize => write!(f, "The surface size must not be zero"),
+            Self::MonitorUnavailable(fullscreen) => {
+                write!(f, "The monitor for {fullscreen:?} is not available")
+            },
+            Self::NotSupported(err) => err.fmt(f),
+            Self::Ignored => write!(f, "The request to create the window was ignored"),
+            Self::Os(err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for CreateWindowError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::NotSupported(err) => Some(err),
+            Self::Os(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<NotSupportedError> for CreateWindowError {
+    fn from(value: NotSupportedError) -> Self {
+        Self::NotSupported(value)
+    }
+}
+
+impl From<OsError> for CreateWindowError {
+    fn from(value: OsError) -> Self {
+        Self::Os(value)
+    }
+}
+
+impl From<RequestError> for CreateWindowError {
+    fn from(value: RequestError) -> Self {
+        match value {
+            RequestError::NotSupported(err) => Self::NotSupported(err),
+            RequestError::Ignored => Self::Ignored,
+            RequestError::Os(err) => Self::Os(err),
+        }
+    }
+}
+
+impl From<CreateWindowError> for RequestError {
+    fn from(value: CreateWindowError) -> Self {
+        match value {
+            CreateWindowError::UnsupportedParentWindow => {
+                Self::NotSupported(NotSupportedError::new("unsupported parent window"))
+            },
+            CreateWindowError::InvalidSurfaceSize => {
+                Self::NotSupported(NotSupportedError::new("zero surface size"))
+            },
+            CreateWindowError::MonitorUnavailable(_) => {
+                Self::NotSupported(NotSupportedError::new("fullscreen monitor is not available"))
+            },
+            CreateWindowError::NotSupported(err) => Self::NotSupported(err),
+            CreateWindowError::Ignored => Self::Ignored,
+            CreateWindowError::Os(err) => Self::Os(err),
+        }
+    }
+}
+
 /// The requested operation is not supported.
 #[derive(Debug)]
 pub struct NotSupportedError {
@@ -143,3 +247,44 @@ impl Error for OsError {
 macro_rules! os_error {
     ($error:expr) => {{ crate::error::OsError::new(line!(), file!(), $error) }};
 }
+
+#[cfg(test)]
+mod tests {
+    use dpi::{LogicalSize, PhysicalSize};
+
+    use super::*;
+    use crate::window::WindowAttributes;
+
+    #[test]
+    fn exit_code_result() {
+        assert!(EventLoopError::exit_result(0).is_ok());
+        assert!(matches!(EventLoopError::exit_result(3), Err(EventLoopError::ExitFailure(3))));
+    }
+
+    #[test]
+    fn zero_surface_size_is_invalid() {
+        for size in [PhysicalSize::new(0, 600).into(), LogicalSize::new(800.0, 0.0).into()] {
+            let attributes = WindowAttributes::default().with_surface_size::<dpi::Size>(size);
+            let err = attributes.validate().unwrap_err();
+            assert!(matches!(err, CreateWindowError::InvalidSurfaceSize), "{err:?}");
+            assert!(matches!(RequestError::from(err), RequestError::NotSupported(_)));
+        }
+
+        let attributes = WindowAttributes::default().with_surface_size(PhysicalSize::new(800, 600));
+        assert!(attributes.validate().is_ok());
+    }
+
+    #[test]
+    fn create_window_error_conversions() {
+        let err = CreateWindowError::from(RequestError::Ignored);
+        assert!(matches!(err, CreateWindowError::Ignored), "{err:?}");
+        assert!(matches!(RequestError::from(err), RequestError::Ignored));
+
+        let err = CreateWindowError::from(RequestError::Os(os_error!("failed")));
+        assert!(err.source().is_some_and(|source| source.is::<OsError>()));
+        assert!(matches!(RequestError::from(err), RequestError::Os(_)));
+
+        let err = CreateWindowError::from(NotSupportedError::new("parent window"));
+        assert!(err.source().is_some_and(|source| source.is::<NotSupportedError>()));
+    }
+}
@@ -16,7 +16,7 @@ use crate::event_loop::{AsyncRequestSerial, DndAction};
 use crate::keyboard::{self, ModifiersKeyState, ModifiersKeys, ModifiersState};
 #[cfg(doc)]
 use crate::window::Window;
-use crate::window::{ActivationToken, Theme};
+use crate::window::{ActivationToken, CursorGrabMode, ImeCapabilities, Theme};
 
 /// Describes the reason the event loop is resuming.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -29,8 +29,26 @@ pub enum StartCause {
     ResumeTimeReached { start: Instant, requested_resume: Instant },
 
     /// Sent if the OS has new events to send to the window, after a wait was requested. Contains
-    /// the moment the wait was requested and the resume time, if requested.
-    WaitCancelled { start: Instant, requested_resume: Option<Instant> },
+    /// the moment the wait was requested, the resume time, if requested, and what cancelled the
+    /// wait.
+    WaitCancelled { start: Instant, requested_resume: Option<Instant>, reason: WaitCancelReason },
+
+    /// Sent instead of [`StartCause::WaitCancelled`] if the only reason the event loop was woken
+    /// up is a call to [`Window::request_redraw`]. Contains the moment the wait was requested and
+    /// the resume time, if requested.
+    ///
+    /// This is only a hint for applications that do heavy work in
+    /// [`ApplicationHandler::new_events`] and want to skip it when only redrawing, the
+    /// [`WindowEvent::RedrawRequested`] events that follow are authoritative.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web / X11:** Supported.
+    /// - **Other platforms:** Never sent, [`StartCause::WaitCancelled`] is sent instead.
+    ///
+    /// [`Window::request_redraw`]: crate::window::Window::request_redraw
+    /// [`ApplicationHandler::new_events`]: crate::application::ApplicationHandler::new_events
+    RedrawRequested { start: Instant, requested_resume: Option<Instant> },
 
     /// Sent if the event loop is being resumed after the loop's control flow was set to
     /// [`ControlFlow::Poll`].
@@ -42,6 +60,65 @@ pub enum StartCause {
     Init,
 }
 
+/// Describes what cancelled the wait of a [`StartCause::WaitCancelled`].
+///
+/// This is a best-effort hint, applications can use it to skip work on trivial wake-ups, but the
+/// events that follow are authoritative. When several sources woke up the event loop at once, the
+/// first one of [`Self::OsEvent`], [`Self::UserWakeUp`] and [`Self::Redraw`] is reported.
+///
+/// ## Platform-specific
+///
+/// - **X11 / Wayland / Web:** Supported.
+/// - **Other platforms:** Always [`Self::Unknown`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum WaitCancelReason {
+    /// The windowing system has new events, e.g. input or window state changes.
+    OsEvent,
+    /// [`EventLoopProxy::wake_up`] was called.
+    ///
+    /// [`EventLoopProxy::wake_up`]: crate::event_loop::EventLoopProxy::wake_up
+    UserWakeUp,
+    /// A window requested a redraw.
+    ///
+    /// On platforms sending [`StartCause::RedrawRequested`], that's sent instead when a redraw is
+    /// the only reason to wake up.
+    Redraw,
+    /// The reason isn't known.
+    Unknown,
+}
+
+/// Describes how a window's focus changed, see [`WindowEvent::Focused`].
+///
+/// This is a best-effort hint, [`Self::Unknown`] is reported whenever the source can't be
+/// determined.
+///
+/// ## Platform-specific
+///
+/// - **Windows:** Reports [`Self::Pointer`] when activated by a click and [`Self::Programmatic`]
+///   for [`Window::focus_window`].
+/// - **Web:** Reports [`Self::Programmatic`] for [`Window::focus_window`], otherwise follows the
+///   browser's [`:focus-visible`] heuristics: [`Self::Keyboard`] if it would show a focus
+///   indicator, and [`Self::Pointer`] if not.
+/// - **Other platforms:** Always [`Self::Unknown`].
+///
+/// [`Window::focus_window`]: crate::window::Window::focus_window
+/// [`:focus-visible`]: https://developer.mozilla.org/en-US/docs/Web/CSS/:focus-visible
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum FocusSource {
+    /// The focus changed because of a pointer interaction, e.g. clicking into the window.
+    Pointer,
+    /// The focus changed because of keyboard navigation, e.g. pressing <kbd>Tab</kbd>.
+    Keyboard,
+    /// The application requested the focus change, e.g. with [`Window::focus_window`].
+    ///
+    /// [`Window::focus_window`]: crate::window::Window::focus_window
+    Programmatic,
+    /// The source isn't known.
+    Unknown,
+}
+
 /// Describes an event from a [`Window`].
 #[derive(Debug, Clone, PartialEq)]
 pub enum WindowEvent {
@@ -62,16 +139,55 @@ pub enum WindowEvent {
     /// The position of the window has changed.
     ///
     /// Contains the window's new position in desktop coordinates (can also be retrieved with
-    /// [`Window::outer_position`]).
+    /// [`Window::outer_position`]). Convert it with [`Window::scale_factor`] to get logical
+    /// coordinates, like [`Window::outer_position_logical`] does.
     ///
     /// ## Platform-specific
     ///
     /// - **iOS / Android / Web / Wayland:** Unsupported.
     Moved(PhysicalPosition<i32>),
 
+    /// The system started moving the window after [`Window::drag_window`].
+    ///
+    /// Followed by [`WindowEvent::MoveEnded`] once the move is done. Custom title bars can use them
+    /// to reflect the moving state. As [`Window::drag_window`] does nothing when the left mouse
+    /// button isn't pressed, this event tells whether the move actually started.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Also sent for moves started with the system title bar or menu.
+    /// - **X11:** Sent when the window manager grabs the pointer to move the window.
+    /// - **Wayland:** Sent when the compositor takes the pointer focus to move the window, also for
+    ///   moves started with the client-side decorations.
+    /// - **macOS / iOS / Android / Web / Orbital:** Unsupported.
+    ///
+    /// [`Window::drag_window`]: crate::window::Window::drag_window
+    MoveStarted,
+
+    /// The system stopped moving the window, sent after [`WindowEvent::MoveStarted`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11 / Wayland:** Neither the window manager nor the compositor report the end of a move,
+    ///   so it's inferred from the pointer coming back to the window. When the pointer is released
+    ///   outside of the window, the event is only sent once the pointer enters it again.
+    /// - **macOS / iOS / Android / Web / Orbital:** Unsupported.
+    MoveEnded,
+
     /// The window has been requested to close.
     CloseRequested,
 
+    /// The user asked for help on the window, e.g. with the [`WindowButtons::HELP`] button.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Sent for `WM_HELP`, which the system also sends when <kbd>F1</kbd> is
+    ///   pressed.
+    /// - **macOS / X11 / Wayland / iOS / Android / Web / Orbital:** Never sent.
+    ///
+    /// [`WindowButtons::HELP`]: crate::window::WindowButtons::HELP
+    HelpRequested,
+
     /// The window has been destroyed.
     Destroyed,
 
@@ -119,7 +235,12 @@ pub enum WindowEvent {
         /// the operation completes.
         proposed_action: Option<DndAction>,
     },
-    /// A drag operation has dropped file(s) on the window.
+    /// A drag operation has dropped data on the window.
+    ///
+    /// The dropped data isn't limited to files: the types it's offered in are listed by
+    /// [`DataTransfer`](crate::data_transfer::DataTransfer) and any of them can be requested with
+    /// [`crate::event_loop::ActiveEventLoop::fetch_data_transfer`]. The data transfer is only
+    /// guaranteed to be available while handling this event.
     DragDropped {
         /// ID of the data transfer object, see
         /// [`crate::event_loop::ActiveEventLoop::data_transfer`].
@@ -176,11 +297,32 @@ pub enum WindowEvent {
 
     /// The window gained or lost focus.
     ///
-    /// The parameter is true if the window has gained focus, and false if it has lost focus.
-    ///
     /// Windows are unfocused upon creation, but will usually be focused by the system soon
     /// afterwards.
-    Focused(bool),
+    Focused {
+        /// `true` if the window has gained focus, and `false` if it has lost focus.
+        focused: bool,
+        /// How the focus changed, e.g. to only draw focus indicators for keyboard navigation.
+        via: FocusSource,
+    },
+
+    /// The effective cursor grab of the window changed.
+    ///
+    /// The grab requested with [`Window::set_cursor_grab`] may be released by the system, and
+    /// restored later. This reports those transitions, e.g. so that games can pause when the
+    /// cursor is no longer confined. It's independent of [`WindowEvent::Focused`], and isn't sent
+    /// when the grab is released with [`Window::set_cursor_grab`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Sent with [`CursorGrabMode::None`] when the window is unmapped, which releases
+    ///   the grab, and with the requested mode when it's re-applied.
+    /// - **Windows:** Sent with [`CursorGrabMode::None`] when the window loses the focus, which
+    ///   releases the cursor clip, and with the requested mode when it gets the focus back.
+    /// - **Wayland:** Sent when the compositor activates or deactivates the pointer lock or
+    ///   confinement, including when it's first activated after [`Window::set_cursor_grab`].
+    /// - **macOS / iOS / Android / Web / Orbital:** Unsupported.
+    CursorGrabChanged(CursorGrabMode),
 
     /// An event from the keyboard has been received.
     ///
@@ -303,7 +445,25 @@ pub enum WindowEvent {
     },
 
     /// A mouse wheel movement or touchpad scroll occurred.
-    MouseWheel { device_id: Option<DeviceId>, delta: MouseScrollDelta, phase: TouchPhase },
+    MouseWheel {
+        device_id: Option<DeviceId>,
+        delta: MouseScrollDelta,
+        phase: TouchPhase,
+
+        /// Whether the scroll is generated by the system to continue a touchpad scroll with
+        /// inertia, after the fingers were lifted.
+        ///
+        /// Applications can use it to stop the momentum scrolling when the user touches the
+        /// touchpad again, while the [`phase`](WindowEvent::MouseWheel::phase) tracks both the
+        /// direct and the momentum scrolling.
+        ///
+        /// ## Platform-specific
+        ///
+        /// - **macOS:** Set from `NSEvent.momentumPhase`.
+        /// - **Other platforms:** Always `false`, inertial scrolling is either not reported
+        ///   separately or left to the application.
+        momentum: bool,
+    },
 
     /// An mouse button press has been received.
     PointerButton {
@@ -329,6 +489,21 @@ pub enum WindowEvent {
         primary: bool,
 
         button: ButtonSource,
+
+        /// The number of consecutive clicks of the button, counted by the system with its own
+        /// double-click interval and distance, e.g. `2` for the press of a double-click.
+        ///
+        /// `None` means the count isn't reported and the application should do its own timing,
+        /// e.g. with [`ActiveEventLoop::system_pointer_config`].
+        ///
+        /// ## Platform-specific
+        ///
+        /// - **macOS:** Set from `NSEvent.clickCount`.
+        /// - **Windows:** Set for mouse buttons, up to `2` for a double-click.
+        /// - **Other platforms:** Always `None`.
+        ///
+        /// [`ActiveEventLoop::system_pointer_config`]: crate::event_loop::ActiveEventLoop::system_pointer_config
+        click_count: Option<u32>,
     },
 
     /// Multi-finger hold gesture on the touchpad or touchscreen without movement.
@@ -437,13 +612,22 @@ pub enum WindowEvent {
     /// window is resized to the value suggested by the OS, but it can be changed to any value.
     ///
     /// This event will not necessarily be emitted upon window creation, query
-    /// [`Window::scale_factor`] if you need to determine the window's initial scale factor.
+    /// [`Window::scale_factor`] if you need to determine the window's initial scale factor, or
+    /// create the window with [`WindowAttributes::with_initial_scale_event`] to always receive it
+    /// right before the first [`WindowEvent::SurfaceResized`].
     ///
     /// For more information about DPI in general, see the [`dpi`] crate.
     ///
     /// [`Window::scale_factor`]: crate::window::Window::scale_factor
+    /// [`WindowAttributes::with_initial_scale_event`]: crate::window::WindowAttributes::with_initial_scale_event
     ScaleFactorChanged {
+        /// The new scale factor.
         scale_factor: f64,
+        /// The scale factor before the change.
+        ///
+        /// This is the same as `scale_factor` for the event requested with
+        /// [`WindowAttributes::with_initial_scale_event`].
+        old_scale_factor: f64,
         /// Handle to update surface size during scale changes.
         ///
         /// See [`SurfaceSizeWriter`] docs for more details.
@@ -459,6 +643,8 @@ pub enum WindowEvent {
     ///
     /// ## Platform-specific
     ///
+    /// - **Windows:** Also reported when a high contrast theme is turned on or off, see
+    ///   [`Theme::HighContrastDark`].
     /// - **iOS / Android / X11 / Wayland / Orbital:** Unsupported.
     ThemeChanged(Theme),
 
@@ -491,6 +677,32 @@ pub enum WindowEvent {
     /// [`transform`]: https://developer.mozilla.org/en-US/docs/Web/CSS/transform
     Occluded(bool),
 
+    /// The window was maximized, or restored from being maximized.
+    ///
+    /// This is reported both when the user changes the state, e.g. with the window decorations,
+    /// and after [`Window::set_maximized`], so custom decorations can keep their maximize button
+    /// in sync.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Reported when the window manager changes `_NET_WM_STATE`, a window counts as
+    ///   maximized when it's maximized on both axes.
+    /// - **Windows:** Reported from the size type of `WM_SIZE`. A maximized window stays maximized
+    ///   while it's minimized, so no change is reported for it.
+    /// - **Wayland:** Reported with the configure that changes the state.
+    /// - **macOS / iOS / Android / Web / Orbital:** Unsupported.
+    MaximizedChanged(bool),
+
+    /// The window was minimized, or restored from being minimized.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Reported when the window manager changes `_NET_WM_STATE`, which not all of them
+    ///   do for minimized windows.
+    /// - **Windows:** Reported from the size type of `WM_SIZE`.
+    /// - **Wayland / macOS / iOS / Android / Web / Orbital:** Unsupported.
+    MinimizedChanged(bool),
+
     /// Emitted when a window should be redrawn.
     ///
     /// This gets triggered in a few scenarios:
@@ -657,23 +869,76 @@ impl From<MouseButton> for ButtonSource {
 /// Whenever you receive an event arising from a particular input device, this event contains a
 /// `DeviceId` which identifies its origin. Note that devices may be virtual (representing an
 /// on-screen cursor and keyboard focus) or physical. Virtual devices typically aggregate inputs
-/// from multiple physical devices.
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct DeviceId(i64);
+/// from multiple physical devices, see [`DeviceId::is_virtual`].
+///
+/// An id stays the same while the device is connected, but may be reused for another device after
+/// it's removed. Only the underlying integer identifies the device, so ids compare equal regardless
+/// of whether they know if the device is virtual.
+#[derive(Debug, Copy, Clone)]
+pub struct DeviceId {
+    id: i64,
+    is_virtual: Option<bool>,
+}
 
 impl DeviceId {
     /// Convert the [`DeviceId`] into the underlying integer.
     ///
     /// This is useful if you need to pass the ID across an FFI boundary, or store it in an atomic.
     pub const fn into_raw(self) -> i64 {
-        self.0
+        self.id
     }
 
     /// Construct a [`DeviceId`] from the underlying integer.
     ///
-    /// This should only be called with integers returned from [`DeviceId::into_raw`].
+    /// This should only be called with integers returned from [`DeviceId::into_raw`]. Whether the
+    /// device is virtual is unknown for the returned id.
     pub const fn from_raw(id: i64) -> Self {
-        Self(id)
+        Self { id, is_virtual: None }
+    }
+
+    /// Record whether the device is virtual, for backends translating events.
+    pub const fn with_virtual(self, is_virtual: bool) -> Self {
+        Self { id: self.id, is_virtual: Some(is_virtual) }
+    }
+
+    /// Whether the device is virtual, aggregating the input of physical devices, like the
+    /// on-screen pointer moved by all mice.
+    ///
+    /// Returns `None` when it's unknown.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** XInput2 master devices are virtual, slave devices are physical.
+    /// - **Windows:** Raw input devices are physical.
+    /// - **Wayland / macOS / iOS / Android / Web / Orbital:** Always returns `None`.
+    pub const fn is_virtual(&self) -> Option<bool> {
+        self.is_virtual
+    }
+}
+
+impl PartialEq for DeviceId {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for DeviceId {}
+
+impl PartialOrd for DeviceId {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for DeviceId {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.id.cmp(&other.id)
+    }
+}
+
+impl std::hash::Hash for DeviceId {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
     }
 }
 
@@ -741,6 +1006,24 @@ pub enum DeviceEvent {
     },
 
     Key(RawKeyEvent),
+
+    /// The device has been connected.
+    ///
+    /// This is a raw input device notification, and is not sent for monitors being connected.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Sent for both master and slave XInput2 devices.
+    /// - **Windows:** Not sent when device events are disabled with [`DeviceEvents::Never`].
+    /// - **macOS / Wayland / iOS / Android / Web / Orbital:** Unsupported.
+    ///
+    /// [`DeviceEvents::Never`]: crate::event_loop::DeviceEvents::Never
+    Added,
+
+    /// The device has been disconnected.
+    ///
+    /// See [`DeviceEvent::Added`] for details.
+    Removed,
 }
 
 /// Describes a keyboard input as a raw device event.
@@ -750,6 +1033,12 @@ pub enum DeviceEvent {
 /// repeat or the initial keypress. An application may emulate this by, for
 /// example keeping a Map/Set of pressed keys and determining whether a keypress
 /// corresponds to an already pressed key.
+///
+/// Whether held keys repeat depends on the platform, see
+/// [`ActiveEventLoop::set_device_key_repeat`] to request repeats where they aren't reported by
+/// default.
+///
+/// [`ActiveEventLoop::set_device_key_repeat`]: crate::event_loop::ActiveEventLoop::set_device_key_repeat
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct RawKeyEvent {
@@ -759,6 +1048,7 @@ pub struct RawKeyEvent {
 
 /// Describes a keyboard input targeting a window.
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct KeyEvent {
     /// Represents the position of a key independent of the currently active layout.
     ///
@@ -907,6 +1197,22 @@ pub struct KeyEvent {
     /// - **iOS:** Unimplemented, this field is always the same value as `logical_key`.
     /// - **Web:** Unsupported, this field is always the same value as `logical_key`.
     pub key_without_modifiers: keyboard::Key,
+
+    /// The number of code points of pending dead-key input that [`text`][Self::text] commits.
+    ///
+    /// When a dead key was pressed before this keypress, its character is committed together with
+    /// this event's text, either combined with it (e.g. `´` followed by `e` produces `"é"`) or as
+    /// the two-character fallback described on [`text`][Self::text] (e.g. `´` followed by `x`
+    /// produces `"´x"`). In both cases this is `Some(1)`, so text widgets can tell what the
+    /// composition consumed without guessing from the text.
+    ///
+    /// This is `None` when no dead-key input was pending.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Reported when a dead key was pending.
+    /// - **Others:** Unsupported, this field is always `None`.
+    pub composed_length: Option<usize>,
 }
 
 /// Describes keyboard modifiers event.
@@ -919,12 +1225,46 @@ pub struct Modifiers {
     //
     // The field providing a metadata, it shouldn't be used as a source of truth.
     pub(crate) pressed_mods: ModifiersKeys,
+
+    // NOTE: Modifiers keys known to be released, the state of the keys in neither set is unknown.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) released_mods: ModifiersKeys,
 }
 
 impl Modifiers {
     /// Create a new modifiers from state and pressed mods.
+    ///
+    /// The state of the keys that aren't pressed is unknown, see [`Self::with_released_mods`].
     pub fn new(state: ModifiersState, pressed_mods: ModifiersKeys) -> Self {
-        Self { state, pressed_mods }
+        Self { state, pressed_mods, released_mods: ModifiersKeys::empty() }
+    }
+
+    /// Set the modifiers keys known to be released.
+    ///
+    /// Keys that are also pressed are reported as [`ModifiersKeyState::Pressed`].
+    pub fn with_released_mods(mut self, released_mods: ModifiersKeys) -> Self {
+        self.released_mods = released_mods;
+        self
+    }
+
+    /// Mark all modifiers keys that aren't pressed as released.
+    ///
+    /// Only correct when the state of every modifier key is tracked. The keys of a modifier that
+    /// is active without any of its keys pressed, e.g. a sticky modifier, stay unknown.
+    #[doc(hidden)]
+    pub fn with_other_mods_released(mut self) -> Self {
+        self.released_mods = !self.pressed_mods;
+        for (modifier, keys) in [
+            (ModifiersState::SHIFT, ModifiersKeys::LSHIFT | ModifiersKeys::RSHIFT),
+            (ModifiersState::CONTROL, ModifiersKeys::LCONTROL | ModifiersKeys::RCONTROL),
+            (ModifiersState::ALT, ModifiersKeys::LALT | ModifiersKeys::RALT),
+            (ModifiersState::META, ModifiersKeys::LMETA | ModifiersKeys::RMETA),
+        ] {
+            if self.state.contains(modifier) && !self.pressed_mods.intersects(keys) {
+                self.released_mods.remove(keys);
+            }
+        }
+        self
     }
 
     /// The logical state of the modifiers.
@@ -975,6 +1315,8 @@ impl Modifiers {
     fn mod_state(&self, modifier: ModifiersKeys) -> ModifiersKeyState {
         if self.pressed_mods.contains(modifier) {
             ModifiersKeyState::Pressed
+        } else if self.released_mods.contains(modifier) {
+            ModifiersKeyState::Released
         } else {
             ModifiersKeyState::Unknown
         }
@@ -983,7 +1325,7 @@ impl Modifiers {
 
 impl From<ModifiersState> for Modifiers {
     fn from(value: ModifiersState) -> Self {
-        Self { state: value, pressed_mods: Default::default() }
+        Self::new(value, Default::default())
     }
 }
 
@@ -1008,7 +1350,7 @@ impl From<ModifiersState> for Modifiers {
 /// Ime::Preedit("`", Some((0, 0)))
 /// // Press "E" key
 /// Ime::Preedit("", None) // Synthetic event generated by winit to clear preedit.
-/// Ime::Commit("é")
+/// Ime::Commit("é", None)
 /// ```
 ///
 /// Additionally, certain input devices are configured to display a candidate box that allow the
@@ -1029,7 +1371,7 @@ impl From<ModifiersState> for Modifiers {
 /// Ime::Preedit("啊b", Some((3, 3)))
 /// // Press space key
 /// Ime::Preedit("", None) // Synthetic event generated by winit to clear preedit.
-/// Ime::Commit("啊不")
+/// Ime::Commit("啊不", None)
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -1039,7 +1381,15 @@ pub enum Ime {
     /// After getting this event you could receive [`Preedit`][Self::Preedit] and
     /// [`Commit`][Self::Commit] events. You should also start performing IME related requests
     /// like [`Window::set_ime_cursor_area`].
-    Enabled,
+    ///
+    /// The value is the intersection of the capabilities requested with
+    /// [`ImeRequest::Enable`] and those the backend supports, the same as
+    /// [`Window::ime_capabilities`]. Data for other capabilities is ignored, so it doesn't need
+    /// to be sent.
+    ///
+    /// [`ImeRequest::Enable`]: crate::window::ImeRequest::Enable
+    /// [`Window::ime_capabilities`]: crate::window::Window::ime_capabilities
+    Enabled(ImeCapabilities),
 
     /// Notifies when a new composing text should be set at the cursor position.
     ///
@@ -1047,13 +1397,29 @@ pub enum Ime {
     /// position. When it's `None`, the cursor should be hidden. When `String` is an empty string
     /// this indicates that preedit was cleared.
     ///
-    /// The cursor position is byte-wise indexed, assuming UTF-8.
+    /// The cursor position is byte-wise indexed, assuming UTF-8. Both offsets are on `char`
+    /// boundaries of the preedit string, so it's safe to slice the string with them.
     Preedit(String, Option<(usize, usize)>),
 
     /// Notifies when text should be inserted into the editor widget.
     ///
+    /// The value represents a pair of the committed string and the range of the surrounding text
+    /// the string replaces. When the range is `None`, the text should be inserted at the cursor
+    /// position. Otherwise, the text between the range begin and end should be replaced with the
+    /// committed string.
+    ///
+    /// The range is byte-wise indexed into the surrounding text set with
+    /// [`ImeRequestData::with_surrounding_text`], assuming UTF-8.
+    ///
     /// Right before this event winit will send empty [`Self::Preedit`] event.
-    Commit(String),
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** The range is reported when the text replaces the current selection.
+    /// - **Others:** The range is always `None`.
+    ///
+    /// [`ImeRequestData::with_surrounding_text`]: crate::window::ImeRequestData::with_surrounding_text
+    Commit(String, Option<(usize, usize)>),
 
     /// Delete text surrounding the cursor or selection.
     ///
@@ -1078,6 +1444,37 @@ pub enum Ime {
     Disabled,
 }
 
+impl Ime {
+    /// Create a [`Preedit`][Self::Preedit] event, moving cursor offsets that aren't on a `char`
+    /// boundary of `text` back to the previous one.
+    ///
+    /// Input methods are known to report offsets in the middle of a code point, which would make
+    /// slicing the preedit string panic.
+    #[doc(hidden)]
+    pub fn preedit(text: String, cursor_range: Option<(usize, usize)>) -> Self {
+        let cursor_range = cursor_range.map(|(begin, end)| {
+            let clamped = (floor_char_boundary(&text, begin), floor_char_boundary(&text, end));
+            if clamped != (begin, end) {
+                tracing::warn!(
+                    "IME preedit cursor {:?} isn't on char boundaries of {text:?}, using \
+                     {clamped:?}",
+                    (begin, end),
+                );
+            }
+            clamped
+        });
+        Self::Preedit(text, cursor_range)
+    }
+}
+
+/// The largest `char` boundary of `text` that isn't past `index`.
+fn floor_char_boundary(text: &str, index: usize) -> usize {
+    if index >= text.len() {
+        return text.len();
+    }
+    (0..=index).rev().find(|&index| text.is_char_boundary(index)).unwrap_or(0)
+}
+
 /// Describes touch-screen input state.
 #[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -1437,6 +1834,19 @@ impl ElementState {
 /// - **macOS:** Supports all button variants.
 /// - **X11:** Technically supports further buttons than this (0..=250), these are emitted in
 ///   `ButtonSource::Unknown`.
+///
+/// The platform button numbers are already mapped to these variants by the backends, so the side
+/// buttons are reported as [`Self::Back`] and [`Self::Forward`] everywhere:
+///
+/// | Platform | [`Self::Back`]        | [`Self::Forward`]     | Further buttons                 |
+/// | -------- | --------------------- | --------------------- | ------------------------------- |
+/// | X11      | button 8              | button 9              | buttons 10..=36, then `Unknown` |
+/// | Wayland  | `BTN_SIDE`            | `BTN_EXTRA`           | up to `BTN_JOYSTICK`            |
+/// | Windows  | `XBUTTON1`            | `XBUTTON2`            | none                            |
+/// | macOS    | `buttonNumber` 3      | `buttonNumber` 4      | `buttonNumber` 5..=31           |
+/// | Web      | `MouseEvent.button` 3 | `MouseEvent.button` 4 | `Unknown`                       |
+///
+/// Only button codes without a known meaning are left in [`ButtonSource::Unknown`].
 #[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[repr(u8)]
@@ -1581,6 +1991,31 @@ pub enum MouseScrollDelta {
     PixelDelta(PhysicalPosition<f64>),
 }
 
+impl MouseScrollDelta {
+    /// Returns the delta in pixels, converting lines with the given line height in pixels.
+    ///
+    /// The sign is preserved, so positive values always mean that the content should move right
+    /// and down, whatever the variant.
+    pub fn to_pixels(&self, line_height: f64) -> PhysicalPosition<f64> {
+        match *self {
+            MouseScrollDelta::LineDelta(x, y) => {
+                PhysicalPosition::new(x as f64 * line_height, y as f64 * line_height)
+            },
+            MouseScrollDelta::PixelDelta(delta) => delta,
+        }
+    }
+
+    /// Returns the delta in lines, converting pixels with the given line height in pixels.
+    ///
+    /// This is the inverse of [`MouseScrollDelta::to_pixels`], the sign is preserved as well.
+    pub fn to_lines(&self, line_height: f64) -> (f64, f64) {
+        match *self {
+            MouseScrollDelta::LineDelta(x, y) => (x as f64, y as f64),
+            MouseScrollDelta::PixelDelta(delta) => (delta.x / line_height, delta.y / line_height),
+        }
+    }
+}
+
 /// Handle to synchronously change the size of the window from the [`WindowEvent`].
 #[derive(Debug, Clone)]
 pub struct SurfaceSizeWriter {
@@ -1650,15 +2085,20 @@ mod tests {
             let dnd_data = DataTransferId::from_raw(123);
 
             with_window_event(CloseRequested);
+            with_window_event(HelpRequested);
             with_window_event(Destroyed);
-            with_window_event(Focused(true));
+            with_window_event(Focused { focused: true, via: event::FocusSource::Keyboard });
+            with_window_event(Focused { focused: false, via: event::FocusSource::Unknown });
+            with_window_event(CursorGrabChanged(crate::window::CursorGrabMode::Confined));
             with_window_event(Moved((0, 0).into()));
+            with_window_event(MoveStarted);
+            with_window_event(MoveEnded);
             with_window_event(SurfaceResized((0, 0).into()));
             with_window_event(DragEntered { id: dnd_data, position: None });
             with_window_event(DragPosition { id: dnd_data, position: (0, 0).into(), proposed_action: Some(DndAction::Copy) });
             with_window_event(DragDropped { id: dnd_data, proposed_action: Some(DndAction::Copy) });
             with_window_event(DragLeft { id: dnd_data });
-            with_window_event(Ime(Enabled));
+            with_window_event(Ime(Enabled(crate::window::ImeCapabilities::new().with_cursor_area())));
             with_window_event(PointerMoved {
                 device_id: None,
                 primary: true,
@@ -1682,6 +2122,13 @@ mod tests {
                 device_id: None,
                 delta: event::MouseScrollDelta::LineDelta(0.0, 0.0),
                 phase: event::TouchPhase::Started,
+                momentum: false,
+            });
+            with_window_event(MouseWheel {
+                device_id: None,
+                delta: event::MouseScrollDelta::PixelDelta((0.0, 0.0).into()),
+                phase: event::TouchPhase::Moved,
+                momentum: true,
             });
             with_window_event(PointerButton {
                 device_id: None,
@@ -1689,6 +2136,7 @@ mod tests {
                 state: event::ElementState::Pressed,
                 position: (0, 0).into(),
                 button: event::ButtonSource::Unknown(0),
+                click_count: Some(2),
             });
             with_window_event(PointerButton {
                 device_id: None,
@@ -1699,6 +2147,7 @@ mod tests {
                     finger_id: fid,
                     force: Some(event::Force::Normalized(0.0)),
                 },
+                click_count: None,
             });
             with_window_event(PinchGesture {
                 device_id: None,
@@ -1718,7 +2167,10 @@ mod tests {
             });
             with_window_event(TouchpadPressure { device_id: None, pressure: 0.0, stage: 0 });
             with_window_event(ThemeChanged(crate::window::Theme::Light));
+            with_window_event(ThemeChanged(crate::window::Theme::HighContrastDark));
             with_window_event(Occluded(true));
+            with_window_event(MaximizedChanged(true));
+            with_window_event(MinimizedChanged(false));
         }};
         (device: $closure:expr) => {{
             use event::DeviceEvent::*;
@@ -1729,6 +2181,8 @@ mod tests {
             with_device_event(PointerMotion { delta: (0.0, 0.0).into() });
             with_device_event(MouseWheel { delta: event::MouseScrollDelta::LineDelta(0.0, 0.0) });
             with_device_event(Button { button: 0, state: event::ElementState::Pressed });
+            with_device_event(Added);
+            with_device_event(Removed);
         }};
     }
 
@@ -1822,6 +2276,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_scroll_delta_conversions() {
+        let line = event::MouseScrollDelta::LineDelta(1.5, -2.0);
+        assert_eq!(line.to_pixels(20.0), PhysicalPosition::new(30.0, -40.0));
+        assert_eq!(line.to_lines(20.0), (1.5, -2.0));
+
+        let pixel = event::MouseScrollDelta::PixelDelta(PhysicalPosition::new(-30.0, 5.0));
+        assert_eq!(pixel.to_pixels(20.0), PhysicalPosition::new(-30.0, 5.0));
+        assert_eq!(pixel.to_lines(20.0), (-1.5, 0.25));
+
+        // Converting back and forth is lossless.
+        let lines = pixel.to_lines(20.0);
+        let line = event::MouseScrollDelta::LineDelta(lines.0 as f32, lines.1 as f32);
+        assert_eq!(line.to_pixels(20.0), PhysicalPosition::new(-30.0, 5.0));
+    }
+
+    #[test]
+    fn device_id_virtual_flag() {
+        let id = event::DeviceId::from_raw(2);
+        assert_eq!(id.is_virtual(), None);
+
+        let master = id.with_virtual(true);
+        assert_eq!(master.is_virtual(), Some(true));
+        assert_eq!(master.into_raw(), 2);
+
+        // The flag doesn't take part in the identity of the device.
+        assert_eq!(master, id);
+        let set = HashSet::from([master]);
+        assert!(set.contains(&id));
+        assert_ne!(master, event::DeviceId::from_raw(3).with_virtual(true));
+    }
+
     #[allow(clippy::clone_on_copy)]
     #[test]
     fn ensure_attrs_do_not_panic() {
@@ -1840,8 +2326,108 @@ mod tests {
 
         HashSet::new().insert(event::TouchPhase::Started.clone());
         HashSet::new().insert(event::MouseButton::Left.clone());
-        HashSet::new().insert(event::Ime::Enabled);
+        HashSet::new().insert(event::Ime::Enabled(crate::window::ImeCapabilities::new()));
 
         let _ = event::Force::Calibrated { force: 0.0, max_possible_force: 0.0 }.clone();
     }
+
+    #[test]
+    fn modifiers_key_state() {
+        use crate::keyboard::{ModifiersKeyState, ModifiersKeys, ModifiersState};
+
+        // Without any side known, only the logical state is available.
+        let mods = event::Modifiers::from(ModifiersState::SHIFT);
+        assert_eq!(mods.lshift_state(), ModifiersKeyState::Unknown);
+        assert_eq!(mods.rshift_state(), ModifiersKeyState::Unknown);
+
+        // Left shift pressed.
+        let mods = event::Modifiers::new(ModifiersState::SHIFT, ModifiersKeys::LSHIFT)
+            .with_released_mods(!ModifiersKeys::LSHIFT);
+        assert_eq!(mods.lshift_state(), ModifiersKeyState::Pressed);
+        assert_eq!(mods.rshift_state(), ModifiersKeyState::Released);
+        assert_eq!(mods.lcontrol_state(), ModifiersKeyState::Released);
+
+        // Right shift pressed as well, then left shift released.
+        let pressed = ModifiersKeys::LSHIFT | ModifiersKeys::RSHIFT;
+        let mods =
+            event::Modifiers::new(ModifiersState::SHIFT, pressed).with_released_mods(!pressed);
+        assert_eq!(mods.lshift_state(), ModifiersKeyState::Pressed);
+        assert_eq!(mods.rshift_state(), ModifiersKeyState::Pressed);
+        let mods = event::Modifiers::new(ModifiersState::SHIFT, ModifiersKeys::RSHIFT)
+            .with_released_mods(!ModifiersKeys::RSHIFT);
+        assert_eq!(mods.lshift_state(), ModifiersKeyState::Released);
+        assert_eq!(mods.rshift_state(), ModifiersKeyState::Pressed);
+
+        // Pressed wins over released.
+        let mods = event::Modifiers::new(ModifiersState::ALT, ModifiersKeys::RALT)
+            .with_released_mods(ModifiersKeys::all());
+        assert_eq!(mods.ralt_state(), ModifiersKeyState::Pressed);
+        assert_eq!(mods.lalt_state(), ModifiersKeyState::Released);
+
+        // Active modifiers without a pressed key, e.g. sticky ones, keep their keys unknown.
+        let mods = event::Modifiers::new(
+            ModifiersState::SHIFT | ModifiersState::CONTROL,
+            ModifiersKeys::LCONTROL,
+        )
+        .with_other_mods_released();
+        assert_eq!(mods.lshift_state(), ModifiersKeyState::Unknown);
+        assert_eq!(mods.rshift_state(), ModifiersKeyState::Unknown);
+        assert_eq!(mods.lcontrol_state(), ModifiersKeyState::Pressed);
+        assert_eq!(mods.rcontrol_state(), ModifiersKeyState::Released);
+        assert_eq!(mods.lalt_state(), ModifiersKeyState::Released);
+
+        // Sides left out of both sets stay unknown.
+        let mods = event::Modifiers::new(ModifiersState::empty(), ModifiersKeys::empty())
+            .with_released_mods(ModifiersKeys::LMETA);
+        assert_eq!(mods.lsuper_state(), ModifiersKeyState::Released);
+        assert_eq!(mods.rsuper_state(), ModifiersKeyState::Unknown);
+    }
+
+    #[test]
+    fn ime_preedit_cursor_on_char_boundaries() {
+        use event::Ime;
+
+        // "啊" is 3 bytes long, "b" starts at byte 3.
+        let text = String::from("啊b");
+        assert_eq!(
+            Ime::preedit(text.clone(), Some((1, 2))),
+            Ime::Preedit(text.clone(), Some((0, 0)))
+        );
+        assert_eq!(
+            Ime::preedit(text.clone(), Some((2, 4))),
+            Ime::Preedit(text.clone(), Some((0, 4)))
+        );
+        assert_eq!(
+            Ime::preedit(text.clone(), Some((3, 10))),
+            Ime::Preedit(text.clone(), Some((3, 4)))
+        );
+        assert_eq!(Ime::preedit(text.clone(), None), Ime::Preedit(text.clone(), None));
+
+        for offset in 0..=text.len() + 1 {
+            let Ime::Preedit(text, Some((begin, end))) =
+                Ime::preedit(text.clone(), Some((offset, offset)))
+            else {
+                unreachable!();
+            };
+            assert!(text.is_char_boundary(begin) && text.is_char_boundary(end));
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn ime_commit_serde_round_trip() {
+        use event::Ime;
+
+        for commit in
+            [Ime::Commit(String::from("啊不"), None), Ime::Commit(String::from("é"), Some((3, 6)))]
+        {
+            let json = serde_json::to_string(&commit).unwrap();
+            assert_eq!(serde_json::from_str::<Ime>(&json).unwrap(), commit);
+        }
+
+        assert_eq!(
+            serde_json::to_value(Ime::Commit(String::from("é"), Some((3, 6)))).unwrap(),
+            serde_json::json!({ "Commit": ["é", [3, 6]] })
+        );
+    }
 }
@@ -3,18 +3,22 @@ pub mod pump_events;
 pub mod register;
 pub mod run_on_demand;
 
+use std::collections::HashSet;
 use std::fmt::{self, Debug};
-use std::sync::Arc;
+use std::mem;
 use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
-use rwh_06::{DisplayHandle, HandleError, HasDisplayHandle};
+use dpi::PhysicalSize;
+use rwh_06::{DisplayHandle, HandleError, HasDisplayHandle, RawDisplayHandle};
 
 use crate::Instant;
 use crate::as_any::AsAny;
 use crate::cursor::{CustomCursor, CustomCursorSource};
 use crate::data_transfer::{DataTransfer, DataTransferId, DataTransferSend, TransferType};
-use crate::error::{NotSupportedError, RequestError};
+use crate::error::{CreateWindowError, NotSupportedError, RequestError};
+use crate::event::{DeviceId, FingerId};
 use crate::icon::Icon;
 use crate::monitor::MonitorHandle;
 use crate::window::{Theme, Window, WindowAttributes, WindowId};
@@ -27,15 +31,20 @@ pub trait ActiveEventLoop: AsAny + fmt::Debug {
     /// Create the window.
     ///
     /// Possible causes of error include denied permission, incompatible system, and lack of memory.
+    /// See [`CreateWindowError`] for the failures an application can react to, e.g. by retrying
+    /// without fullscreen on [`CreateWindowError::MonitorUnavailable`].
     ///
     /// ## Platform-specific
     ///
     /// - **Web:** The window is created but not inserted into the Web page automatically. Please
     ///   see the Web platform module for more information.
+    /// - **X11:** Reports [`CreateWindowError::UnsupportedParentWindow`] and
+    ///   [`CreateWindowError::MonitorUnavailable`].
+    /// - **Windows:** Reports [`CreateWindowError::UnsupportedParentWindow`].
     fn create_window(
         &self,
         window_attributes: WindowAttributes,
-    ) -> Result<Box<dyn Window>, RequestError>;
+    ) -> Result<Box<dyn Window>, CreateWindowError>;
 
     /// Create custom cursor.
     ///
@@ -60,7 +69,8 @@ pub trait ActiveEventLoop: AsAny + fmt::Debug {
     ///
     /// ## Platform-specific
     ///
-    /// - **Wayland:** Always returns `None`.
+    /// - **Wayland:** There's no primary monitor, the top-left-most monitor is returned as a best
+    ///   guess.
     /// - **Web:** Always returns `None` without `detailed monitor permissions`.
     fn primary_monitor(&self) -> Option<MonitorHandle>;
 
@@ -77,13 +87,41 @@ pub trait ActiveEventLoop: AsAny + fmt::Debug {
     /// [`DeviceEvent`]: crate::event::DeviceEvent
     fn listen_device_events(&self, allowed: DeviceEvents);
 
+    /// Sets whether holding a key down repeats its [`DeviceEvent::Key`].
+    ///
+    /// When enabled, a held key reports repeated presses as raw key events at the system's key
+    /// repeat rate, for applications that only consume device events. Window keyboard input
+    /// already reports repeats with [`KeyEvent::repeat`]. Disabled by default.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Repeats are synthesized for the most recently pressed key, at the XKB autorepeat
+    ///   rate read when enabling.
+    /// - **Windows:** Raw keyboard input is already repeated by the system, so this has no effect.
+    /// - **Wayland / macOS / iOS / Android / Web / Orbital:** Unsupported.
+    ///
+    /// [`DeviceEvent::Key`]: crate::event::DeviceEvent::Key
+    /// [`KeyEvent::repeat`]: crate::event::KeyEvent::repeat
+    fn set_device_key_repeat(&self, enabled: bool) {
+        let _ = enabled;
+    }
+
     /// Returns the current system theme.
     ///
     /// Returns `None` if it cannot be determined on the current platform.
     ///
+    /// This doesn't depend on any window, so it can be used to pick a theme before creating the
+    /// first window. Changes are reported with [`ApplicationHandler::system_theme_changed`].
+    ///
     /// ## Platform-specific
     ///
-    /// - **iOS / Android / Wayland / x11 / Orbital:** Unsupported.
+    /// - **Windows:** Read from the dark mode setting of apps and high contrast mode.
+    /// - **macOS:** Read from the application's effective appearance.
+    /// - **Web:** Read from the `prefers-color-scheme` media query.
+    /// - **Wayland / X11:** Unsupported, the freedesktop appearance portal isn't read.
+    /// - **iOS / Android / Orbital:** Unsupported.
+    ///
+    /// [`ApplicationHandler::system_theme_changed`]: crate::application::ApplicationHandler::system_theme_changed
     fn system_theme(&self) -> Option<Theme>;
 
     /// Sets the [`ControlFlow`].
@@ -92,6 +130,57 @@ pub trait ActiveEventLoop: AsAny + fmt::Debug {
     /// Gets the current [`ControlFlow`].
     fn control_flow(&self) -> ControlFlow;
 
+    /// Returns the system configuration of pointer gestures.
+    ///
+    /// Applications doing their own click detection should use it instead of hardcoded values, to
+    /// behave like the rest of the system. Changes to the configuration are not reported, so it
+    /// should be queried again when needed.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Read from the `Net/DoubleClickTime`, `Net/DoubleClickDistance` and
+    ///   `Net/DndDragThreshold` XSETTINGS.
+    /// - **macOS:** Only the double click interval is read from the system.
+    /// - **Wayland / iOS / Android / Web / Orbital:** Unsupported, always returns the
+    ///   [default](PointerConfig::default) configuration.
+    fn system_pointer_config(&self) -> PointerConfig {
+        PointerConfig::default()
+    }
+
+    /// Returns whether the finger is currently touching, or `None` if it's unknown.
+    ///
+    /// A finger is active from its [`WindowEvent::PointerEntered`] until its
+    /// [`WindowEvent::PointerLeft`] was delivered, after which the system may reuse the
+    /// [`FingerId`]. The liveness is only accurate within the current event loop iteration.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland / Windows / macOS / iOS / Android / Web / Orbital:** Unsupported, always returns
+    ///   `None`.
+    ///
+    /// [`WindowEvent::PointerEntered`]: crate::event::WindowEvent::PointerEntered
+    /// [`WindowEvent::PointerLeft`]: crate::event::WindowEvent::PointerLeft
+    fn is_finger_active(&self, finger_id: FingerId) -> Option<bool> {
+        let _ = finger_id;
+        None
+    }
+
+    /// Returns whether the device is currently connected, or `None` if it's unknown.
+    ///
+    /// A device stops being active before its [`DeviceEvent::Removed`] is delivered. The liveness
+    /// is only accurate within the current event loop iteration.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland / Windows / macOS / iOS / Android / Web / Orbital:** Unsupported, always returns
+    ///   `None`.
+    ///
+    /// [`DeviceEvent::Removed`]: crate::event::DeviceEvent::Removed
+    fn is_device_active(&self, device_id: DeviceId) -> Option<bool> {
+        let _ = device_id;
+        None
+    }
+
     /// Stop the event loop.
     ///
     /// ## Platform-specific
@@ -104,9 +193,30 @@ pub trait ActiveEventLoop: AsAny + fmt::Debug {
     /// [qa1561]: https://developer.apple.com/library/archive/qa/qa1561/_index.html
     fn exit(&self);
 
+    /// Stop the event loop with an exit code.
+    ///
+    /// [`exit()`][Self::exit] is the same as an exit code of `0`. A non-zero code is returned as
+    /// [`EventLoopError::ExitFailure`] when running the event loop, e.g. from
+    /// [`run_app_on_demand`], and as [`PumpStatus::Exit`] from [`pump_app_events`], so it can be
+    /// returned from `main`.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** The page keeps running, so the code is never reported.
+    /// - **iOS:** Unsupported, see [`exit()`][Self::exit].
+    ///
+    /// [`EventLoopError::ExitFailure`]: crate::error::EventLoopError::ExitFailure
+    /// [`run_app_on_demand`]: run_on_demand::EventLoopExtRunOnDemand::run_app_on_demand
+    /// [`PumpStatus::Exit`]: pump_events::PumpStatus::Exit
+    /// [`pump_app_events`]: pump_events::EventLoopExtPumpEvents::pump_app_events
+    fn exit_with_code(&self, code: i32) {
+        let _ = code;
+        self.exit();
+    }
+
     /// Returns whether the [`ActiveEventLoop`] is about to stop.
     ///
-    /// Set by [`exit()`][Self::exit].
+    /// Set by [`exit()`][Self::exit] and [`exit_with_code()`][Self::exit_with_code].
     fn exiting(&self) -> bool;
 
     /// Gets a persistent reference to the underlying platform display.
@@ -117,6 +227,18 @@ pub trait ActiveEventLoop: AsAny + fmt::Debug {
     /// Get the raw-window-handle handle.
     fn rwh_06_handle(&self) -> &dyn HasDisplayHandle;
 
+    /// Returns the [`Backend`] the event loop is running on.
+    ///
+    /// This is mostly useful on Linux/BSD, where the backend is picked at runtime, to know which
+    /// extension traits can be used and what kind of handle [`Self::rwh_06_handle`] returns.
+    ///
+    /// The default implementation derives it from the display handle, and returns `None` when
+    /// the handle is unavailable or of no known backend.
+    fn backend(&self) -> Option<Backend> {
+        let handle = self.rwh_06_handle().display_handle().ok()?;
+        Backend::from_raw_display_handle(handle.as_raw())
+    }
+
     /// Request to fetch a type from a [data transfer](crate::data_transfer::DataTransfer).
     ///
     /// This may be called multiple times on the same [`DataTransferId`] with different types,
@@ -349,6 +471,24 @@ impl EventLoopProxy {
         self.proxy.wake_up();
     }
 
+    /// Wake up the [`ActiveEventLoop`], resulting in
+    /// [`ApplicationHandler::proxy_wake_up_coalesced()`] being called with `key`.
+    ///
+    /// Calls with the same `key` are coalesced until the key is delivered, so the application sees
+    /// each pending key at most once per event loop iteration, no matter how often it was
+    /// requested. Calls with different keys are delivered separately, in no particular order.
+    ///
+    /// Like [`wake_up()`][Self::wake_up], this wakes the event loop, so
+    /// [`ApplicationHandler::proxy_wake_up()`] is called before the pending keys are delivered.
+    ///
+    /// If the event loop is no longer running, this is a no-op.
+    ///
+    /// [`ApplicationHandler::proxy_wake_up()`]: crate::application::ApplicationHandler::proxy_wake_up
+    /// [`ApplicationHandler::proxy_wake_up_coalesced()`]: crate::application::ApplicationHandler::proxy_wake_up_coalesced
+    pub fn wake_up_coalesced(&self, key: u64) {
+        self.proxy.wake_up_coalesced(key);
+    }
+
     pub fn new(proxy: Arc<dyn EventLoopProxyProvider>) -> Self {
         Self { proxy }
     }
@@ -357,6 +497,46 @@ impl EventLoopProxy {
 pub trait EventLoopProxyProvider: Send + Sync + Debug {
     /// See [`EventLoopProxy::wake_up`] for details.
     fn wake_up(&self);
+
+    /// See [`EventLoopProxy::wake_up_coalesced`] for details.
+    ///
+    /// Implementations usually record the key in [`PendingWakeUps`] and only call
+    /// [`wake_up()`][Self::wake_up] if it wasn't pending already.
+    ///
+    /// The default implementation only calls [`wake_up()`][Self::wake_up], without delivering the
+    /// key.
+    fn wake_up_coalesced(&self, key: u64) {
+        let _ = key;
+        self.wake_up();
+    }
+}
+
+/// The keys passed to [`EventLoopProxy::wake_up_coalesced`] that weren't delivered yet.
+///
+/// This is shared between an [`EventLoopProxyProvider`] and its event loop, which drains it with
+/// [`take()`][Self::take] when handling a wake-up.
+#[derive(Debug, Default)]
+pub struct PendingWakeUps {
+    keys: Mutex<HashSet<u64>>,
+}
+
+impl PendingWakeUps {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record `key` as pending.
+    ///
+    /// Returns `false` if it was already pending, in which case a wake-up has already been
+    /// requested for it.
+    pub fn insert(&self, key: u64) -> bool {
+        self.keys.lock().unwrap().insert(key)
+    }
+
+    /// Take all the pending keys, in no particular order.
+    pub fn take(&self) -> HashSet<u64> {
+        mem::take(&mut *self.keys.lock().unwrap())
+    }
 }
 
 /// A proxy for the underlying display handle.
@@ -432,6 +612,17 @@ pub enum ControlFlow {
     ///
     /// [`Poll`]: Self::Poll
     WaitUntil(Instant),
+
+    /// Behave as [`Poll`] until the given time is reached, and as [`Wait`] afterwards.
+    ///
+    /// Useful for time-boxed animations: set the deadline once when the animation starts, or
+    /// extend it on each iteration while the animation keeps going, and the event loop goes back
+    /// to sleep on its own once it's over. The value stays set after the deadline, so
+    /// [`ActiveEventLoop::control_flow`] keeps returning it.
+    ///
+    /// [`Poll`]: Self::Poll
+    /// [`Wait`]: Self::Wait
+    PollUntil(Instant),
 }
 
 impl ControlFlow {
@@ -448,13 +639,65 @@ impl ControlFlow {
             None => Self::Wait,
         }
     }
+
+    /// The longest a single [`WaitUntil`] wait blocks for.
+    ///
+    /// Deadlines further out than this are not an error, but the event loop wakes up once this
+    /// much time has passed and then waits again, which protects the OS timer APIs from
+    /// pathological values such as [`Instant`]s centuries in the future.
+    ///
+    /// [`WaitUntil`]: Self::WaitUntil
+    pub const MAX_WAIT_TIMEOUT: Duration = Duration::from_secs(24 * 60 * 60);
+
+    /// How long the event loop should block for, measured from `now`.
+    ///
+    /// Returns `None` for [`Wait`], [`Duration::ZERO`] for [`Poll`] and for a [`WaitUntil`]
+    /// deadline that has already passed, and otherwise the time left until the deadline, capped
+    /// at [`MAX_WAIT_TIMEOUT`]. A [`PollUntil`] behaves as given by [`Self::effective`].
+    ///
+    /// [`Wait`]: Self::Wait
+    /// [`Poll`]: Self::Poll
+    /// [`WaitUntil`]: Self::WaitUntil
+    /// [`PollUntil`]: Self::PollUntil
+    /// [`MAX_WAIT_TIMEOUT`]: Self::MAX_WAIT_TIMEOUT
+    pub fn timeout(self, now: Instant) -> Option<Duration> {
+        match self {
+            Self::Poll => Some(Duration::ZERO),
+            Self::Wait => None,
+            Self::WaitUntil(deadline) => {
+                Some(deadline.saturating_duration_since(now).min(Self::MAX_WAIT_TIMEOUT))
+            },
+            Self::PollUntil(deadline) => (now < deadline).then_some(Duration::ZERO),
+        }
+    }
+
+    /// The [`ControlFlow`] the event loop behaves as at `now`.
+    ///
+    /// This resolves [`PollUntil`] to [`Poll`] before its deadline and to [`Wait`] from then
+    /// on, and returns other values unchanged, so an event loop only has to deal with the other
+    /// variants.
+    ///
+    /// [`Poll`]: Self::Poll
+    /// [`Wait`]: Self::Wait
+    /// [`PollUntil`]: Self::PollUntil
+    #[doc(hidden)]
+    pub fn effective(self, now: Instant) -> Self {
+        match self {
+            Self::PollUntil(deadline) if now < deadline => Self::Poll,
+            Self::PollUntil(_) => Self::Wait,
+            control_flow => control_flow,
+        }
+    }
 }
 
 /// Control when device events are captured.
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DeviceEvents {
-    /// Report device events regardless of window focus.
+    /// Report device events regardless of window focus, overriding
+    /// [`Window::set_receive_device_events`].
+    ///
+    /// [`Window::set_receive_device_events`]: crate::window::Window::set_receive_device_events
     Always,
     /// Only capture device events while the window is focused.
     #[default]
@@ -463,6 +706,73 @@ pub enum DeviceEvents {
     Never,
 }
 
+/// System configuration of pointer gestures.
+///
+/// See [`ActiveEventLoop::system_pointer_config`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PointerConfig {
+    /// The maximum time between two clicks for them to be a double click.
+    pub double_click_interval: Duration,
+    /// The size of the area around the first click the second click must land in for them to be a
+    /// double click.
+    pub double_click_distance: PhysicalSize<u32>,
+    /// The size of the area the pointer must leave while a button is held for a drag to start.
+    pub drag_threshold: PhysicalSize<u32>,
+}
+
+impl Default for PointerConfig {
+    /// The defaults match the ones of Windows.
+    fn default() -> Self {
+        Self {
+            double_click_interval: Duration::from_millis(500),
+            double_click_distance: PhysicalSize::new(4, 4),
+            drag_threshold: PhysicalSize::new(4, 4),
+        }
+    }
+}
+
+/// The windowing system backing an event loop.
+///
+/// See [`ActiveEventLoop::backend`].
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Backend {
+    /// The X Window System, used on Linux/BSD.
+    X11,
+    /// Wayland, used on Linux/BSD.
+    Wayland,
+    /// Win32, used on Windows.
+    Windows,
+    /// AppKit, used on macOS.
+    AppKit,
+    /// UIKit, used on iOS, tvOS and visionOS.
+    UIKit,
+    /// The DOM, used on Web.
+    Web,
+    /// The Android NDK.
+    Android,
+    /// Orbital, used on Redox.
+    Orbital,
+}
+
+impl Backend {
+    /// The backend providing a display handle of this kind, if any.
+    pub fn from_raw_display_handle(handle: RawDisplayHandle) -> Option<Self> {
+        match handle {
+            RawDisplayHandle::Xlib(_) | RawDisplayHandle::Xcb(_) => Some(Self::X11),
+            RawDisplayHandle::Wayland(_) => Some(Self::Wayland),
+            RawDisplayHandle::Windows(_) => Some(Self::Windows),
+            RawDisplayHandle::AppKit(_) => Some(Self::AppKit),
+            RawDisplayHandle::UiKit(_) => Some(Self::UIKit),
+            RawDisplayHandle::Web(_) => Some(Self::Web),
+            RawDisplayHandle::Android(_) => Some(Self::Android),
+            RawDisplayHandle::Orbital(_) => Some(Self::Orbital),
+            _ => None,
+        }
+    }
+}
+
 /// A unique identifier of the winit's async request.
 ///
 /// This could be used to identify the async request once it's done
@@ -486,3 +796,183 @@ impl AsyncRequestSerial {
         Self { serial }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn control_flow_timeout() {
+        let now = Instant::now();
+        assert_eq!(ControlFlow::Poll.timeout(now), Some(Duration::ZERO));
+        assert_eq!(ControlFlow::Wait.timeout(now), None);
+        assert_eq!(ControlFlow::WaitUntil(now).timeout(now), Some(Duration::ZERO));
+
+        let later = now + Duration::from_millis(16);
+        assert_eq!(ControlFlow::WaitUntil(later).timeout(now), Some(Duration::from_millis(16)));
+    }
+
+    #[test]
+    fn control_flow_timeout_past_deadline() {
+        let now = Instant::now() + Duration::from_secs(1);
+        let past = now - Duration::from_secs(1);
+        assert_eq!(ControlFlow::WaitUntil(past).timeout(now), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn control_flow_timeout_far_future() {
+        let now = Instant::now();
+        let far = now + Duration::from_secs(100 * 365 * 24 * 60 * 60);
+        assert_eq!(ControlFlow::WaitUntil(far).timeout(now), Some(ControlFlow::MAX_WAIT_TIMEOUT));
+    }
+
+    #[test]
+    fn poll_until_turns_into_wait() {
+        let now = Instant::now();
+        let deadline = now + Duration::from_millis(100);
+        let control_flow = ControlFlow::PollUntil(deadline);
+
+        assert_eq!(control_flow.effective(now), ControlFlow::Poll);
+        assert_eq!(control_flow.timeout(now), Some(Duration::ZERO));
+
+        assert_eq!(control_flow.effective(deadline), ControlFlow::Wait);
+        assert_eq!(control_flow.timeout(deadline), None);
+
+        // Extending the deadline keeps polling.
+        let extended = ControlFlow::PollUntil(deadline + Duration::from_millis(100));
+        assert_eq!(extended.effective(deadline), ControlFlow::Poll);
+
+        // Other values are unaffected.
+        assert_eq!(
+            ControlFlow::WaitUntil(deadline).effective(now),
+            ControlFlow::WaitUntil(deadline)
+        );
+        assert_eq!(ControlFlow::Wait.effective(now), ControlFlow::Wait);
+    }
+
+    #[test]
+    fn pending_wake_ups_coalesce() {
+        let pending = PendingWakeUps::new();
+        assert!(pending.insert(1));
+        assert!(pending.insert(2));
+        assert!(!pending.insert(1));
+
+        assert_eq!(pending.take(), HashSet::from([1, 2]));
+        assert!(pending.take().is_empty());
+
+        // Delivered keys may be requested again.
+        assert!(pending.insert(1));
+    }
+
+    #[test]
+    fn wake_up_coalesced_defaults_to_wake_up() {
+        #[derive(Debug, Default)]
+        struct Proxy(AtomicUsize);
+
+        impl EventLoopProxyProvider for Proxy {
+            fn wake_up(&self) {
+                self.0.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        let proxy = Arc::new(Proxy::default());
+        EventLoopProxy::new(proxy.clone()).wake_up_coalesced(1);
+        assert_eq!(proxy.0.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn backend_from_display_handle() {
+        use rwh_06::{
+            AndroidDisplayHandle, HaikuDisplayHandle, WindowsDisplayHandle, XcbDisplayHandle,
+            XlibDisplayHandle,
+        };
+
+        let backend = |handle: RawDisplayHandle| Backend::from_raw_display_handle(handle);
+        assert_eq!(backend(XlibDisplayHandle::new(None, 0).into()), Some(Backend::X11));
+        assert_eq!(backend(XcbDisplayHandle::new(None, 0).into()), Some(Backend::X11));
+        assert_eq!(backend(WindowsDisplayHandle::new().into()), Some(Backend::Windows));
+        assert_eq!(backend(AndroidDisplayHandle::new().into()), Some(Backend::Android));
+        assert_eq!(backend(HaikuDisplayHandle::new().into()), None);
+    }
+
+    #[test]
+    fn default_backend_follows_display_handle() {
+        use rwh_06::{HaikuDisplayHandle, XlibDisplayHandle};
+
+        #[derive(Debug)]
+        struct EventLoop(Option<RawDisplayHandle>);
+
+        impl HasDisplayHandle for EventLoop {
+            fn display_handle(&self) -> Result<DisplayHandle<'_>, HandleError> {
+                let raw = self.0.ok_or(HandleError::Unavailable)?;
+                // SAFETY: The handle is never used as a real display.
+                Ok(unsafe { DisplayHandle::borrow_raw(raw) })
+            }
+        }
+
+        impl ActiveEventLoop for EventLoop {
+            fn create_proxy(&self) -> EventLoopProxy {
+                unreachable!()
+            }
+
+            fn create_window(
+                &self,
+                _: WindowAttributes,
+            ) -> Result<Box<dyn Window>, CreateWindowError> {
+                unreachable!()
+            }
+
+            fn create_custom_cursor(
+                &self,
+                _: CustomCursorSource,
+            ) -> Result<CustomCursor, RequestError> {
+                unreachable!()
+            }
+
+            fn available_monitors(&self) -> Box<dyn Iterator<Item = MonitorHandle>> {
+                unreachable!()
+            }
+
+            fn primary_monitor(&self) -> Option<MonitorHandle> {
+                unreachable!()
+            }
+
+            fn listen_device_events(&self, _: DeviceEvents) {
+                unreachable!()
+            }
+
+            fn system_theme(&self) -> Option<Theme> {
+                unreachable!()
+            }
+
+            fn set_control_flow(&self, _: ControlFlow) {
+                unreachable!()
+            }
+
+            fn control_flow(&self) -> ControlFlow {
+                unreachable!()
+            }
+
+            fn exit(&self) {
+                unreachable!()
+            }
+
+            fn exiting(&self) -> bool {
+                unreachable!()
+            }
+
+            fn owned_display_handle(&self) -> OwnedDisplayHandle {
+                unreachable!()
+            }
+
+            fn rwh_06_handle(&self) -> &dyn HasDisplayHandle {
+                self
+            }
+        }
+
+        let x11 = EventLoop(Some(XlibDisplayHandle::new(None, 0).into()));
+        assert_eq!(x11.backend(), Some(Backend::X11));
+        assert_eq!(EventLoop(Some(HaikuDisplayHandle::new().into())).backend(), None);
+        assert_eq!(EventLoop(None).backend(), None);
+    }
+}
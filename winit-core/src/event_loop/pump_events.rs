@@ -106,7 +106,10 @@ pub trait EventLoopExtPumpEvents {
     ) -> PumpStatus;
 }
 
-/// The return status for `pump_events`
+/// The return status for [`EventLoopExtPumpEvents::pump_app_events`].
+///
+/// Once it returned [`PumpStatus::Exit`], pumping again starts a new run of the event loop with
+/// [`StartCause::Init`][crate::event::StartCause::Init].
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum PumpStatus {
     /// Continue running external loop.
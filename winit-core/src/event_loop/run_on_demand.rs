@@ -27,6 +27,9 @@ pub trait EventLoopExtRunOnDemand {
     ///
     /// See the [`set_control_flow()`] docs on how to change the event loop's behavior.
     ///
+    /// A non-zero code passed to [`exit_with_code()`] is returned as
+    /// [`EventLoopError::ExitFailure`].
+    ///
     /// # Caveats
     /// - This extension isn't available on all platforms, since it's not always possible to return
     ///   to the caller (specifically this is impossible on iOS and Web).
@@ -49,6 +52,7 @@ pub trait EventLoopExtRunOnDemand {
     /// - **iOS:** It's not possible to stop and start an `UIApplication` repeatedly on iOS.
     ///
     /// [`exit()`]: ActiveEventLoop::exit()
+    /// [`exit_with_code()`]: ActiveEventLoop::exit_with_code()
     /// [`set_control_flow()`]: ActiveEventLoop::set_control_flow()
     fn run_app_on_demand<A: ApplicationHandler>(&mut self, app: A) -> Result<(), EventLoopError>;
 }
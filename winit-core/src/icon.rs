@@ -14,6 +14,47 @@ pub struct Icon(pub Arc<dyn IconProvider>);
 // TODO remove that once split.
 pub trait IconProvider: AsAny + fmt::Debug + Send + Sync {}
 
+impl Icon {
+    /// Creates an icon from 32bpp RGBA data.
+    ///
+    /// See [`RgbaIcon::new`] for more details.
+    pub fn from_rgba(rgba: Vec<u8>, width: u32, height: u32) -> Result<Self, BadIcon> {
+        RgbaIcon::new(rgba, width, height).map(Into::into)
+    }
+
+    /// Creates an icon by decoding a PNG image.
+    ///
+    /// This avoids depending on an image decoding crate just to set a window icon. Returns
+    /// [`BadIcon::UnsupportedFormat`] if `bytes` don't contain a PNG image.
+    #[cfg(feature = "image")]
+    pub fn from_png(bytes: &[u8]) -> Result<Self, BadIcon> {
+        Self::decode(bytes, image::ImageFormat::Png)
+    }
+
+    /// Creates an icon by decoding an ICO file, which may hold the icon in several sizes.
+    ///
+    /// An [`Icon`] holds a single image which the backends scale to the sizes they need, so the
+    /// largest image of the file is used. Returns [`BadIcon::UnsupportedFormat`] if `bytes` don't
+    /// contain an ICO file.
+    #[cfg(feature = "image")]
+    pub fn from_ico(bytes: &[u8]) -> Result<Self, BadIcon> {
+        Self::decode(bytes, image::ImageFormat::Ico)
+    }
+
+    #[cfg(feature = "image")]
+    fn decode(bytes: &[u8], format: image::ImageFormat) -> Result<Self, BadIcon> {
+        if image::guess_format(bytes).ok() != Some(format) {
+            return Err(BadIcon::UnsupportedFormat);
+        }
+
+        let image = image::load_from_memory_with_format(bytes, format)
+            .map_err(|err| BadIcon::DecodingFailed(Box::new(err)))?
+            .into_rgba8();
+        let (width, height) = image.dimensions();
+        Self::from_rgba(image.into_raw(), width, height)
+    }
+}
+
 impl Deref for Icon {
     type Target = dyn IconProvider;
 
@@ -35,6 +76,11 @@ pub enum BadIcon {
     DimensionsVsPixelCount { width: u32, height: u32, width_x_height: usize, pixel_count: usize },
     /// Produced when underlying OS functionality failed to create the icon
     OsError(io::Error),
+    /// Produced when the image passed to [`Icon::from_png`] or [`Icon::from_ico`] isn't in the
+    /// expected format.
+    UnsupportedFormat,
+    /// Produced when decoding the image passed to [`Icon::from_png`] or [`Icon::from_ico`] failed.
+    DecodingFailed(Box<dyn Error + Send + Sync>),
 }
 
 impl fmt::Display for BadIcon {
@@ -54,6 +100,10 @@ impl fmt::Display for BadIcon {
                 )
             },
             BadIcon::OsError(e) => write!(f, "OS error when instantiating the icon: {e:?}"),
+            BadIcon::UnsupportedFormat => {
+                write!(f, "The icon image isn't in the expected format, PNG or ICO.")
+            },
+            BadIcon::DecodingFailed(e) => write!(f, "Failed to decode the icon image: {e}"),
         }
     }
 }
@@ -105,3 +155,48 @@ impl From<RgbaIcon> for Icon {
         Self(Arc::new(value))
     }
 }
+
+#[cfg(all(test, feature = "image"))]
+mod tests {
+    use super::{BadIcon, Icon, RgbaIcon};
+
+    #[test]
+    fn icon_from_png() {
+        use image::ImageEncoder;
+        use image::codecs::png::PngEncoder;
+
+        let rgba = [255, 0, 0, 255].repeat(4 * 2);
+        let mut png = Vec::new();
+        PngEncoder::new(&mut png)
+            .write_image(&rgba, 4, 2, image::ExtendedColorType::Rgba8)
+            .unwrap();
+
+        let icon = Icon::from_png(&png).unwrap();
+        let icon = icon.cast_ref::<RgbaIcon>().unwrap();
+        assert_eq!((icon.width(), icon.height()), (4, 2));
+        assert_eq!(icon.buffer(), rgba);
+
+        assert!(matches!(Icon::from_png(b"GIF89a"), Err(BadIcon::UnsupportedFormat)));
+    }
+
+    #[test]
+    fn icon_from_ico_uses_largest_size() {
+        use image::codecs::ico::{IcoEncoder, IcoFrame};
+
+        let small = [255, 0, 0, 255].repeat(16 * 16);
+        let large = [0, 0, 255, 255].repeat(32 * 32);
+        let frames = [
+            IcoFrame::as_png(&small, 16, 16, image::ExtendedColorType::Rgba8).unwrap(),
+            IcoFrame::as_png(&large, 32, 32, image::ExtendedColorType::Rgba8).unwrap(),
+        ];
+        let mut ico = Vec::new();
+        IcoEncoder::new(&mut ico).encode_images(&frames).unwrap();
+
+        let icon = Icon::from_ico(&ico).unwrap();
+        let icon = icon.cast_ref::<RgbaIcon>().unwrap();
+        assert_eq!((icon.width(), icon.height()), (32, 32));
+        assert_eq!(icon.buffer(), large);
+
+        assert!(matches!(Icon::from_png(&ico), Err(BadIcon::UnsupportedFormat)));
+    }
+}
@@ -344,6 +344,13 @@ impl Key<SmolStr> {
 impl Key {
     /// Convert a key to its approximate textual equivalent.
     ///
+    /// This doesn't need a [`KeyEvent`], e.g. to render the label of a keybinding. It's the text
+    /// that [`KeyEvent::text`] reports for the key when it doesn't come from the platform's text
+    /// input, so it's `None` for dead keys and for named keys without a canonical text.
+    ///
+    /// [`KeyEvent`]: crate::event::KeyEvent
+    /// [`KeyEvent::text`]: crate::event::KeyEvent::text
+    ///
     /// # Examples
     ///
     /// ```
@@ -456,6 +463,13 @@ pub enum ModifiersKeyState {
     /// of [`ModifiersState::SHIFT`] will be active.
     #[default]
     Unknown,
+    /// The particular modifier is known to be released.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS / Android / Web:** Never reported, these platforms don't track the individual
+    ///   modifier keys.
+    Released,
 }
 
 // NOTE: the exact modifier key is not used to represent modifiers state in the
@@ -484,3 +498,31 @@ bitflags! {
         const RSUPER   = Self::RMETA.bits();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_to_text() {
+        let cases = [
+            (Key::Character("a".into()), Some("a")),
+            (Key::Character("啊".into()), Some("啊")),
+            (Key::Character(" ".into()), Some(" ")),
+            (Key::Named(NamedKey::Enter), Some("\r")),
+            (Key::Named(NamedKey::Tab), Some("\t")),
+            (Key::Named(NamedKey::Backspace), Some("\x08")),
+            (Key::Named(NamedKey::Escape), Some("\x1b")),
+            (Key::Named(NamedKey::ArrowLeft), None),
+            (Key::Named(NamedKey::F1), None),
+            (Key::Named(NamedKey::Shift), None),
+            (Key::Dead(Some('`')), None),
+            (Key::Dead(None), None),
+            (Key::Unidentified(NativeKey::Unidentified), None),
+        ];
+
+        for (key, text) in cases {
+            assert_eq!(key.to_text(), text, "{key:?}");
+        }
+    }
+}
@@ -6,6 +6,7 @@
 //! - [`ActiveEventLoop::available_monitors`][crate::event_loop::ActiveEventLoop::available_monitors].
 //! - [`Window::available_monitors`][crate::window::Window::available_monitors].
 use std::borrow::Cow;
+use std::cmp::Reverse;
 use std::fmt;
 use std::num::{NonZeroU16, NonZeroU32};
 use std::ops::Deref;
@@ -63,13 +64,17 @@ pub trait MonitorHandleProvider: AsAny + fmt::Debug + Send + Sync {
 
     /// Native platform identifier of this monitor.
     ///
+    /// This can be used to match the monitor with the ones reported by graphics APIs, e.g. to pick
+    /// the adapter driving it.
+    ///
     /// # Platform-specific
     ///
-    /// - **Windows**: This is `HMONITOR`.
-    /// - **macOS**: This is `CGDirectDisplayID`.
-    /// - **iOS**: This is `UIScreen*`.
-    /// - **Wayland**: This is the ID of the `wl_output` device.
-    /// - **X11**: This is the ID of the CRTC.
+    /// - **Windows**: This is the `HMONITOR`, as used by DXGI's `DXGI_OUTPUT_DESC::Monitor`.
+    /// - **macOS**: This is the `CGDirectDisplayID`.
+    /// - **iOS**: This is the `UIScreen*` pointer.
+    /// - **Wayland**: This is the protocol ID of the `wl_output` object.
+    /// - **X11**: This is the XID of the RandR CRTC, not of the output. The output is available
+    ///   through `MonitorHandleExtX11::randr_output`.
     /// - **Web**: This is an internal ID not meant for consumption.
     fn native_id(&self) -> u64;
 
@@ -109,6 +114,80 @@ pub trait MonitorHandleProvider: AsAny + fmt::Debug + Send + Sync {
 
     /// Returns all fullscreen video modes supported by this monitor.
     fn video_modes(&self) -> Box<dyn Iterator<Item = VideoMode>>;
+
+    /// Returns the video mode of this monitor closest to the given size and refresh rate, for use
+    /// with [`Fullscreen::Exclusive`].
+    ///
+    /// The size is matched first, then the refresh rate, and the highest refresh rate and bit
+    /// depth break ties. Without a refresh rate, the highest one for the closest size is picked.
+    ///
+    /// Returns `None` if the monitor doesn't report any video modes.
+    fn closest_video_mode(
+        &self,
+        size: PhysicalSize<u32>,
+        refresh_rate_millihertz: Option<NonZeroU32>,
+    ) -> Option<VideoMode> {
+        self.video_modes().min_by_key(|mode| {
+            let size_diff = u64::from(mode.size.width.abs_diff(size.width))
+                + u64::from(mode.size.height.abs_diff(size.height));
+            let refresh_diff = match (refresh_rate_millihertz, mode.refresh_rate_millihertz) {
+                (Some(requested), Some(rate)) => rate.get().abs_diff(requested.get()),
+                (Some(_), None) => u32::MAX,
+                (None, _) => 0,
+            };
+            (
+                size_diff,
+                refresh_diff,
+                Reverse(mode.refresh_rate_millihertz),
+                Reverse(mode.bit_depth),
+            )
+        })
+    }
+
+    /// Returns the physical size of the monitor in millimeters as `(width, height)`.
+    ///
+    /// Returns `None` if the size is unknown.
+    ///
+    /// This value comes straight from the monitor's EDID, and manufacturers sometimes report
+    /// bogus values (e.g. the aspect ratio instead of the size, or `0x0` for projectors), so it
+    /// should only be treated as advisory.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS / Android / Wayland / Web / Orbital:** Always returns `None`.
+    fn physical_size_mm(&self) -> Option<(u32, u32)> {
+        None
+    }
+
+    /// Returns whether the monitor is built into the device, e.g. the panel of a laptop.
+    ///
+    /// Returns `None` if it's unknown.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Inferred from the name of the RandR output, as outputs of built-in panels are
+    ///   conventionally named `eDP`, `LVDS` or `DSI` by the drivers. Drivers using other names,
+    ///   e.g. in virtual machines, are reported as external, so `Some(false)` is only a guess.
+    /// - **Windows:** Reported for monitors connected to an internal or embedded output.
+    /// - **iOS / Android / Wayland / Web / Orbital:** Always returns `None`.
+    fn is_builtin(&self) -> Option<bool> {
+        None
+    }
+
+    /// Returns whether this is a placeholder rather than a real monitor.
+    ///
+    /// Placeholders are returned where a monitor is always expected, but none could be found,
+    /// e.g. on a headless setup or while all outputs are disconnected. They have no video modes,
+    /// and their name and geometry are made up, so they shouldn't be shown to the user.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Returned when RandR reports no active output.
+    /// - **Other platforms:** Report the absence of monitors with `None` or an empty list instead,
+    ///   so this always returns `false`.
+    fn is_placeholder(&self) -> bool {
+        false
+    }
 }
 
 impl PartialEq for dyn MonitorHandleProvider + '_ {
@@ -184,8 +263,297 @@ impl fmt::Display for VideoMode {
 /// Fullscreen modes.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Fullscreen {
+    /// Exclusive fullscreen on the monitor, switching it to the given video mode.
+    ///
+    /// The video mode must be one of the monitor's own [`video_modes`], e.g. one picked with
+    /// [`closest_video_mode`]. Requests with a video mode of another monitor are rejected.
+    ///
+    /// [`video_modes`]: MonitorHandleProvider::video_modes
+    /// [`closest_video_mode`]: MonitorHandleProvider::closest_video_mode
     Exclusive(MonitorHandle, VideoMode),
 
     /// Providing `None` to `Borderless` will fullscreen on the current monitor.
     Borderless(Option<MonitorHandle>),
 }
+
+impl Fullscreen {
+    /// Whether the video mode of [`Fullscreen::Exclusive`] is one of its monitor's
+    /// [`video_modes`].
+    ///
+    /// Always `true` for [`Fullscreen::Borderless`].
+    ///
+    /// [`video_modes`]: MonitorHandleProvider::video_modes
+    pub fn has_valid_video_mode(&self) -> bool {
+        match self {
+            Self::Exclusive(monitor, video_mode) => {
+                monitor.video_modes().any(|mode| mode == *video_mode)
+            },
+            Self::Borderless(_) => true,
+        }
+    }
+}
+
+/// Whether [`Window::set_fullscreen`] can apply `fullscreen`.
+///
+/// A [`Fullscreen::Exclusive`] with a video mode of another monitor is rejected with an error
+/// logged, see [`Fullscreen::has_valid_video_mode`].
+///
+/// [`Window::set_fullscreen`]: crate::window::Window::set_fullscreen
+#[doc(hidden)]
+pub fn validate_fullscreen(fullscreen: Option<&Fullscreen>) -> bool {
+    let valid = fullscreen.is_none_or(Fullscreen::has_valid_video_mode);
+    if !valid {
+        tracing::error!("the video mode of `Fullscreen::Exclusive` doesn't belong to its monitor");
+    }
+
+    valid
+}
+
+/// The monitor [`Window::primary_monitor`] returns.
+///
+/// That's `primary`, unless the system reports no primary monitor or only a
+/// [placeholder](MonitorHandleProvider::is_placeholder), in which case it's the `current` monitor
+/// of the window.
+///
+/// [`Window::primary_monitor`]: crate::window::Window::primary_monitor
+#[doc(hidden)]
+pub fn primary_or_current_monitor(
+    primary: Option<MonitorHandle>,
+    current: impl FnOnce() -> Option<MonitorHandle>,
+) -> Option<MonitorHandle> {
+    primary.filter(|monitor| !monitor.is_placeholder()).or_else(current)
+}
+
+/// The window state from before entering fullscreen, restored when leaving it.
+///
+/// This is what [`WindowAttributes::with_restore_on_unfullscreen`] toggles. `T` holds whatever the
+/// platform needs to put the window back, e.g. its frame and style.
+///
+/// [`WindowAttributes::with_restore_on_unfullscreen`]: crate::window::WindowAttributes::with_restore_on_unfullscreen
+#[doc(hidden)]
+#[derive(Debug, Clone)]
+pub struct FullscreenRestore<T> {
+    saved: Option<T>,
+    enabled: bool,
+}
+
+impl<T> FullscreenRestore<T> {
+    /// Create an empty state, `enabled` is [`WindowAttributes::restore_on_unfullscreen`].
+    ///
+    /// [`WindowAttributes::restore_on_unfullscreen`]: crate::window::WindowAttributes::restore_on_unfullscreen
+    pub fn new(enabled: bool) -> Self {
+        Self { saved: None, enabled }
+    }
+
+    /// Save the state when entering fullscreen, unless one is saved already.
+    ///
+    /// Switching between fullscreen modes keeps the state from before the first one, instead of
+    /// saving the fullscreen state.
+    pub fn save(&mut self, state: T) {
+        if self.saved.is_none() {
+            self.saved = Some(state);
+        }
+    }
+
+    /// Replace the saved state, e.g. with a more accurate one.
+    pub fn set(&mut self, state: T) {
+        self.saved = Some(state);
+    }
+
+    /// Whether a state is saved, i.e. the window is in fullscreen.
+    pub fn is_saved(&self) -> bool {
+        self.saved.is_some()
+    }
+
+    /// Take the state to restore when leaving fullscreen.
+    ///
+    /// Returns `None` when restoring is disabled, the saved state is dropped either way.
+    pub fn take(&mut self) -> Option<T> {
+        self.saved.take().filter(|_| self.enabled)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct TestMonitor(Vec<VideoMode>);
+
+    #[derive(Debug)]
+    struct PlaceholderMonitor;
+
+    impl MonitorHandleProvider for PlaceholderMonitor {
+        fn id(&self) -> u128 {
+            1
+        }
+
+        fn native_id(&self) -> u64 {
+            1
+        }
+
+        fn name(&self) -> Option<Cow<'_, str>> {
+            None
+        }
+
+        fn position(&self) -> Option<PhysicalPosition<i32>> {
+            None
+        }
+
+        fn scale_factor(&self) -> f64 {
+            1.0
+        }
+
+        fn current_video_mode(&self) -> Option<VideoMode> {
+            None
+        }
+
+        fn video_modes(&self) -> Box<dyn Iterator<Item = VideoMode>> {
+            Box::new(std::iter::empty())
+        }
+
+        fn is_placeholder(&self) -> bool {
+            true
+        }
+    }
+
+    impl MonitorHandleProvider for TestMonitor {
+        fn id(&self) -> u128 {
+            0
+        }
+
+        fn native_id(&self) -> u64 {
+            0
+        }
+
+        fn name(&self) -> Option<Cow<'_, str>> {
+            None
+        }
+
+        fn position(&self) -> Option<PhysicalPosition<i32>> {
+            None
+        }
+
+        fn scale_factor(&self) -> f64 {
+            1.0
+        }
+
+        fn current_video_mode(&self) -> Option<VideoMode> {
+            self.0.first().copied()
+        }
+
+        fn video_modes(&self) -> Box<dyn Iterator<Item = VideoMode>> {
+            Box::new(self.0.clone().into_iter())
+        }
+    }
+
+    fn mode(width: u32, height: u32, refresh_rate_hz: u32, bit_depth: u16) -> VideoMode {
+        VideoMode::new(
+            PhysicalSize::new(width, height),
+            NonZeroU16::new(bit_depth),
+            NonZeroU32::new(refresh_rate_hz * 1000),
+        )
+    }
+
+    fn hz(refresh_rate_hz: u32) -> Option<NonZeroU32> {
+        NonZeroU32::new(refresh_rate_hz * 1000)
+    }
+
+    #[test]
+    fn closest_video_mode_prefers_size() {
+        let monitor = TestMonitor(vec![
+            mode(1280, 720, 60, 32),
+            mode(1920, 1080, 60, 32),
+            mode(2560, 1440, 144, 32),
+        ]);
+        let size = PhysicalSize::new(1900, 1000);
+        assert_eq!(monitor.closest_video_mode(size, hz(144)), Some(mode(1920, 1080, 60, 32)));
+    }
+
+    #[test]
+    fn closest_video_mode_refresh_rate() {
+        let monitor = TestMonitor(vec![
+            mode(1920, 1080, 60, 32),
+            mode(1920, 1080, 144, 32),
+            mode(1920, 1080, 120, 32),
+            mode(1920, 1080, 120, 24),
+        ]);
+        let size = PhysicalSize::new(1920, 1080);
+        assert_eq!(monitor.closest_video_mode(size, hz(100)), Some(mode(1920, 1080, 120, 32)));
+        assert_eq!(monitor.closest_video_mode(size, hz(60)), Some(mode(1920, 1080, 60, 32)));
+        assert_eq!(monitor.closest_video_mode(size, None), Some(mode(1920, 1080, 144, 32)));
+    }
+
+    #[test]
+    fn closest_video_mode_without_modes() {
+        let monitor = TestMonitor(Vec::new());
+        assert_eq!(monitor.closest_video_mode(PhysicalSize::new(800, 600), None), None);
+    }
+
+    #[test]
+    fn primary_monitor_falls_back_to_current() {
+        // Monitors compare by ID, so compare the handles themselves.
+        fn is(monitor: Option<MonitorHandle>, expected: &MonitorHandle) -> bool {
+            monitor.is_some_and(|monitor| Arc::ptr_eq(&monitor.0, &expected.0))
+        }
+
+        let primary = MonitorHandle(Arc::new(TestMonitor(Vec::new())));
+        let current = MonitorHandle(Arc::new(TestMonitor(Vec::new())));
+        let placeholder = MonitorHandle(Arc::new(PlaceholderMonitor));
+
+        // The current monitor isn't even queried when there's a primary one.
+        let monitor = primary_or_current_monitor(Some(primary.clone()), || unreachable!());
+        assert!(is(monitor, &primary));
+
+        // No primary monitor, e.g. on Web, or only a placeholder as on X11.
+        let monitor = primary_or_current_monitor(None, || Some(current.clone()));
+        assert!(is(monitor, &current));
+        let monitor = primary_or_current_monitor(Some(placeholder), || Some(current.clone()));
+        assert!(is(monitor, &current));
+
+        assert!(primary_or_current_monitor(None, || None).is_none());
+    }
+
+    #[test]
+    fn fullscreen_restore_keeps_the_first_state() {
+        let mut restore = FullscreenRestore::new(true);
+        assert_eq!(restore.take(), None);
+
+        // Borderless, then exclusive fullscreen.
+        restore.save("windowed");
+        restore.save("borderless");
+        assert!(restore.is_saved());
+        assert_eq!(restore.take(), Some("windowed"));
+        assert_eq!(restore.take(), None);
+
+        // Leaving fullscreen allows saving again.
+        restore.save("borderless");
+        assert_eq!(restore.take(), Some("borderless"));
+
+        restore.save("borderless");
+        restore.set("windowed");
+        assert_eq!(restore.take(), Some("windowed"));
+    }
+
+    #[test]
+    fn fullscreen_restore_disabled() {
+        let mut restore = FullscreenRestore::new(false);
+        restore.save("windowed");
+        assert_eq!(restore.take(), None);
+        assert!(!restore.is_saved());
+    }
+
+    #[test]
+    fn exclusive_video_mode_validated() {
+        let monitor = MonitorHandle(Arc::new(TestMonitor(vec![mode(1920, 1080, 60, 32)])));
+        let valid = Fullscreen::Exclusive(monitor.clone(), mode(1920, 1080, 60, 32));
+        assert!(valid.has_valid_video_mode());
+        let invalid = Fullscreen::Exclusive(monitor, mode(3840, 2160, 60, 32));
+        assert!(!invalid.has_valid_video_mode());
+        assert!(Fullscreen::Borderless(None).has_valid_video_mode());
+
+        assert!(validate_fullscreen(Some(&valid)));
+        assert!(!validate_fullscreen(Some(&invalid)));
+        assert!(validate_fullscreen(None));
+    }
+}
@@ -11,7 +11,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::as_any::AsAny;
 use crate::cursor::Cursor;
-use crate::error::RequestError;
+use crate::error::{CreateWindowError, NotSupportedError, RequestError};
 use crate::icon::Icon;
 use crate::monitor::{Fullscreen, MonitorHandle};
 
@@ -61,6 +61,7 @@ pub struct WindowAttributes {
     pub maximized: bool,
     pub visible: bool,
     pub transparent: bool,
+    pub background: Option<[u8; 4]>,
     pub blur: bool,
     pub decorations: bool,
     pub window_icon: Option<Icon>,
@@ -68,9 +69,12 @@ pub struct WindowAttributes {
     pub content_protected: bool,
     pub window_level: WindowLevel,
     pub active: bool,
+    pub initial_scale_event: bool,
+    pub realize_deferred: bool,
     pub cursor: Cursor,
     pub(crate) parent_window: Option<SendSyncRawWindowHandle>,
     pub fullscreen: Option<Fullscreen>,
+    pub restore_on_unfullscreen: bool,
     pub platform: Option<Box<dyn PlatformWindowAttributes>>,
 }
 
@@ -80,6 +84,32 @@ impl WindowAttributes {
         self.parent_window.as_ref().map(|handle| &handle.0)
     }
 
+    /// Check the attributes for errors independent of the platform.
+    ///
+    /// Backends call this before creating the window, so applications don't need to.
+    pub fn validate(&self) -> Result<(), CreateWindowError> {
+        let is_empty = |size: Size| match size {
+            Size::Physical(size) => size.width == 0 || size.height == 0,
+            Size::Logical(size) => !(size.width > 0.0 && size.height > 0.0),
+        };
+        if self.surface_size.is_some_and(is_empty) {
+            return Err(CreateWindowError::InvalidSurfaceSize);
+        }
+
+        let effective_buttons = self.effective_enabled_buttons();
+        if self.enabled_buttons != WindowButtons::default()
+            && self.enabled_buttons != effective_buttons
+        {
+            tracing::warn!(
+                "enabled buttons {:?} contradict the decorations and resizability of the window, \
+                 it has {effective_buttons:?}",
+                self.enabled_buttons
+            );
+        }
+
+        Ok(())
+    }
+
     /// Requests the surface to be of specific dimensions.
     ///
     /// If this is not set, some platform-specific dimensions will be used.
@@ -165,7 +195,11 @@ impl WindowAttributes {
 
     /// Sets the enabled window buttons.
     ///
-    /// The default is [`WindowButtons::all`]
+    /// The default is [`WindowButtons::default`], without [`WindowButtons::HELP`].
+    ///
+    /// The buttons are part of the decorations, so a window without decorations has no buttons,
+    /// and a window that isn't resizable has no maximize button. A warning is logged when creating
+    /// a window with buttons that contradict these settings.
     ///
     /// See [`Window::set_enabled_buttons`] for details.
     #[inline]
@@ -174,6 +208,20 @@ impl WindowAttributes {
         self
     }
 
+    /// The buttons the window ends up with, after applying the decorations and resizability.
+    fn effective_enabled_buttons(&self) -> WindowButtons {
+        if !self.decorations {
+            return WindowButtons::empty();
+        }
+
+        let mut buttons = self.enabled_buttons;
+        buttons.set(
+            WindowButtons::MAXIMIZE,
+            buttons.contains(WindowButtons::MAXIMIZE) && self.resizable,
+        );
+        buttons
+    }
+
     /// Sets the initial title of the window in the title bar.
     ///
     /// The default is `"winit window"`.
@@ -190,12 +238,37 @@ impl WindowAttributes {
     /// The default is `None`.
     ///
     /// See [`Window::set_fullscreen`] for details.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11 / Windows / macOS:** With [`Fullscreen::Exclusive`], a visible window is only shown
+    ///   once the video mode was switched, so it doesn't show up in its windowed state first, and
+    ///   [`Window::fullscreen`] reports the exclusive mode right after creation.
     #[inline]
     pub fn with_fullscreen(mut self, fullscreen: Option<Fullscreen>) -> Self {
         self.fullscreen = fullscreen;
         self
     }
 
+    /// Sets whether leaving fullscreen restores the position and surface size the window had
+    /// before entering it.
+    ///
+    /// Disable this when the application positions the window itself after leaving fullscreen.
+    ///
+    /// The default is `true`.
+    ///
+    /// See [`Window::set_fullscreen`] for details.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS / Wayland:** Unsupported, the system always restores the window.
+    /// - **iOS / Android / Web / Orbital:** Unsupported.
+    #[inline]
+    pub fn with_restore_on_unfullscreen(mut self, restore: bool) -> Self {
+        self.restore_on_unfullscreen = restore;
+        self
+    }
+
     /// Request that the window is maximized upon creation.
     ///
     /// The default is `false`.
@@ -232,6 +305,30 @@ impl WindowAttributes {
         self
     }
 
+    /// Sets the color the window is cleared to until the application draws to it, as `[r, g, b,
+    /// a]`.
+    ///
+    /// Without it, the contents of a newly created window are undefined until the first frame is
+    /// presented, which shows up as garbage or a flash of another color. The alpha is only used
+    /// with [`transparent`][Self::with_transparent] windows, other windows are cleared to an opaque
+    /// color.
+    ///
+    /// The default is `None`, which leaves the background to the platform.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Set as the background pixel of the window.
+    /// - **Windows:** Painted on `WM_ERASEBKGND`. GDI has no alpha, so transparent windows are
+    ///   painted with the premultiplied color, a fully transparent color stays see-through.
+    /// - **Wayland:** Unsupported, a surface isn't shown until the application attaches a buffer to
+    ///   it, so there's nothing to clear.
+    /// - **macOS / iOS / Android / Web / Orbital:** Unsupported.
+    #[inline]
+    pub fn with_background(mut self, background: Option<[u8; 4]>) -> Self {
+        self.background = background;
+        self
+    }
+
     /// Sets whether the background of the window should be blurred by the system.
     ///
     /// The default is `false`.
@@ -326,7 +423,11 @@ impl WindowAttributes {
     ///
     /// ## Platform-specific:
     ///
-    /// **Android / iOS / X11 / Wayland / Orbital:** Unsupported.
+    /// - **X11:** When `false`, `_NET_WM_USER_TIME` is set to zero and the window isn't raised when
+    ///   mapped. This is only a hint, which the window manager may ignore.
+    /// - **Wayland:** When `false`, the window isn't activated with the activation token, the
+    ///   compositor may still focus it.
+    /// - **Android / iOS / Orbital:** Unsupported.
     ///
     /// [`WindowEvent::Focused`]: crate::event::WindowEvent::Focused
     #[inline]
@@ -335,6 +436,53 @@ impl WindowAttributes {
         self
     }
 
+    /// Whether to deliver a [`WindowEvent::ScaleFactorChanged`] with the initial scale factor.
+    ///
+    /// When `true`, the window receives one [`WindowEvent::ScaleFactorChanged`] when its surface
+    /// is first configured, right before the first [`WindowEvent::SurfaceResized`], even if the
+    /// scale factor is the default one. Its [`SurfaceSizeWriter`] can be used to finalize the
+    /// initial size, so the initial and later scale factors are handled by the same code.
+    ///
+    /// The default is `false`.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** When the size is changed with the [`SurfaceSizeWriter`], the first
+    ///   [`WindowEvent::SurfaceResized`] still carries the old size, and is followed by another one
+    ///   once the window was resized.
+    /// - **Windows / macOS / iOS / Android / Web / Orbital:** Unsupported.
+    ///
+    /// [`WindowEvent::ScaleFactorChanged`]: crate::event::WindowEvent::ScaleFactorChanged
+    /// [`WindowEvent::SurfaceResized`]: crate::event::WindowEvent::SurfaceResized
+    /// [`SurfaceSizeWriter`]: crate::event::SurfaceSizeWriter
+    #[inline]
+    pub fn with_initial_scale_event(mut self, initial_scale_event: bool) -> Self {
+        self.initial_scale_event = initial_scale_event;
+        self
+    }
+
+    /// Whether to create the window without realizing it, until [`Window::realize`] is called.
+    ///
+    /// An unrealized window isn't shown, and the compositor doesn't see it yet, which gives the
+    /// application a chance to configure it, e.g. its level or its region, before it's presented
+    /// instead of reconfiguring a visible window. [`Window::set_visible`] with `true` also realizes
+    /// the window.
+    ///
+    /// The default is `false`.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** The window isn't mapped, even if it's [visible][Self::with_visible].
+    /// - **Wayland:** The initial commit of the surface is deferred, so the window is only
+    ///   configured after it's realized.
+    /// - **Windows / macOS / iOS / Android / Web / Orbital:** Unsupported, windows are always
+    ///   realized when they're created.
+    #[inline]
+    pub fn with_realize_deferred(mut self, realize_deferred: bool) -> Self {
+        self.realize_deferred = realize_deferred;
+        self
+    }
+
     /// Modifies the cursor icon of the window.
     ///
     /// The default is [`CursorIcon::Default`].
@@ -394,6 +542,7 @@ impl Clone for WindowAttributes {
             maximized: self.maximized,
             visible: self.visible,
             transparent: self.transparent,
+            background: self.background,
             blur: self.blur,
             decorations: self.decorations,
             window_icon: self.window_icon.clone(),
@@ -401,9 +550,12 @@ impl Clone for WindowAttributes {
             content_protected: self.content_protected,
             window_level: self.window_level,
             active: self.active,
+            initial_scale_event: self.initial_scale_event,
+            realize_deferred: self.realize_deferred,
             cursor: self.cursor.clone(),
             parent_window: self.parent_window.clone(),
             fullscreen: self.fullscreen.clone(),
+            restore_on_unfullscreen: self.restore_on_unfullscreen,
             platform: self.platform.as_ref().map(|platform| platform.box_clone()),
         }
     }
@@ -413,12 +565,15 @@ impl Default for WindowAttributes {
     #[inline]
     fn default() -> WindowAttributes {
         WindowAttributes {
-            enabled_buttons: WindowButtons::all(),
+            enabled_buttons: WindowButtons::default(),
             title: String::from("winit window"),
             decorations: true,
             resizable: true,
             visible: true,
             active: true,
+            initial_scale_event: false,
+            realize_deferred: false,
+            restore_on_unfullscreen: true,
             surface_resize_increments: Default::default(),
             content_protected: Default::default(),
             min_surface_size: Default::default(),
@@ -429,6 +584,7 @@ impl Default for WindowAttributes {
             window_level: Default::default(),
             window_icon: Default::default(),
             transparent: Default::default(),
+            background: Default::default(),
             fullscreen: Default::default(),
             maximized: Default::default(),
             position: Default::default(),
@@ -439,6 +595,150 @@ impl Default for WindowAttributes {
     }
 }
 
+/// Clamp the dimensions of a surface size to at least 1, warning when they were smaller.
+///
+/// No platform can represent an empty surface, so this sanitizes the size passed to
+/// [`Window::request_surface_size`].
+#[doc(hidden)]
+pub fn clamp_surface_size(size: Size) -> Size {
+    let clamped = match size {
+        Size::Physical(size) => PhysicalSize::new(size.width.max(1), size.height.max(1)).into(),
+        // Also catches NaN.
+        Size::Logical(size) => {
+            let clamp = |value: f64| if value >= 1.0 { value } else { 1.0 };
+            LogicalSize::new(clamp(size.width), clamp(size.height)).into()
+        },
+    };
+
+    if clamped != size {
+        tracing::warn!(
+            "Requested surface size {size:?} is smaller than 1x1, clamping it to {clamped:?}"
+        );
+    }
+
+    clamped
+}
+
+/// The state of an interactive move started with [`Window::drag_window`].
+///
+/// Tells when to send [`WindowEvent::MoveStarted`], once the window system took the pointer to
+/// move the window, and [`WindowEvent::MoveEnded`], once the window receives pointer input again.
+///
+/// [`WindowEvent::MoveStarted`]: crate::event::WindowEvent::MoveStarted
+/// [`WindowEvent::MoveEnded`]: crate::event::WindowEvent::MoveEnded
+#[doc(hidden)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DragMove(DragMoveState);
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+enum DragMoveState {
+    #[default]
+    None,
+    /// The window system was asked to move the window.
+    Requested,
+    /// The window system took the pointer to move the window.
+    Active,
+}
+
+impl DragMove {
+    /// The window system was asked to move the window while a button is held.
+    ///
+    /// Without a held button the window system doesn't start a move, so nothing should be
+    /// requested then.
+    pub fn request(&mut self) {
+        self.0 = DragMoveState::Requested;
+    }
+
+    /// The window system took the pointer, returns whether that started the requested move.
+    pub fn pointer_grabbed(&mut self) -> bool {
+        let requested = self.0 == DragMoveState::Requested;
+        if requested {
+            self.0 = DragMoveState::Active;
+        }
+        requested
+    }
+
+    /// The window received pointer input, returns whether that ended a move.
+    ///
+    /// A `released` button means the window system won't start the requested move anymore, so
+    /// that's dropped as well.
+    pub fn pointer_input(&mut self, released: bool) -> bool {
+        match self.0 {
+            DragMoveState::Active => {
+                self.0 = DragMoveState::None;
+                true
+            },
+            DragMoveState::Requested if released => {
+                self.0 = DragMoveState::None;
+                false
+            },
+            _ => false,
+        }
+    }
+}
+
+/// The request made by the deprecated [`Window::set_ime_allowed`].
+fn ime_allowed_request(allowed: bool) -> ImeRequest {
+    if !allowed {
+        return ImeRequest::Disable;
+    }
+
+    let position = LogicalPosition::new(0, 0);
+    let size = LogicalSize::new(0, 0);
+    let ime_caps = ImeCapabilities::new().with_hint_and_purpose().with_cursor_area();
+    let request_data = ImeRequestData {
+        hint_and_purpose: Some((ImeHint::NONE, ImePurpose::Normal)),
+        // WARNING: there's nothing sensible to use here by default.
+        cursor_area: Some((position.into(), size.into())),
+        ..ImeRequestData::default()
+    };
+
+    // Enable the capabilities the old API had, surrounding text wasn't one of them.
+    ImeRequest::Enable(ImeEnableRequest::new(ime_caps, request_data).unwrap())
+}
+
+/// Applies `delta` to a surface dimension for [`Window::resize_by`].
+///
+/// The delta is rounded away from zero to a multiple of `increment`, and the result is clamped to
+/// `min` and `max`.
+#[doc(hidden)]
+pub fn resize_dimension(
+    size: u32,
+    delta: i32,
+    min: Option<u32>,
+    max: Option<u32>,
+    increment: Option<u32>,
+) -> u32 {
+    let mut delta = delta as i64;
+    if let Some(increment) = increment.filter(|&increment| increment > 1) {
+        let increment = increment as i64;
+        delta = delta.signum() * (delta.abs() + increment - 1) / increment * increment;
+    }
+
+    let min = min.unwrap_or(1).max(1) as i64;
+    let max = max.unwrap_or(u32::MAX).max(min as u32) as i64;
+    (size as i64 + delta).clamp(min, max) as u32
+}
+
+/// Convert a position relative to a monitor's origin to desktop coordinates.
+fn position_on_monitor(
+    origin: PhysicalPosition<i32>,
+    scale_factor: f64,
+    position: Position,
+) -> PhysicalPosition<i32> {
+    let offset = position.to_physical::<i32>(scale_factor);
+    PhysicalPosition::new(origin.x + offset.x, origin.y + offset.y)
+}
+
+/// Whether `size`, computed at `expected_scale`, is a different logical size at `scale_factor`.
+///
+/// Differences below a logical pixel are rounding and don't count.
+fn surface_size_scale_mismatch(size: Size, expected_scale: f64, scale_factor: f64) -> bool {
+    let expected = size.to_logical::<f64>(expected_scale);
+    let actual = size.to_logical::<f64>(scale_factor);
+    (expected.width - actual.width).abs() >= 1.0 || (expected.height - actual.height).abs() >= 1.0
+}
+
 /// Wrapper for [`rwh_06::RawWindowHandle`] for [`WindowAttributes::parent_window`].
 ///
 /// # Safety
@@ -543,6 +843,22 @@ pub trait Window: AsAny + Send + Sync + fmt::Debug {
     /// [`MonitorHandleProvider::scale_factor`]: crate::monitor::MonitorHandleProvider::scale_factor.
     fn scale_factor(&self) -> f64;
 
+    /// Returns the exact fractional scale of the window as a reduced `(numerator, denominator)`
+    /// pair, e.g. `(3, 2)` for 150%.
+    ///
+    /// This is only meaningful on backends doing client-side fractional scaling, where it avoids
+    /// the rounding of [`Window::scale_factor`] when computing buffer sizes. Returns `None` when
+    /// the scale isn't known as a fraction.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** Reported from the wp-fractional-scale protocol, `None` when the compositor
+    ///   doesn't support it.
+    /// - **Other platforms:** Always returns `None`.
+    fn fractional_scale(&self) -> Option<(u32, u32)> {
+        None
+    }
+
     /// Queues a [`WindowEvent::RedrawRequested`] event to be emitted that aligns with the windowing
     /// system drawing loop.
     ///
@@ -605,6 +921,28 @@ pub trait Window: AsAny + Send + Sync + fmt::Debug {
     /// [`WindowEvent::RedrawRequested`]: crate::event::WindowEvent::RedrawRequested
     fn pre_present_notify(&self);
 
+    /// Cap the rate of [`WindowEvent::RedrawRequested`] for this window, in frames per second.
+    ///
+    /// Redraws requested faster than the cap are coalesced and delivered once the interval since
+    /// the previous redraw elapsed, even under [`ControlFlow::Poll`]. This is useful to keep an
+    /// always-animating window from redrawing an idle one at the same rate.
+    ///
+    /// This caps the rate, it doesn't pace redraws: a window which requests redraws less often
+    /// isn't redrawn more often, and the cap doesn't replace vsync. `None`, the default, and
+    /// `Some(0)` mean unthrottled.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** Redraws stay aligned with `requestAnimationFrame`, so the effective rate is the
+    ///   cap rounded down to a divisor of the display refresh rate.
+    /// - **Wayland / Windows / macOS / iOS / Android / Orbital:** Unsupported.
+    ///
+    /// [`WindowEvent::RedrawRequested`]: crate::event::WindowEvent::RedrawRequested
+    /// [`ControlFlow::Poll`]: crate::event_loop::ControlFlow::Poll
+    fn set_max_redraw_rate(&self, rate: Option<u32>) {
+        let _ = rate;
+    }
+
     /// Reset the dead key state of the keyboard.
     ///
     /// This is useful when a dead key is bound to trigger an action. Then
@@ -612,7 +950,14 @@ pub trait Window: AsAny + Send + Sync + fmt::Debug {
     /// follow-up text input won't be affected by the dead key.
     ///
     /// ## Platform-specific
-    /// - **Web, macOS:** Does nothing
+    ///
+    /// - **macOS:** Only works while IME is enabled with [`Window::request_ime_update`]. It
+    ///   discards the marked text of the view's input context, which holds the pending dead key,
+    ///   and clears an IME preedit in progress with an empty
+    ///   [`Ime::Preedit`][crate::event::Ime::Preedit]. Otherwise, the system composes dead keys
+    ///   into the key event's text on its own, and a pending dead key can't be dropped.
+    /// - **Web:** Unsupported, browsers don't expose their dead key state. A warning is logged the
+    ///   first time this is called.
     // ---------------------------
     // Developers' Note: If this cannot be implemented on every desktop platform
     // at least, then this function should be provided through a platform specific
@@ -651,9 +996,50 @@ pub trait Window: AsAny + Send + Sync + fmt::Debug {
     /// ## Platform-specific
     ///
     /// - **Web:** Returns the top-left coordinates relative to the viewport.
-    /// - **Android / Wayland:** Always returns [`RequestError::NotSupported`].
+    /// - **Android / Wayland:** Always returns [`RequestError::NotSupported`]. Windows on Wayland
+    ///   are always top-level surfaces, which the compositor places without telling the client.
     fn outer_position(&self) -> Result<PhysicalPosition<i32>, RequestError>;
 
+    /// The [`outer_position`] in logical coordinates, converted with the window's
+    /// [`scale_factor`].
+    ///
+    /// This is useful to persist the window layout in the same coordinates it's restored with,
+    /// independently of the scale factor of the monitor the window is on.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** Returns the top-left coordinates relative to the viewport.
+    /// - **Android / Wayland:** Always returns [`RequestError::NotSupported`].
+    ///
+    /// [`outer_position`]: Self::outer_position
+    /// [`scale_factor`]: Self::scale_factor
+    fn outer_position_logical(&self) -> Result<LogicalPosition<f64>, RequestError> {
+        Ok(self.outer_position()?.to_logical(self.scale_factor()))
+    }
+
+    /// The position of the top-left hand corner of the surface relative to the top-left hand
+    /// corner of the desktop.
+    ///
+    /// This is the surface origin in the same coordinate system as [`Window::outer_position`], and
+    /// is useful for positioning overlays relative to the rendered content. Prefer this over
+    /// adding [`Window::surface_position`] to [`Window::outer_position`] manually, since backends
+    /// with a native way to query the surface origin use it directly.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Uses `ClientToScreen`.
+    /// - **macOS:** Uses the window's content rectangle in screen space.
+    /// - **Web:** Returns the top-left coordinates relative to the viewport.
+    /// - **Android / Wayland:** Always returns [`RequestError::NotSupported`].
+    fn surface_outer_position(&self) -> Result<PhysicalPosition<i32>, RequestError> {
+        let outer_position = self.outer_position()?;
+        let surface_position = self.surface_position();
+        Ok(PhysicalPosition::new(
+            outer_position.x + surface_position.x,
+            outer_position.y + surface_position.y,
+        ))
+    }
+
     /// Sets the position of the window on the desktop.
     ///
     /// See [`Window::outer_position`] for more information about the coordinates.
@@ -682,6 +1068,33 @@ pub trait Window: AsAny + Send + Sync + fmt::Debug {
     /// [`transform`]: https://developer.mozilla.org/en-US/docs/Web/CSS/transform
     fn set_outer_position(&self, position: Position);
 
+    /// Sets the position of the window relative to the top-left corner of `monitor`.
+    ///
+    /// This is [`Window::set_outer_position`] with `position` offset by the monitor's
+    /// [`position`][crate::monitor::MonitorHandleProvider::position], so that e.g.
+    /// `PhysicalPosition::new(0, 0)` puts the window in the top-left corner of `monitor`, even
+    /// when the monitor has a negative origin. A logical `position` is converted using the
+    /// monitor's scale factor. Like [`Window::set_outer_position`], this automatically
+    /// un-maximizes the window if it's maximized.
+    ///
+    /// The window isn't moved if the position of `monitor` is unknown.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS:** The position is offset in points, as the desktop is laid out in points, so the
+    ///   window lands on `monitor` whatever the scale factor of the window.
+    /// - **Android / Wayland:** Unsupported.
+    /// - **Web:** Unsupported without `detailed monitor permissions`.
+    fn set_position_on_monitor(&self, monitor: &MonitorHandle, position: Position) {
+        let Some(origin) = monitor.position() else {
+            tracing::warn!("Can't position window on a monitor without a known position");
+            return;
+        };
+        self.set_outer_position(
+            position_on_monitor(origin, monitor.scale_factor(), position).into(),
+        );
+    }
+
     /// Returns the size of the window's render-able surface.
     ///
     /// This is the dimensions you should pass to things like Wgpu or Glutin when configuring the
@@ -695,6 +1108,9 @@ pub trait Window: AsAny + Send + Sync + fmt::Debug {
     /// ## Platform-specific
     ///
     /// - **Web:** Returns the size of the canvas element. Doesn't account for CSS [`transform`].
+    /// - **Wayland:** Returns the size requested with [`WindowAttributes::with_surface_size`] or
+    ///   [`Window::request_surface_size`] until the compositor configured the window, the size is
+    ///   only authoritative after the first [`WindowEvent::SurfaceResized`].
     ///
     /// [`transform`]: https://developer.mozilla.org/en-US/docs/Web/CSS/transform
     /// [`WindowEvent::SurfaceResized`]: crate::event::WindowEvent::SurfaceResized
@@ -717,6 +1133,9 @@ pub trait Window: AsAny + Send + Sync + fmt::Debug {
     ///
     /// The request could automatically un-maximize the window if it's maximized.
     ///
+    /// A zero width or height is clamped to 1, with a warning, and the returned size reflects the
+    /// clamped size.
+    ///
     /// ```no_run
     /// # use dpi::{LogicalSize, PhysicalSize};
     /// # use winit_core::window::Window;
@@ -738,6 +1157,62 @@ pub trait Window: AsAny + Send + Sync + fmt::Debug {
     #[must_use]
     fn request_surface_size(&self, size: Size) -> Option<PhysicalSize<u32>>;
 
+    /// Like [`Window::request_surface_size`], but warns when `size` was computed for a scale
+    /// factor other than the window's.
+    ///
+    /// `expected_scale` is the scale factor the caller assumed when computing `size`, e.g. the one
+    /// of the last [`WindowEvent::ScaleFactorChanged`]. A warning is logged if `size` describes a
+    /// different logical size at the window's current [scale factor](Window::scale_factor), which
+    /// typically happens when a physical size is computed at a stale scale after a DPI change. A
+    /// [`LogicalSize`] never warns, since it doesn't depend on the scale factor.
+    ///
+    /// This is a debugging helper, the size is requested as is either way.
+    ///
+    /// [`WindowEvent::ScaleFactorChanged`]: crate::event::WindowEvent::ScaleFactorChanged
+    #[must_use]
+    fn request_surface_size_checked(
+        &self,
+        size: Size,
+        expected_scale: f64,
+    ) -> Option<PhysicalSize<u32>> {
+        let scale_factor = self.scale_factor();
+        if !dpi::validate_scale_factor(expected_scale) {
+            tracing::warn!(expected_scale, "Requested surface size with an invalid scale factor");
+        } else if surface_size_scale_mismatch(size, expected_scale, scale_factor) {
+            tracing::warn!(
+                ?size,
+                expected_scale,
+                scale_factor,
+                "Requested surface size was computed for a different scale factor than the \
+                 window's"
+            );
+        }
+        self.request_surface_size(size)
+    }
+
+    /// Sets whether to suppress the [`WindowEvent::SurfaceResized`] echo of
+    /// [`Window::request_surface_size`].
+    ///
+    /// When enabled, the [`WindowEvent::SurfaceResized`] resulting directly from a just-issued
+    /// [`Window::request_surface_size`] call is not delivered to the application if it matches
+    /// the requested size, since the application already knows about it. Later resizes driven by
+    /// the system are still delivered.
+    ///
+    /// The echo is only recognized by its size, so if the system happens to resize the window to
+    /// the requested size before the echo arrives (e.g. because the request was ignored), that
+    /// resize is suppressed instead.
+    ///
+    /// The default is `false`.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS / Wayland / iOS / Android / Web / Orbital:** Unsupported.
+    ///
+    /// [`WindowEvent::SurfaceResized`]: crate::event::WindowEvent::SurfaceResized
+    fn set_suppress_resize_echo(&self, suppress: bool) {
+        let _ = suppress;
+    }
+
     /// Returns the size of the entire window.
     ///
     /// These dimensions include window decorations like the title bar and borders. If you don't
@@ -768,7 +1243,11 @@ pub trait Window: AsAny + Send + Sync + fmt::Debug {
     ///
     /// ## Platform-specific
     ///
-    /// - **Android / Orbital / Wayland / Windows / X11:** Unimplemented, returns `(0, 0, 0, 0)`.
+    /// - **Android / Orbital / Windows / X11:** Unimplemented, returns `(0, 0, 0, 0)`.
+    /// - **Wayland:** Reflects the space reserved by the compositor rather than hardware cutouts:
+    ///   the part of the surface which, together with the decorations, doesn't fit the
+    ///   `xdg_toplevel` configure bounds. Decorations themselves are drawn outside of the surface,
+    ///   so they never overlap it.
     ///
     /// ## Example
     ///
@@ -792,6 +1271,28 @@ pub trait Window: AsAny + Send + Sync + fmt::Debug {
     /// ```
     fn safe_area(&self) -> PhysicalInsets<u32>;
 
+    /// Request margins around the window, carved out of the surface.
+    ///
+    /// Unlike [`Window::safe_area`], which reports the part of the surface that is unobstructed,
+    /// this tells the windowing system that the edges of the surface aren't part of the window,
+    /// e.g. because the application draws a shadow or padding there. The margins are still part
+    /// of the surface: [`Window::surface_size`] includes them and the application draws them, but
+    /// the window manager or compositor uses the remaining area to place, tile and snap the
+    /// window. `None` removes the margins.
+    ///
+    /// Whether the margins are honored depends on the window manager or compositor.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** Applied with `xdg_surface::set_window_geometry`. Sizes picked by the
+    ///   compositor, e.g. when maximized or tiled, exclude the margins, and the surface is grown by
+    ///   them. Ignored while winit draws client-side decorations.
+    /// - **X11:** Applied with `_GTK_FRAME_EXTENTS`, only honored by some window managers.
+    /// - **Windows / macOS / iOS / Android / Web / Orbital:** Unsupported.
+    fn set_surface_margins(&self, margins: Option<PhysicalInsets<u32>>) {
+        let _ = margins;
+    }
+
     /// Sets a minimum dimensions of the window's surface.
     ///
     /// ```no_run
@@ -853,6 +1354,8 @@ pub trait Window: AsAny + Send + Sync + fmt::Debug {
     ///
     /// ## Platform-specific
     ///
+    /// - **X11:** Titles set within one event loop iteration are coalesced, only the last one is
+    ///   written to the window at the end of the iteration.
     /// - **iOS / Android:** Unsupported.
     fn set_title(&self, title: &str);
 
@@ -869,8 +1372,9 @@ pub trait Window: AsAny + Send + Sync + fmt::Debug {
     ///
     /// - **macOS:** This will reset the window's background color.
     /// - **Web / iOS / Android:** Unsupported.
-    /// - **X11:** Can only be set while building the window, with
-    ///   [`WindowAttributes::with_transparent`].
+    /// - **X11:** Transparency can only be toggled at runtime when the window was created with
+    ///   [`WindowAttributes::with_transparent`], as that picks the ARGB visual needed for it.
+    ///   Otherwise, a warning is logged and the call has no effect.
     fn set_transparent(&self, transparent: bool);
 
     /// Change the window blur state.
@@ -891,9 +1395,23 @@ pub trait Window: AsAny + Send + Sync + fmt::Debug {
     ///
     /// ## Platform-specific
     ///
-    /// - **Android / Wayland / Web:** Unsupported.
+    /// - **Android / Web:** Unsupported.
+    /// - **Wayland:** Windows can't be hidden, `true` only realizes a window created with
+    ///   [`WindowAttributes::with_realize_deferred`].
     fn set_visible(&self, visible: bool);
 
+    /// Realizes a window created with [`WindowAttributes::with_realize_deferred`], presenting it
+    /// to the compositor.
+    ///
+    /// The window is shown if it was created [visible][WindowAttributes::with_visible] and wasn't
+    /// hidden since. This does nothing for windows that are already realized.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows / macOS / iOS / Android / Web / Orbital:** Unsupported, windows are always
+    ///   realized.
+    fn realize(&self) {}
+
     /// Gets the window's current visibility state.
     ///
     /// `None` means it couldn't be determined, so it is not recommended to use this to drive your
@@ -912,6 +1430,11 @@ pub trait Window: AsAny + Send + Sync + fmt::Debug {
     /// entering fullscreen mode, etc. Also, the window could still be resized by calling
     /// [`Window::request_surface_size`].
     ///
+    /// A non-resizable window can't be maximized by the user, so [`WindowButtons::MAXIMIZE`] is
+    /// removed from the [`enabled_buttons`] until the window is made resizable again. The buttons
+    /// requested with [`set_enabled_buttons`] are kept, so making the window resizable restores the
+    /// maximize button only if it was requested.
+    ///
     /// ## Platform-specific
     ///
     /// This only has an effect on desktop platforms.
@@ -920,30 +1443,40 @@ pub trait Window: AsAny + Send + Sync + fmt::Debug {
     /// - **iOS / Android / Web:** Unsupported.
     ///
     /// [`WindowEvent::SurfaceResized`]: crate::event::WindowEvent::SurfaceResized
+    /// [`enabled_buttons`]: Self::enabled_buttons
+    /// [`set_enabled_buttons`]: Self::set_enabled_buttons
     fn set_resizable(&self, resizable: bool);
 
     /// Gets the window's current resizable state.
     ///
     /// ## Platform-specific
     ///
-    /// - **X11:** Not implemented.
+    /// - **X11:** Always `true` on Xfwm, see [`Window::set_resizable`].
     /// - **iOS / Android / Web:** Unsupported.
     fn is_resizable(&self) -> bool;
 
     /// Sets the enabled window buttons.
     ///
+    /// [`WindowButtons::MAXIMIZE`] only takes effect while the window is resizable, see
+    /// [`Window::set_resizable`].
+    ///
     /// ## Platform-specific
     ///
-    /// - **Wayland / X11 / Orbital:** Not implemented.
+    /// - **X11:** Relies on Motif hints, which not all window managers honor.
+    /// - **Wayland / Orbital:** Not implemented.
     /// - **Web / iOS / Android:** Unsupported.
     fn set_enabled_buttons(&self, buttons: WindowButtons);
 
     /// Gets the enabled window buttons.
     ///
+    /// [`WindowButtons::MAXIMIZE`] is never enabled for a non-resizable window.
+    ///
     /// ## Platform-specific
     ///
-    /// - **Wayland / X11 / Orbital:** Not implemented. Always returns [`WindowButtons::all`].
-    /// - **Web / iOS / Android:** Unsupported. Always returns [`WindowButtons::all`].
+    /// - **Wayland:** Only reflects the resizable state, otherwise returns
+    ///   [`WindowButtons::default`].
+    /// - **Orbital:** Not implemented. Always returns [`WindowButtons::default`].
+    /// - **Web / iOS / Android:** Unsupported. Always returns [`WindowButtons::default`].
     fn enabled_buttons(&self) -> WindowButtons;
 
     /// Minimize the window, or put it back from the minimized state.
@@ -970,6 +1503,8 @@ pub trait Window: AsAny + Send + Sync + fmt::Debug {
 
     /// Sets the window to maximized or back.
     ///
+    /// This is the same as calling [`Window::set_maximized_axes`] with `maximized` for both axes.
+    ///
     /// ## Platform-specific
     ///
     /// - **iOS / Android / Web:** Unsupported.
@@ -977,13 +1512,46 @@ pub trait Window: AsAny + Send + Sync + fmt::Debug {
 
     /// Gets the window's current maximized state.
     ///
+    /// Returns `true` only when the window is maximized along both axes, see
+    /// [`Window::maximized_axes`].
+    ///
     /// ## Platform-specific
     ///
     /// - **iOS / Android / Web:** Unsupported.
     fn is_maximized(&self) -> bool;
 
+    /// Sets the window to maximized or back, for each axis independently.
+    ///
+    /// Maximizing only one axis lets the window span e.g. the full width of the monitor while
+    /// keeping its height.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows / macOS / Wayland / Orbital:** Only a full maximize is possible, so the window
+    ///   is maximized when both axes are `true` and restored otherwise.
+    /// - **iOS / Android / Web:** Unsupported.
+    fn set_maximized_axes(&self, horizontal: bool, vertical: bool) {
+        self.set_maximized(horizontal && vertical);
+    }
+
+    /// Gets the window's current maximized state as `(horizontal, vertical)`.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows / macOS / Wayland / Orbital:** Both axes always report the same value as
+    ///   [`Window::is_maximized`].
+    /// - **iOS / Android / Web:** Unsupported.
+    fn maximized_axes(&self) -> (bool, bool) {
+        let maximized = self.is_maximized();
+        (maximized, maximized)
+    }
+
     /// Set the window's fullscreen state.
     ///
+    /// Leaving fullscreen restores the position and surface size the window had before entering
+    /// it, switching between fullscreen modes keeps the ones from before the first. See
+    /// [`WindowAttributes::with_restore_on_unfullscreen`] to opt out.
+    ///
     /// ## Platform-specific
     ///
     /// - **macOS:** [`Fullscreen::Exclusive`] provides true exclusive mode with a video mode
@@ -1062,6 +1630,17 @@ pub trait Window: AsAny + Send + Sync + fmt::Debug {
     /// - **Wayland:** The compositor needs to implement `xdg_toplevel_icon`.
     fn set_window_icon(&self, window_icon: Option<Icon>);
 
+    /// Gets the window icon.
+    ///
+    /// This is the last icon set with [`Window::set_window_icon`] or
+    /// [`WindowAttributes::with_window_icon`], it's not read back from the windowing system, so it
+    /// doesn't tell whether the icon is actually shown.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS / Android / Web / macOS / Orbital:** Unsupported. Always returns `None`.
+    fn window_icon(&self) -> Option<Icon>;
+
     /// Set the IME cursor editing area, where the `position` is the top left corner of that area
     /// in surface coordinates and `size` is the size of this area starting from the position. An
     /// example of such area could be a input field in the UI or line in the editor.
@@ -1097,6 +1676,7 @@ pub trait Window: AsAny + Send + Sync + fmt::Debug {
     ///
     /// ## Platform-specific
     ///
+    /// - **X11 / Wayland:** The area is clamped to the surface.
     /// - **iOS / Android / Web / Orbital:** Unsupported.
     ///
     /// [chinese]: https://support.apple.com/guide/chinese-input-method/use-the-candidate-window-cim12992/104/mac/12.0
@@ -1122,6 +1702,11 @@ pub trait Window: AsAny + Send + Sync + fmt::Debug {
     ///
     /// IME is **not** allowed by default.
     ///
+    /// Allowing IME enables it with exactly the capabilities the old API supported, so
+    /// [`Window::ime_capabilities`] then returns
+    /// `Some(ImeCapabilities::new().with_hint_and_purpose().with_cursor_area())`. In particular,
+    /// surrounding text isn't enabled, use [`Window::request_ime_update`] for it.
+    ///
     /// ## Platform-specific
     ///
     /// - **macOS:** IME must be enabled to receive text-input where dead-key sequences are
@@ -1134,31 +1719,14 @@ pub trait Window: AsAny + Send + Sync + fmt::Debug {
     /// [`KeyboardInput`]: crate::event::WindowEvent::KeyboardInput
     #[deprecated = "use Window::request_ime_update instead"]
     fn set_ime_allowed(&self, allowed: bool) {
-        let action = if allowed {
-            let position = LogicalPosition::new(0, 0);
-            let size = LogicalSize::new(0, 0);
-            let ime_caps = ImeCapabilities::new().with_hint_and_purpose().with_cursor_area();
-            let request_data = ImeRequestData {
-                hint_and_purpose: Some((ImeHint::NONE, ImePurpose::Normal)),
-                // WARNING: there's nothing sensible to use here by default.
-                cursor_area: Some((position.into(), size.into())),
-                ..ImeRequestData::default()
-            };
-
-            // Enable all capabilities to reflect the old behavior.
-            ImeRequest::Enable(ImeEnableRequest::new(ime_caps, request_data).unwrap())
-        } else {
-            ImeRequest::Disable
-        };
-
-        let _ = self.request_ime_update(action);
+        let _ = self.request_ime_update(ime_allowed_request(allowed));
     }
 
     /// Sets the IME purpose for the window using [`ImePurpose`].
     ///
     /// ## Platform-specific
     ///
-    /// - **iOS / Android / Web / Windows / X11 / macOS / Orbital:** Unsupported.
+    /// - **Web / Windows / X11 / macOS / Orbital:** Unsupported.
     #[deprecated = "use Window::request_ime_update instead"]
     fn set_ime_purpose(&self, purpose: ImePurpose) {
         if self.ime_capabilities().map(|caps| caps.hint_and_purpose()).unwrap_or(false) {
@@ -1183,6 +1751,11 @@ pub trait Window: AsAny + Send + Sync + fmt::Debug {
     ///
     /// IME is **not** enabled by default.
     ///
+    /// The [cursor area](ImeRequestData::with_cursor_area) is interpreted in the surface
+    /// coordinates of this window at its current [scale factor](Window::scale_factor), and
+    /// converted to physical coordinates with it before being sent to the IME, see
+    /// [`ImeRequest::to_physical`].
+    ///
     /// ## Example
     ///
     /// ```no_run
@@ -1217,11 +1790,16 @@ pub trait Window: AsAny + Send + Sync + fmt::Debug {
     /// ```
     fn request_ime_update(&self, request: ImeRequest) -> Result<(), ImeRequestError>;
 
-    /// Return enabled by the client [`ImeCapabilities`] for this window.
+    /// Return the [`ImeCapabilities`] enabled for this window.
+    ///
+    /// These are the capabilities requested with [`ImeRequest::Enable`] that the backend
+    /// supports, as reported by [`Ime::Enabled`].
     ///
     /// When the IME is not yet enabled it'll return `None`.
     ///
     /// By default IME is disabled, thus will return `None`.
+    ///
+    /// [`Ime::Enabled`]: crate::event::Ime::Enabled
     fn ime_capabilities(&self) -> Option<ImeCapabilities>;
 
     /// Brings the window to the front and sets input focus. Has no effect if the window is
@@ -1258,6 +1836,30 @@ pub trait Window: AsAny + Send + Sync + fmt::Debug {
     /// - **Wayland:** Requires `xdg_activation_v1` protocol, `None` has no effect.
     fn request_user_attention(&self, request_type: Option<UserAttentionType>);
 
+    /// Sets whether the application is asking the user to confirm closing the window.
+    ///
+    /// [`WindowEvent::CloseRequested`] is only a request, and the application may want to show a
+    /// confirmation prompt, e.g. about unsaved changes, before dropping the window. Marking the
+    /// confirmation as pending lets the backend tell the system that the window is still
+    /// responsive, so it doesn't get flagged as hung while the prompt is up. Set it back to `false`
+    /// once the user made a decision.
+    ///
+    /// This is purely a hint, the window is never closed by winit itself, and calling
+    /// [`ActiveEventLoop::exit`] still exits the event loop while a confirmation is pending.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Windows:** Disables window ghosting, which replaces windows not processing messages with
+    ///   a frozen copy offering to close them. This can only be done for the whole process and
+    ///   can't be reverted, so setting it back to `false` has no effect.
+    /// - **macOS / X11 / Wayland / iOS / Android / Web / Orbital:** Unsupported.
+    ///
+    /// [`WindowEvent::CloseRequested`]: crate::event::WindowEvent::CloseRequested
+    /// [`ActiveEventLoop::exit`]: crate::event_loop::ActiveEventLoop::exit
+    fn set_close_confirmation_pending(&self, pending: bool) {
+        let _ = pending;
+    }
+
     /// Set or override the window theme.
     ///
     /// Specify `None` to reset the theme to the system default.
@@ -1268,6 +1870,8 @@ pub trait Window: AsAny + Send + Sync + fmt::Debug {
     ///   get the system preference.
     /// - **X11:** Sets `_GTK_THEME_VARIANT` hint to `dark` or `light` and if `None` is used, it
     ///   will default to  [`Theme::Dark`].
+    /// - **Windows:** The high contrast variants are applied like their regular counterparts, since
+    ///   only the user can turn on a high contrast theme.
     /// - **iOS / Android / Web / Orbital:** Unsupported.
     fn set_theme(&self, theme: Option<Theme>);
 
@@ -1292,20 +1896,33 @@ pub trait Window: AsAny + Send + Sync + fmt::Debug {
     /// [`NSWindowSharingNone`]: https://developer.apple.com/documentation/appkit/nswindowsharingtype/nswindowsharingnone
     fn set_content_protected(&self, protected: bool);
 
+    /// Gets whether the window contents are protected from being captured by other apps.
+    ///
+    /// See [`Window::set_content_protected`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **iOS / Android / X11 / Wayland / Web / Orbital:** Unsupported. Always returns `false`.
+    fn is_content_protected(&self) -> bool;
+
     /// Gets the current title of the window.
     ///
     /// ## Platform-specific
     ///
-    /// - **iOS / Android / x11 / Wayland / Web:** Unsupported. Always returns an empty string.
+    /// - **X11:** Returns the last title set, even if it wasn't written to the window yet.
+    /// - **iOS / Android / Wayland / Web:** Unsupported. Always returns an empty string.
     fn title(&self) -> String;
 
     /// Modifies the cursor icon of the window.
     ///
+    /// Use [`Cursor::with_fallback`] to provide cursors to show if the requested one can't be
+    /// shown, otherwise the previous cursor is kept.
+    ///
     /// ## Platform-specific
     ///
     /// - **iOS / Android / Orbital:** Unsupported.
     /// - **Web:** Custom cursors have to be loaded and decoded first, until then the previous
-    ///   cursor is shown.
+    ///   cursor is shown. If loading fails, the next fallback is used.
     fn set_cursor(&self, cursor: Cursor);
 
     /// Changes the position of the cursor in window coordinates.
@@ -1324,7 +1941,10 @@ pub trait Window: AsAny + Send + Sync + fmt::Debug {
     ///
     /// ## Platform-specific
     ///
-    /// - **Wayland**: Cursor must be in [`CursorGrabMode::Locked`].
+    /// - **X11**: Before the window is mapped, the position is queued and applied once it's shown.
+    /// - **Wayland**: Cursor must be in [`CursorGrabMode::Locked`]. While the lock is requested but
+    ///   not in effect yet, e.g. before the pointer entered the window, the position is queued and
+    ///   applied once it is.
     /// - **iOS / Android / Web / Orbital:** Always returns an [`RequestError::NotSupported`].
     fn set_cursor_position(&self, position: Position) -> Result<(), RequestError>;
 
@@ -1343,6 +1963,19 @@ pub trait Window: AsAny + Send + Sync + fmt::Debug {
     ///     .unwrap();
     /// # }
     /// ```
+    ///
+    /// The requested mode is kept until it's changed, and the grab is re-applied when the window
+    /// comes back from a fullscreen, focus or visibility transition that released it. Passing
+    /// [`CursorGrabMode::None`] releases the grab and clears the requested mode.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** The grab is re-applied when the window gets the focus, is mapped again, or is
+    ///   resized after its fullscreen state changed. A re-applied grab that fails is only logged.
+    /// - **Windows:** The cursor clip follows the window when it's moved or resized, and is
+    ///   re-applied when the window gets the focus.
+    /// - **macOS:** The grab is released when the window loses the focus, and re-applied when it
+    ///   gets it back.
     fn set_cursor_grab(&self, mode: CursorGrabMode) -> Result<(), RequestError>;
 
     /// Modifies the cursor's visibility.
@@ -1359,10 +1992,34 @@ pub trait Window: AsAny + Send + Sync + fmt::Debug {
     /// - **iOS / Android:** Unsupported.
     fn set_cursor_visible(&self, visible: bool);
 
+    /// Sets whether device events are delivered while this window is focused.
+    ///
+    /// [`DeviceEvent`]s aren't tied to a window, so with multiple windows, e.g. one of them
+    /// locking the cursor, raw [`DeviceEvent::PointerMotion`] arrives no matter which window is
+    /// focused. Passing `false` drops device events while this window is focused, so only windows
+    /// which opted in see them. The default is `true`.
+    ///
+    /// This only refines [`DeviceEvents::WhenFocused`], with [`DeviceEvents::Always`] device
+    /// events are delivered regardless of this setting.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Web:** Device events are delivered while any focused canvas receives them.
+    /// - **Wayland / Windows / macOS / iOS / Android / Orbital:** Unsupported.
+    ///
+    /// [`DeviceEvent`]: crate::event::DeviceEvent
+    /// [`DeviceEvent::PointerMotion`]: crate::event::DeviceEvent::PointerMotion
+    /// [`DeviceEvents::WhenFocused`]: crate::event_loop::DeviceEvents::WhenFocused
+    /// [`DeviceEvents::Always`]: crate::event_loop::DeviceEvents::Always
+    fn set_receive_device_events(&self, receive: bool) {
+        let _ = receive;
+    }
+
     /// Moves the window with the left mouse button until the button is released.
     ///
     /// There's no guarantee that this will work unless the left mouse button was pressed
-    /// immediately before this function is called.
+    /// immediately before this function is called. A [`WindowEvent::MoveStarted`] is sent once the
+    /// move actually started, and a [`WindowEvent::MoveEnded`] when it's done.
     ///
     /// ## Platform-specific
     ///
@@ -1370,6 +2027,9 @@ pub trait Window: AsAny + Send + Sync + fmt::Debug {
     /// - **Wayland:** Requires the cursor to be inside the window to be dragged.
     /// - **macOS:** May prevent the button release event to be triggered.
     /// - **iOS / Android / Web:** Always returns an [`RequestError::NotSupported`].
+    ///
+    /// [`WindowEvent::MoveStarted`]: crate::event::WindowEvent::MoveStarted
+    /// [`WindowEvent::MoveEnded`]: crate::event::WindowEvent::MoveEnded
     fn drag_window(&self) -> Result<(), RequestError>;
 
     /// Resizes the window with the left mouse button until the button is released.
@@ -1379,10 +2039,33 @@ pub trait Window: AsAny + Send + Sync + fmt::Debug {
     ///
     /// ## Platform-specific
     ///
+    /// - **X11:** If the left mouse button isn't pressed, e.g. when called from a keyboard
+    ///   shortcut, a keyboard-driven resize is started instead, and `direction` is ignored.
     /// - **macOS:** Always returns an [`RequestError::NotSupported`]
     /// - **iOS / Android / Web:** Always returns an [`RequestError::NotSupported`].
     fn drag_resize_window(&self, direction: ResizeDirection) -> Result<(), RequestError>;
 
+    /// Changes the surface size by `delta`, e.g. to step a keyboard-driven resize.
+    ///
+    /// The new size respects the minimum and maximum surface sizes, and `delta` is rounded away
+    /// from zero to a multiple of the [surface resize increments], so every call changes the size
+    /// by at least one increment until a limit is reached.
+    ///
+    /// Like with [`Window::request_surface_size`], the new size is delivered with a
+    /// [`WindowEvent::SurfaceResized`].
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **macOS / Wayland / iOS / Android / Web / Orbital:** Always returns an
+    ///   [`RequestError::NotSupported`].
+    ///
+    /// [surface resize increments]: Window::set_surface_resize_increments
+    /// [`WindowEvent::SurfaceResized`]: crate::event::WindowEvent::SurfaceResized
+    fn resize_by(&self, delta: PhysicalSize<i32>) -> Result<(), RequestError> {
+        let _ = delta;
+        Err(NotSupportedError::new("resize_by is not supported").into())
+    }
+
     /// Show [window menu] at a specified position in surface coordinates.
     ///
     /// This is the context menu that is normally shown when interacting with
@@ -1420,13 +2103,16 @@ pub trait Window: AsAny + Send + Sync + fmt::Debug {
 
     /// Returns the primary monitor of the system.
     ///
-    /// Returns `None` if it can't identify any monitor as a primary one.
-    ///
-    /// This is the same as [`ActiveEventLoop::primary_monitor`], and is provided for convenience.
+    /// Unlike [`ActiveEventLoop::primary_monitor`], this falls back to
+    /// [`current_monitor`][Self::current_monitor] when the system doesn't identify any monitor as
+    /// a primary one, so that placing a window relative to "a" monitor rarely needs a `None` case.
+    /// Returns `None` only if neither can be determined.
     ///
     /// ## Platform-specific
     ///
-    /// - **Wayland:** Always returns `None`.
+    /// - **Wayland:** There's no primary monitor, the top-left-most monitor is returned as a best
+    ///   guess.
+    /// - **Web:** Returns the current monitor without `detailed monitor permissions`.
     ///
     /// [`ActiveEventLoop::primary_monitor`]: crate::event_loop::ActiveEventLoop::primary_monitor
     fn primary_monitor(&self) -> Option<MonitorHandle>;
@@ -1482,8 +2168,13 @@ pub enum CursorGrabMode {
     ///
     /// ## Platform-specific
     ///
-    /// - **macOS:** Not implemented. Always returns [`RequestError::NotSupported`] for now.
+    /// - **macOS:** The cursor is dissociated from the mouse like with [`Locked`], and winit moves
+    ///   it with the mouse deltas, clamped to the surface. [`DeviceEvent::PointerMotion`] still
+    ///   reports the unclamped deltas.
     /// - **iOS / Android / Web:** Always returns an [`RequestError::NotSupported`].
+    ///
+    /// [`Locked`]: Self::Locked
+    /// [`DeviceEvent::PointerMotion`]: crate::event::DeviceEvent::PointerMotion
     Confined,
 
     /// The cursor is locked inside the window area to the certain position.
@@ -1529,6 +2220,16 @@ impl From<ResizeDirection> for CursorIcon {
 }
 
 /// The theme variant to use.
+///
+/// ## Platform-specific
+///
+/// - **Windows:** The high contrast variants are reported while a high contrast theme is active,
+///   depending on whether its window background is dark.
+/// - **X11 / Wayland:** Unsupported, the contrast setting of the freedesktop appearance portal
+///   isn't read. The high contrast variants are never reported, and are applied like their regular
+///   counterparts.
+/// - **macOS / Web:** The high contrast variants are never reported, and are applied like their
+///   regular counterparts.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Theme {
@@ -1537,6 +2238,24 @@ pub enum Theme {
 
     /// Use the dark variant.
     Dark,
+
+    /// Use the high contrast light variant, for accessibility.
+    HighContrastLight,
+
+    /// Use the high contrast dark variant, for accessibility.
+    HighContrastDark,
+}
+
+impl Theme {
+    /// Whether this is a dark variant, i.e. [`Theme::Dark`] or [`Theme::HighContrastDark`].
+    pub fn is_dark(self) -> bool {
+        matches!(self, Self::Dark | Self::HighContrastDark)
+    }
+
+    /// Whether this is a high contrast variant.
+    pub fn is_high_contrast(self) -> bool {
+        matches!(self, Self::HighContrastLight | Self::HighContrastDark)
+    }
 }
 
 /// ## Platform-specific
@@ -1570,6 +2289,23 @@ bitflags::bitflags! {
         const CLOSE  = 1 << 0;
         const MINIMIZE  = 1 << 1;
         const MAXIMIZE  = 1 << 2;
+        /// The context help button, see [`WindowEvent::HelpRequested`].
+        ///
+        /// ## Platform-specific
+        ///
+        /// - **Windows:** The system only shows the button on windows without minimize and
+        ///   maximize buttons.
+        /// - **macOS / X11 / Wayland / iOS / Android / Web / Orbital:** Ignored.
+        ///
+        /// [`WindowEvent::HelpRequested`]: crate::event::WindowEvent::HelpRequested
+        const HELP  = 1 << 3;
+    }
+}
+
+impl Default for WindowButtons {
+    /// The close, minimize and maximize buttons, [`WindowButtons::HELP`] is opt-in.
+    fn default() -> Self {
+        Self::CLOSE | Self::MINIMIZE | Self::MAXIMIZE
     }
 }
 
@@ -1607,7 +2343,11 @@ pub enum WindowLevel {
 ///
 /// ## Platform-specific
 ///
-/// - **iOS / Android / Web / Windows / X11 / macOS / Orbital:** Unsupported.
+/// - **Wayland:** Sent as the content purpose of `text-input-v3`.
+/// - **iOS:** Picks the keyboard type, [`Password`][Self::Password] and [`Pin`][Self::Pin] also
+///   hide the entered text.
+/// - **Android:** Sets the input type of the editor, only supported with `GameActivity`.
+/// - **Web / Windows / X11 / macOS / Orbital:** Unsupported.
 #[non_exhaustive]
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -1640,6 +2380,19 @@ pub enum ImePurpose {
     DateTime,
 }
 
+/// The direction of the text being edited with the IME.
+///
+/// See [`ImeRequestData::with_text_direction`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TextDirection {
+    /// Left-to-right text, e.g. Latin or Cyrillic scripts (default).
+    #[default]
+    Ltr,
+    /// Right-to-left text, e.g. Arabic or Hebrew scripts.
+    Rtl,
+}
+
 bitflags! {
     /// IME hints
     ///
@@ -1791,6 +2544,28 @@ impl ImeSurroundingText {
     pub fn anchor(&self) -> usize {
         self.anchor
     }
+
+    /// Moves the caret and the other end of the selection within the text.
+    ///
+    /// The offsets are validated like in [`Self::new`], the selection is left unchanged when this
+    /// fails.
+    pub fn set_selection(
+        &mut self,
+        cursor: usize,
+        anchor: usize,
+    ) -> Result<(), ImeSurroundingTextError> {
+        if !self.text.is_char_boundary(cursor) {
+            return Err(ImeSurroundingTextError::CursorBadPosition);
+        }
+
+        if !self.text.is_char_boundary(anchor) {
+            return Err(ImeSurroundingTextError::AnchorBadPosition);
+        }
+
+        self.cursor = cursor;
+        self.anchor = anchor;
+        Ok(())
+    }
 }
 
 /// Request to send to IME.
@@ -1816,6 +2591,22 @@ pub enum ImeRequest {
     Disable,
 }
 
+impl ImeRequest {
+    /// Converts the cursor area of the request to physical coordinates.
+    ///
+    /// See [`ImeRequestData::to_physical`].
+    pub fn to_physical(self, scale_factor: f64) -> Self {
+        match self {
+            Self::Enable(enable) => Self::Enable(ImeEnableRequest {
+                capabilities: enable.capabilities,
+                request_data: enable.request_data.to_physical(scale_factor),
+            }),
+            Self::Update(request_data) => Self::Update(request_data.to_physical(scale_factor)),
+            Self::Disable => Self::Disable,
+        }
+    }
+}
+
 /// Initial IME request.
 #[derive(Debug, Clone, PartialEq)]
 pub struct ImeEnableRequest {
@@ -1828,11 +2619,19 @@ impl ImeEnableRequest {
     ///
     /// This will return [`None`] if some capability was requested but its initial value was not
     /// set by the user or value was set by the user, but capability not requested.
+    ///
+    /// The [`ImeRequestData::direction`] is part of the cursor area, so setting it also requires
+    /// [`ImeCapabilities::cursor_area()`], and the [`ImeRequestData::selection`] requires
+    /// [`ImeCapabilities::surrounding_text()`].
     pub fn new(capabilities: ImeCapabilities, request_data: ImeRequestData) -> Option<Self> {
         if capabilities.cursor_area() ^ request_data.cursor_area.is_some() {
             return None;
         }
 
+        if request_data.direction.is_some() && !capabilities.cursor_area() {
+            return None;
+        }
+
         if capabilities.hint_and_purpose() ^ request_data.hint_and_purpose.is_some() {
             return None;
         }
@@ -1840,6 +2639,10 @@ impl ImeEnableRequest {
         if capabilities.surrounding_text() ^ request_data.surrounding_text.is_some() {
             return None;
         }
+
+        if request_data.selection.is_some() && !capabilities.surrounding_text() {
+            return None;
+        }
         Some(Self { capabilities, request_data })
     }
 
@@ -1870,12 +2673,19 @@ impl ImeEnableRequest {
 ///
 /// New capabilities may be added to this struct in the future.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ImeCapabilities(ImeCapabilitiesFlags);
 
 impl ImeCapabilities {
     /// Returns a new empty set of capabilities.
-    pub fn new() -> Self {
-        Self::default()
+    pub const fn new() -> Self {
+        Self(ImeCapabilitiesFlags::empty())
+    }
+
+    /// Returns the capabilities contained in both `self` and `other`.
+    #[doc(hidden)]
+    pub const fn intersection(self, other: Self) -> Self {
+        Self(self.0.intersection(other.0))
     }
 
     /// Marks `hint and purpose` as supported.
@@ -1937,6 +2747,7 @@ impl ImeCapabilities {
 }
 
 bitflags! {
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
     #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
     pub(crate) struct ImeCapabilitiesFlags : u8 {
         /// Client supports setting IME hint and purpose.
@@ -1966,10 +2777,22 @@ pub struct ImeRequestData {
     ///
     /// To support updating it, enable [`ImeCapabilities::cursor_area()`].
     pub cursor_area: Option<(Position, Size)>,
+    /// The direction of the text in the [`Self::cursor_area`], used to anchor the candidate box.
+    ///
+    /// Only applied together with [`Self::cursor_area`], `None` keeps the direction of the
+    /// previous request, which is [`TextDirection::Ltr`] when the IME is enabled.
+    pub direction: Option<TextDirection>,
     /// The text surrounding the caret
     ///
     /// To support updating it, enable [`ImeCapabilities::surrounding_text()`].
     pub surrounding_text: Option<ImeSurroundingText>,
+    /// The caret and the other end of the selection within the surrounding text, in bytes.
+    ///
+    /// Applied after [`Self::surrounding_text`], so it can move the selection within the text
+    /// sent by the same or an earlier request.
+    ///
+    /// To support updating it, enable [`ImeCapabilities::surrounding_text()`].
+    pub selection: Option<(usize, usize)>,
 }
 
 impl ImeRequestData {
@@ -2015,6 +2838,7 @@ impl ImeRequestData {
     ///
     /// ## Platform-specific
     ///
+    /// - **X11 / Wayland:** The area is clamped to the surface.
     /// - **iOS / Android / Web / Orbital:** Unsupported.
     ///
     /// [chinese]: https://support.apple.com/guide/chinese-input-method/use-the-candidate-window-cim12992/104/mac/12.0
@@ -2023,6 +2847,68 @@ impl ImeRequestData {
         Self { cursor_area: Some((position, size)), ..self }
     }
 
+    /// Sets the direction of the text in the IME cursor editing area.
+    ///
+    /// The candidate box is anchored where the text ends, which is the right edge of the
+    /// [cursor area](Self::with_cursor_area) for left-to-right scripts, and the left edge for
+    /// right-to-left ones. The direction is kept until it's changed, and is applied together with
+    /// the cursor area.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** Moves the XIM spot location from the bottom right to the bottom left corner of
+    ///   the area for right-to-left text.
+    /// - **Wayland:** `text-input-v3` has no notion of the text direction, so the placement is left
+    ///   to the compositor.
+    /// - **Windows / macOS / iOS / Android / Web / Orbital:** Unsupported.
+    pub fn with_text_direction(self, direction: TextDirection) -> Self {
+        Self { direction: Some(direction), ..self }
+    }
+
+    /// Converts the [cursor area](Self::with_cursor_area) to physical coordinates.
+    ///
+    /// Backends call this with the current scale factor of the window before dispatching the
+    /// request, so a mix of logical and physical units always resolves against the same scale
+    /// factor.
+    pub fn to_physical(self, scale_factor: f64) -> Self {
+        let Some((position, size)) = self.cursor_area else {
+            return self;
+        };
+
+        let position: PhysicalPosition<f64> = position.to_physical(scale_factor);
+        let size: PhysicalSize<f64> = size.to_physical(scale_factor);
+        Self { cursor_area: Some((position.into(), size.into())), ..self }
+    }
+
+    /// Clamps the [cursor area](Self::with_cursor_area) to a surface of the given size.
+    ///
+    /// Some input methods misplace the candidate box when the area is partly outside of the
+    /// surface, so backends clamp the area before sending it to them. An area inside of the surface
+    /// is kept untouched.
+    pub fn clamp_cursor_area(self, surface_size: PhysicalSize<u32>, scale_factor: f64) -> Self {
+        let Some((position, size)) = self.cursor_area else {
+            return self;
+        };
+
+        let position: PhysicalPosition<f64> = position.to_physical(scale_factor);
+        let size: PhysicalSize<f64> = size.to_physical(scale_factor);
+        let clamp = |start: f64, length: f64, max: u32| {
+            let max = max as f64;
+            let clamped_start = start.clamp(0.0, max);
+            (clamped_start, (start + length).clamp(0.0, max) - clamped_start)
+        };
+        let (x, width) = clamp(position.x, size.width, surface_size.width);
+        let (y, height) = clamp(position.y, size.height, surface_size.height);
+
+        if (x, y, width, height) == (position.x, position.y, size.width, size.height) {
+            return self;
+        }
+
+        let cursor_area =
+            (PhysicalPosition::new(x, y).into(), PhysicalSize::new(width, height).into());
+        Self { cursor_area: Some(cursor_area), ..self }
+    }
+
     /// Describes the text surrounding the caret.
     ///
     /// The IME can then continue providing suggestions for the continuation of the existing text,
@@ -2031,6 +2917,21 @@ impl ImeRequestData {
     pub fn with_surrounding_text(self, surrounding_text: ImeSurroundingText) -> Self {
         Self { surrounding_text: Some(surrounding_text), ..self }
     }
+
+    /// Moves the caret and the selection within the last sent
+    /// [surrounding text](Self::with_surrounding_text), without sending the text again.
+    ///
+    /// `cursor` and `anchor` are byte offsets like in [`ImeSurroundingText::new`]. The backend
+    /// validates them against the last surrounding text with
+    /// [`ImeSurroundingText::set_selection`] and discards the update when they're out of it.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **Wayland:** Sent with the `text-input-v3` surrounding text.
+    /// - **Other platforms:** Unsupported, like the surrounding text.
+    pub fn with_selection(self, cursor: usize, anchor: usize) -> Self {
+        Self { selection: Some((cursor, anchor)), ..self }
+    }
 }
 
 /// Error from sending request to IME with
@@ -2095,14 +2996,53 @@ impl ActivationToken {
 #[cfg(test)]
 mod tests {
 
-    use dpi::{LogicalPosition, LogicalSize, Position, Size};
+    use dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize, Position, Size};
 
     use super::{
-        ImeCapabilities, ImeEnableRequest, ImeRequestData, ImeSurroundingText,
-        ImeSurroundingTextError,
+        DragMove, ImeCapabilities, ImeEnableRequest, ImeRequest, ImeRequestData,
+        ImeSurroundingText, ImeSurroundingTextError, TextDirection, WindowAttributes,
+        WindowButtons, clamp_surface_size, ime_allowed_request, position_on_monitor,
+        resize_dimension, surface_size_scale_mismatch,
     };
     use crate::window::{ImeHint, ImePurpose};
 
+    #[test]
+    fn drag_move_started_and_ended() {
+        let mut drag_move = DragMove::default();
+        assert!(!drag_move.pointer_grabbed());
+
+        drag_move.request();
+        // Motion queued before the window system grabbed the pointer doesn't drop the request.
+        assert!(!drag_move.pointer_input(false));
+        assert!(drag_move.pointer_grabbed());
+        assert!(drag_move.pointer_input(false));
+        assert!(!drag_move.pointer_input(false));
+    }
+
+    #[test]
+    fn drag_move_not_started_is_dropped() {
+        let mut drag_move = DragMove::default();
+        drag_move.request();
+
+        // The button was released without the window system starting the move, so a later grab,
+        // e.g. from another client, isn't reported as the move.
+        assert!(!drag_move.pointer_input(true));
+        assert!(!drag_move.pointer_grabbed());
+        assert!(!drag_move.pointer_input(false));
+    }
+
+    #[test]
+    fn ime_allowed_request_caps() {
+        let ImeRequest::Enable(request) = ime_allowed_request(true) else {
+            panic!("allowing IME doesn't enable it");
+        };
+        assert_eq!(
+            request.capabilities(),
+            &ImeCapabilities::new().with_hint_and_purpose().with_cursor_area()
+        );
+        assert_eq!(ime_allowed_request(false), ImeRequest::Disable);
+    }
+
     #[test]
     fn ime_initial_request_caps_match() {
         let position: Position = LogicalPosition::new(0, 0).into();
@@ -2169,7 +3109,25 @@ mod tests {
             .is_some()
         );
 
-        let text: &[u8] = ['a' as u8; 8000].as_slice();
+        assert!(
+            ImeEnableRequest::new(
+                ImeCapabilities::new(),
+                ImeRequestData::default().with_text_direction(TextDirection::Rtl)
+            )
+            .is_none()
+        );
+
+        assert!(
+            ImeEnableRequest::new(
+                ImeCapabilities::new().with_cursor_area(),
+                ImeRequestData::default()
+                    .with_cursor_area(position, size)
+                    .with_text_direction(TextDirection::Rtl)
+            )
+            .is_some()
+        );
+
+        let text: &[u8] = [b'a'; 8000].as_slice();
         let text = std::str::from_utf8(text).unwrap();
         assert_eq!(
             ImeSurroundingText::new(text.into(), 0, 0),
@@ -2186,4 +3144,196 @@ mod tests {
             Err(ImeSurroundingTextError::CursorBadPosition),
         );
     }
+
+    #[test]
+    fn ime_selection_update_validated() {
+        let mut surrounding = ImeSurroundingText::new("граница".into(), 0, 0).unwrap();
+
+        assert_eq!(surrounding.set_selection(4, 2), Ok(()));
+        assert_eq!((surrounding.cursor(), surrounding.anchor()), (4, 2));
+
+        let len = surrounding.text().len();
+        assert_eq!(surrounding.set_selection(len, len), Ok(()));
+
+        assert_eq!(
+            surrounding.set_selection(len + 1, 0),
+            Err(ImeSurroundingTextError::CursorBadPosition),
+        );
+        assert_eq!(
+            surrounding.set_selection(1, 0),
+            Err(ImeSurroundingTextError::CursorBadPosition)
+        );
+        assert_eq!(
+            surrounding.set_selection(0, 3),
+            Err(ImeSurroundingTextError::AnchorBadPosition)
+        );
+        // A failed update leaves the selection untouched.
+        assert_eq!((surrounding.cursor(), surrounding.anchor()), (len, len));
+
+        let surrounding = ImeSurroundingText::new("foobar".into(), 0, 0).unwrap();
+        let caps = ImeCapabilities::new().with_surrounding_text();
+        let request_data = ImeRequestData::default().with_selection(3, 3);
+        assert!(ImeEnableRequest::new(ImeCapabilities::new(), request_data.clone()).is_none());
+        assert!(
+            ImeEnableRequest::new(caps, request_data.with_surrounding_text(surrounding)).is_some()
+        );
+    }
+
+    #[test]
+    fn ime_cursor_area_clamped_to_surface() {
+        let surface_size = PhysicalSize::new(800, 600);
+
+        let request_data = ImeRequestData::default().with_cursor_area(
+            PhysicalPosition::new(-10, 580).into(),
+            PhysicalSize::new(100, 50).into(),
+        );
+        let (position, size) =
+            request_data.clamp_cursor_area(surface_size, 1.0).cursor_area.unwrap();
+        assert_eq!(position.to_physical::<i32>(1.0), PhysicalPosition::new(0, 580));
+        assert_eq!(size.to_physical::<u32>(1.0), PhysicalSize::new(90, 20));
+
+        // Completely outside of the surface.
+        let request_data = ImeRequestData::default().with_cursor_area(
+            LogicalPosition::new(500, 400).into(),
+            LogicalSize::new(10, 10).into(),
+        );
+        let (position, size) =
+            request_data.clamp_cursor_area(surface_size, 2.0).cursor_area.unwrap();
+        assert_eq!(position.to_physical::<i32>(2.0), PhysicalPosition::new(800, 600));
+        assert_eq!(size.to_physical::<u32>(2.0), PhysicalSize::new(0, 0));
+
+        // Valid areas are untouched.
+        let request_data = ImeRequestData::default().with_cursor_area(
+            LogicalPosition::new(10.5, 20.0).into(),
+            LogicalSize::new(100, 20).into(),
+        );
+        assert_eq!(request_data.clone().clamp_cursor_area(surface_size, 2.0), request_data);
+        let request_data = ImeRequestData::default();
+        assert_eq!(request_data.clone().clamp_cursor_area(surface_size, 1.0), request_data);
+    }
+
+    #[test]
+    fn ime_cursor_area_to_physical() {
+        let request_data = ImeRequestData::default().with_cursor_area(
+            LogicalPosition::new(10.0, 20.5).into(),
+            PhysicalSize::new(30, 40).into(),
+        );
+        let request = ImeRequest::Update(request_data).to_physical(2.0);
+        let ImeRequest::Update(request_data) = request else { unreachable!() };
+        assert_eq!(
+            request_data.cursor_area,
+            Some((PhysicalPosition::new(20.0, 41.0).into(), PhysicalSize::new(30.0, 40.0).into())),
+        );
+
+        let request_data = ImeRequestData::default().with_cursor_area(
+            PhysicalPosition::new(5, 5).into(),
+            LogicalSize::new(8.0, 4.0).into(),
+        );
+        let enable =
+            ImeEnableRequest::new(ImeCapabilities::new().with_cursor_area(), request_data).unwrap();
+        let ImeRequest::Enable(enable) = ImeRequest::Enable(enable).to_physical(2.0) else {
+            unreachable!()
+        };
+        assert_eq!(
+            enable.request_data().cursor_area,
+            Some((PhysicalPosition::new(5.0, 5.0).into(), PhysicalSize::new(16.0, 8.0).into())),
+        );
+
+        assert_eq!(ImeRequest::Disable.to_physical(2.0), ImeRequest::Disable);
+    }
+
+    #[test]
+    fn resize_dimension_respects_constraints() {
+        assert_eq!(resize_dimension(100, 10, None, None, None), 110);
+        assert_eq!(resize_dimension(100, -200, None, None, None), 1);
+        assert_eq!(resize_dimension(100, 10, None, Some(105), None), 105);
+        assert_eq!(resize_dimension(100, -10, Some(95), None, None), 95);
+        assert_eq!(resize_dimension(100, 1, None, None, Some(16)), 116);
+        assert_eq!(resize_dimension(100, -17, None, None, Some(16)), 68);
+    }
+
+    #[test]
+    fn zero_surface_size_clamped() {
+        let size = clamp_surface_size(LogicalSize::new(0, 100).into());
+        assert_eq!(size, LogicalSize::new(1.0, 100.0).into());
+        let size = clamp_surface_size(PhysicalSize::new(800, 0).into());
+        assert_eq!(size, PhysicalSize::new(800, 1).into());
+        let size = clamp_surface_size(LogicalSize::new(f64::NAN, -5.0).into());
+        assert_eq!(size, LogicalSize::new(1.0, 1.0).into());
+        let size = clamp_surface_size(LogicalSize::new(0.5, 100.0).into());
+        assert_eq!(size, LogicalSize::new(1.0, 100.0).into());
+
+        // Valid sizes are untouched.
+        let size: Size = LogicalSize::new(400.5, 200.0).into();
+        assert_eq!(clamp_surface_size(size), size);
+    }
+
+    #[test]
+    fn contradictory_buttons_normalized() {
+        let attrs = WindowAttributes::default()
+            .with_decorations(false)
+            .with_enabled_buttons(WindowButtons::CLOSE);
+        assert_eq!(attrs.effective_enabled_buttons(), WindowButtons::empty());
+
+        let attrs = WindowAttributes::default()
+            .with_resizable(false)
+            .with_enabled_buttons(WindowButtons::CLOSE | WindowButtons::MAXIMIZE);
+        assert_eq!(attrs.effective_enabled_buttons(), WindowButtons::CLOSE);
+
+        let attrs = WindowAttributes::default().with_enabled_buttons(WindowButtons::MINIMIZE);
+        assert_eq!(attrs.effective_enabled_buttons(), WindowButtons::MINIMIZE);
+        assert_eq!(
+            WindowAttributes::default().effective_enabled_buttons(),
+            WindowButtons::default()
+        );
+    }
+
+    #[test]
+    fn window_buttons_help_is_opt_in() {
+        assert!(!WindowButtons::default().contains(WindowButtons::HELP));
+        assert_eq!(WindowButtons::all(), WindowButtons::default() | WindowButtons::HELP);
+
+        // Disabling the other buttons keeps the help button, as Windows requires for showing it.
+        let buttons = WindowButtons::all() - WindowButtons::MINIMIZE - WindowButtons::MAXIMIZE;
+        assert_eq!(buttons, WindowButtons::CLOSE | WindowButtons::HELP);
+        let dialog = WindowButtons::default() - WindowButtons::MINIMIZE - WindowButtons::MAXIMIZE;
+        assert_eq!(dialog, WindowButtons::CLOSE);
+
+        // The help button isn't affected by the decorations and resizability otherwise.
+        let attrs = WindowAttributes::default()
+            .with_resizable(false)
+            .with_enabled_buttons(WindowButtons::CLOSE | WindowButtons::HELP);
+        assert_eq!(attrs.effective_enabled_buttons(), WindowButtons::CLOSE | WindowButtons::HELP);
+    }
+
+    #[test]
+    fn position_relative_to_monitor_origin() {
+        let origin = PhysicalPosition::new(1920, -200);
+        let position = position_on_monitor(origin, 1.0, PhysicalPosition::new(10, 20).into());
+        assert_eq!(position, PhysicalPosition::new(1930, -180));
+
+        // Logical positions are scaled with the monitor's scale factor, not the origin.
+        let position = position_on_monitor(origin, 2.0, LogicalPosition::new(10.0, 20.0).into());
+        assert_eq!(position, PhysicalPosition::new(1940, -160));
+
+        let origin = PhysicalPosition::new(-1280, 0);
+        let position = position_on_monitor(origin, 1.5, PhysicalPosition::new(0, 0).into());
+        assert_eq!(position, origin);
+    }
+
+    #[test]
+    fn surface_size_at_stale_scale_mismatches() {
+        // An 800x600 logical size computed at 2x, requested after moving to a 1x monitor.
+        let size: Size = PhysicalSize::new(1600, 1200).into();
+        assert!(surface_size_scale_mismatch(size, 2.0, 1.0));
+        assert!(!surface_size_scale_mismatch(size, 2.0, 2.0));
+
+        // Sub-pixel differences are rounding, not scale confusion.
+        let size: Size = PhysicalSize::new(100, 100).into();
+        assert!(!surface_size_scale_mismatch(size, 1.0, 1.005));
+
+        // Logical sizes don't depend on the scale factor.
+        let size: Size = LogicalSize::new(800.0, 600.0).into();
+        assert!(!surface_size_scale_mismatch(size, 2.0, 1.0));
+    }
 }
@@ -14,13 +14,13 @@ use redox_event::{EventFlags, EventQueue, UserData};
 use smol_str::SmolStr;
 use winit_core::application::ApplicationHandler;
 use winit_core::cursor::{CustomCursor, CustomCursorSource};
-use winit_core::error::{EventLoopError, NotSupportedError, RequestError};
-use winit_core::event::{self, Ime, Modifiers, StartCause};
+use winit_core::error::{CreateWindowError, EventLoopError, NotSupportedError, RequestError};
+use winit_core::event::{self, Ime, Modifiers, StartCause, WaitCancelReason};
 use winit_core::event_loop::pump_events::PumpStatus;
 use winit_core::event_loop::{
-    ActiveEventLoop as RootActiveEventLoop, ControlFlow, DeviceEvents,
+    ActiveEventLoop as RootActiveEventLoop, Backend, ControlFlow, DeviceEvents,
     EventLoopProxy as CoreEventLoopProxy, EventLoopProxyProvider,
-    OwnedDisplayHandle as CoreOwnedDisplayHandle,
+    OwnedDisplayHandle as CoreOwnedDisplayHandle, PendingWakeUps,
 };
 use winit_core::keyboard::{
     Key, KeyCode, KeyLocation, ModifiersKeys, ModifiersState, NamedKey, NativeKey, NativeKeyCode,
@@ -302,7 +302,7 @@ impl EventState {
         pressed_mods
             .set(ModifiersKeys::RMETA, self.keyboard.contains(KeyboardModifierState::RMETA));
 
-        Modifiers::new(state, pressed_mods)
+        Modifiers::new(state, pressed_mods).with_other_mods_released()
     }
 }
 
@@ -341,12 +341,16 @@ impl EventLoop {
             windows: Vec::new(),
             window_target: ActiveEventLoop {
                 control_flow: Cell::new(ControlFlow::default()),
-                exit: Cell::new(false),
+                exit: Cell::new(None),
                 creates: Mutex::new(VecDeque::new()),
                 redraws: Arc::new(Mutex::new(VecDeque::new())),
                 destroys: Arc::new(Mutex::new(VecDeque::new())),
                 event_socket,
-                event_loop_proxy: Arc::new(EventLoopProxy { wake_socket, user_events_sender }),
+                event_loop_proxy: Arc::new(EventLoopProxy {
+                    user_events_sender,
+                    pending_wake_ups: PendingWakeUps::new(),
+                    wake_socket,
+                }),
             },
             user_events_receiver,
         })
@@ -411,6 +415,7 @@ impl EventLoop {
                         text,
                         key_without_modifiers,
                         text_with_all_modifiers,
+                        composed_length: None,
                     },
                     is_synthetic: false,
                 };
@@ -435,7 +440,7 @@ impl EventLoop {
                 app.window_event(
                     window_target,
                     window_id,
-                    event::WindowEvent::Ime(Ime::Commit(character.into())),
+                    event::WindowEvent::Ime(Ime::Commit(character.into(), None)),
                 );
             },
             EventOption::Mouse(MouseEvent { x, y }) => {
@@ -448,9 +453,8 @@ impl EventLoop {
                 });
             },
             EventOption::MouseRelative(MouseRelativeEvent { dx, dy }) => {
-                app.device_event(window_target, None, event::DeviceEvent::PointerMotion {
-                    delta: (dx as f64, dy as f64),
-                });
+                let event = event::DeviceEvent::PointerMotion { delta: (dx as f64, dy as f64) };
+                app.device_event_with_window(window_target, None, event, Some(window_id));
             },
             EventOption::Button(ButtonEvent { left, middle, right }) => {
                 while let Some((button, state)) = event_state.mouse(left, middle, right) {
@@ -460,6 +464,7 @@ impl EventLoop {
                         state,
                         position: event_state.mouse_pos.into(),
                         button: button.into(),
+                        click_count: None,
                     });
                 }
             },
@@ -468,13 +473,17 @@ impl EventLoop {
                     device_id: None,
                     delta: event::MouseScrollDelta::LineDelta(x as f32, y as f32),
                     phase: event::TouchPhase::Moved,
+                    momentum: false,
                 });
             },
             EventOption::Quit(QuitEvent {}) => {
                 app.window_event(window_target, window_id, event::WindowEvent::CloseRequested);
             },
             EventOption::Focus(FocusEvent { focused }) => {
-                app.window_event(window_target, window_id, event::WindowEvent::Focused(focused));
+                app.window_event(window_target, window_id, event::WindowEvent::Focused {
+                    focused,
+                    via: event::FocusSource::Unknown,
+                });
             },
             EventOption::Move(MoveEvent { x, y }) => {
                 app.window_event(
@@ -525,11 +534,8 @@ impl EventLoop {
     ) -> Result<(), EventLoopError> {
         loop {
             match self.pump_app_events(None, &mut app) {
-                PumpStatus::Exit(0) => {
-                    break Ok(());
-                },
                 PumpStatus::Exit(code) => {
-                    break Err(EventLoopError::ExitFailure(code));
+                    break EventLoopError::exit_result(code);
                 },
                 _ => {
                     continue;
@@ -633,6 +639,9 @@ impl EventLoop {
 
             while self.user_events_receiver.try_recv().is_ok() {
                 app.proxy_wake_up(&self.window_target);
+                for key in self.window_target.event_loop_proxy.pending_wake_ups.take() {
+                    app.proxy_wake_up_coalesced(&self.window_target, key);
+                }
             }
 
             // To avoid deadlocks the redraws lock is not held during event processing.
@@ -659,23 +668,21 @@ impl EventLoop {
         if !self.loop_running {
             self.loop_running = true;
 
+            // The exit code of the previous run doesn't carry over.
+            self.window_target.exit.set(None);
+
             // Run the initial loop iteration.
             self.single_iteration(&mut app, StartCause::Init);
         }
 
-        if self.window_target.exit.get() {
+        if let Some(code) = self.window_target.exit.get() {
             self.loop_running = false;
-            // TODO: other exit codes
-            return PumpStatus::Exit(0);
+            return PumpStatus::Exit(code);
         }
 
         let start = Instant::now();
         let timeout = {
-            let requested_resume = match self.window_target.control_flow() {
-                ControlFlow::Poll => Some(Duration::ZERO),
-                ControlFlow::Wait => None,
-                ControlFlow::WaitUntil(instant) => Some(instant.saturating_duration_since(start)),
-            };
+            let requested_resume = self.window_target.control_flow().timeout(start);
             min_timeout(timeout, requested_resume)
         };
 
@@ -708,12 +715,20 @@ impl EventLoop {
         }
 
         // Normal window event or spurious timeout.
-        let cause = match self.window_target.control_flow() {
-            ControlFlow::Poll => StartCause::Poll,
-            ControlFlow::Wait => StartCause::WaitCancelled { start, requested_resume: None },
+        let cause = match self.window_target.control_flow().effective(start) {
+            ControlFlow::Poll | ControlFlow::PollUntil(_) => StartCause::Poll,
+            ControlFlow::Wait => StartCause::WaitCancelled {
+                start,
+                requested_resume: None,
+                reason: WaitCancelReason::Unknown,
+            },
             ControlFlow::WaitUntil(deadline) => {
                 if Instant::now() < deadline {
-                    StartCause::WaitCancelled { start, requested_resume: Some(deadline) }
+                    StartCause::WaitCancelled {
+                        start,
+                        requested_resume: Some(deadline),
+                        reason: WaitCancelReason::Unknown,
+                    }
                 } else {
                     StartCause::ResumeTimeReached { start, requested_resume: deadline }
                 }
@@ -734,6 +749,7 @@ impl EventLoop {
 #[derive(Debug)]
 pub struct EventLoopProxy {
     user_events_sender: mpsc::SyncSender<()>,
+    pending_wake_ups: PendingWakeUps,
     pub(super) wake_socket: TimeSocket,
 }
 
@@ -745,6 +761,12 @@ impl EventLoopProxyProvider for EventLoopProxy {
             self.wake_socket.wake().unwrap();
         }
     }
+
+    fn wake_up_coalesced(&self, key: u64) {
+        if self.pending_wake_ups.insert(key) {
+            self.wake_up();
+        }
+    }
 }
 
 impl Unpin for EventLoopProxy {}
@@ -759,7 +781,7 @@ redox_event::user_data! {
 #[derive(Debug)]
 pub struct ActiveEventLoop {
     control_flow: Cell<ControlFlow>,
-    exit: Cell<bool>,
+    exit: Cell<Option<i32>>,
     pub(super) creates: Mutex<VecDeque<Arc<RedoxSocket>>>,
     pub(super) redraws: Arc<Mutex<VecDeque<WindowId>>>,
     pub(super) destroys: Arc<Mutex<VecDeque<WindowId>>>,
@@ -775,7 +797,8 @@ impl RootActiveEventLoop for ActiveEventLoop {
     fn create_window(
         &self,
         window_attributes: winit_core::window::WindowAttributes,
-    ) -> Result<Box<dyn CoreWindow>, RequestError> {
+    ) -> Result<Box<dyn CoreWindow>, CreateWindowError> {
+        window_attributes.validate()?;
         Ok(Box::new(Window::new(self, window_attributes)?))
     }
 
@@ -806,11 +829,15 @@ impl RootActiveEventLoop for ActiveEventLoop {
     }
 
     fn exit(&self) {
-        self.exit.set(true);
+        self.exit.set(Some(0));
+    }
+
+    fn exit_with_code(&self, code: i32) {
+        self.exit.set(Some(code));
     }
 
     fn exiting(&self) -> bool {
-        self.exit.get()
+        self.exit.get().is_some()
     }
 
     fn owned_display_handle(&self) -> CoreOwnedDisplayHandle {
@@ -820,6 +847,10 @@ impl RootActiveEventLoop for ActiveEventLoop {
     fn rwh_06_handle(&self) -> &dyn rwh_06::HasDisplayHandle {
         self
     }
+
+    fn backend(&self) -> Option<Backend> {
+        Some(Backend::Orbital)
+    }
 }
 
 impl rwh_06::HasDisplayHandle for ActiveEventLoop {
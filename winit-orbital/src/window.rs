@@ -232,6 +232,7 @@ impl CoreWindow for Window {
 
     #[inline]
     fn request_surface_size(&self, size: Size) -> Option<PhysicalSize<u32>> {
+        let size = winit_core::window::clamp_surface_size(size);
         let (w, h): (u32, u32) = size.to_physical::<u32>(self.scale_factor()).into();
         self.window_socket.write(format!("S,{w},{h}").as_bytes()).expect("failed to set size");
         None
@@ -373,6 +374,10 @@ impl CoreWindow for Window {
     #[inline]
     fn set_window_icon(&self, _window_icon: Option<winit_core::icon::Icon>) {}
 
+    fn window_icon(&self) -> Option<winit_core::icon::Icon> {
+        None
+    }
+
     fn request_ime_update(&self, _: window::ImeRequest) -> Result<(), window::ImeRequestError> {
         Err(window::ImeRequestError::NotSupported)
     }
@@ -449,7 +454,7 @@ impl CoreWindow for Window {
 
     #[inline]
     fn enabled_buttons(&self) -> window::WindowButtons {
-        window::WindowButtons::all()
+        window::WindowButtons::default()
     }
 
     #[inline]
@@ -467,6 +472,10 @@ impl CoreWindow for Window {
 
     fn set_content_protected(&self, _protected: bool) {}
 
+    fn is_content_protected(&self) -> bool {
+        false
+    }
+
     fn rwh_06_window_handle(&self) -> &dyn rwh_06::HasWindowHandle {
         self
     }
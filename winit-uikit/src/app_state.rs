@@ -19,7 +19,7 @@ use objc2_ui_kit::{UIApplication, UICoordinateSpace, UIView};
 use winit_common::core_foundation::EventLoopProxy;
 use winit_common::event_handler::EventHandler;
 use winit_core::application::ApplicationHandler;
-use winit_core::event::{StartCause, SurfaceSizeWriter, WindowEvent};
+use winit_core::event::{StartCause, SurfaceSizeWriter, WaitCancelReason, WindowEvent};
 use winit_core::event_loop::ControlFlow;
 use winit_core::window::WindowId;
 
@@ -56,6 +56,7 @@ pub struct ScaleFactorChanged {
     pub(super) window: Retained<WinitUIWindow>,
     pub(super) suggested_size: PhysicalSize<u32>,
     pub(super) scale_factor: f64,
+    pub(super) old_scale_factor: f64,
 }
 
 impl EventWrapper {
@@ -105,7 +106,13 @@ static GLOBAL: MainThreadBound<OnceCell<AppState>> =
 impl AppState {
     pub(crate) fn setup_global(mtm: MainThreadMarker) -> bool {
         let event_loop_proxy = Arc::new(EventLoopProxy::new(mtm, move || {
-            get_handler(mtm).handle(|app| app.proxy_wake_up(&ActiveEventLoop { mtm }));
+            let keys = AppState::get(mtm).event_loop_proxy.take_pending_wake_ups();
+            get_handler(mtm).handle(|app| {
+                app.proxy_wake_up(&ActiveEventLoop { mtm });
+                for key in keys {
+                    app.proxy_wake_up_coalesced(&ActiveEventLoop { mtm }, key);
+                }
+            });
         }));
         GLOBAL
             .get(mtm)
@@ -149,15 +156,26 @@ impl AppState {
         }
 
         let start_cause = match (self.control_flow.get(), self.state.get()) {
-            (ControlFlow::Poll, AppStateImpl::PollFinished) => StartCause::Poll,
-            (ControlFlow::Wait, AppStateImpl::Waiting { start }) => {
-                StartCause::WaitCancelled { start, requested_resume: None }
+            (ControlFlow::Poll | ControlFlow::PollUntil(_), AppStateImpl::PollFinished) => {
+                StartCause::Poll
+            },
+            // A `PollUntil` whose deadline passed before we went to sleep waits like `Wait`.
+            (ControlFlow::Wait | ControlFlow::PollUntil(_), AppStateImpl::Waiting { start }) => {
+                StartCause::WaitCancelled {
+                    start,
+                    requested_resume: None,
+                    reason: WaitCancelReason::Unknown,
+                }
             },
             (ControlFlow::WaitUntil(requested_resume), AppStateImpl::Waiting { start }) => {
                 if Instant::now() >= requested_resume {
                     StartCause::ResumeTimeReached { start, requested_resume }
                 } else {
-                    StartCause::WaitCancelled { start, requested_resume: Some(requested_resume) }
+                    StartCause::WaitCancelled {
+                        start,
+                        requested_resume: Some(requested_resume),
+                        reason: WaitCancelReason::Unknown,
+                    }
                 }
             },
             s => bug!("`EventHandler` unexpectedly woke up {:?}", s),
@@ -185,7 +203,7 @@ impl AppState {
             s => bug!("unexpected state {:?}", s),
         };
 
-        let new = self.control_flow.get();
+        let new = self.control_flow.get().effective(Instant::now());
         match (old, new) {
             (ControlFlow::Wait, ControlFlow::Wait) => {
                 let start = Instant::now();
@@ -203,13 +221,14 @@ impl AppState {
                 self.state.set(AppStateImpl::Waiting { start });
                 self.waker.stop()
             },
-            (_, ControlFlow::WaitUntil(new_instant)) => {
+            (_, ControlFlow::WaitUntil(_)) => {
                 let start = Instant::now();
                 self.state.set(AppStateImpl::Waiting { start });
-                self.waker.start_at(new_instant)
+                let timeout = new.timeout(start).unwrap_or_default();
+                self.waker.start_at(start + timeout)
             },
             // Unlike on macOS, handle Poll to Poll transition here to call the waker
-            (_, ControlFlow::Poll) => {
+            (_, ControlFlow::Poll | ControlFlow::PollUntil(_)) => {
                 self.state.set(AppStateImpl::PollFinished);
                 self.waker.start()
             },
@@ -297,8 +316,9 @@ pub(crate) fn handle_nonuser_events<I: IntoIterator<Item = EventWrapper>>(
         return;
     }
 
-    if !get_handler(mtm).ready() {
+    if !get_handler(mtm).ready() || !this.has_launched() {
         // Prevent re-entrancy; queue the events up for once we're done handling the event instead.
+        // Events arriving before launch are queued until after `NewEvents(Init)` as well.
         let mut queued_events = this.queued_events.take();
         queued_events.extend(events);
         this.queued_events.set(queued_events);
@@ -402,12 +422,12 @@ pub fn handle_events_cleared(mtm: MainThreadMarker) {
 }
 
 pub(crate) fn handle_resumed(mtm: MainThreadMarker) {
-    get_handler(mtm).handle(|app| app.resumed(&ActiveEventLoop { mtm }));
+    get_handler(mtm).handle(|app| app.resumed(&ActiveEventLoop { mtm }, true));
     handle_nonuser_events(mtm, []);
 }
 
 pub(crate) fn handle_suspended(mtm: MainThreadMarker) {
-    get_handler(mtm).handle(|app| app.suspended(&ActiveEventLoop { mtm }));
+    get_handler(mtm).handle(|app| app.suspended(&ActiveEventLoop { mtm }, true));
     handle_nonuser_events(mtm, []);
 }
 
@@ -448,11 +468,12 @@ fn handle_wrapped_event(mtm: MainThreadMarker, event: EventWrapper) {
 }
 
 fn handle_hidpi_proxy(mtm: MainThreadMarker, event: ScaleFactorChanged) {
-    let ScaleFactorChanged { suggested_size, scale_factor, window } = event;
+    let ScaleFactorChanged { suggested_size, scale_factor, old_scale_factor, window } = event;
     let new_surface_size = Arc::new(Mutex::new(suggested_size));
     get_handler(mtm).handle(|app| {
         app.window_event(&ActiveEventLoop { mtm }, window.id(), WindowEvent::ScaleFactorChanged {
             scale_factor,
+            old_scale_factor,
             surface_size_writer: SurfaceSizeWriter::new(Arc::downgrade(&new_surface_size)),
         });
     });
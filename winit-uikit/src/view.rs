@@ -9,9 +9,9 @@ use objc2_core_foundation::{CGFloat, CGPoint, CGRect};
 use objc2_foundation::{NSObject, NSSet, NSString};
 use objc2_ui_kit::{
     UIEvent, UIForceTouchCapability, UIGestureRecognizer, UIGestureRecognizerDelegate,
-    UIGestureRecognizerState, UIKeyInput, UIPanGestureRecognizer, UIPinchGestureRecognizer,
-    UIResponder, UIRotationGestureRecognizer, UITapGestureRecognizer, UITextInputTraits, UITouch,
-    UITouchPhase, UITouchType, UITraitEnvironment, UIView,
+    UIGestureRecognizerState, UIKeyInput, UIKeyboardType, UIPanGestureRecognizer,
+    UIPinchGestureRecognizer, UIResponder, UIRotationGestureRecognizer, UITapGestureRecognizer,
+    UITextInputTraits, UITouch, UITouchPhase, UITouchType, UITraitEnvironment, UIView,
 };
 use tracing::{debug, debug_span, trace_span};
 use winit_core::event::{
@@ -19,6 +19,7 @@ use winit_core::event::{
     TabletToolAngle, TabletToolButton, TabletToolData, TabletToolKind, TouchPhase, WindowEvent,
 };
 use winit_core::keyboard::{Key, KeyCode, KeyLocation, NamedKey, NativeKeyCode, PhysicalKey};
+use winit_core::window::ImePurpose;
 
 use super::app_state::{self, EventWrapper};
 use super::window::WinitUIWindow;
@@ -36,6 +37,10 @@ pub struct WinitViewState {
 
     primary_finger: Cell<Option<FingerId>>,
     fingers: Cell<u8>,
+
+    // The text input traits for the IME purpose.
+    keyboard_type: Cell<UIKeyboardType>,
+    secure_text_entry: Cell<bool>,
 }
 
 define_class!(
@@ -83,6 +88,7 @@ define_class!(
         fn set_content_scale_factor(&self, untrusted_scale_factor: CGFloat) {
             let _entered = debug_span!("setContentScaleFactor:").entered();
             let mtm = MainThreadMarker::new().unwrap();
+            let old_scale_factor = self.contentScaleFactor() as f64;
             let _: () =
                 unsafe { msg_send![super(self), setContentScaleFactor: untrusted_scale_factor] };
 
@@ -116,6 +122,7 @@ define_class!(
                 std::iter::once(EventWrapper::ScaleFactorChanged(app_state::ScaleFactorChanged {
                     window,
                     scale_factor,
+                    old_scale_factor,
                     suggested_size: size.to_physical(scale_factor),
                 }))
                 .chain(std::iter::once(EventWrapper::Window {
@@ -330,7 +337,17 @@ define_class!(
         }
     }
 
-    unsafe impl UITextInputTraits for WinitView {}
+    unsafe impl UITextInputTraits for WinitView {
+        #[unsafe(method(keyboardType))]
+        fn keyboard_type(&self) -> UIKeyboardType {
+            self.ivars().keyboard_type.get()
+        }
+
+        #[unsafe(method(isSecureTextEntry))]
+        fn is_secure_text_entry(&self) -> bool {
+            self.ivars().secure_text_entry.get()
+        }
+    }
 
     unsafe impl UIKeyInput for WinitView {
         #[unsafe(method(hasText))]
@@ -371,6 +388,9 @@ impl WinitView {
 
             primary_finger: Cell::new(None),
             fingers: Cell::new(0),
+
+            keyboard_type: Cell::new(UIKeyboardType::Default),
+            secure_text_entry: Cell::new(false),
         });
         let this: Retained<Self> = unsafe { msg_send![super(this), initWithFrame: frame] };
 
@@ -388,6 +408,29 @@ impl WinitView {
         (**self).window().map(|window| window.downcast().unwrap())
     }
 
+    /// Pick the keyboard for the IME purpose, reloading it when it's shown already.
+    pub(crate) fn set_ime_purpose(&self, purpose: ImePurpose) {
+        let keyboard_type = match purpose {
+            ImePurpose::Terminal => UIKeyboardType::ASCIICapable,
+            ImePurpose::Number | ImePurpose::Date | ImePurpose::Time | ImePurpose::DateTime => {
+                UIKeyboardType::NumbersAndPunctuation
+            },
+            ImePurpose::Phone => UIKeyboardType::PhonePad,
+            ImePurpose::Url => UIKeyboardType::URL,
+            ImePurpose::Email => UIKeyboardType::EmailAddress,
+            ImePurpose::Pin => UIKeyboardType::NumberPad,
+            _ => UIKeyboardType::Default,
+        };
+        let secure_text_entry = matches!(purpose, ImePurpose::Password | ImePurpose::Pin);
+
+        let ivars = self.ivars();
+        let old_keyboard_type = ivars.keyboard_type.replace(keyboard_type);
+        let old_secure_text_entry = ivars.secure_text_entry.replace(secure_text_entry);
+        if old_keyboard_type != keyboard_type || old_secure_text_entry != secure_text_entry {
+            self.reloadInputViews();
+        }
+    }
+
     pub(crate) fn recognize_pinch_gesture(&self, should_recognize: bool) {
         let mtm = MainThreadMarker::from(self);
         if should_recognize {
@@ -571,6 +614,7 @@ impl WinitView {
                             } else {
                                 ButtonSource::Touch { finger_id, force }
                             },
+                            click_count: None,
                         },
                     });
                 },
@@ -629,6 +673,7 @@ impl WinitView {
                                 } else {
                                     ButtonSource::Touch { finger_id, force }
                                 },
+                                click_count: None,
                             },
                         });
                     }
@@ -711,6 +756,7 @@ impl WinitView {
                                 None
                             },
                             key_without_modifiers: Key::Character(text.clone()),
+                            composed_length: None,
                         },
                         is_synthetic: false,
                     },
@@ -738,6 +784,7 @@ impl WinitView {
                         text: None,
                         text_with_all_modifiers: None,
                         key_without_modifiers: Key::Named(NamedKey::Backspace),
+                        composed_length: None,
                     },
                     is_synthetic: false,
                 },
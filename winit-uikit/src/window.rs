@@ -19,7 +19,7 @@ use objc2_ui_kit::{
 use tracing::{debug, debug_span, warn};
 use winit_core::cursor::Cursor;
 use winit_core::error::{NotSupportedError, RequestError};
-use winit_core::event::WindowEvent;
+use winit_core::event::{FocusSource, WindowEvent};
 use winit_core::icon::Icon;
 use winit_core::monitor::{Fullscreen, MonitorHandle as CoreMonitorHandle};
 use winit_core::window::{
@@ -50,7 +50,7 @@ define_class!(
             let mtm = MainThreadMarker::new().unwrap();
             app_state::handle_nonuser_event(mtm, EventWrapper::Window {
                 window_id: self.id(),
-                event: WindowEvent::Focused(true),
+                event: WindowEvent::Focused { focused: true, via: FocusSource::Unknown },
             });
             let _: () = unsafe { msg_send![super(self), becomeKeyWindow] };
         }
@@ -61,7 +61,7 @@ define_class!(
             let mtm = MainThreadMarker::new().unwrap();
             app_state::handle_nonuser_event(mtm, EventWrapper::Window {
                 window_id: self.id(),
-                event: WindowEvent::Focused(false),
+                event: WindowEvent::Focused { focused: false, via: FocusSource::Unknown },
             });
             let _: () = unsafe { msg_send![super(self), resignKeyWindow] };
         }
@@ -251,7 +251,7 @@ impl Inner {
     #[inline]
     pub fn enabled_buttons(&self) -> WindowButtons {
         warn!("`Window::enabled_buttons` is ignored on iOS");
-        WindowButtons::all()
+        WindowButtons::default()
     }
 
     pub fn scale_factor(&self) -> f64 {
@@ -308,6 +308,10 @@ impl Inner {
     }
 
     pub(crate) fn set_fullscreen(&self, monitor: Option<Fullscreen>) {
+        if !winit_core::monitor::validate_fullscreen(monitor.as_ref()) {
+            return;
+        }
+
         let mtm = MainThreadMarker::new().unwrap();
         let uiscreen = match &monitor {
             Some(Fullscreen::Exclusive(monitor, video_mode)) => {
@@ -388,18 +392,27 @@ impl Inner {
         let mut current_caps = self.ime_capabilities.lock().unwrap();
         match request {
             ImeRequest::Enable(enable) => {
-                let (capabilities, _) = enable.into_raw();
+                let (capabilities, request_data) = enable.into_raw();
                 if current_caps.is_some() {
                     return Err(ImeRequestError::AlreadyEnabled);
                 }
-                *current_caps = Some(capabilities);
+                // Only the purpose is applied, through the keyboard type.
+                *current_caps =
+                    Some(capabilities.intersection(ImeCapabilities::new().with_hint_and_purpose()));
 
+                if let Some((_, purpose)) = request_data.hint_and_purpose {
+                    self.view.set_ime_purpose(purpose);
+                }
                 self.view.becomeFirstResponder();
             },
-            ImeRequest::Update(_) => {
+            ImeRequest::Update(request_data) => {
                 if current_caps.is_none() {
                     return Err(ImeRequestError::NotEnabled);
                 }
+
+                if let Some((_, purpose)) = request_data.hint_and_purpose {
+                    self.view.set_ime_purpose(purpose);
+                }
             },
             ImeRequest::Disable => {
                 *current_caps = None;
@@ -730,6 +743,10 @@ impl CoreWindow for Window {
         self.maybe_wait_on_main(|delegate| delegate.set_window_icon(window_icon));
     }
 
+    fn window_icon(&self) -> Option<Icon> {
+        None
+    }
+
     fn request_ime_update(&self, request: ImeRequest) -> Result<(), ImeRequestError> {
         self.maybe_wait_on_main(|delegate| delegate.request_ime_update(request))
     }
@@ -762,6 +779,10 @@ impl CoreWindow for Window {
         self.maybe_wait_on_main(|delegate| delegate.set_content_protected(protected));
     }
 
+    fn is_content_protected(&self) -> bool {
+        false
+    }
+
     fn title(&self) -> String {
         self.maybe_wait_on_main(|delegate| delegate.title())
     }
@@ -27,16 +27,18 @@ use wayland_client::protocol::wl_shm::Format;
 use winit_core::application::ApplicationHandler;
 use winit_core::cursor::{CustomCursor as CoreCustomCursor, CustomCursorSource};
 use winit_core::data_transfer::{DataTransfer, DataTransferId, DataTransferSend, TransferType};
-use winit_core::error::{EventLoopError, NotSupportedError, OsError, RequestError};
+use winit_core::error::{
+    CreateWindowError, EventLoopError, NotSupportedError, OsError, RequestError,
+};
 use winit_core::event::{DeviceEvent, StartCause, SurfaceSizeWriter, WindowEvent};
 use winit_core::event_loop::pump_events::PumpStatus;
 use winit_core::event_loop::{
-    ActiveEventLoop as RootActiveEventLoop, AsyncRequestSerial, ControlFlow, DeviceEvents,
-    DndAction, DragIcon, OwnedDisplayHandle as CoreOwnedDisplayHandle,
+    ActiveEventLoop as RootActiveEventLoop, AsyncRequestSerial, Backend, ControlFlow, DeviceEvents,
+    DndAction, DragIcon, OwnedDisplayHandle as CoreOwnedDisplayHandle, PendingWakeUps,
 };
 use winit_core::icon::RgbaIcon;
 use winit_core::monitor::MonitorHandle as CoreMonitorHandle;
-use winit_core::window::Theme;
+use winit_core::window::{ActivationToken, Theme};
 
 use crate::dnd::{MimeData, dnd_action_winit_to_wl};
 use crate::types::cursor::WaylandCustomCursor;
@@ -49,8 +51,8 @@ use proxy::EventLoopProxy;
 use sink::EventSink;
 pub use winit_core::event_loop::EventLoopProxy as CoreEventLoopProxy;
 
-use super::output::MonitorHandle;
 use super::state::{WindowCompositorUpdate, WinitState};
+use super::types::xdg_activation::XdgActivationTokenData;
 use super::window::state::FrameCallbackState;
 use super::{WindowId, logical_to_physical_rounded};
 
@@ -72,6 +74,9 @@ pub struct EventLoop {
     compositor_updates: Vec<WindowCompositorUpdate>,
     window_ids: Vec<WindowId>,
 
+    /// Keys passed to `wake_up_coalesced` that weren't delivered yet.
+    pending_wake_ups: Arc<PendingWakeUps>,
+
     /// The Wayland dispatcher to has raw access to the queue when needed, such as
     /// when creating a new window.
     wayland_dispatcher: WaylandDispatcher,
@@ -153,12 +158,13 @@ impl EventLoop {
             })
             .map_err(|err| os_error!(err))?;
 
+        let pending_wake_ups = Arc::new(PendingWakeUps::new());
         let handle = Arc::new(OwnedDisplayHandle::new(connection));
         let active_event_loop = ActiveEventLoop {
             handle: handle.clone(),
             wayland_dispatcher: wayland_dispatcher.clone(),
             event_loop_awakener,
-            event_loop_proxy: EventLoopProxy::new(ping).into(),
+            event_loop_proxy: EventLoopProxy::new(ping, pending_wake_ups.clone()).into(),
             queue_handle,
             control_flow: Cell::new(ControlFlow::default()),
             exit: Cell::new(None),
@@ -170,6 +176,7 @@ impl EventLoop {
             compositor_updates: Vec::new(),
             buffer_sink: EventSink::default(),
             window_ids: Vec::new(),
+            pending_wake_ups,
             handle,
             wayland_dispatcher,
             event_loop,
@@ -187,11 +194,8 @@ impl EventLoop {
         self.active_event_loop.clear_exit();
         let exit = loop {
             match self.pump_app_events(None, &mut app) {
-                PumpStatus::Exit(0) => {
-                    break Ok(());
-                },
                 PumpStatus::Exit(code) => {
-                    break Err(EventLoopError::ExitFailure(code));
+                    break EventLoopError::exit_result(code);
                 },
                 _ => {
                     continue;
@@ -216,6 +220,9 @@ impl EventLoop {
         if !self.loop_running {
             self.loop_running = true;
 
+            // The exit code of the previous run doesn't carry over.
+            self.active_event_loop.clear_exit();
+
             // Run the initial loop iteration.
             self.single_iteration(&mut app, StartCause::Init);
         }
@@ -259,14 +266,11 @@ impl EventLoop {
             let start = Instant::now();
 
             timeout = {
-                let control_flow_timeout = match self.control_flow() {
-                    ControlFlow::Wait => None,
-                    ControlFlow::Poll => Some(Duration::ZERO),
-                    ControlFlow::WaitUntil(wait_deadline) => {
-                        Some(wait_deadline.saturating_duration_since(start))
-                    },
-                };
-                min_timeout(control_flow_timeout, timeout)
+                let control_flow_timeout = self.control_flow().timeout(start);
+                let activation_timeout = self
+                    .with_state(|state| state.activation_timeouts.next_deadline())
+                    .map(|deadline| deadline.saturating_duration_since(start));
+                min_timeout(min_timeout(control_flow_timeout, timeout), activation_timeout)
             };
 
             // NOTE Ideally we should flush as the last thing we do before polling
@@ -295,12 +299,19 @@ impl EventLoop {
 
             // NB: `StartCause::Init` is handled as a special case and doesn't need
             // to be considered here
-            let cause = match self.control_flow() {
-                ControlFlow::Poll => StartCause::Poll,
-                ControlFlow::Wait => StartCause::WaitCancelled { start, requested_resume: None },
+            let reason = self.with_state(|state| state.wait_cancel_reason());
+            let cause = match self.control_flow().effective(start) {
+                ControlFlow::Poll | ControlFlow::PollUntil(_) => StartCause::Poll,
+                ControlFlow::Wait => {
+                    StartCause::WaitCancelled { start, requested_resume: None, reason }
+                },
                 ControlFlow::WaitUntil(deadline) => {
                     if Instant::now() < deadline {
-                        StartCause::WaitCancelled { start, requested_resume: Some(deadline) }
+                        StartCause::WaitCancelled {
+                            start,
+                            requested_resume: Some(deadline),
+                            reason,
+                        }
                     } else {
                         StartCause::ResumeTimeReached { start, requested_resume: deadline }
                     }
@@ -344,6 +355,9 @@ impl EventLoop {
         // Indicate user wake up.
         if self.with_state(|state| mem::take(&mut state.proxy_wake_up)) {
             app.proxy_wake_up(&self.active_event_loop);
+            for key in self.pending_wake_ups.take() {
+                app.proxy_wake_up_coalesced(&self.active_event_loop, key);
+            }
         }
 
         // Drain the pending compositor updates.
@@ -366,6 +380,7 @@ impl EventLoop {
                 let new_surface_size = Arc::new(Mutex::new(physical_size));
                 let event = WindowEvent::ScaleFactorChanged {
                     scale_factor,
+                    old_scale_factor: compositor_update.old_scale_factor.unwrap_or(scale_factor),
                     surface_size_writer: SurfaceSizeWriter::new(Arc::downgrade(&new_surface_size)),
                 };
 
@@ -421,6 +436,18 @@ impl EventLoop {
             }
         }
 
+        // Give up on the activation tokens the compositor didn't answer in time.
+        let now = Instant::now();
+        self.with_state(|state| {
+            for (window_id, serial) in state.activation_timeouts.take_expired(now) {
+                let token = ActivationToken::from_raw(String::new());
+                buffer_sink.push_window_event(
+                    WindowEvent::ActivationTokenDone { serial, token },
+                    window_id,
+                );
+            }
+        });
+
         // Push the events directly from the window.
         self.with_state(|state| {
             buffer_sink.append(&mut state.window_events_sink.lock().unwrap());
@@ -431,7 +458,7 @@ impl EventLoop {
                     app.window_event(&self.active_event_loop, window_id, event)
                 },
                 Event::DeviceEvent { event } => {
-                    app.device_event(&self.active_event_loop, None, event)
+                    app.device_event_with_window(&self.active_event_loop, None, event, None)
                 },
             }
         }
@@ -446,7 +473,7 @@ impl EventLoop {
                     app.window_event(&self.active_event_loop, window_id, event)
                 },
                 Event::DeviceEvent { event } => {
-                    app.device_event(&self.active_event_loop, None, event)
+                    app.device_event_with_window(&self.active_event_loop, None, event, None)
                 },
             }
         }
@@ -632,6 +659,10 @@ impl RootActiveEventLoop for ActiveEventLoop {
         self.exit.set(Some(0))
     }
 
+    fn exit_with_code(&self, code: i32) {
+        self.exit.set(Some(code))
+    }
+
     fn exiting(&self) -> bool {
         self.exit.get().is_some()
     }
@@ -661,25 +692,22 @@ impl RootActiveEventLoop for ActiveEventLoop {
     fn create_window(
         &self,
         window_attributes: winit_core::window::WindowAttributes,
-    ) -> Result<Box<dyn winit_core::window::Window>, RequestError> {
+    ) -> Result<Box<dyn winit_core::window::Window>, CreateWindowError> {
+        window_attributes.validate()?;
         let window = crate::Window::new(self, window_attributes)?;
         Ok(Box::new(window))
     }
 
     fn available_monitors(&self) -> Box<dyn Iterator<Item = CoreMonitorHandle>> {
-        Box::new(
-            self.state
-                .borrow()
-                .output_state
-                .outputs()
-                .map(MonitorHandle::new)
-                .map(|inner| CoreMonitorHandle(Arc::new(inner))),
-        )
+        // Use the list shared with the windows, so both return the monitors in the same order.
+        let monitors = self.state.borrow().monitors.lock().unwrap().clone();
+        Box::new(monitors.into_iter().map(|inner| CoreMonitorHandle(Arc::new(inner))))
     }
 
     fn primary_monitor(&self) -> Option<CoreMonitorHandle> {
-        // There's no primary monitor on Wayland.
-        None
+        let monitors = self.state.borrow().monitors.lock().unwrap().clone();
+        crate::output::primary_monitor(monitors.into_iter())
+            .map(|monitor| CoreMonitorHandle(Arc::new(monitor)))
     }
 
     fn owned_display_handle(&self) -> CoreOwnedDisplayHandle {
@@ -690,6 +718,10 @@ impl RootActiveEventLoop for ActiveEventLoop {
         self
     }
 
+    fn backend(&self) -> Option<Backend> {
+        Some(Backend::Wayland)
+    }
+
     fn fetch_data_transfer(
         &self,
         id: DataTransferId,
@@ -917,6 +949,33 @@ impl fmt::Display for UnknownDataTransfer {
 impl std::error::Error for UnknownDataTransfer {}
 
 impl ActiveEventLoop {
+    /// Request an activation token for the window, giving up after `timeout`.
+    pub fn request_activation_token_with_timeout(
+        &self,
+        window: &crate::Window,
+        timeout: Duration,
+    ) -> Result<AsyncRequestSerial, RequestError> {
+        let mut state = self.state.borrow_mut();
+        let xdg_activation = match state.xdg_activation.as_ref() {
+            Some(xdg_activation) => xdg_activation.global().clone(),
+            None => return Err(NotSupportedError::new("xdg_activation_v1 is not available").into()),
+        };
+
+        let serial = AsyncRequestSerial::get();
+        let window_id = super::make_wid(window.surface());
+
+        let data = XdgActivationTokenData::ObtainWithTimeout((window_id, serial));
+        let xdg_activation_token = xdg_activation.get_activation_token(&self.queue_handle, data);
+        xdg_activation_token.set_surface(window.surface());
+        xdg_activation_token.commit();
+
+        state.activation_timeouts.push(window_id, serial, Instant::now() + timeout);
+        // Wake up the event loop to wait for the deadline.
+        self.event_loop_awakener.ping();
+
+        Ok(serial)
+    }
+
     fn clear_exit(&self) {
         self.exit.set(None)
     }
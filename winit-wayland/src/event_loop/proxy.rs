@@ -3,23 +3,32 @@
 use std::sync::Arc;
 
 use sctk::reexports::calloop::ping::Ping;
-use winit_core::event_loop::{EventLoopProxy as CoreEventLoopProxy, EventLoopProxyProvider};
+use winit_core::event_loop::{
+    EventLoopProxy as CoreEventLoopProxy, EventLoopProxyProvider, PendingWakeUps,
+};
 
 /// A handle that can be sent across the threads and used to wake up the `EventLoop`.
 #[derive(Debug)]
 pub struct EventLoopProxy {
     ping: Ping,
+    pending_wake_ups: Arc<PendingWakeUps>,
 }
 
 impl EventLoopProxyProvider for EventLoopProxy {
     fn wake_up(&self) {
         self.ping.ping();
     }
+
+    fn wake_up_coalesced(&self, key: u64) {
+        if self.pending_wake_ups.insert(key) {
+            self.ping.ping();
+        }
+    }
 }
 
 impl EventLoopProxy {
-    pub fn new(ping: Ping) -> Self {
-        Self { ping }
+    pub fn new(ping: Ping, pending_wake_ups: Arc<PendingWakeUps>) -> Self {
+        Self { ping, pending_wake_ups }
     }
 }
 
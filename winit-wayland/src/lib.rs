@@ -71,6 +71,9 @@ pub trait EventLoopExtWayland {
 /// Additional methods when building event loop that are specific to Wayland.
 pub trait EventLoopBuilderExtWayland {
     /// Force using Wayland.
+    ///
+    /// Building the event loop returns an error if Wayland is unavailable, instead of falling back
+    /// to another backend.
     fn with_wayland(&mut self) -> &mut Self;
 
     /// Whether to allow the event loop to be created off of the main thread.
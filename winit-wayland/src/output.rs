@@ -80,6 +80,19 @@ impl PartialEq for MonitorHandle {
 
 impl Eq for MonitorHandle {}
 
+/// Pick the monitor reported as the primary one.
+///
+/// Wayland has no concept of a primary monitor, so this is a best guess: the top-left-most one, the
+/// first enumerated on ties.
+pub(crate) fn primary_monitor(
+    monitors: impl Iterator<Item = MonitorHandle>,
+) -> Option<MonitorHandle> {
+    monitors.min_by_key(|monitor| {
+        let position = monitor.position().unwrap_or_default();
+        (position.x, position.y)
+    })
+}
+
 /// Convert the wayland's [`Mode`] to winit's [`VideoMode`].
 fn wayland_mode_to_core_mode(mode: Mode) -> VideoMode {
     VideoMode::new(
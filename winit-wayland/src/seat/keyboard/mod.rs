@@ -11,8 +11,8 @@ use sctk::reexports::client::protocol::wl_keyboard::{
 use sctk::reexports::client::protocol::wl_seat::WlSeat;
 use sctk::reexports::client::{Connection, Dispatch, Proxy, QueueHandle, WEnum};
 use tracing::warn;
-use winit_common::xkb::Context;
-use winit_core::event::{ElementState, WindowEvent};
+use winit_common::xkb::{Context, ModifiersKeysState};
+use winit_core::event::{ElementState, FocusSource, WindowEvent};
 use winit_core::keyboard::ModifiersState;
 
 use crate::WindowId;
@@ -59,9 +59,16 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
                     warn!("unknown keymap format 0x{:x}", value)
                 },
             },
-            WlKeyboardEvent::Enter { surface, .. } => {
+            WlKeyboardEvent::Enter { surface, keys, .. } => {
                 let window_id = crate::make_wid(&surface);
 
+                // The keys that are already held down.
+                keyboard_state.modifiers_keys.clear();
+                for key in keys.chunks_exact(4) {
+                    let key = u32::from_ne_bytes(key.try_into().unwrap()) + 8;
+                    keyboard_state.modifiers_keys.update(key, ElementState::Pressed);
+                }
+
                 // Mark the window as focused.
                 let was_unfocused = match state.windows.get_mut().get(&window_id) {
                     Some(window) => {
@@ -83,15 +90,18 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
 
                 // The keyboard focus is considered as general focus.
                 if was_unfocused {
-                    state.events_sink.push_window_event(WindowEvent::Focused(true), window_id);
+                    state.events_sink.push_window_event(
+                        WindowEvent::Focused { focused: true, via: FocusSource::Unknown },
+                        window_id,
+                    );
                 }
 
                 // HACK: this is just for GNOME not fixing their ordering issue of modifiers.
                 if std::mem::take(&mut seat_state.modifiers_pending) {
-                    state.events_sink.push_window_event(
-                        WindowEvent::ModifiersChanged(seat_state.modifiers.into()),
-                        window_id,
-                    );
+                    let modifiers = keyboard_state.modifiers_keys.modifiers(seat_state.modifiers);
+                    state
+                        .events_sink
+                        .push_window_event(WindowEvent::ModifiersChanged(modifiers), window_id);
                 }
             },
             WlKeyboardEvent::Leave { surface, .. } => {
@@ -118,15 +128,20 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
                 // We don't need to update it above, because the next `Enter` will overwrite
                 // anyway.
                 *data.window_id.lock().unwrap() = None;
+                keyboard_state.modifiers_keys.clear();
 
                 if !focused {
                     // Notify that no modifiers are being pressed.
+                    let modifiers =
+                        keyboard_state.modifiers_keys.modifiers(ModifiersState::empty());
+                    state
+                        .events_sink
+                        .push_window_event(WindowEvent::ModifiersChanged(modifiers), window_id);
+
                     state.events_sink.push_window_event(
-                        WindowEvent::ModifiersChanged(ModifiersState::empty().into()),
+                        WindowEvent::Focused { focused: false, via: FocusSource::Unknown },
                         window_id,
                     );
-
-                    state.events_sink.push_window_event(WindowEvent::Focused(false), window_id);
                 }
             },
             WlKeyboardEvent::Key { key, state: WEnum::Value(key_state), .. }
@@ -141,6 +156,8 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
                     ElementState::Pressed,
                     key_state == WlKeyState::Repeated,
                 );
+                // The compositor follows up with `Modifiers`, which reports the keys.
+                keyboard_state.modifiers_keys.update(key, ElementState::Pressed);
 
                 let delay = match keyboard_state.repeat_info {
                     RepeatInfo::Repeat { delay, .. } => delay,
@@ -215,6 +232,8 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
                     ElementState::Released,
                     false,
                 );
+                // The compositor follows up with `Modifiers`, which reports the keys.
+                keyboard_state.modifiers_keys.update(key, ElementState::Released);
 
                 if keyboard_state.repeat_info != RepeatInfo::Disable
                     && keyboard_state.xkb_context.keymap_mut().unwrap().key_repeats(key)
@@ -247,10 +266,10 @@ impl Dispatch<WlKeyboard, KeyboardData, WinitState> for WinitState {
                     },
                 };
 
-                state.events_sink.push_window_event(
-                    WindowEvent::ModifiersChanged(seat_state.modifiers.into()),
-                    window_id,
-                );
+                let modifiers = keyboard_state.modifiers_keys.modifiers(seat_state.modifiers);
+                state
+                    .events_sink
+                    .push_window_event(WindowEvent::ModifiersChanged(modifiers), window_id);
             },
             WlKeyboardEvent::RepeatInfo { rate, delay } => {
                 keyboard_state.repeat_info = if rate == 0 {
@@ -291,6 +310,9 @@ pub struct KeyboardState {
 
     /// The current repeat raw key.
     pub current_repeat: Option<u32>,
+
+    /// The modifier keys held down.
+    pub modifiers_keys: ModifiersKeysState,
 }
 
 impl KeyboardState {
@@ -302,6 +324,7 @@ impl KeyboardState {
             repeat_info: RepeatInfo::default(),
             repeat_token: None,
             current_repeat: None,
+            modifiers_keys: ModifiersKeysState::default(),
         }
     }
 }
@@ -16,7 +16,7 @@ use tracing::warn;
 use wayland_protocols::wp::pointer_gestures::zv1::client::zwp_pointer_gesture_hold_v1::ZwpPointerGestureHoldV1;
 use wayland_protocols::wp::pointer_gestures::zv1::client::zwp_pointer_gesture_pinch_v1::ZwpPointerGesturePinchV1;
 use wayland_protocols::wp::tablet::zv2::client::zwp_tablet_seat_v2::ZwpTabletSeatV2;
-use winit_core::event::WindowEvent;
+use winit_core::event::{FocusSource, WindowEvent};
 use winit_core::keyboard::ModifiersState;
 
 use crate::state::WinitState;
@@ -295,7 +295,10 @@ impl WinitState {
             let had_focus = window.has_focus();
             window.remove_seat_focus(seat);
             if had_focus != window.has_focus() {
-                self.events_sink.push_window_event(WindowEvent::Focused(false), *window_id);
+                self.events_sink.push_window_event(
+                    WindowEvent::Focused { focused: false, via: FocusSource::Unknown },
+                    *window_id,
+                );
             }
         }
     }
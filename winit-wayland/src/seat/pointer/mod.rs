@@ -11,8 +11,8 @@ use sctk::reexports::client::protocol::wl_pointer::WlPointer;
 use sctk::reexports::client::protocol::wl_seat::WlSeat;
 use sctk::reexports::client::protocol::wl_surface::WlSurface;
 use sctk::reexports::client::{Connection, Proxy, QueueHandle, Dispatch};
-use sctk::reexports::protocols::wp::pointer_constraints::zv1::client::zwp_confined_pointer_v1::ZwpConfinedPointerV1;
-use sctk::reexports::protocols::wp::pointer_constraints::zv1::client::zwp_locked_pointer_v1::ZwpLockedPointerV1;
+use sctk::reexports::protocols::wp::pointer_constraints::zv1::client::zwp_confined_pointer_v1::{self, ZwpConfinedPointerV1};
+use sctk::reexports::protocols::wp::pointer_constraints::zv1::client::zwp_locked_pointer_v1::{self, ZwpLockedPointerV1};
 use sctk::reexports::protocols::wp::cursor_shape::v1::client::wp_cursor_shape_device_v1::WpCursorShapeDeviceV1;
 use sctk::reexports::protocols::wp::cursor_shape::v1::client::wp_cursor_shape_manager_v1::WpCursorShapeManagerV1;
 use sctk::reexports::protocols::wp::pointer_constraints::zv1::client::zwp_pointer_constraints_v1::{Lifetime, ZwpPointerConstraintsV1};
@@ -32,6 +32,7 @@ use winit_core::event::{
     ButtonSource, ElementState, MouseButton, MouseScrollDelta, PointerKind, PointerSource,
     TouchPhase, WindowEvent,
 };
+use winit_core::window::CursorGrabMode;
 
 use crate::WindowId;
 use crate::state::WinitState;
@@ -85,6 +86,20 @@ impl PointerHandler for WinitState {
             let position: PhysicalPosition<f64> =
                 LogicalPosition::new(event.position.0, event.position.1).to_physical(scale_factor);
 
+            {
+                let mut pointer_data = pointer.winit_data().inner.lock().unwrap();
+                match event.kind {
+                    // Buttons pressed before entering aren't reported.
+                    PointerEventKind::Enter { .. } => pointer_data.pressed_buttons = 0,
+                    PointerEventKind::Press { .. } => pointer_data.pressed_buttons += 1,
+                    PointerEventKind::Release { .. } => {
+                        pointer_data.pressed_buttons =
+                            pointer_data.pressed_buttons.saturating_sub(1)
+                    },
+                    _ => (),
+                }
+            }
+
             match event.kind {
                 // Pointer movements on decorations.
                 PointerEventKind::Enter { .. } | PointerEventKind::Motion { .. }
@@ -187,6 +202,7 @@ impl PointerHandler for WinitState {
                             state,
                             position,
                             button,
+                            click_count: None,
                         },
                         window_id,
                     );
@@ -239,11 +255,28 @@ impl PointerHandler for WinitState {
                     };
 
                     self.events_sink.push_window_event(
-                        WindowEvent::MouseWheel { device_id: None, delta, phase },
+                        WindowEvent::MouseWheel { device_id: None, delta, phase, momentum: false },
                         window_id,
                     )
                 },
             }
+
+            // The compositor takes the pointer focus while it moves the window.
+            let moved = match event.kind {
+                PointerEventKind::Leave { .. } => {
+                    window.drag_move_started().then_some(WindowEvent::MoveStarted)
+                },
+                PointerEventKind::Enter { .. } | PointerEventKind::Motion { .. } => {
+                    window.drag_move_ended(false).then_some(WindowEvent::MoveEnded)
+                },
+                PointerEventKind::Release { .. } => {
+                    window.drag_move_ended(true).then_some(WindowEvent::MoveEnded)
+                },
+                _ => None,
+            };
+            if let Some(event) = moved {
+                self.events_sink.push_window_event(event, window_id);
+            }
         }
     }
 }
@@ -284,7 +317,7 @@ impl WinitPointerData {
                 None,
                 Lifetime::Persistent,
                 queue_handle,
-                GlobalData,
+                crate::make_wid(surface),
             ));
         }
     }
@@ -309,7 +342,7 @@ impl WinitPointerData {
             None,
             Lifetime::Persistent,
             queue_handle,
-            GlobalData,
+            crate::make_wid(surface),
         ));
     }
 
@@ -335,6 +368,11 @@ impl WinitPointerData {
         self.sctk_data.latest_button_serial().unwrap_or_default()
     }
 
+    /// Whether a button is held on a surface of this client.
+    pub fn is_button_pressed(&self) -> bool {
+        self.inner.lock().unwrap().pressed_buttons > 0
+    }
+
     /// Last enter serial.
     pub fn latest_enter_serial(&self) -> u32 {
         self.sctk_data.latest_enter_serial().unwrap_or_default()
@@ -377,6 +415,9 @@ pub struct WinitPointerDataInner {
     /// Serial of the last button event.
     latest_button_serial: u32,
 
+    /// The number of buttons held since the pointer entered a surface.
+    pressed_buttons: u32,
+
     /// Currently focused window.
     surface: Option<WindowId>,
 
@@ -403,6 +444,7 @@ impl Default for WinitPointerDataInner {
             locked_pointer: None,
             confined_pointer: None,
             latest_button_serial: 0,
+            pressed_buttons: 0,
             phase: TouchPhase::Ended,
         }
     }
@@ -467,27 +509,39 @@ impl Dispatch<ZwpPointerConstraintsV1, GlobalData, WinitState> for PointerConstr
     }
 }
 
-impl Dispatch<ZwpLockedPointerV1, GlobalData, WinitState> for PointerConstraintsState {
+impl Dispatch<ZwpLockedPointerV1, WindowId, WinitState> for PointerConstraintsState {
     fn event(
-        _state: &mut WinitState,
+        state: &mut WinitState,
         _proxy: &ZwpLockedPointerV1,
-        _event: <ZwpLockedPointerV1 as wayland_client::Proxy>::Event,
-        _data: &GlobalData,
+        event: <ZwpLockedPointerV1 as wayland_client::Proxy>::Event,
+        window_id: &WindowId,
         _conn: &Connection,
         _qhandle: &QueueHandle<WinitState>,
     ) {
+        let mode = match event {
+            zwp_locked_pointer_v1::Event::Locked => CursorGrabMode::Locked,
+            zwp_locked_pointer_v1::Event::Unlocked => CursorGrabMode::None,
+            _ => return,
+        };
+        state.events_sink.push_window_event(WindowEvent::CursorGrabChanged(mode), *window_id);
     }
 }
 
-impl Dispatch<ZwpConfinedPointerV1, GlobalData, WinitState> for PointerConstraintsState {
+impl Dispatch<ZwpConfinedPointerV1, WindowId, WinitState> for PointerConstraintsState {
     fn event(
-        _state: &mut WinitState,
+        state: &mut WinitState,
         _proxy: &ZwpConfinedPointerV1,
-        _event: <ZwpConfinedPointerV1 as wayland_client::Proxy>::Event,
-        _data: &GlobalData,
+        event: <ZwpConfinedPointerV1 as wayland_client::Proxy>::Event,
+        window_id: &WindowId,
         _conn: &Connection,
         _qhandle: &QueueHandle<WinitState>,
     ) {
+        let mode = match event {
+            zwp_confined_pointer_v1::Event::Confined => CursorGrabMode::Confined,
+            zwp_confined_pointer_v1::Event::Unconfined => CursorGrabMode::None,
+            _ => return,
+        };
+        state.events_sink.push_window_event(WindowEvent::CursorGrabChanged(mode), *window_id);
     }
 }
 
@@ -521,5 +575,5 @@ delegate_dispatch!(WinitState: [ WlPointer: WinitPointerData] => SeatState);
 delegate_dispatch!(WinitState: [ WpCursorShapeManagerV1: GlobalData] => SeatState);
 delegate_dispatch!(WinitState: [ WpCursorShapeDeviceV1: GlobalData] => SeatState);
 delegate_dispatch!(WinitState: [ZwpPointerConstraintsV1: GlobalData] => PointerConstraintsState);
-delegate_dispatch!(WinitState: [ZwpLockedPointerV1: GlobalData] => PointerConstraintsState);
-delegate_dispatch!(WinitState: [ZwpConfinedPointerV1: GlobalData] => PointerConstraintsState);
+delegate_dispatch!(WinitState: [ZwpLockedPointerV1: WindowId] => PointerConstraintsState);
+delegate_dispatch!(WinitState: [ZwpConfinedPointerV1: WindowId] => PointerConstraintsState);
@@ -77,7 +77,8 @@ impl Dispatch<ZwpTextInputV3, TextInputData, WinitState> for TextInputState {
                     text_input.set_state(Some(text_input_state), true);
                     // The input method doesn't have to reply anything, so a synthetic event
                     // carrying an empty state notifies the application about its presence.
-                    state.events_sink.push_window_event(WindowEvent::Ime(Ime::Enabled), window_id);
+                    let event = WindowEvent::Ime(Ime::Enabled(text_input_state.capabilities()));
+                    state.events_sink.push_window_event(event, window_id);
                 }
 
                 window.text_input_entered(text_input);
@@ -131,10 +132,18 @@ impl Dispatch<ZwpTextInputV3, TextInputData, WinitState> for TextInputState {
                 };
 
                 // Just in case some IME sends an event for the disabled window.
+                let mut selection = None;
                 if let Some(window) = windows.get(&window_id) {
-                    if window.lock().unwrap().text_input_state().is_none() {
-                        return;
-                    }
+                    let window = window.lock().unwrap();
+                    let text_input_state = match window.text_input_state() {
+                        Some(text_input_state) => text_input_state,
+                        None => return,
+                    };
+
+                    selection = text_input_state.surrounding_text().and_then(|surrounding| {
+                        let (cursor, anchor) = (surrounding.cursor(), surrounding.anchor());
+                        (cursor != anchor).then(|| (cursor.min(anchor), cursor.max(anchor)))
+                    });
                 };
 
                 // The events are sent to the user separately, so
@@ -149,9 +158,8 @@ impl Dispatch<ZwpTextInputV3, TextInputData, WinitState> for TextInputState {
                 // 5. Insert new preedit text in cursor position.
                 // 6. Place cursor inside preedit text.
 
-                if let Some(DeleteSurroundingText { before, after }) =
-                    text_input_data.pending_delete.take()
-                {
+                let pending_delete = text_input_data.pending_delete.take();
+                if let Some(DeleteSurroundingText { before, after }) = pending_delete {
                     state.events_sink.push_window_event(
                         WindowEvent::Ime(Ime::DeleteSurrounding {
                             before_bytes: before,
@@ -174,11 +182,13 @@ impl Dispatch<ZwpTextInputV3, TextInputData, WinitState> for TextInputState {
                     text_input_data.last_preedit_empty = true;
                 }
 
-                // Send `Commit`.
+                // Send `Commit`. Without an explicit deletion, the commit replaces the selection.
                 if let Some(text) = text_input_data.pending_commit.take() {
-                    state
-                        .events_sink
-                        .push_window_event(WindowEvent::Ime(Ime::Commit(text)), window_id);
+                    let replace_range = if pending_delete.is_none() { selection } else { None };
+                    state.events_sink.push_window_event(
+                        WindowEvent::Ime(Ime::Commit(text, replace_range)),
+                        window_id,
+                    );
                 }
 
                 // Send preedit.
@@ -188,7 +198,7 @@ impl Dispatch<ZwpTextInputV3, TextInputData, WinitState> for TextInputState {
 
                     text_input_data.last_preedit_empty = false;
                     state.events_sink.push_window_event(
-                        WindowEvent::Ime(Ime::Preedit(preedit.text, cursor_range)),
+                        WindowEvent::Ime(Ime::preedit(preedit.text, cursor_range)),
                         window_id,
                     );
                 }
@@ -373,6 +383,14 @@ impl ClientState {
                 warn!("discarding IME surrounding text update without capability enabled.");
             }
         }
+
+        if let Some((cursor, anchor)) = request_data.selection {
+            if !self.capabilities.surrounding_text() {
+                warn!("discarding IME selection update without capability enabled.");
+            } else if let Err(err) = self.surrounding_text.set_selection(cursor, anchor) {
+                warn!("discarding IME selection update: {err}.");
+            }
+        }
     }
 
     pub fn content_type(&self) -> Option<ContentType> {
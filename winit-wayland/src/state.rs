@@ -1,4 +1,5 @@
 use std::cell::RefCell;
+use std::mem;
 use std::sync::atomic::Ordering;
 use std::sync::{Arc, Mutex};
 
@@ -22,6 +23,7 @@ use sctk::shm::slot::SlotPool;
 use sctk::shm::{Shm, ShmHandler};
 use sctk::subcompositor::SubcompositorState;
 use winit_core::error::OsError;
+use winit_core::event::{WaitCancelReason, WindowEvent};
 
 use crate::WindowId;
 use crate::dnd::DndState;
@@ -35,7 +37,7 @@ use crate::types::bgr_effects::BgrEffectManager;
 use crate::types::wp_fractional_scaling::FractionalScalingManager;
 use crate::types::wp_tablet_input_v2::TabletManager;
 use crate::types::wp_viewporter::ViewporterState;
-use crate::types::xdg_activation::XdgActivationState;
+use crate::types::xdg_activation::{ActivationTimeouts, XdgActivationState};
 use crate::types::xdg_toplevel_icon_manager::XdgToplevelIconManagerState;
 use crate::window::{WindowRequests, WindowState};
 
@@ -94,6 +96,9 @@ pub struct WinitState {
     /// Xdg activation.
     pub xdg_activation: Option<XdgActivationState>,
 
+    /// The activation token requests waiting for the compositor with a timeout.
+    pub activation_timeouts: ActivationTimeouts,
+
     /// Xdg toplevel icon manager
     pub xdg_toplevel_icon_manager: Option<XdgToplevelIconManagerState>,
 
@@ -200,6 +205,7 @@ impl WinitState {
 
             xdg_shell: XdgShell::bind(globals, queue_handle).map_err(|err| os_error!(err))?,
             xdg_activation: XdgActivationState::bind(globals, queue_handle).ok(),
+            activation_timeouts: Default::default(),
             xdg_toplevel_icon_manager: XdgToplevelIconManagerState::bind(globals, queue_handle)
                 .ok(),
 
@@ -263,9 +269,12 @@ impl WinitState {
                 self.window_compositor_updates.len() - 1
             };
 
-            // Update the scale factor right away.
-            window.lock().unwrap().set_scale_factor(scale_factor);
-            self.window_compositor_updates[pos].scale_changed = true;
+            // Update the scale factor right away, keeping the one the application last saw.
+            let mut window = window.lock().unwrap();
+            let update = &mut self.window_compositor_updates[pos];
+            update.old_scale_factor.get_or_insert(window.scale_factor());
+            update.scale_changed = true;
+            window.set_scale_factor(scale_factor);
         } else if let Some(pointer) = self.pointer_surfaces.get(&surface.id()) {
             // Get the window, where the pointer resides right now.
             let focused_window = match pointer.pointer().winit_data().focused_window() {
@@ -279,6 +288,24 @@ impl WinitState {
         }
     }
 
+    /// What woke up the event loop, by order of precedence.
+    pub fn wait_cancel_reason(&self) -> WaitCancelReason {
+        if !self.events_sink.is_empty() || !self.window_compositor_updates.is_empty() {
+            WaitCancelReason::OsEvent
+        } else if self.proxy_wake_up {
+            WaitCancelReason::UserWakeUp
+        } else if self
+            .window_requests
+            .borrow()
+            .values()
+            .any(|requests| requests.redraw_requested.load(Ordering::Relaxed))
+        {
+            WaitCancelReason::Redraw
+        } else {
+            WaitCancelReason::Unknown
+        }
+    }
+
     pub fn queue_close(updates: &mut Vec<WindowCompositorUpdate>, window_id: WindowId) {
         let pos = if let Some(pos) = updates.iter().position(|update| update.window_id == window_id)
         {
@@ -323,15 +350,30 @@ impl WindowHandler for WinitState {
             self.window_compositor_updates.len() - 1
         };
 
-        // Populate the configure to the window.
-        self.window_compositor_updates[pos].resized |= self
+        let mut window = self
             .windows
             .get_mut()
-            .get_mut(&window_id)
+            .get(&window_id)
             .expect("got configure for dead window.")
             .lock()
-            .unwrap()
-            .configure(configure, &self.shm, &self.subcompositor_state);
+            .unwrap();
+
+        let was_maximized = window.last_configure.as_ref().is_some_and(|c| c.is_maximized());
+        let maximized = configure.is_maximized();
+
+        // Populate the configure to the window.
+        self.window_compositor_updates[pos].resized |=
+            window.configure(configure, &self.shm, &self.subcompositor_state);
+
+        if maximized != was_maximized {
+            self.events_sink.push_window_event(WindowEvent::MaximizedChanged(maximized), window_id);
+        }
+
+        // Deliver the initial scale factor with the first configure if the application asked for
+        // it.
+        self.window_compositor_updates[pos].scale_changed |=
+            mem::take(&mut window.initial_scale_event);
+        drop(window);
 
         // NOTE: configure demands wl_surface::commit, however winit doesn't commit on behalf of the
         // users, since it can break a lot of things, thus it'll ask users to redraw instead.
@@ -457,13 +499,22 @@ pub struct WindowCompositorUpdate {
     /// New scale factor.
     pub scale_changed: bool,
 
+    /// The scale factor before the first change of this update.
+    pub old_scale_factor: Option<f64>,
+
     /// Close the window.
     pub close_window: bool,
 }
 
 impl WindowCompositorUpdate {
     fn new(window_id: WindowId) -> Self {
-        Self { window_id, resized: false, scale_changed: false, close_window: false }
+        Self {
+            window_id,
+            resized: false,
+            scale_changed: false,
+            old_scale_factor: None,
+            close_window: false,
+        }
     }
 }
 
@@ -14,6 +14,19 @@ use crate::state::WinitState;
 /// The scaling factor denominator.
 const SCALE_DENOMINATOR: f64 = 120.;
 
+/// Convert a scale factor received as `scale / 120` back into a reduced fraction.
+pub fn to_fraction(scale_factor: f64) -> (u32, u32) {
+    fn gcd(a: u32, b: u32) -> u32 {
+        if b == 0 { a } else { gcd(b, a % b) }
+    }
+
+    // The scale was computed from an integer numerator, so rounding recovers it exactly.
+    let numerator = (scale_factor * SCALE_DENOMINATOR).round() as u32;
+    let denominator = SCALE_DENOMINATOR as u32;
+    let divisor = gcd(numerator, denominator);
+    (numerator / divisor, denominator / divisor)
+}
+
 /// Fractional scaling manager.
 #[derive(Debug)]
 pub struct FractionalScalingManager {
@@ -250,6 +250,7 @@ impl Dispatch<ZwpTabletToolV2, TabletToolData, WinitState> for TabletManager {
                                     button,
                                     data: data.tool_state.clone(),
                                 },
+                                click_count: None,
                             }
                         },
                         TabletEvent::Left => WindowEvent::PointerLeft {
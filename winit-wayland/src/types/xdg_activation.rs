@@ -2,6 +2,7 @@
 
 use std::sync::Weak;
 use std::sync::atomic::AtomicBool;
+use std::time::Instant;
 
 use sctk::globals::GlobalData;
 use sctk::reexports::client::globals::{BindError, GlobalList};
@@ -75,7 +76,10 @@ impl Dispatch<XdgActivationTokenV1, XdgActivationTokenData, WinitState> for XdgA
                     attention_requested.store(false, std::sync::atomic::Ordering::Relaxed);
                 }
             },
-            XdgActivationTokenData::Obtain((window_id, serial)) => {
+            XdgActivationTokenData::ObtainWithTimeout((_, serial))
+                if !state.activation_timeouts.answer(*serial) => {},
+            XdgActivationTokenData::Obtain((window_id, serial))
+            | XdgActivationTokenData::ObtainWithTimeout((window_id, serial)) => {
                 state.events_sink.push_window_event(
                     winit_core::event::WindowEvent::ActivationTokenDone {
                         serial: *serial,
@@ -96,7 +100,95 @@ pub enum XdgActivationTokenData {
     Attention((WlSurface, Weak<AtomicBool>)),
     /// Get a token to be passed outside of the winit.
     Obtain((WindowId, AsyncRequestSerial)),
+    /// Get a token, giving up when it isn't done before its deadline in [`ActivationTimeouts`].
+    ObtainWithTimeout((WindowId, AsyncRequestSerial)),
+}
+
+/// The activation token requests that give up when the compositor doesn't answer in time.
+///
+/// The token object of a request that timed out is destroyed if the compositor answers late.
+#[derive(Debug, Default)]
+pub struct ActivationTimeouts {
+    pending: Vec<PendingToken>,
+}
+
+#[derive(Debug)]
+struct PendingToken {
+    window_id: WindowId,
+    serial: AsyncRequestSerial,
+    deadline: Instant,
+}
+
+impl ActivationTimeouts {
+    pub fn push(&mut self, window_id: WindowId, serial: AsyncRequestSerial, deadline: Instant) {
+        self.pending.push(PendingToken { window_id, serial, deadline });
+    }
+
+    /// Mark the request as done, returning `false` when it timed out already.
+    pub fn answer(&mut self, serial: AsyncRequestSerial) -> bool {
+        let len = self.pending.len();
+        self.pending.retain(|pending| pending.serial != serial);
+        self.pending.len() != len
+    }
+
+    /// The earliest deadline, for the event loop to wake up at.
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.pending.iter().map(|pending| pending.deadline).min()
+    }
+
+    /// Give up on the requests whose deadline passed.
+    pub fn take_expired(&mut self, now: Instant) -> Vec<(WindowId, AsyncRequestSerial)> {
+        let mut expired = Vec::new();
+        self.pending.retain(|pending| {
+            if pending.deadline > now {
+                return true;
+            }
+
+            expired.push((pending.window_id, pending.serial));
+            false
+        });
+        expired
+    }
 }
 
 delegate_dispatch!(WinitState: [ XdgActivationV1: GlobalData] => XdgActivationState);
 delegate_dispatch!(WinitState: [ XdgActivationTokenV1: XdgActivationTokenData] => XdgActivationState);
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn expired_request_is_not_answered() {
+        let mut timeouts = ActivationTimeouts::default();
+        let window_id = WindowId::from_raw(1);
+        let start = Instant::now();
+        let (first, second) = (AsyncRequestSerial::get(), AsyncRequestSerial::get());
+        timeouts.push(window_id, first, start + Duration::from_millis(100));
+        timeouts.push(window_id, second, start + Duration::from_millis(50));
+        assert_eq!(timeouts.next_deadline(), Some(start + Duration::from_millis(50)));
+
+        assert!(timeouts.take_expired(start).is_empty());
+        assert_eq!(timeouts.take_expired(start + Duration::from_millis(50)), [(window_id, second)]);
+        assert_eq!(timeouts.next_deadline(), Some(start + Duration::from_millis(100)));
+
+        // The late answer is dropped, the request was done with an empty token already.
+        assert!(!timeouts.answer(second));
+        assert!(timeouts.answer(first));
+        assert_eq!(timeouts.next_deadline(), None);
+    }
+
+    #[test]
+    fn answered_request_does_not_expire() {
+        let mut timeouts = ActivationTimeouts::default();
+        let serial = AsyncRequestSerial::get();
+        let start = Instant::now();
+        timeouts.push(WindowId::from_raw(1), serial, start);
+
+        assert!(timeouts.answer(serial));
+        assert!(!timeouts.answer(serial));
+        assert!(timeouts.take_expired(start + Duration::from_secs(1)).is_empty());
+    }
+}
@@ -75,6 +75,9 @@ pub struct Window {
 
     /// The event sink to deliver synthetic events.
     window_events_sink: Arc<Mutex<EventSink>>,
+
+    /// Whether the initial commit was deferred until `realize`.
+    unrealized: AtomicBool,
 }
 
 impl Window {
@@ -124,6 +127,8 @@ impl Window {
 
         window_state.set_window_icon(attributes.window_icon);
 
+        window_state.initial_scale_event = attributes.initial_scale_event;
+
         // Set transparency hint.
         window_state.set_transparent(attributes.transparent);
 
@@ -167,10 +172,7 @@ impl Window {
             _ => (),
         };
 
-        match attributes.cursor {
-            Cursor::Icon(icon) => window_state.set_cursor(icon),
-            Cursor::Custom(cursor) => window_state.set_custom_cursor(cursor),
-        }
+        window_state.select_cursor(attributes.cursor);
 
         // Apply resize increments.
         if let Some(increments) = attributes.surface_resize_increments {
@@ -178,21 +180,27 @@ impl Window {
             window_state.set_resize_increments(Some(increments));
         }
 
-        // Activate the window when the token is passed.
-        if let (Some(xdg_activation), Some(token)) = (xdg_activation.as_ref(), activation_token) {
+        // Activate the window when the token is passed, unless it should stay in the background.
+        if let (Some(xdg_activation), Some(token), true) =
+            (xdg_activation.as_ref(), activation_token, attributes.active)
+        {
             xdg_activation.activate(token.into_raw(), &surface);
         }
 
-        // XXX Do initial commit.
-        window.commit();
+        // XXX Do initial commit, unless the application wants to realize the window later.
+        let unrealized = attributes.realize_deferred;
+        if !unrealized {
+            window.commit();
+        }
 
         // Add the window and window requests into the state.
         let window_state = Arc::new(Mutex::new(window_state));
         let window_id = super::make_wid(&surface);
         state.windows.get_mut().insert(window_id, window_state.clone());
 
+        // Drawing before the initial configure is a protocol error.
         let window_requests = WindowRequests {
-            redraw_requested: AtomicBool::new(true),
+            redraw_requested: AtomicBool::new(!unrealized),
             closed: AtomicBool::new(false),
         };
         let window_requests = Arc::new(window_requests);
@@ -207,8 +215,8 @@ impl Window {
         // Do a roundtrip.
         event_queue.roundtrip(&mut state).map_err(|err| os_error!(err))?;
 
-        // XXX Wait for the initial configure to arrive.
-        while !window_state.lock().unwrap().is_configured() {
+        // XXX Wait for the initial configure to arrive, it's only sent after the initial commit.
+        while !unrealized && !window_state.lock().unwrap().is_configured() {
             event_queue.blocking_dispatch(&mut state).map_err(|err| os_error!(err))?;
         }
 
@@ -229,6 +237,7 @@ impl Window {
             event_loop_awakener,
             window_requests,
             window_events_sink,
+            unrealized: AtomicBool::new(unrealized),
         })
     }
 
@@ -295,6 +304,11 @@ impl CoreWindow for Window {
     }
 
     fn request_redraw(&self) {
+        // Drawing before the initial configure is a protocol error.
+        if self.unrealized.load(Ordering::Relaxed) {
+            return;
+        }
+
         // NOTE: try to not wake up the loop when the event was already scheduled and not yet
         // processed by the loop, because if at this point the value was `true` it could only
         // mean that the loop still haven't dispatched the value to the client and will do
@@ -336,12 +350,11 @@ impl CoreWindow for Window {
     }
 
     fn surface_size(&self) -> PhysicalSize<u32> {
-        let window_state = self.window_state.lock().unwrap();
-        let scale_factor = window_state.scale_factor();
-        super::logical_to_physical_rounded(window_state.surface_size(), scale_factor)
+        self.window_state.lock().unwrap().physical_surface_size()
     }
 
     fn request_surface_size(&self, size: Size) -> Option<PhysicalSize<u32>> {
+        let size = winit_core::window::clamp_surface_size(size);
         let mut window_state = self.window_state.lock().unwrap();
         let new_size = window_state.request_surface_size(size);
         self.request_redraw();
@@ -355,7 +368,15 @@ impl CoreWindow for Window {
     }
 
     fn safe_area(&self) -> PhysicalInsets<u32> {
-        PhysicalInsets::new(0, 0, 0, 0)
+        let window_state = self.window_state.lock().unwrap();
+        let scale_factor = window_state.scale_factor();
+        window_state.safe_area().to_physical(scale_factor)
+    }
+
+    fn set_surface_margins(&self, margins: Option<PhysicalInsets<u32>>) {
+        self.window_state.lock().unwrap().set_surface_margins(margins);
+        // NOTE: Requires commit to be applied.
+        self.request_redraw();
     }
 
     fn set_min_surface_size(&self, min_size: Option<Size>) {
@@ -401,8 +422,19 @@ impl CoreWindow for Window {
         self.window_state.lock().unwrap().set_transparent(transparent);
     }
 
-    fn set_visible(&self, _visible: bool) {
-        // Not possible on Wayland.
+    fn set_visible(&self, visible: bool) {
+        // Hiding isn't possible on Wayland.
+        if visible {
+            self.realize();
+        }
+    }
+
+    fn realize(&self) {
+        if self.unrealized.swap(false, Ordering::Relaxed) {
+            // The initial configure will request a redraw.
+            self.window.commit();
+            self.event_loop_awakener.ping();
+        }
     }
 
     fn is_visible(&self) -> Option<bool> {
@@ -426,7 +458,10 @@ impl CoreWindow for Window {
 
     fn enabled_buttons(&self) -> WindowButtons {
         // TODO(kchibisov) v5 of the xdg_shell allows that.
-        WindowButtons::all()
+        let mut buttons = WindowButtons::default();
+        // The frame hides the maximize button of non-resizable windows.
+        buttons.set(WindowButtons::MAXIMIZE, self.is_resizable());
+        buttons
     }
 
     fn set_minimized(&self, minimized: bool) {
@@ -497,6 +532,11 @@ impl CoreWindow for Window {
         self.window_state.lock().unwrap().scale_factor()
     }
 
+    #[inline]
+    fn fractional_scale(&self) -> Option<(u32, u32)> {
+        self.window_state.lock().unwrap().fractional_scale()
+    }
+
     #[inline]
     fn set_blur(&self, blur: bool) {
         if self.window_state.lock().unwrap().set_blur(blur) {
@@ -520,12 +560,19 @@ impl CoreWindow for Window {
         self.window_state.lock().unwrap().set_window_icon(window_icon)
     }
 
+    fn window_icon(&self) -> Option<winit_core::icon::Icon> {
+        self.window_state.lock().unwrap().window_icon()
+    }
+
     #[inline]
     fn request_ime_update(&self, request: ImeRequest) -> Result<(), ImeRequestError> {
         let state_changed = self.window_state.lock().unwrap().request_ime_update(request)?;
 
         if let Some(allowed) = state_changed {
-            let event = WindowEvent::Ime(if allowed { Ime::Enabled } else { Ime::Disabled });
+            let event = WindowEvent::Ime(match self.ime_capabilities() {
+                Some(capabilities) if allowed => Ime::Enabled(capabilities),
+                _ => Ime::Disabled,
+            });
             self.window_events_sink.lock().unwrap().push_window_event(event, self.window_id);
             self.event_loop_awakener.ping();
         }
@@ -571,7 +618,10 @@ impl CoreWindow for Window {
     }
 
     fn set_theme(&self, theme: Option<Theme>) {
-        self.window_state.lock().unwrap().set_theme(theme)
+        if self.window_state.lock().unwrap().set_theme(theme) {
+            // NOTE: Requires commit to be applied.
+            self.request_redraw();
+        }
     }
 
     fn theme(&self) -> Option<Theme> {
@@ -580,13 +630,12 @@ impl CoreWindow for Window {
 
     fn set_content_protected(&self, _protected: bool) {}
 
-    fn set_cursor(&self, cursor: Cursor) {
-        let window_state = &mut self.window_state.lock().unwrap();
+    fn is_content_protected(&self) -> bool {
+        false
+    }
 
-        match cursor {
-            Cursor::Icon(icon) => window_state.set_cursor(icon),
-            Cursor::Custom(cursor) => window_state.set_custom_cursor(cursor),
-        }
+    fn set_cursor(&self, cursor: Cursor) {
+        self.window_state.lock().unwrap().select_cursor(cursor);
     }
 
     fn set_cursor_position(&self, position: Position) -> Result<(), RequestError> {
@@ -656,8 +705,9 @@ impl CoreWindow for Window {
     }
 
     fn primary_monitor(&self) -> Option<CoreMonitorHandle> {
-        // NOTE: There's no such concept on Wayland.
-        None
+        let monitors = self.monitors.lock().unwrap().clone();
+        output::primary_monitor(monitors.into_iter())
+            .map(|monitor| CoreMonitorHandle(Arc::new(monitor)))
     }
 
     /// Get the raw-window-handle v0.6 display handle.
@@ -4,7 +4,10 @@ use std::num::NonZeroU32;
 use std::sync::{Arc, Mutex, Weak};
 use std::time::Duration;
 
-use dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize, Size};
+use dpi::{
+    LogicalInsets, LogicalPosition, LogicalSize, PhysicalInsets, PhysicalPosition, PhysicalSize,
+    Size,
+};
 use foldhash::HashSet;
 use sctk::compositor::{CompositorState, Region, SurfaceData, SurfaceDataExt};
 use sctk::globals::GlobalData;
@@ -29,10 +32,11 @@ use sctk::shm::slot::SlotPool;
 use sctk::subcompositor::SubcompositorState;
 use tracing::{info, warn};
 use wayland_protocols::xdg::toplevel_icon::v1::client::xdg_toplevel_icon_manager_v1::XdgToplevelIconManagerV1;
-use winit_core::cursor::{CursorIcon, CustomCursor as CoreCustomCursor};
+use winit_core::cursor::{Cursor, CursorIcon, CustomCursor as CoreCustomCursor};
 use winit_core::error::{NotSupportedError, RequestError};
 use winit_core::window::{
-    CursorGrabMode, ImeCapabilities, ImeRequest, ImeRequestError, ResizeDirection, Theme, WindowId,
+    CursorGrabMode, ImeCapabilities, ImeRequest, ImeRequestData, ImeRequestError, ResizeDirection,
+    Theme, WindowId,
 };
 
 use crate::event_loop::OwnedDisplayHandle;
@@ -44,6 +48,7 @@ use crate::seat::{
 use crate::state::{WindowCompositorUpdate, WinitState};
 use crate::types::bgr_effects::{BgrEffectManager, SurfaceBlurEffect};
 use crate::types::cursor::{CustomCursor, SelectedCursor, WaylandCustomCursor};
+use crate::types::wp_fractional_scaling;
 use crate::types::xdg_toplevel_icon_manager::ToplevelIcon;
 
 #[cfg(feature = "sctk-adwaita")]
@@ -86,6 +91,9 @@ pub struct WindowState {
     /// The current window title.
     title: String,
 
+    /// The last icon applied to the window.
+    window_icon: Option<winit_core::icon::Icon>,
+
     /// Xdg toplevel icon manager to request icon setting.
     xdg_toplevel_icon_manager: Option<XdgToplevelIconManagerV1>,
 
@@ -115,6 +123,9 @@ pub struct WindowState {
     /// The current cursor grabbing mode.
     cursor_grab_mode: GrabState,
 
+    /// The cursor position to set once the requested pointer lock is in effect.
+    pending_cursor_position: Option<LogicalPosition<f64>>,
+
     /// The input method properties provided by the application to the IME.
     ///
     /// This state is cached here so that the window can automatically send the state to the IME as
@@ -150,6 +161,12 @@ pub struct WindowState {
     /// configure.
     initial_size: Option<Size>,
 
+    /// Whether to deliver `ScaleFactorChanged` with the first configure.
+    pub initial_scale_event: bool,
+
+    /// The margins around the window geometry requested by the user.
+    surface_margins: Option<PhysicalInsets<u32>>,
+
     /// The state of the frame callback.
     frame_callback_state: FrameCallbackState,
 
@@ -163,6 +180,9 @@ pub struct WindowState {
     /// The value is the serial of the event triggered moved.
     has_pending_move: Option<u32>,
 
+    /// The state of an interactive move, used to report its start and end.
+    drag_move: winit_core::window::DragMove,
+
     /// The underlying SCTK window.
     pub window: Window,
 
@@ -210,6 +230,7 @@ impl WindowState {
             handle,
             csd_fails: false,
             cursor_grab_mode: GrabState::new(),
+            pending_cursor_position: None,
             selected_cursor: Default::default(),
             cursor_visible: true,
             decorate: true,
@@ -219,6 +240,7 @@ impl WindowState {
             frame_callback_state: FrameCallbackState::None,
             seat_focus: Default::default(),
             has_pending_move: None,
+            drag_move: Default::default(),
             text_input_state: None,
             last_configure: None,
             max_surface_size: None,
@@ -234,9 +256,12 @@ impl WindowState {
             size: initial_size.to_logical(1.),
             stateless_size: initial_size.to_logical(1.),
             initial_size: Some(initial_size),
+            initial_scale_event: false,
+            surface_margins: None,
             text_inputs: Vec::new(),
             theme,
             title: String::default(),
+            window_icon: None,
             transparent: false,
             viewport,
             window,
@@ -334,6 +359,7 @@ impl WindowState {
         }
 
         let stateless = Self::is_stateless(&configure);
+        let margins = self.geometry_margins();
 
         let (mut new_size, constrain) = if let Some(frame) = self.frame.as_mut() {
             // Configure the window states.
@@ -357,6 +383,13 @@ impl WindowState {
             }
         };
 
+        // The size picked by the compositor is the one of the window geometry, so grow the surface
+        // by the margins.
+        if !constrain {
+            new_size.width += margins.left + margins.right;
+            new_size.height += margins.top + margins.bottom;
+        }
+
         // Apply configure bounds only when compositor let the user decide what size to pick.
         if constrain {
             let bounds = self.surface_size_bounds(&configure);
@@ -469,18 +502,38 @@ impl WindowState {
     }
 
     /// Start the window drag.
-    pub fn drag_window(&self) -> Result<(), RequestError> {
+    pub fn drag_window(&mut self) -> Result<(), RequestError> {
         let xdg_toplevel = self.window.xdg_toplevel();
         // TODO(kchibisov) handle touch serials.
+        let mut button_pressed = false;
         self.apply_on_pointer(|_, data| {
             let serial = data.latest_button_serial();
             let seat = data.seat();
             xdg_toplevel._move(seat, serial);
+            button_pressed |= data.is_button_pressed();
         });
 
+        // The compositor only moves the window while the button is held.
+        if button_pressed {
+            self.drag_move.request();
+        }
+
         Ok(())
     }
 
+    /// Marks the requested move as started once the compositor took the pointer focus, returning
+    /// whether a move was requested.
+    pub fn drag_move_started(&mut self) -> bool {
+        self.drag_move.pointer_grabbed()
+    }
+
+    /// Marks the move as done, returning whether a move was active.
+    ///
+    /// A `released` button also drops a requested move the compositor never started.
+    pub fn drag_move_ended(&mut self, released: bool) -> bool {
+        self.drag_move.pointer_input(released)
+    }
+
     /// Tells whether the window should be closed.
     #[allow(clippy::too_many_arguments)]
     pub fn frame_click(
@@ -545,6 +598,7 @@ impl WindowState {
             // so try to apply move.
             if let Some(serial) = cursor.is_some().then_some(serial).flatten() {
                 self.window.move_(seat, serial);
+                self.drag_move.request();
                 None
             } else {
                 cursor
@@ -574,8 +628,14 @@ impl WindowState {
             // Restore min/max sizes of the window.
             self.reload_min_max_hints();
         } else {
-            self.set_min_surface_size(Some(self.size));
-            self.set_max_surface_size(Some(self.size));
+            // Pin the size without touching the requested limits, so they can be restored.
+            let size: LogicalSize<u32> = self
+                .frame
+                .as_ref()
+                .map(|frame| frame.add_borders(self.size.width, self.size.height).into())
+                .unwrap_or(self.size);
+            self.window.set_min_size(Some(size.into()));
+            self.window.set_max_size(Some(size.into()));
         }
 
         // Reload the state on the frame as well.
@@ -608,6 +668,33 @@ impl WindowState {
         self.size
     }
 
+    /// Get the physical surface size, which is the requested size until the first configure.
+    ///
+    /// The logical size can't be used before that, since the scale factor the requested size must
+    /// be converted with may only be known once the window is configured.
+    pub fn physical_surface_size(&self) -> PhysicalSize<u32> {
+        match self.initial_size {
+            Some(initial_size) => initial_size.to_physical(self.scale_factor()),
+            None => logical_to_physical_rounded(self.size, self.scale_factor()),
+        }
+    }
+
+    /// Get the part of the surface which doesn't fit the configure bounds.
+    ///
+    /// The bounds are the space the compositor doesn't reserve, e.g. for panels, and already
+    /// account for the decorations, which are drawn outside of the surface.
+    pub fn safe_area(&self) -> LogicalInsets<u32> {
+        let bounds = match self.last_configure.as_ref() {
+            Some(configure) if Self::is_stateless(configure) => self.surface_size_bounds(configure),
+            // The compositor picked the size, so the surface fits.
+            _ => (None, None),
+        };
+
+        let right = bounds.0.map_or(0, |bound_w| self.size.width.saturating_sub(bound_w.get()));
+        let bottom = bounds.1.map_or(0, |bound_h| self.size.height.saturating_sub(bound_h.get()));
+        LogicalInsets::new(0, 0, bottom, right)
+    }
+
     /// Whether the window received initial configure event from the compositor.
     #[inline]
     pub fn is_configured(&self) -> bool {
@@ -645,6 +732,12 @@ impl WindowState {
 
         let mode = self.cursor_grab_mode.user_grab_mode;
         let _ = self.set_cursor_grab_inner(mode);
+
+        if self.cursor_grab_mode.current_grab_mode == CursorGrabMode::Locked {
+            if let Some(position) = self.pending_cursor_position.take() {
+                let _ = self.set_cursor_position(position);
+            }
+        }
     }
 
     /// Pointer has left the top-level.
@@ -676,7 +769,9 @@ impl WindowState {
     pub fn reload_cursor_style(&mut self) {
         if self.cursor_visible {
             match &self.selected_cursor {
-                SelectedCursor::Named(icon) => self.set_cursor(*icon),
+                SelectedCursor::Named(icon) => {
+                    self.set_cursor(*icon);
+                },
                 SelectedCursor::Custom(cursor) => self.apply_custom_cursor(cursor),
             }
         } else {
@@ -700,11 +795,16 @@ impl WindowState {
 
     /// Try to resize the window when the user can do so.
     pub fn request_surface_size(&mut self, surface_size: Size) -> PhysicalSize<u32> {
+        // Apply the latest request with the first configure.
+        if self.initial_size.is_some() {
+            self.initial_size = Some(surface_size);
+        }
+
         if self.last_configure.as_ref().map(Self::is_stateless).unwrap_or(true) {
             self.resize(surface_size.to_logical(self.scale_factor()))
         }
 
-        logical_to_physical_rounded(self.surface_size(), self.scale_factor())
+        self.physical_surface_size()
     }
 
     /// Resize the window to the new surface size.
@@ -716,6 +816,8 @@ impl WindowState {
             self.stateless_size = surface_size;
         }
 
+        let margins = self.geometry_margins();
+
         // Update the inner frame.
         let ((x, y), outer_size) = if let Some(frame) = self.frame.as_mut() {
             // Resize only visible frame.
@@ -731,6 +833,13 @@ impl WindowState {
             ((0, 0), self.size)
         };
 
+        // Carve the margins out of the window geometry.
+        let (x, y) = (x + margins.left as i32, y + margins.top as i32);
+        let outer_size = LogicalSize::new(
+            outer_size.width.saturating_sub(margins.left + margins.right).max(1),
+            outer_size.height.saturating_sub(margins.top + margins.bottom).max(1),
+        );
+
         // Reload the hint.
         self.reload_transparency_hint();
 
@@ -756,34 +865,77 @@ impl WindowState {
         }
     }
 
+    /// Set the margins around the window geometry.
+    pub fn set_surface_margins(&mut self, margins: Option<PhysicalInsets<u32>>) {
+        self.surface_margins = margins;
+        // Reload the window geometry.
+        self.resize(self.size);
+    }
+
+    /// The margins carved out of the window geometry, only applied without visible frame.
+    fn geometry_margins(&self) -> LogicalInsets<u32> {
+        match self.surface_margins {
+            Some(margins) if self.frame.as_ref().is_none_or(|frame| frame.is_hidden()) => {
+                margins.to_logical(self.scale_factor())
+            },
+            _ => LogicalInsets::new(0, 0, 0, 0),
+        }
+    }
+
     /// Get the scale factor of the window.
     #[inline]
     pub fn scale_factor(&self) -> f64 {
         self.scale_factor
     }
 
-    /// Set the cursor icon.
-    pub fn set_cursor(&mut self, cursor_icon: CursorIcon) {
-        self.selected_cursor = SelectedCursor::Named(cursor_icon);
+    /// Get the exact scale factor as a reduced fraction, when fractional scaling is used.
+    pub fn fractional_scale(&self) -> Option<(u32, u32)> {
+        self.fractional_scale.as_ref()?;
+        Some(wp_fractional_scaling::to_fraction(self.scale_factor))
+    }
 
-        if !self.cursor_visible {
-            return;
+    /// Set the cursor, using the first entry of a fallback chain that can be shown.
+    pub(crate) fn select_cursor(&mut self, cursor: Cursor) {
+        for candidate in cursor.candidates() {
+            let applied = match candidate {
+                Cursor::Icon(icon) => self.set_cursor(*icon),
+                Cursor::Custom(cursor) => self.set_custom_cursor(cursor.clone()),
+                Cursor::Fallback(_) => unreachable!("fallback chains are flattened"),
+            };
+
+            if applied {
+                break;
+            }
         }
+    }
 
-        self.apply_on_pointer(|pointer, _| {
-            if pointer.set_cursor(&self.handle.connection, cursor_icon).is_err() {
-                warn!("Failed to set cursor to {:?}", cursor_icon);
+    /// Set the cursor icon, returning whether it could be shown.
+    pub fn set_cursor(&mut self, cursor_icon: CursorIcon) -> bool {
+        if self.cursor_visible {
+            let mut applied = true;
+            self.apply_on_pointer(|pointer, _| {
+                if pointer.set_cursor(&self.handle.connection, cursor_icon).is_err() {
+                    warn!("Failed to set cursor to {:?}", cursor_icon);
+                    applied = false;
+                }
+            });
+
+            if !applied {
+                return false;
             }
-        })
+        }
+
+        self.selected_cursor = SelectedCursor::Named(cursor_icon);
+        true
     }
 
-    /// Set the custom cursor icon.
-    pub(crate) fn set_custom_cursor(&mut self, cursor: CoreCustomCursor) {
+    /// Set the custom cursor icon, returning whether it could be shown.
+    pub(crate) fn set_custom_cursor(&mut self, cursor: CoreCustomCursor) -> bool {
         let cursor = match cursor.cast_ref::<WaylandCustomCursor>() {
             Some(cursor) => cursor,
             None => {
                 tracing::error!("unrecognized cursor passed to Wayland backend");
-                return;
+                return false;
             },
         };
 
@@ -797,6 +949,7 @@ impl WindowState {
         }
 
         self.selected_cursor = SelectedCursor::Custom(cursor);
+        true
     }
 
     /// Set the resize increments of the window.
@@ -864,7 +1017,9 @@ impl WindowState {
             .unwrap_or(size);
 
         self.min_surface_size = size;
-        self.window.set_min_size(Some(size.into()));
+        if self.resizable {
+            self.window.set_min_size(Some(size.into()));
+        }
     }
 
     /// Set maximum inner window size.
@@ -877,16 +1032,24 @@ impl WindowState {
         });
 
         self.max_surface_size = size;
-        self.window.set_max_size(size.map(Into::into));
+        if self.resizable {
+            self.window.set_max_size(size.map(Into::into));
+        }
     }
 
     /// Set the CSD theme.
-    pub fn set_theme(&mut self, theme: Option<Theme>) {
+    ///
+    /// Returns `true` when the frame was repainted and the window must be committed.
+    pub fn set_theme(&mut self, theme: Option<Theme>) -> bool {
         self.theme = theme;
         #[cfg(feature = "sctk-adwaita")]
         if let Some(frame) = self.frame.as_mut() {
-            frame.set_config(create_sctk_adwaita_config(theme))
+            frame.set_config(create_sctk_adwaita_config(theme));
+            // Repaint right away, otherwise the frame keeps the old colors until the next
+            // configure or redraw.
+            return self.refresh_frame();
         }
+        false
     }
 
     /// The current theme for CSD decorations.
@@ -904,6 +1067,9 @@ impl WindowState {
         self.set_cursor_grab_inner(mode)?;
         // Update user grab on success.
         self.cursor_grab_mode.user_grab_mode = mode;
+        if mode != CursorGrabMode::Locked {
+            self.pending_cursor_position = None;
+        }
         Ok(())
     }
 
@@ -983,11 +1149,23 @@ impl WindowState {
     }
 
     /// Set the position of the cursor.
-    pub fn set_cursor_position(&self, position: LogicalPosition<f64>) -> Result<(), RequestError> {
+    pub fn set_cursor_position(
+        &mut self,
+        position: LogicalPosition<f64>,
+    ) -> Result<(), RequestError> {
         if self.pointer_constraints.is_none() {
             return Err(NotSupportedError::new("zwp_pointer_constraints is not available").into());
         }
 
+        // Apply the position once the requested lock is in effect, a pointer must be over the
+        // mapped window for that.
+        if self.cursor_grab_mode.current_grab_mode != CursorGrabMode::Locked
+            && self.cursor_grab_mode.user_grab_mode == CursorGrabMode::Locked
+        {
+            self.pending_cursor_position = Some(position);
+            return Ok(());
+        }
+
         // Position can be set only for locked cursor.
         if self.cursor_grab_mode.current_grab_mode != CursorGrabMode::Locked {
             return Err(NotSupportedError::new(
@@ -1009,7 +1187,9 @@ impl WindowState {
 
         if self.cursor_visible {
             match &self.selected_cursor {
-                SelectedCursor::Named(icon) => self.set_cursor(*icon),
+                SelectedCursor::Named(icon) => {
+                    self.set_cursor(*icon);
+                },
                 SelectedCursor::Custom(cursor) => self.apply_custom_cursor(cursor),
             }
         } else {
@@ -1061,6 +1241,13 @@ impl WindowState {
         self.seat_focus.remove(seat);
     }
 
+    /// Clamp the IME cursor area of the request to the window surface.
+    fn clamp_ime_cursor_area(&self, request_data: ImeRequestData) -> ImeRequestData {
+        let scale_factor = self.scale_factor();
+        let surface_size = logical_to_physical_rounded(self.surface_size(), scale_factor);
+        request_data.clamp_cursor_area(surface_size, scale_factor)
+    }
+
     /// Atomically update input method state.
     ///
     /// Returns `None` if an input method state haven't changed. Alternatively `Some(true)` and
@@ -1069,6 +1256,7 @@ impl WindowState {
         &mut self,
         request: ImeRequest,
     ) -> Result<Option<bool>, ImeRequestError> {
+        let request = request.to_physical(self.scale_factor());
         let state_change = match request {
             ImeRequest::Enable(enable) => {
                 let (capabilities, request_data) = enable.into_raw();
@@ -1077,6 +1265,8 @@ impl WindowState {
                     return Err(ImeRequestError::AlreadyEnabled);
                 }
 
+                let request_data = self.clamp_ime_cursor_area(request_data);
+
                 self.text_input_state = Some(TextInputClientState::new(
                     capabilities,
                     request_data,
@@ -1085,6 +1275,7 @@ impl WindowState {
                 true
             },
             ImeRequest::Update(request_data) => {
+                let request_data = self.clamp_ime_cursor_area(request_data);
                 let scale_factor = self.scale_factor();
                 if let Some(text_input_state) = self.text_input_state.as_mut() {
                     text_input_state.update(request_data, scale_factor);
@@ -1184,6 +1375,12 @@ impl WindowState {
         self.title = title;
     }
 
+    /// The last icon applied to the window.
+    #[inline]
+    pub fn window_icon(&self) -> Option<winit_core::icon::Icon> {
+        self.window_icon.clone()
+    }
+
     /// Set the window's icon
     pub fn set_window_icon(&mut self, window_icon: Option<winit_core::icon::Icon>) {
         let xdg_toplevel_icon_manager = match self.xdg_toplevel_icon_manager.as_ref() {
@@ -1194,7 +1391,7 @@ impl WindowState {
             },
         };
 
-        let (toplevel_icon, xdg_toplevel_icon) = match window_icon {
+        let (toplevel_icon, xdg_toplevel_icon) = match window_icon.clone() {
             Some(icon) => {
                 let mut image_pool = self.image_pool.lock().unwrap();
                 let toplevel_icon = match ToplevelIcon::new(icon, &mut image_pool) {
@@ -1217,6 +1414,7 @@ impl WindowState {
 
         xdg_toplevel_icon_manager.set_icon(self.window.xdg_toplevel(), xdg_toplevel_icon.as_ref());
         self.toplevel_icon = toplevel_icon;
+        self.window_icon = window_icon;
 
         if let Some(xdg_toplevel_icon) = xdg_toplevel_icon {
             xdg_toplevel_icon.destroy();
@@ -1312,8 +1510,8 @@ fn resize_direction_to_xdg(direction: ResizeDirection) -> XdgResizeEdge {
 #[cfg(feature = "sctk-adwaita")]
 fn create_sctk_adwaita_config(theme: Option<Theme>) -> sctk_adwaita::FrameConfig {
     let config = match theme {
-        Some(Theme::Light) => sctk_adwaita::FrameConfig::light(),
-        Some(Theme::Dark) => sctk_adwaita::FrameConfig::dark(),
+        Some(theme) if theme.is_dark() => sctk_adwaita::FrameConfig::dark(),
+        Some(_) => sctk_adwaita::FrameConfig::light(),
         None => sctk_adwaita::FrameConfig::auto(),
     };
     #[cfg(feature = "csd-adwaita-notitlebar")]
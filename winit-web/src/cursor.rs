@@ -197,94 +197,111 @@ impl CursorHandler {
     }
 
     pub fn set_cursor(&self, cursor: Cursor) {
+        let candidates: Vec<_> = cursor.candidates().collect();
         let mut this = self.0.borrow_mut();
 
-        match cursor {
-            Cursor::Icon(icon) => {
-                if let SelectedCursor::Icon(old_icon)
-                | SelectedCursor::Loading { previous: Previous::Icon(old_icon), .. } =
-                    &this.cursor
-                {
-                    if *old_icon == icon {
-                        return;
+        // Use the first cursor in the fallback chain that can be shown.
+        for (index, candidate) in candidates.iter().enumerate() {
+            match candidate {
+                Cursor::Icon(icon) => {
+                    let icon = *icon;
+                    if let SelectedCursor::Icon(old_icon)
+                    | SelectedCursor::Loading { previous: Previous::Icon(old_icon), .. } =
+                        &this.cursor
+                    {
+                        if *old_icon == icon {
+                            return;
+                        }
                     }
-                }
 
-                this.cursor = SelectedCursor::Icon(icon);
-                this.set_style();
-            },
-            Cursor::Custom(cursor) => {
-                let cursor = match cursor.cast_ref::<CustomCursor>() {
-                    Some(cursor) => cursor,
-                    None => return,
-                };
-
-                if let SelectedCursor::Loading { cursor: old_cursor, .. }
-                | SelectedCursor::Image(old_cursor)
-                | SelectedCursor::Animation { cursor: old_cursor, .. } = &this.cursor
-                {
-                    if old_cursor == cursor {
-                        return;
+                    this.cursor = SelectedCursor::Icon(icon);
+                    this.set_style();
+                },
+                Cursor::Custom(cursor) => {
+                    let cursor = match cursor.cast_ref::<CustomCursor>() {
+                        Some(cursor) => cursor,
+                        None => continue,
+                    };
+
+                    if let SelectedCursor::Loading { cursor: old_cursor, .. }
+                    | SelectedCursor::Image(old_cursor)
+                    | SelectedCursor::Animation { cursor: old_cursor, .. } = &this.cursor
+                    {
+                        if old_cursor == cursor {
+                            return;
+                        }
                     }
-                }
 
-                let state = cursor.state.get(this.main_thread).borrow();
-
-                match state.deref() {
-                    ImageState::Loading { notifier, .. } => {
-                        let notified = notifier.notified();
-                        let handle = DropAbortHandle::new(AbortHandle::new());
-                        let task = Abortable::new(handle.handle(), {
-                            let weak = Rc::downgrade(&self.0);
-                            async move {
-                                let _ = notified.await;
-                                let handler = weak
-                                    .upgrade()
-                                    .expect("`CursorHandler` invalidated without aborting");
-                                handler.borrow_mut().notify();
+                    let state = cursor.state.get(this.main_thread).borrow();
+
+                    match state.deref() {
+                        ImageState::Loading { notifier, .. } => {
+                            let notified = notifier.notified();
+                            let handle = DropAbortHandle::new(AbortHandle::new());
+                            let task = Abortable::new(handle.handle(), {
+                                let weak = Rc::downgrade(&self.0);
+                                async move {
+                                    let _ = notified.await;
+                                    let handler = weak
+                                        .upgrade()
+                                        .expect("`CursorHandler` invalidated without aborting");
+                                    let fallback = handler.borrow_mut().notify();
+
+                                    if let Some(fallback) = fallback {
+                                        CursorHandler(handler).set_cursor(fallback);
+                                    }
+                                }
+                            });
+                            wasm_bindgen_futures::spawn_local(async move {
+                                let _ = task.await;
+                            });
+
+                            drop(state);
+                            this.cursor = SelectedCursor::Loading {
+                                cursor: cursor.clone(),
+                                previous: mem::take(&mut this.cursor).into(),
+                                fallback: candidates[index + 1..]
+                                    .iter()
+                                    .map(|&cursor| cursor.clone())
+                                    .collect(),
+                                _handle: handle,
+                            };
+                        },
+                        ImageState::Failed(error) => {
+                            tracing::error!(
+                                "trying to load custom cursor that has failed to load: {error}"
+                            );
+                            continue;
+                        },
+                        ImageState::Image(_) => {
+                            drop(state);
+                            this.cursor = SelectedCursor::Image(cursor.clone());
+                            this.set_style();
+                        },
+                        ImageState::Animation(animation) => {
+                            let canvas: &CanvasAnimateExt = this.canvas.unchecked_ref();
+                            let animation = canvas.animate_with_keyframe_animation_options(
+                                Some(&animation.keyframes),
+                                &animation.options,
+                            );
+                            drop(state);
+
+                            if !this.visible {
+                                animation.cancel();
                             }
-                        });
-                        wasm_bindgen_futures::spawn_local(async move {
-                            let _ = task.await;
-                        });
-
-                        drop(state);
-                        this.cursor = SelectedCursor::Loading {
-                            cursor: cursor.clone(),
-                            previous: mem::take(&mut this.cursor).into(),
-                            _handle: handle,
-                        };
-                    },
-                    ImageState::Failed(error) => {
-                        tracing::error!(
-                            "trying to load custom cursor that has failed to load: {error}"
-                        )
-                    },
-                    ImageState::Image(_) => {
-                        drop(state);
-                        this.cursor = SelectedCursor::Image(cursor.clone());
-                        this.set_style();
-                    },
-                    ImageState::Animation(animation) => {
-                        let canvas: &CanvasAnimateExt = this.canvas.unchecked_ref();
-                        let animation = canvas.animate_with_keyframe_animation_options(
-                            Some(&animation.keyframes),
-                            &animation.options,
-                        );
-                        drop(state);
-
-                        if !this.visible {
-                            animation.cancel();
-                        }
 
-                        this.cursor = SelectedCursor::Animation {
-                            animation: AnimationDropper(animation),
-                            cursor: cursor.clone(),
-                        };
-                        this.set_style();
-                    },
-                };
-            },
+                            this.cursor = SelectedCursor::Animation {
+                                animation: AnimationDropper(animation),
+                                cursor: cursor.clone(),
+                            };
+                            this.set_style();
+                        },
+                    };
+                },
+                Cursor::Fallback(_) => unreachable!("fallback chains are flattened"),
+            }
+
+            return;
         }
     }
 
@@ -335,8 +352,12 @@ impl Inner {
         }
     }
 
-    fn notify(&mut self) {
-        let SelectedCursor::Loading { cursor, previous, .. } = mem::take(&mut self.cursor) else {
+    /// Applies a finished custom cursor, returning the remaining fallback chain if it failed to
+    /// load.
+    fn notify(&mut self) -> Option<Cursor> {
+        let SelectedCursor::Loading { cursor, previous, fallback, .. } =
+            mem::take(&mut self.cursor)
+        else {
             unreachable!("found wrong state")
         };
 
@@ -365,19 +386,34 @@ impl Inner {
             },
             ImageState::Failed(error) => {
                 tracing::error!("custom cursor failed to load: {error}");
-                self.cursor = previous.into()
+                self.cursor = previous.into();
+
+                if !fallback.is_empty() {
+                    return Some(Cursor::Fallback(fallback));
+                }
             },
             ImageState::Loading { .. } => unreachable!("notified without being ready"),
         }
+
+        None
     }
 }
 
 #[derive(Debug)]
 enum SelectedCursor {
     Icon(CursorIcon),
-    Loading { cursor: CustomCursor, previous: Previous, _handle: DropAbortHandle },
+    Loading {
+        cursor: CustomCursor,
+        previous: Previous,
+        /// Cursors to try if this one fails to load.
+        fallback: Vec<Cursor>,
+        _handle: DropAbortHandle,
+    },
     Image(CustomCursor),
-    Animation { cursor: CustomCursor, animation: AnimationDropper },
+    Animation {
+        cursor: CustomCursor,
+        animation: AnimationDropper,
+    },
 }
 
 impl Default for SelectedCursor {
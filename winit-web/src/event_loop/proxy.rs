@@ -1,16 +1,20 @@
+use std::collections::HashSet;
 use std::future;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::task::Poll;
 
-use winit_core::event_loop::EventLoopProxyProvider;
+use winit_core::event_loop::{EventLoopProxyProvider, PendingWakeUps};
 
 use super::super::main_thread::MainThreadMarker;
 use crate::r#async::{AtomicWaker, Wrapper};
 use crate::event_loop::runner::WeakShared;
 
 #[derive(Debug)]
-pub struct EventLoopProxy(Wrapper<WeakShared, Arc<State>, ()>);
+pub struct EventLoopProxy {
+    wrapper: Wrapper<WeakShared, Arc<State>, ()>,
+    pending_wake_ups: PendingWakeUps,
+}
 
 #[derive(Debug)]
 struct State {
@@ -27,7 +31,7 @@ impl EventLoopProxy {
             closed: AtomicBool::new(false),
         });
 
-        Self(Wrapper::new(
+        let wrapper = Wrapper::new(
             main_thread,
             runner,
             |runner, _| {
@@ -75,7 +79,9 @@ impl EventLoopProxy {
                 state.awoken.store(true, Ordering::Relaxed);
                 state.waker.wake();
             },
-        ))
+        );
+
+        Self { wrapper, pending_wake_ups: PendingWakeUps::new() }
     }
 
     pub fn take(&self) -> bool {
@@ -84,13 +90,18 @@ impl EventLoopProxy {
             "this should only be called from the main thread"
         );
 
-        self.0.with_sender_data(|state| state.awoken.swap(false, Ordering::Relaxed))
+        self.wrapper.with_sender_data(|state| state.awoken.swap(false, Ordering::Relaxed))
+    }
+
+    /// Take the keys passed to `wake_up_coalesced` since this was last called.
+    pub fn take_pending_wake_ups(&self) -> HashSet<u64> {
+        self.pending_wake_ups.take()
     }
 }
 
 impl Drop for EventLoopProxy {
     fn drop(&mut self) {
-        self.0.with_sender_data(|state| {
+        self.wrapper.with_sender_data(|state| {
             state.closed.store(true, Ordering::Relaxed);
             state.waker.wake();
         });
@@ -99,6 +110,12 @@ impl Drop for EventLoopProxy {
 
 impl EventLoopProxyProvider for EventLoopProxy {
     fn wake_up(&self) {
-        self.0.send(())
+        self.wrapper.send(())
+    }
+
+    fn wake_up_coalesced(&self, key: u64) {
+        if self.pending_wake_ups.insert(key) {
+            self.wake_up();
+        }
     }
 }
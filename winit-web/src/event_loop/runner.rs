@@ -9,13 +9,13 @@ use dpi::PhysicalSize;
 use wasm_bindgen::JsCast;
 use wasm_bindgen::prelude::Closure;
 use web_sys::{Document, KeyboardEvent, Navigator, PageTransitionEvent, PointerEvent, WheelEvent};
-use web_time::{Duration, Instant};
+use web_time::Instant;
 use winit_core::application::ApplicationHandler;
 use winit_core::event::{
-    DeviceEvent, DeviceId, ElementState, RawKeyEvent, StartCause, WindowEvent,
+    DeviceEvent, DeviceId, ElementState, RawKeyEvent, StartCause, WaitCancelReason, WindowEvent,
 };
 use winit_core::event_loop::{ControlFlow, DeviceEvents};
-use winit_core::window::WindowId;
+use winit_core::window::{Theme, WindowId};
 
 use super::proxy::EventLoopProxy;
 use super::state::State;
@@ -45,7 +45,7 @@ struct Execution {
     control_flow: Cell<ControlFlow>,
     poll_strategy: Cell<PollStrategy>,
     wait_until_strategy: Cell<WaitUntilStrategy>,
-    exit: Cell<bool>,
+    exit: Cell<Option<i32>>,
     runner: RefCell<RunnerEnum>,
     suspended: Cell<bool>,
     events: RefCell<VecDeque<Event>>,
@@ -60,6 +60,7 @@ struct Execution {
     pub(crate) monitor: Rc<MonitorHandler>,
     safe_area: Rc<SafeAreaHandle>,
     page_transition_event_handle: RefCell<Option<backend::PageTransitionEventHandle>>,
+    dark_mode_handle: RefCell<Option<backend::DarkModeHandle>>,
     device_events: Cell<DeviceEvents>,
     on_mouse_move: OnEventHandle<PointerEvent>,
     on_wheel: OnEventHandle<WheelEvent>,
@@ -121,13 +122,15 @@ impl Runner {
 
     /// Returns the corresponding `StartCause` for the current `state`, or `None`
     /// when in `Exit` state.
-    fn maybe_start_cause(&self) -> Option<StartCause> {
+    fn maybe_start_cause(&self, reason: WaitCancelReason) -> Option<StartCause> {
         Some(match self.state {
             State::Init => StartCause::Init,
             State::Poll { .. } => StartCause::Poll,
-            State::Wait { start } => StartCause::WaitCancelled { start, requested_resume: None },
+            State::Wait { start } => {
+                StartCause::WaitCancelled { start, requested_resume: None, reason }
+            },
             State::WaitUntil { start, end, .. } => {
-                StartCause::WaitCancelled { start, requested_resume: Some(end) }
+                StartCause::WaitCancelled { start, requested_resume: Some(end), reason }
             },
             State::Exit => return None,
         })
@@ -139,6 +142,9 @@ impl Runner {
             Event::WindowEvent { window_id, event } => {
                 self.app.window_event(&self.event_loop, window_id, event)
             },
+            Event::WindowEvents { window_id, events } => {
+                self.app.window_events(&self.event_loop, window_id, &events)
+            },
             Event::ScaleChange { canvas, size, scale } => {
                 if let Some(canvas) = canvas.upgrade() {
                     canvas.handle_scale_change(
@@ -152,13 +158,22 @@ impl Runner {
                 }
             },
             Event::DeviceEvent { device_id, event } => {
-                self.app.device_event(&self.event_loop, device_id, event)
+                self.app.device_event_with_window(&self.event_loop, device_id, event, None)
+            },
+            Event::UserWakeUp => {
+                self.app.proxy_wake_up(&self.event_loop);
+                for key in runner.event_loop_proxy().take_pending_wake_ups() {
+                    self.app.proxy_wake_up_coalesced(&self.event_loop, key);
+                }
             },
-            Event::UserWakeUp => self.app.proxy_wake_up(&self.event_loop),
-            Event::Suspended => self.app.suspended(&self.event_loop),
-            Event::Resumed => self.app.resumed(&self.event_loop),
+            // Only pages kept in the bfcache are suspended, and their canvas is kept with them.
+            Event::Suspended => self.app.suspended(&self.event_loop, false),
+            Event::Resumed => self.app.resumed(&self.event_loop, false),
             Event::CreateSurfaces => self.app.can_create_surfaces(&self.event_loop),
             Event::AboutToWait => self.app.about_to_wait(&self.event_loop),
+            Event::SystemThemeChanged(theme) => {
+                self.app.system_theme_changed(&self.event_loop, theme)
+            },
         }
     }
 }
@@ -191,7 +206,7 @@ impl Shared {
                 control_flow: Cell::new(ControlFlow::default()),
                 poll_strategy: Cell::new(PollStrategy::default()),
                 wait_until_strategy: Cell::new(WaitUntilStrategy::default()),
-                exit: Cell::new(false),
+                exit: Cell::new(None),
                 runner: RefCell::new(RunnerEnum::Pending),
                 suspended: Cell::new(false),
                 events: RefCell::new(VecDeque::new()),
@@ -205,6 +220,7 @@ impl Shared {
                 monitor: Rc::new(monitor),
                 safe_area: Rc::new(safe_area),
                 page_transition_event_handle: RefCell::new(None),
+                dark_mode_handle: RefCell::new(None),
                 device_events: Cell::default(),
                 on_mouse_move: RefCell::new(None),
                 on_wheel: RefCell::new(None),
@@ -302,6 +318,14 @@ impl Shared {
             },
         ));
 
+        *self.0.dark_mode_handle.borrow_mut() = Some(backend::on_dark_mode(self.window(), {
+            let runner = self.clone();
+            move |is_dark_mode| {
+                let theme = if is_dark_mode { Theme::Dark } else { Theme::Light };
+                runner.send_event(Event::SystemThemeChanged(theme));
+            }
+        }));
+
         let runner = self.clone();
         let window = self.window().clone();
         let navigator = self.navigator().clone();
@@ -479,7 +503,7 @@ impl Shared {
 
     pub fn request_redraw(&self, id: WindowId) {
         self.0.redraw_pending.borrow_mut().insert(id);
-        self.send_events([]);
+        self.wake_up([], true);
     }
 
     fn init(&self) {
@@ -499,8 +523,17 @@ impl Shared {
     // Run the logic for waking from a WaitUntil, which involves clearing the queue
     // Generally there shouldn't be events built up when this is called
     pub fn resume_time_reached(&self, start: Instant, requested_resume: Instant) {
-        let start_cause =
-            Event::NewEvents(StartCause::ResumeTimeReached { start, requested_resume });
+        // The timeout is capped at `ControlFlow::MAX_WAIT_TIMEOUT`, so it might fire early.
+        let start_cause = if Instant::now() < requested_resume {
+            StartCause::WaitCancelled {
+                start,
+                requested_resume: Some(requested_resume),
+                reason: WaitCancelReason::Unknown,
+            }
+        } else {
+            StartCause::ResumeTimeReached { start, requested_resume }
+        };
+        let start_cause = Event::NewEvents(start_cause);
         self.run_until_cleared(iter::once(start_cause));
     }
 
@@ -549,6 +582,12 @@ impl Shared {
     //
     // It will determine if the event should be immediately sent to the user or buffered for later
     pub(crate) fn send_events(&self, events: impl IntoIterator<Item = Event>) {
+        self.wake_up(events, false);
+    }
+
+    // Add a series of events to the event loop runner, `redraw_only` signals that the only reason
+    // to wake up is a redraw request.
+    fn wake_up(&self, events: impl IntoIterator<Item = Event>, redraw_only: bool) {
         // If the event loop is closed, it should discard any new events
         if self.is_closed() {
             return;
@@ -578,16 +617,33 @@ impl Shared {
         }
         // At this point, we know this is a fresh set of events
         // Now we determine why new events are incoming, and handle the events
+        let events: Vec<_> = events.into_iter().collect();
+        let reason = if redraw_only {
+            WaitCancelReason::Redraw
+        } else if events.iter().any(|event| !matches!(event, Event::UserWakeUp)) {
+            WaitCancelReason::OsEvent
+        } else if !events.is_empty() {
+            WaitCancelReason::UserWakeUp
+        } else {
+            WaitCancelReason::Unknown
+        };
         let start_cause = match (self.0.runner.borrow().maybe_runner())
             .unwrap_or_else(|| {
                 unreachable!("The runner cannot process events when it is not attached")
             })
-            .maybe_start_cause()
+            .maybe_start_cause(reason)
         {
             Some(c) => c,
             // If we're in the exit state, don't do event processing
             None => return,
         };
+        // Let the application know if it was only woken up to redraw.
+        let start_cause = match start_cause {
+            StartCause::WaitCancelled { start, requested_resume, .. } if redraw_only => {
+                StartCause::RedrawRequested { start, requested_resume }
+            },
+            cause => cause,
+        };
         // Take the start event, then the events provided to this function, and run an iteration of
         // the event loop
         let start_event = Event::NewEvents(start_cause);
@@ -613,8 +669,26 @@ impl Shared {
     //
     // This will also process any events that have been queued or that are queued during processing
     fn run_until_cleared(&self, events: impl Iterator<Item = Event>) {
-        for event in events {
-            self.handle_event(event);
+        let mut events = events.peekable();
+        while let Some(event) = events.next() {
+            let Event::WindowEvent { window_id, event } = event else {
+                self.handle_event(event);
+                continue;
+            };
+
+            // Batch the consecutive events of the same window.
+            let mut batch = vec![event];
+            while let Some(Event::WindowEvent { event, .. }) = events.next_if(
+                |next| matches!(next, Event::WindowEvent { window_id: id, .. } if *id == window_id),
+            ) {
+                batch.push(event);
+            }
+
+            if batch.len() == 1 {
+                self.handle_event(Event::WindowEvent { window_id, event: batch.pop().unwrap() });
+            } else {
+                self.handle_event(Event::WindowEvents { window_id, events: batch });
+            }
         }
         self.process_destroy_pending_windows();
 
@@ -703,8 +777,8 @@ impl Shared {
         let new_state = if self.exiting() {
             State::Exit
         } else {
-            match self.control_flow() {
-                ControlFlow::Poll => {
+            match self.control_flow().effective(Instant::now()) {
+                ControlFlow::Poll | ControlFlow::PollUntil(_) => {
                     let cloned = self.clone();
                     State::Poll {
                         _request: backend::Schedule::new(
@@ -718,7 +792,7 @@ impl Shared {
                 ControlFlow::WaitUntil(end) => {
                     let start = Instant::now();
 
-                    let delay = if end <= start { Duration::from_millis(0) } else { end - start };
+                    let delay = self.control_flow().timeout(start).unwrap_or_default();
 
                     let cloned = self.clone();
 
@@ -744,6 +818,7 @@ impl Shared {
     fn handle_loop_destroyed(&self) {
         let all_canvases = std::mem::take(&mut *self.0.all_canvases.borrow_mut());
         *self.0.page_transition_event_handle.borrow_mut() = None;
+        *self.0.dark_mode_handle.borrow_mut() = None;
         *self.0.on_mouse_move.borrow_mut() = None;
         *self.0.on_wheel.borrow_mut() = None;
         *self.0.on_mouse_press.borrow_mut() = None;
@@ -798,7 +873,11 @@ impl Shared {
             DeviceEvents::Always => true,
             DeviceEvents::WhenFocused => {
                 self.0.all_canvases.borrow().iter().any(|(_, canvas, _)| {
-                    if let Some(canvas) = canvas.upgrade() { canvas.has_focus.get() } else { false }
+                    if let Some(canvas) = canvas.upgrade() {
+                        canvas.has_focus.get() && canvas.receive_device_events.get()
+                    } else {
+                        false
+                    }
                 })
             },
             DeviceEvents::Never => false,
@@ -814,11 +893,15 @@ impl Shared {
     }
 
     pub(crate) fn exit(&self) {
-        self.0.exit.set(true)
+        self.0.exit.set(Some(0))
+    }
+
+    pub(crate) fn exit_with_code(&self, code: i32) {
+        self.0.exit.set(Some(code))
     }
 
     pub(crate) fn exiting(&self) -> bool {
-        self.0.exit.get()
+        self.0.exit.get().is_some()
     }
 
     pub(crate) fn set_poll_strategy(&self, strategy: PollStrategy) {
@@ -867,6 +950,7 @@ impl WeakShared {
 pub(crate) enum Event {
     NewEvents(StartCause),
     WindowEvent { window_id: WindowId, event: WindowEvent },
+    WindowEvents { window_id: WindowId, events: Vec<WindowEvent> },
     ScaleChange { canvas: Weak<backend::Canvas>, size: PhysicalSize<u32>, scale: f64 },
     DeviceEvent { device_id: Option<DeviceId>, event: DeviceEvent },
     Suspended,
@@ -874,4 +958,5 @@ pub(crate) enum Event {
     Resumed,
     AboutToWait,
     UserWakeUp,
+    SystemThemeChanged(Theme),
 }
@@ -7,10 +7,10 @@ use std::sync::Arc;
 use web_sys::Element;
 use winit_core::application::ApplicationHandler;
 use winit_core::cursor::{CustomCursor as CoreCustomCursor, CustomCursorSource};
-use winit_core::error::{NotSupportedError, RequestError};
-use winit_core::event::{ElementState, KeyEvent, TouchPhase, WindowEvent};
+use winit_core::error::{CreateWindowError, NotSupportedError, RequestError};
+use winit_core::event::{ElementState, FocusSource, KeyEvent, TouchPhase, WindowEvent};
 use winit_core::event_loop::{
-    ActiveEventLoop as RootActiveEventLoop, ControlFlow, DeviceEvents,
+    ActiveEventLoop as RootActiveEventLoop, Backend, ControlFlow, DeviceEvents,
     EventLoopProxy as RootEventLoopProxy, OwnedDisplayHandle as CoreOwnedDisplayHandle,
 };
 use winit_core::keyboard::ModifiersState;
@@ -89,17 +89,17 @@ impl ActiveEventLoop {
 
             runner.send_events(clear_modifiers.into_iter().chain(iter::once(Event::WindowEvent {
                 window_id,
-                event: WindowEvent::Focused(false),
+                event: WindowEvent::Focused { focused: false, via: FocusSource::Unknown },
             })));
         });
 
         let runner = self.runner.clone();
         let has_focus = canvas.has_focus.clone();
-        canvas.on_focus(move || {
+        canvas.on_focus(move |via| {
             if !has_focus.replace(true) {
                 runner.send_event(Event::WindowEvent {
                     window_id,
-                    event: WindowEvent::Focused(true),
+                    event: WindowEvent::Focused { focused: true, via },
                 });
             }
         });
@@ -117,8 +117,10 @@ impl ActiveEventLoop {
 
         if focused {
             canvas.has_focus.set(true);
-            self.runner
-                .send_event(Event::WindowEvent { window_id, event: WindowEvent::Focused(true) })
+            self.runner.send_event(Event::WindowEvent {
+                window_id,
+                event: WindowEvent::Focused { focused: true, via: FocusSource::Unknown },
+            })
         }
 
         let runner = self.runner.clone();
@@ -147,6 +149,7 @@ impl ActiveEventLoop {
                                 repeat,
                                 text_with_all_modifiers: text,
                                 key_without_modifiers: logical_key,
+                                composed_length: None,
                             },
                             is_synthetic: false,
                         },
@@ -182,6 +185,7 @@ impl ActiveEventLoop {
                                 repeat,
                                 text_with_all_modifiers: text,
                                 key_without_modifiers: logical_key,
+                                composed_length: None,
                             },
                             is_synthetic: false,
                         },
@@ -296,6 +300,7 @@ impl ActiveEventLoop {
                             state,
                             position,
                             button,
+                            click_count: None,
                         },
                     }]));
                 }
@@ -323,6 +328,7 @@ impl ActiveEventLoop {
                         state: ElementState::Pressed,
                         position,
                         button,
+                        click_count: None,
                     },
                 })));
             }
@@ -351,6 +357,7 @@ impl ActiveEventLoop {
                         state: ElementState::Released,
                         position,
                         button,
+                        click_count: None,
                     },
                 })));
             }
@@ -375,6 +382,7 @@ impl ActiveEventLoop {
                         device_id: None,
                         delta,
                         phase: TouchPhase::Moved,
+                        momentum: false,
                     },
                 },
             )));
@@ -436,7 +444,19 @@ impl ActiveEventLoop {
         });
 
         let runner = self.runner.clone();
-        canvas.on_animation_frame(move || runner.request_redraw(window_id));
+        let canvas_weak = Rc::downgrade(canvas);
+        canvas.on_animation_frame(move || {
+            let Some(canvas) = canvas_weak.upgrade() else {
+                return;
+            };
+
+            if canvas.throttle_redraw() {
+                // Skip this frame, the redraw stays aligned with the next one.
+                canvas.request_animation_frame();
+            } else {
+                runner.request_redraw(window_id);
+            }
+        });
 
         canvas.on_context_menu();
     }
@@ -490,7 +510,8 @@ impl RootActiveEventLoop for ActiveEventLoop {
     fn create_window(
         &self,
         window_attributes: winit_core::window::WindowAttributes,
-    ) -> Result<Box<dyn winit_core::window::Window>, RequestError> {
+    ) -> Result<Box<dyn winit_core::window::Window>, CreateWindowError> {
+        window_attributes.validate()?;
         let window = Window::new(self, window_attributes)?;
         Ok(Box::new(window))
     }
@@ -537,6 +558,10 @@ impl RootActiveEventLoop for ActiveEventLoop {
         self.runner.exit()
     }
 
+    fn exit_with_code(&self, code: i32) {
+        self.runner.exit_with_code(code)
+    }
+
     fn exiting(&self) -> bool {
         self.runner.exiting()
     }
@@ -548,6 +573,10 @@ impl RootActiveEventLoop for ActiveEventLoop {
     fn rwh_06_handle(&self) -> &dyn rwh_06::HasDisplayHandle {
         self
     }
+
+    fn backend(&self) -> Option<Backend> {
+        Some(Backend::Web)
+    }
 }
 
 impl rwh_06::HasDisplayHandle for ActiveEventLoop {
@@ -11,10 +11,11 @@ use web_sys::{
     CssStyleDeclaration, Document, Event, FocusEvent, HtmlCanvasElement, KeyboardEvent, Navigator,
     PointerEvent, WheelEvent,
 };
+use web_time::{Duration, Instant};
 use winit_core::error::RequestError;
 use winit_core::event::{
-    ButtonSource, DeviceId, ElementState, MouseScrollDelta, PointerKind, PointerSource,
-    SurfaceSizeWriter, WindowEvent,
+    ButtonSource, DeviceId, ElementState, FocusSource, MouseScrollDelta, PointerKind,
+    PointerSource, SurfaceSizeWriter, WindowEvent,
 };
 use winit_core::keyboard::{Key, KeyLocation, ModifiersState, PhysicalKey};
 use winit_core::monitor::Fullscreen;
@@ -37,9 +38,17 @@ pub struct Canvas {
     common: Common,
     id: WindowId,
     pub has_focus: Rc<Cell<bool>>,
+    // Whether the focus is changed by `Window::focus_window`.
+    focus_requested: Rc<Cell<bool>>,
+    // Whether device events are delivered while this canvas is focused.
+    pub receive_device_events: Cell<bool>,
     pub prevent_default: Rc<Cell<bool>>,
     pub is_intersecting: Cell<Option<bool>>,
     pub cursor: CursorHandler,
+    redraw_interval: Cell<Option<Duration>>,
+    last_redraw: Cell<Option<Instant>>,
+    // The scale factor last reported with `ScaleFactorChanged`.
+    scale_factor: Cell<f64>,
     handlers: RefCell<Handlers>,
 }
 
@@ -168,9 +177,14 @@ impl Canvas {
             common,
             id,
             has_focus: Rc::new(Cell::new(false)),
+            focus_requested: Rc::new(Cell::new(false)),
+            receive_device_events: Cell::new(true),
             prevent_default: Rc::new(Cell::new(web_attributes.prevent_default)),
             is_intersecting: Cell::new(None),
             cursor,
+            redraw_interval: Cell::new(None),
+            last_redraw: Cell::new(None),
+            scale_factor: Cell::new(super::scale_factor(&window)),
             handlers: RefCell::new(Handlers {
                 animation_frame_handler: AnimationFrameHandler::new(window),
                 on_touch_start: None,
@@ -277,14 +291,34 @@ impl Canvas {
 
     pub fn on_focus<F>(&self, mut handler: F)
     where
-        F: 'static + FnMut(),
+        F: 'static + FnMut(FocusSource),
     {
+        let canvas = self.common.raw.clone();
+        let focus_requested = Rc::clone(&self.focus_requested);
         self.handlers.borrow_mut().on_focus =
             Some(self.common.add_event("focus", move |_: FocusEvent| {
-                handler();
+                let via = if focus_requested.get() {
+                    FocusSource::Programmatic
+                } else {
+                    // Browsers only show focus indicators when focused by keyboard navigation.
+                    match canvas.matches(":focus-visible") {
+                        Ok(true) => FocusSource::Keyboard,
+                        Ok(false) => FocusSource::Pointer,
+                        Err(_) => FocusSource::Unknown,
+                    }
+                };
+                handler(via);
             }));
     }
 
+    /// Focus the canvas, reporting [`FocusSource::Programmatic`].
+    pub fn focus(&self) {
+        self.focus_requested.set(true);
+        // The `focus` event is dispatched synchronously.
+        let _ = self.raw().focus();
+        self.focus_requested.set(false);
+    }
+
     pub fn on_keyboard_release<F>(&self, mut handler: F)
     where
         F: 'static + FnMut(PhysicalKey, Key, Option<SmolStr>, KeyLocation, bool, ModifiersState),
@@ -486,6 +520,29 @@ impl Canvas {
         self.handlers.borrow().animation_frame_handler.request();
     }
 
+    pub(crate) fn set_max_redraw_rate(&self, rate: Option<u32>) {
+        let interval = rate.filter(|&rate| rate != 0).map(|rate| Duration::from_secs(1) / rate);
+        self.redraw_interval.set(interval);
+    }
+
+    /// Returns whether a redraw now would exceed the maximum redraw rate, otherwise records it.
+    pub(crate) fn throttle_redraw(&self) -> bool {
+        // Animation frames don't fire at exact intervals, allow them to be slightly early.
+        const FRAME_JITTER: Duration = Duration::from_millis(1);
+
+        let Some(interval) = self.redraw_interval.get() else {
+            return false;
+        };
+
+        let now = Instant::now();
+        if self.last_redraw.get().is_some_and(|last| now + FRAME_JITTER < last + interval) {
+            return true;
+        }
+
+        self.last_redraw.set(Some(now));
+        false
+    }
+
     pub(crate) fn handle_scale_change(
         &self,
         runner: &super::super::event_loop::runner::Shared,
@@ -493,12 +550,17 @@ impl Canvas {
         current_size: PhysicalSize<u32>,
         scale: f64,
     ) {
-        // First, we send the `ScaleFactorChanged` event:
+        // The safe area insets can change with the zoom, make sure they are up to date before
+        // sending any events.
+        runner.safe_area().refresh();
+
+        // Then we send the `ScaleFactorChanged` event:
         self.set_current_size(current_size);
         let new_size = {
             let new_size = Arc::new(Mutex::new(current_size));
             event_handler(self.id, WindowEvent::ScaleFactorChanged {
                 scale_factor: scale,
+                old_scale_factor: self.scale_factor.replace(scale),
                 surface_size_writer: SurfaceSizeWriter::new(Arc::downgrade(&new_size)),
             });
 
@@ -220,19 +220,31 @@ impl MouseDelta {
     }
 }
 
+/// The number of lines a [`WheelEvent::DOM_DELTA_PAGE`] delta is converted to.
+///
+/// Browsers only report pages when the user configured the mouse to scroll by whole screens and
+/// there is no way to query how many lines that corresponds to, so use a fixed approximation.
+const LINES_PER_PAGE: f64 = 10.0;
+
 pub fn mouse_scroll_delta(
     window: &web_sys::Window,
     event: &WheelEvent,
 ) -> Option<MouseScrollDelta> {
+    // The DOM uses positive values for scrolling down and right, which is the opposite of ours.
     let x = -event.delta_x();
     let y = -event.delta_y();
 
     match event.delta_mode() {
-        WheelEvent::DOM_DELTA_LINE => Some(MouseScrollDelta::LineDelta(x as f32, y as f32)),
+        // Pixel deltas are reported in CSS pixels, which have to be scaled to physical pixels.
         WheelEvent::DOM_DELTA_PIXEL => {
             let delta = LogicalPosition::new(x, y).to_physical(super::scale_factor(window));
             Some(MouseScrollDelta::PixelDelta(delta))
         },
+        WheelEvent::DOM_DELTA_LINE => Some(MouseScrollDelta::LineDelta(x as f32, y as f32)),
+        WheelEvent::DOM_DELTA_PAGE => Some(MouseScrollDelta::LineDelta(
+            (x * LINES_PER_PAGE) as f32,
+            (y * LINES_PER_PAGE) as f32,
+        )),
         _ => None,
     }
 }
@@ -359,3 +371,60 @@ pub fn has_coalesced_events_support(event: &PointerEvent) -> bool {
         })
     })
 }
+
+#[cfg(all(test, target_family = "wasm"))]
+mod tests {
+    use dpi::PhysicalPosition;
+    use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+    use web_sys::{WheelEvent, WheelEventInit};
+    use winit_core::event::MouseScrollDelta;
+
+    use super::{LINES_PER_PAGE, mouse_scroll_delta};
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    fn wheel_event(delta_mode: u32, delta_x: f64, delta_y: f64) -> WheelEvent {
+        let init = WheelEventInit::new();
+        init.set_delta_mode(delta_mode);
+        init.set_delta_x(delta_x);
+        init.set_delta_y(delta_y);
+        WheelEvent::new_with_event_init_dict("wheel", &init).unwrap()
+    }
+
+    #[wasm_bindgen_test]
+    fn pixel_delta_is_scaled() {
+        let window = web_sys::window().unwrap();
+        let scale_factor = window.device_pixel_ratio();
+
+        let event = wheel_event(WheelEvent::DOM_DELTA_PIXEL, 4.0, -10.0);
+        assert_eq!(
+            mouse_scroll_delta(&window, &event),
+            Some(MouseScrollDelta::PixelDelta(PhysicalPosition::new(
+                -4.0 * scale_factor,
+                10.0 * scale_factor
+            )))
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn line_delta() {
+        let window = web_sys::window().unwrap();
+
+        let event = wheel_event(WheelEvent::DOM_DELTA_LINE, -1.0, 3.0);
+        assert_eq!(
+            mouse_scroll_delta(&window, &event),
+            Some(MouseScrollDelta::LineDelta(1.0, -3.0))
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn page_delta_is_converted_to_lines() {
+        let window = web_sys::window().unwrap();
+
+        let event = wheel_event(WheelEvent::DOM_DELTA_PAGE, 0.0, 1.0);
+        assert_eq!(
+            mouse_scroll_delta(&window, &event),
+            Some(MouseScrollDelta::LineDelta(0.0, -LINES_PER_PAGE as f32))
+        );
+    }
+}
@@ -151,6 +151,22 @@ pub fn style_size_property(style: &Style, property: &str) -> f64 {
         .expect("CSS property is not a size in pixel")
 }
 
+pub struct DarkModeHandle {
+    _handle: media_query_handle::MediaQueryListHandle,
+}
+
+pub fn on_dark_mode(
+    window: &web_sys::Window,
+    mut handler: impl FnMut(bool) + 'static,
+) -> DarkModeHandle {
+    let handle = media_query_handle::MediaQueryListHandle::new(
+        window,
+        "(prefers-color-scheme: dark)",
+        move |mql| handler(mql.matches()),
+    );
+    DarkModeHandle { _handle: handle }
+}
+
 pub fn is_dark_mode(window: &web_sys::Window) -> Option<bool> {
     window.match_media("(prefers-color-scheme: dark)").ok().flatten().map(|media| media.matches())
 }
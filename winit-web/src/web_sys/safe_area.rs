@@ -4,6 +4,10 @@ use web_sys::{Document, HtmlHtmlElement, Window};
 
 use super::Style;
 
+const PROPERTY: &str = "--__winit_safe_area";
+const VALUE: &str = "env(safe-area-inset-top) env(safe-area-inset-right) \
+                     env(safe-area-inset-bottom) env(safe-area-inset-left)";
+
 pub struct SafeAreaHandle {
     style: Style,
 }
@@ -13,13 +17,7 @@ impl SafeAreaHandle {
         let document: HtmlHtmlElement = document.document_element().unwrap().unchecked_into();
         #[allow(clippy::disallowed_methods)]
         let write = document.style();
-        write
-            .set_property(
-                "--__winit_safe_area",
-                "env(safe-area-inset-top) env(safe-area-inset-right) env(safe-area-inset-bottom) \
-                 env(safe-area-inset-left)",
-            )
-            .expect("unexpected read-only declaration block");
+        write.set_property(PROPERTY, VALUE).expect("unexpected read-only declaration block");
         #[allow(clippy::disallowed_methods)]
         let read = window
             .get_computed_style(&document)
@@ -30,8 +28,15 @@ impl SafeAreaHandle {
         SafeAreaHandle { style: Style { read, write } }
     }
 
+    /// Re-evaluates the insets, which browsers don't necessarily do on their own when the page is
+    /// zoomed.
+    pub fn refresh(&self) {
+        self.style.remove(PROPERTY);
+        self.style.set(PROPERTY, VALUE);
+    }
+
     pub fn get(&self) -> (LogicalPosition<f64>, LogicalSize<f64>) {
-        let value = self.style.get("--__winit_safe_area");
+        let value = self.style.get(PROPERTY);
 
         let mut values = value
             .split(' ')
@@ -51,6 +56,31 @@ impl SafeAreaHandle {
 
 impl Drop for SafeAreaHandle {
     fn drop(&mut self) {
-        self.style.remove("--__winit_safe_area");
+        self.style.remove(PROPERTY);
+    }
+}
+
+#[cfg(all(test, target_family = "wasm"))]
+mod tests {
+    use wasm_bindgen_test::{wasm_bindgen_test, wasm_bindgen_test_configure};
+
+    use super::{PROPERTY, SafeAreaHandle};
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    #[wasm_bindgen_test]
+    fn refresh_discards_stale_insets() {
+        let window = web_sys::window().unwrap();
+        let document = window.document().unwrap();
+        let handle = SafeAreaHandle::new(&window, &document);
+        let (position, size) = handle.get();
+
+        // Mock insets left over from before a scale change.
+        handle.style.set(PROPERTY, "1px 2px 3px 4px");
+        let (stale_position, _) = handle.get();
+        assert_eq!((stale_position.x, stale_position.y), (4., 1.));
+
+        handle.refresh();
+        assert_eq!(handle.get(), (position, size));
     }
 }
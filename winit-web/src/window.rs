@@ -1,6 +1,7 @@
 use std::cell::Ref;
 use std::fmt;
 use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use dpi::{
     LogicalInsets, LogicalPosition, LogicalSize, PhysicalInsets, PhysicalPosition, PhysicalSize,
@@ -10,7 +11,9 @@ use web_sys::HtmlCanvasElement;
 use winit_core::cursor::Cursor;
 use winit_core::error::{NotSupportedError, RequestError};
 use winit_core::icon::Icon;
-use winit_core::monitor::{Fullscreen, MonitorHandle as CoremMonitorHandle};
+use winit_core::monitor::{
+    Fullscreen, MonitorHandle as CoremMonitorHandle, primary_or_current_monitor,
+};
 use winit_core::window::{
     CursorGrabMode, ImeRequestError, ResizeDirection, Theme, UserAttentionType,
     Window as RootWindow, WindowAttributes, WindowButtons, WindowId, WindowLevel,
@@ -117,8 +120,16 @@ impl RootWindow for Window {
 
     fn pre_present_notify(&self) {}
 
+    fn set_max_redraw_rate(&self, rate: Option<u32>) {
+        self.inner.dispatch(move |inner| inner.canvas.set_max_redraw_rate(rate))
+    }
+
     fn reset_dead_keys(&self) {
-        // Not supported
+        // Browsers compose dead keys themselves and don't expose a way to reset them.
+        static WARNED: AtomicBool = AtomicBool::new(false);
+        if !WARNED.swap(true, Ordering::Relaxed) {
+            tracing::warn!("`Window::reset_dead_keys` is not supported on Web");
+        }
     }
 
     fn surface_position(&self) -> PhysicalPosition<i32> {
@@ -147,6 +158,7 @@ impl RootWindow for Window {
     }
 
     fn request_surface_size(&self, size: Size) -> Option<PhysicalSize<u32>> {
+        let size = winit_core::window::clamp_surface_size(size);
         self.inner.queue(|inner| {
             let size = size.to_logical(self.scale_factor());
             backend::set_canvas_size(
@@ -251,7 +263,7 @@ impl RootWindow for Window {
     fn set_enabled_buttons(&self, _: WindowButtons) {}
 
     fn enabled_buttons(&self) -> WindowButtons {
-        WindowButtons::all()
+        WindowButtons::default()
     }
 
     fn set_minimized(&self, _: bool) {
@@ -304,6 +316,10 @@ impl RootWindow for Window {
         // Currently an intentional no-op
     }
 
+    fn window_icon(&self) -> Option<Icon> {
+        None
+    }
+
     fn ime_capabilities(&self) -> Option<winit_core::window::ImeCapabilities> {
         None
     }
@@ -314,7 +330,7 @@ impl RootWindow for Window {
 
     fn focus_window(&self) {
         self.inner.dispatch(|inner| {
-            let _ = inner.canvas.raw().focus();
+            inner.canvas.focus();
         })
     }
 
@@ -337,6 +353,10 @@ impl RootWindow for Window {
 
     fn set_content_protected(&self, _: bool) {}
 
+    fn is_content_protected(&self) -> bool {
+        false
+    }
+
     fn title(&self) -> String {
         String::new()
     }
@@ -371,6 +391,10 @@ impl RootWindow for Window {
         self.inner.dispatch(move |inner| inner.canvas.cursor.set_cursor_visible(visible))
     }
 
+    fn set_receive_device_events(&self, receive: bool) {
+        self.inner.dispatch(move |inner| inner.canvas.receive_device_events.set(receive))
+    }
+
     fn drag_window(&self) -> Result<(), RequestError> {
         Err(NotSupportedError::new("drag_window is not supported").into())
     }
@@ -399,7 +423,8 @@ impl RootWindow for Window {
     }
 
     fn primary_monitor(&self) -> Option<CoremMonitorHandle> {
-        self.inner.queue(|inner| inner.monitor.primary_monitor()).map(CoremMonitorHandle::from)
+        let primary = self.inner.queue(|inner| inner.monitor.primary_monitor());
+        primary_or_current_monitor(primary.map(CoremMonitorHandle::from), || self.current_monitor())
     }
 
     fn rwh_06_display_handle(&self) -> &dyn rwh_06::HasDisplayHandle {
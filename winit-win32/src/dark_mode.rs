@@ -4,6 +4,7 @@ use std::sync::LazyLock;
 use std::{ffi::c_void, ptr};
 
 use windows_sys::Win32::Foundation::{HWND, LPARAM, S_OK, WPARAM};
+use windows_sys::Win32::Graphics::Gdi::{COLOR_WINDOW, GetSysColor};
 use windows_sys::Win32::System::LibraryLoader::{GetProcAddress, LoadLibraryA};
 use windows_sys::Win32::UI::Accessibility::{HCF_HIGHCONTRASTON, HIGHCONTRASTA};
 use windows_sys::Win32::UI::Controls::SetWindowTheme;
@@ -36,17 +37,17 @@ const LIGHT_THEME_NAME: PCWSTR = w!("");
 /// the system will refresh the title bar automatically,
 /// if we always refresh the title bar, it will blink it on system settings changes
 pub fn try_theme(hwnd: HWND, preferred_theme: Option<Theme>, refresh_title_bar: bool) -> Theme {
+    // The system draws the window itself with a high contrast theme, so only report it.
+    let high_contrast = preferred_theme.is_none().then(high_contrast_theme).flatten();
+
     if *DARK_MODE_SUPPORTED {
         let is_dark_mode = match preferred_theme {
-            Some(theme) => theme == Theme::Dark,
+            Some(theme) => theme.is_dark(),
             None => should_use_dark_mode(),
         };
 
         let theme = if is_dark_mode { Theme::Dark } else { Theme::Light };
-        let theme_name = match theme {
-            Theme::Dark => DARK_THEME_NAME,
-            Theme::Light => LIGHT_THEME_NAME,
-        };
+        let theme_name = if is_dark_mode { DARK_THEME_NAME } else { LIGHT_THEME_NAME };
 
         let status = unsafe { SetWindowTheme(hwnd, theme_name, ptr::null()) };
 
@@ -54,11 +55,16 @@ pub fn try_theme(hwnd: HWND, preferred_theme: Option<Theme>, refresh_title_bar:
             if refresh_title_bar {
                 unsafe { refresh_titlebar_theme_color(hwnd) };
             }
-            return theme;
+            return high_contrast.or(preferred_theme).unwrap_or(theme);
         }
     }
 
-    Theme::Light
+    high_contrast.unwrap_or(Theme::Light)
+}
+
+/// The current system theme.
+pub fn system_theme() -> Theme {
+    high_contrast_theme().unwrap_or(if should_use_dark_mode() { Theme::Dark } else { Theme::Light })
 }
 
 fn set_dark_mode_for_window(hwnd: HWND, is_dark_mode: bool) -> bool {
@@ -148,8 +154,26 @@ fn should_apps_use_dark_mode() -> bool {
         .unwrap_or(false)
 }
 
+/// The high contrast variant of the active theme, if a high contrast theme is turned on.
+fn high_contrast_theme() -> Option<Theme> {
+    if !is_high_contrast() {
+        return None;
+    }
+
+    // High contrast themes come in light and dark, tell them apart by the window background.
+    let background = unsafe { GetSysColor(COLOR_WINDOW) };
+    let [red, green, blue, _] = background.to_le_bytes();
+    let luma = 0.299 * red as f64 + 0.587 * green as f64 + 0.114 * blue as f64;
+
+    Some(if luma < 128. { Theme::HighContrastDark } else { Theme::HighContrastLight })
+}
+
 fn is_high_contrast() -> bool {
-    let mut hc = HIGHCONTRASTA { cbSize: 0, dwFlags: 0, lpszDefaultScheme: ptr::null_mut() };
+    let mut hc = HIGHCONTRASTA {
+        cbSize: std::mem::size_of::<HIGHCONTRASTA>() as _,
+        dwFlags: 0,
+        lpszDefaultScheme: ptr::null_mut(),
+    };
 
     let ok = unsafe {
         SystemParametersInfoA(
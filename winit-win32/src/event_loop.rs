@@ -16,8 +16,9 @@ use windows_sys::Win32::Foundation::{
     FALSE, GetLastError, HANDLE, HWND, LPARAM, LRESULT, POINT, RECT, WAIT_FAILED, WPARAM,
 };
 use windows_sys::Win32::Graphics::Gdi::{
-    GetMonitorInfoW, MONITOR_DEFAULTTONULL, MONITORINFO, MonitorFromRect, MonitorFromWindow,
-    RDW_INTERNALPAINT, RedrawWindow, SC_SCREENSAVE, ScreenToClient, ValidateRect,
+    CreateSolidBrush, DeleteObject, FillRect, GetMonitorInfoW, HDC, MONITOR_DEFAULTTONULL,
+    MONITORINFO, MonitorFromRect, MonitorFromWindow, RDW_INTERNALPAINT, RedrawWindow,
+    SC_SCREENSAVE, ScreenToClient, ValidateRect,
 };
 use windows_sys::Win32::System::Ole::RevokeDragDrop;
 use windows_sys::Win32::System::Threading::{
@@ -27,7 +28,8 @@ use windows_sys::Win32::System::Threading::{
 use windows_sys::Win32::UI::Controls::{HOVER_DEFAULT, WM_MOUSELEAVE};
 use windows_sys::Win32::UI::Input::Ime::{GCS_COMPSTR, GCS_RESULTSTR, ISC_SHOWUICOMPOSITIONWINDOW};
 use windows_sys::Win32::UI::Input::KeyboardAndMouse::{
-    ReleaseCapture, SetCapture, TME_LEAVE, TRACKMOUSEEVENT, TrackMouseEvent,
+    GetDoubleClickTime, GetFocus, ReleaseCapture, SetCapture, TME_LEAVE, TRACKMOUSEEVENT,
+    TrackMouseEvent,
 };
 use windows_sys::Win32::UI::Input::Pointer::{
     POINTER_FLAG_DOWN, POINTER_FLAG_PRIMARY, POINTER_FLAG_UP, POINTER_FLAG_UPDATE,
@@ -40,25 +42,28 @@ use windows_sys::Win32::UI::Input::{
     MOUSE_MOVE_RELATIVE, RAWINPUT, RIM_TYPEKEYBOARD, RIM_TYPEMOUSE,
 };
 use windows_sys::Win32::UI::WindowsAndMessaging::{
-    CREATESTRUCTW, CreateWindowExW, DefWindowProcW, DestroyWindow, DispatchMessageW, GWL_STYLE,
-    GWL_USERDATA, GetClientRect, GetCursorPos, GetMenu, HTCAPTION, HTCLIENT, LoadCursorW,
-    MINMAXINFO, MNC_CLOSE, MSG, MWMO_INPUTAVAILABLE, MsgWaitForMultipleObjectsEx,
-    NCCALCSIZE_PARAMS, PEN_FLAG_BARREL, PEN_FLAG_ERASER, PEN_MASK_PRESSURE, PEN_MASK_ROTATION,
-    PEN_MASK_TILT_X, PEN_MASK_TILT_Y, PM_REMOVE, PT_PEN, PT_TOUCH, PeekMessageW, PostMessageW,
-    QS_ALLINPUT, RI_MOUSE_HWHEEL, RI_MOUSE_WHEEL, RegisterClassExW, RegisterWindowMessageA,
-    SC_MINIMIZE, SC_RESTORE, SIZE_MAXIMIZED, SPI_GETWHEELSCROLLCHARS, SPI_GETWHEELSCROLLLINES,
-    SWP_NOACTIVATE, SWP_NOMOVE, SWP_NOSIZE, SWP_NOZORDER, SetCursor, SetWindowPos,
-    SystemParametersInfoW, TranslateMessage, WHEEL_DELTA, WINDOWPOS, WM_CAPTURECHANGED, WM_CLOSE,
-    WM_CREATE, WM_DESTROY, WM_DPICHANGED, WM_ENTERSIZEMOVE, WM_EXITSIZEMOVE, WM_GETMINMAXINFO,
-    WM_IME_COMPOSITION, WM_IME_ENDCOMPOSITION, WM_IME_SETCONTEXT, WM_IME_STARTCOMPOSITION,
-    WM_INPUT, WM_INPUTLANGCHANGE, WM_KEYDOWN, WM_KEYUP, WM_KILLFOCUS, WM_LBUTTONDOWN, WM_LBUTTONUP,
-    WM_MBUTTONDOWN, WM_MBUTTONUP, WM_MENUCHAR, WM_MOUSEHWHEEL, WM_MOUSEMOVE, WM_MOUSEWHEEL,
-    WM_NCACTIVATE, WM_NCCALCSIZE, WM_NCCREATE, WM_NCDESTROY, WM_NCLBUTTONDOWN, WM_PAINT,
-    WM_POINTERDOWN, WM_POINTERUP, WM_POINTERUPDATE, WM_RBUTTONDOWN, WM_RBUTTONUP, WM_SETCURSOR,
+    CREATESTRUCTW, CreateWindowExW, DefWindowProcW, DestroyWindow, DispatchMessageW, GIDC_ARRIVAL,
+    GIDC_REMOVAL, GWL_STYLE, GWL_USERDATA, GetClientRect, GetCursorPos, GetMenu, GetSystemMetrics,
+    HTCAPTION, HTCLIENT, LoadCursorW, MINMAXINFO, MNC_CLOSE, MSG, MWMO_INPUTAVAILABLE,
+    MsgWaitForMultipleObjectsEx, NCCALCSIZE_PARAMS, PEN_FLAG_BARREL, PEN_FLAG_ERASER,
+    PEN_MASK_PRESSURE, PEN_MASK_ROTATION, PEN_MASK_TILT_X, PEN_MASK_TILT_Y, PM_REMOVE, PT_PEN,
+    PT_TOUCH, PeekMessageW, PostMessageW, QS_ALLINPUT, RI_MOUSE_HWHEEL, RI_MOUSE_WHEEL,
+    RegisterClassExW, RegisterWindowMessageA, SC_MINIMIZE, SC_MOVE, SC_RESTORE, SIZE_MAXIMIZED,
+    SIZE_MINIMIZED, SIZE_RESTORED, SM_CXDOUBLECLK, SM_CXDRAG, SM_CYDOUBLECLK, SM_CYDRAG,
+    SPI_GETWHEELSCROLLCHARS, SPI_GETWHEELSCROLLLINES, SWP_NOACTIVATE, SWP_NOMOVE, SWP_NOSIZE,
+    SWP_NOZORDER, SetCursor, SetWindowPos, SystemParametersInfoW, TranslateMessage, WHEEL_DELTA,
+    WINDOWPOS, WM_CAPTURECHANGED, WM_CLOSE, WM_CREATE, WM_DESTROY, WM_DPICHANGED, WM_ENTERSIZEMOVE,
+    WM_ERASEBKGND, WM_EXITSIZEMOVE, WM_GETMINMAXINFO, WM_HELP, WM_IME_COMPOSITION,
+    WM_IME_ENDCOMPOSITION, WM_IME_SETCONTEXT, WM_IME_STARTCOMPOSITION, WM_INPUT,
+    WM_INPUT_DEVICE_CHANGE, WM_INPUTLANGCHANGE, WM_KEYDOWN, WM_KEYUP, WM_KILLFOCUS,
+    WM_LBUTTONDBLCLK, WM_LBUTTONDOWN, WM_LBUTTONUP, WM_MBUTTONDBLCLK, WM_MBUTTONDOWN, WM_MBUTTONUP,
+    WM_MENUCHAR, WM_MOUSEACTIVATE, WM_MOUSEHWHEEL, WM_MOUSEMOVE, WM_MOUSEWHEEL, WM_NCACTIVATE,
+    WM_NCCALCSIZE, WM_NCCREATE, WM_NCDESTROY, WM_NCLBUTTONDOWN, WM_PAINT, WM_POINTERDOWN,
+    WM_POINTERUP, WM_POINTERUPDATE, WM_RBUTTONDBLCLK, WM_RBUTTONDOWN, WM_RBUTTONUP, WM_SETCURSOR,
     WM_SETFOCUS, WM_SETTINGCHANGE, WM_SIZE, WM_SIZING, WM_SYSCOMMAND, WM_SYSKEYDOWN, WM_SYSKEYUP,
-    WM_TOUCH, WM_WINDOWPOSCHANGED, WM_WINDOWPOSCHANGING, WM_XBUTTONDOWN, WM_XBUTTONUP, WMSZ_BOTTOM,
-    WMSZ_BOTTOMLEFT, WMSZ_BOTTOMRIGHT, WMSZ_LEFT, WMSZ_RIGHT, WMSZ_TOP, WMSZ_TOPLEFT,
-    WMSZ_TOPRIGHT, WNDCLASSEXW, WS_EX_LAYERED, WS_EX_NOACTIVATE, WS_EX_TOOLWINDOW,
+    WM_TOUCH, WM_WINDOWPOSCHANGED, WM_WINDOWPOSCHANGING, WM_XBUTTONDBLCLK, WM_XBUTTONDOWN,
+    WM_XBUTTONUP, WMSZ_BOTTOM, WMSZ_BOTTOMLEFT, WMSZ_BOTTOMRIGHT, WMSZ_LEFT, WMSZ_RIGHT, WMSZ_TOP,
+    WMSZ_TOPLEFT, WMSZ_TOPRIGHT, WNDCLASSEXW, WS_EX_LAYERED, WS_EX_NOACTIVATE, WS_EX_TOOLWINDOW,
     WS_EX_TRANSPARENT, WS_OVERLAPPED, WS_POPUP, WS_VISIBLE,
 };
 use winit_core::application::ApplicationHandler;
@@ -66,20 +71,20 @@ use winit_core::cursor::{CustomCursor, CustomCursorSource};
 use winit_core::data_transfer::{
     DataTransfer, DataTransferId, DataTransferSend, TransferType, TypedData,
 };
-use winit_core::error::{EventLoopError, NotSupportedError, RequestError};
+use winit_core::error::{CreateWindowError, EventLoopError, NotSupportedError, RequestError};
 use winit_core::event::{
-    DeviceEvent, DeviceId, FingerId, Force, Ime, RawKeyEvent, SurfaceSizeWriter, TabletToolButton,
-    TabletToolData, TabletToolKind, TabletToolTilt, TouchPhase, WindowEvent,
+    DeviceEvent, DeviceId, FingerId, FocusSource, Force, Ime, Modifiers, RawKeyEvent,
+    SurfaceSizeWriter, TabletToolButton, TabletToolData, TabletToolKind, TabletToolTilt,
+    TouchPhase, WindowEvent,
 };
 use winit_core::event_loop::pump_events::PumpStatus;
 use winit_core::event_loop::{
-    ActiveEventLoop as RootActiveEventLoop, AsyncRequestSerial, ControlFlow, DeviceEvents,
+    ActiveEventLoop as RootActiveEventLoop, AsyncRequestSerial, Backend, ControlFlow, DeviceEvents,
     DndAction, DragIcon, EventLoopProxy as RootEventLoopProxy, EventLoopProxyProvider,
-    OwnedDisplayHandle as CoreOwnedDisplayHandle,
+    OwnedDisplayHandle as CoreOwnedDisplayHandle, PendingWakeUps, PointerConfig,
 };
-use winit_core::keyboard::ModifiersState;
 use winit_core::monitor::{Fullscreen, MonitorHandle as CoreMonitorHandle};
-use winit_core::window::{Theme, Window as CoreWindow, WindowAttributes, WindowId};
+use winit_core::window::{CursorGrabMode, Theme, Window as CoreWindow, WindowAttributes, WindowId};
 
 pub(super) use self::runner::{Event, EventLoopRunner};
 use super::SelectedCursor;
@@ -128,15 +133,23 @@ impl WindowData {
 
 struct ThreadMsgTargetData {
     event_loop_runner: Rc<EventLoopRunner>,
+    /// The last system theme, to report its changes.
+    system_theme: Cell<Theme>,
 }
 
 impl ThreadMsgTargetData {
     fn send_wakeup(&self) {
-        self.event_loop_runner.send_event(Event::WakeUp);
+        let keys = self.event_loop_runner.pending_wake_ups.take();
+        self.event_loop_runner.send_event(Event::WakeUp(keys));
     }
 
-    fn send_device_event(&self, device_id: DeviceId, event: DeviceEvent) {
-        self.event_loop_runner.send_event(Event::Device { device_id, event });
+    fn send_device_event(
+        &self,
+        device_id: DeviceId,
+        event: DeviceEvent,
+        window_id: Option<WindowId>,
+    ) {
+        self.event_loop_runner.send_event(Event::Device { device_id, event, window_id });
     }
 }
 
@@ -279,7 +292,7 @@ impl EventLoop {
 
         self.runner.reset_runner();
 
-        if exit_code == 0 { Ok(()) } else { Err(EventLoopError::ExitFailure(exit_code)) }
+        EventLoopError::exit_result(exit_code)
     }
 
     pub fn pump_app_events<A: ApplicationHandler>(
@@ -414,14 +427,18 @@ impl ActiveEventLoop {
 
 impl RootActiveEventLoop for ActiveEventLoop {
     fn create_proxy(&self) -> RootEventLoopProxy {
-        let event_loop_proxy = EventLoopProxy { target_window: self.0.thread_msg_target };
+        let event_loop_proxy = EventLoopProxy {
+            target_window: self.0.thread_msg_target,
+            pending_wake_ups: self.0.pending_wake_ups.clone(),
+        };
         RootEventLoopProxy::new(Arc::new(event_loop_proxy))
     }
 
     fn create_window(
         &self,
         window_attributes: WindowAttributes,
-    ) -> Result<Box<dyn CoreWindow>, RequestError> {
+    ) -> Result<Box<dyn CoreWindow>, CreateWindowError> {
+        window_attributes.validate()?;
         Ok(Box::new(Window::new(self, window_attributes)?))
     }
 
@@ -456,13 +473,29 @@ impl RootActiveEventLoop for ActiveEventLoop {
     }
 
     fn system_theme(&self) -> Option<Theme> {
-        Some(if super::dark_mode::should_use_dark_mode() { Theme::Dark } else { Theme::Light })
+        Some(super::dark_mode::system_theme())
     }
 
     fn listen_device_events(&self, allowed: DeviceEvents) {
         raw_input::register_all_mice_and_keyboards_for_raw_input(self.0.thread_msg_target, allowed);
     }
 
+    fn system_pointer_config(&self) -> PointerConfig {
+        let mut config = PointerConfig::default();
+        unsafe {
+            config.double_click_interval = Duration::from_millis(GetDoubleClickTime() as u64);
+            config.double_click_distance = PhysicalSize::new(
+                GetSystemMetrics(SM_CXDOUBLECLK) as u32,
+                GetSystemMetrics(SM_CYDOUBLECLK) as u32,
+            );
+            config.drag_threshold = PhysicalSize::new(
+                GetSystemMetrics(SM_CXDRAG) as u32,
+                GetSystemMetrics(SM_CYDRAG) as u32,
+            );
+        }
+        config
+    }
+
     fn set_control_flow(&self, control_flow: ControlFlow) {
         self.0.set_control_flow(control_flow)
     }
@@ -475,6 +508,10 @@ impl RootActiveEventLoop for ActiveEventLoop {
         self.0.set_exit_code(0)
     }
 
+    fn exit_with_code(&self, code: i32) {
+        self.0.set_exit_code(code)
+    }
+
     fn owned_display_handle(&self) -> CoreOwnedDisplayHandle {
         CoreOwnedDisplayHandle::new(Arc::new(OwnedDisplayHandle))
     }
@@ -483,6 +520,10 @@ impl RootActiveEventLoop for ActiveEventLoop {
         self
     }
 
+    fn backend(&self) -> Option<Backend> {
+        Some(Backend::Windows)
+    }
+
     fn fetch_data_transfer(
         &self,
         id: DataTransferId,
@@ -739,14 +780,7 @@ fn wait_for_messages_impl(
     timeout: Option<Duration>,
 ) {
     let timeout = {
-        let control_flow_timeout = match control_flow {
-            ControlFlow::Wait => None,
-            ControlFlow::Poll => Some(Duration::ZERO),
-            ControlFlow::WaitUntil(wait_deadline) => {
-                let start = Instant::now();
-                Some(wait_deadline.saturating_duration_since(start))
-            },
-        };
+        let control_flow_timeout = control_flow.timeout(Instant::now());
         let timeout = min_timeout(timeout, control_flow_timeout);
         if timeout == Some(Duration::ZERO) {
             // Do not wait if we don't have time.
@@ -867,6 +901,7 @@ type ThreadExecFn = Box<Box<dyn FnMut()>>;
 #[derive(Debug)]
 pub struct EventLoopProxy {
     target_window: HWND,
+    pending_wake_ups: Arc<PendingWakeUps>,
 }
 
 unsafe impl Send for EventLoopProxy {}
@@ -876,6 +911,12 @@ impl EventLoopProxyProvider for EventLoopProxy {
     fn wake_up(&self) {
         unsafe { PostMessageW(self.target_window, USER_EVENT_MSG_ID.get(), 0, 0) };
     }
+
+    fn wake_up_coalesced(&self, key: u64) {
+        if self.pending_wake_ups.insert(key) {
+            self.wake_up();
+        }
+    }
 }
 
 /// A lazily-initialized window message ID.
@@ -942,6 +983,10 @@ pub(crate) static DESTROY_MSG_ID: LazyMessageId = LazyMessageId::new("Winit::Des
 // documentation in the `window_state` module for more information.
 pub(crate) static SET_RETAIN_STATE_ON_SIZE_MSG_ID: LazyMessageId =
     LazyMessageId::new("Winit::SetRetainMaximized\0");
+// Message posted on `WM_MOUSEACTIVATE`, to forget the focus source once the click was handled,
+// whether it activated the window or not.
+// WPARAM and LPARAM are unused.
+static CLEAR_FOCUS_SOURCE_MSG_ID: LazyMessageId = LazyMessageId::new("Winit::ClearFocusSource\0");
 static THREAD_EVENT_TARGET_WINDOW_CLASS: LazyLock<Vec<u16>> =
     LazyLock::new(|| util::encode_wide("Winit Thread Event Target"));
 /// When the taskbar is created, it registers a message with the "TaskbarCreated" string and then
@@ -1008,7 +1053,10 @@ fn insert_event_target_window_data(
     thread_msg_target: HWND,
     event_loop_runner: Rc<EventLoopRunner>,
 ) {
-    let userdata = ThreadMsgTargetData { event_loop_runner };
+    let userdata = ThreadMsgTargetData {
+        event_loop_runner,
+        system_theme: Cell::new(crate::dark_mode::system_theme()),
+    };
     let input_ptr = Box::into_raw(Box::new(userdata));
 
     unsafe { util::set_window_long(thread_msg_target, GWL_USERDATA, input_ptr as isize) };
@@ -1046,17 +1094,17 @@ fn update_modifiers(window: HWND, userdata: &WindowData) {
 
     let modifiers = {
         let mut layouts = LAYOUT_CACHE.lock().unwrap();
-        layouts.get_agnostic_mods()
+        layouts.get_mods()
     };
 
     let mut window_state = userdata.window_state.lock().unwrap();
-    if window_state.modifiers_state != modifiers {
-        window_state.modifiers_state = modifiers;
+    if window_state.modifiers != modifiers {
+        window_state.modifiers = modifiers;
 
         // Drop lock
         drop(window_state);
 
-        userdata.send_window_event(window, ModifiersChanged(modifiers.into()));
+        userdata.send_window_event(window, ModifiersChanged(modifiers));
     }
 }
 
@@ -1065,16 +1113,23 @@ unsafe fn gain_active_focus(window: HWND, userdata: &WindowData) {
 
     update_modifiers(window, userdata);
 
-    userdata.send_window_event(window, Focused(true));
+    let via = userdata.window_state_lock().pending_focus_source.take();
+    userdata.send_window_event(window, Focused {
+        focused: true,
+        via: via.unwrap_or(FocusSource::Unknown),
+    });
 }
 
 unsafe fn lose_active_focus(window: HWND, userdata: &WindowData) {
     use winit_core::event::WindowEvent::{Focused, ModifiersChanged};
 
-    userdata.window_state_lock().modifiers_state = ModifiersState::empty();
-    userdata.send_window_event(window, ModifiersChanged(ModifiersState::empty().into()));
+    let mut window_state = userdata.window_state_lock();
+    window_state.modifiers = Modifiers::default();
+    window_state.pending_focus_source = None;
+    drop(window_state);
+    userdata.send_window_event(window, ModifiersChanged(Modifiers::default()));
 
-    userdata.send_window_event(window, Focused(false));
+    userdata.send_window_event(window, Focused { focused: false, via: FocusSource::Unknown });
 }
 
 /// Any window whose callback is configured to this function will have its events propagated
@@ -1225,20 +1280,31 @@ unsafe fn public_window_callback_inner(
         },
 
         WM_ENTERSIZEMOVE => {
-            userdata
-                .window_state_lock()
-                .set_window_flags_in_place(|f| f.insert(WindowFlags::MARKER_IN_SIZE_MOVE));
+            let moving = {
+                let mut state = userdata.window_state_lock();
+                state.set_window_flags_in_place(|f| f.insert(WindowFlags::MARKER_IN_SIZE_MOVE));
+                state.moving
+            };
+            if moving {
+                userdata.send_window_event(window, WindowEvent::MoveStarted);
+            }
             result = ProcResult::Value(0);
         },
 
         WM_EXITSIZEMOVE => {
-            let mut state = userdata.window_state_lock();
-            if state.dragging {
-                state.dragging = false;
-                unsafe { PostMessageW(window, WM_LBUTTONUP, 0, lparam) };
-            }
+            let moving = {
+                let mut state = userdata.window_state_lock();
+                if state.dragging {
+                    state.dragging = false;
+                    unsafe { PostMessageW(window, WM_LBUTTONUP, 0, lparam) };
+                }
 
-            state.set_window_flags_in_place(|f| f.remove(WindowFlags::MARKER_IN_SIZE_MOVE));
+                state.set_window_flags_in_place(|f| f.remove(WindowFlags::MARKER_IN_SIZE_MOVE));
+                mem::take(&mut state.moving)
+            };
+            if moving {
+                userdata.send_window_event(window, WindowEvent::MoveEnded);
+            }
             result = ProcResult::Value(0);
         },
 
@@ -1280,6 +1346,12 @@ unsafe fn public_window_callback_inner(
             result = ProcResult::Value(0);
         },
 
+        WM_HELP => {
+            use winit_core::event::WindowEvent::HelpRequested;
+            userdata.send_window_event(window, HelpRequested);
+            result = ProcResult::Value(1);
+        },
+
         WM_DESTROY => {
             use winit_core::event::WindowEvent::Destroyed;
             unsafe { RevokeDragDrop(window) };
@@ -1293,6 +1365,20 @@ unsafe fn public_window_callback_inner(
             result = ProcResult::Value(0);
         },
 
+        WM_ERASEBKGND => {
+            let background = userdata.window_state_lock().background;
+            result = match background {
+                Some([r, g, b, _]) => unsafe {
+                    let mut rect: RECT = mem::zeroed();
+                    GetClientRect(window, &mut rect);
+                    let brush = CreateSolidBrush(r as u32 | (g as u32) << 8 | (b as u32) << 16);
+                    FillRect(wparam as HDC, &rect, brush);
+                    DeleteObject(brush);
+                    ProcResult::Value(1)
+                },
+                None => ProcResult::DefWindowProc(wparam),
+            };
+        },
         WM_PAINT if userdata.event_loop_runner.source_drag.get().is_some() => {
             // While a source-side drag is in flight, the app handler is on the stack (we're
             // inside `start_drag` -> `DoDragDrop`), so we can neither dispatch `RedrawRequested`
@@ -1415,12 +1501,21 @@ unsafe fn public_window_callback_inner(
                 userdata.send_window_event(window, Moved(physical_position));
             }
 
+            // Move the cursor clip along with the client area, e.g. when entering fullscreen.
+            if unsafe {
+                (*windowpos).flags & (SWP_NOMOVE | SWP_NOSIZE) != (SWP_NOMOVE | SWP_NOSIZE)
+            } {
+                userdata.window_state_lock().mouse.set_cursor_flags(window, |_| ()).ok();
+            }
+
             // This is necessary for us to still get sent WM_SIZE.
             result = ProcResult::DefWindowProc(wparam);
         },
 
         WM_SIZE => {
-            use winit_core::event::WindowEvent::SurfaceResized;
+            use winit_core::event::WindowEvent::{
+                MaximizedChanged, MinimizedChanged, SurfaceResized,
+            };
             let w = util::loword(lparam as u32) as u32;
             let h = util::hiword(lparam as u32) as u32;
             let physical_size = PhysicalSize::new(w, h);
@@ -1435,12 +1530,38 @@ unsafe fn public_window_callback_inner(
                 }
             }
 
+            // Only the size types of our own window are relevant, `SIZE_MAXSHOW` and
+            // `SIZE_MAXHIDE` are about other windows.
+            if matches!(wparam as u32, SIZE_RESTORED | SIZE_MINIMIZED | SIZE_MAXIMIZED) {
+                let maximized = wparam as u32 == SIZE_MAXIMIZED;
+                let minimized = wparam as u32 == SIZE_MINIMIZED;
+                let mut state = userdata.window_state_lock();
+                let minimized_changed =
+                    mem::replace(&mut state.reported_minimized, minimized) != minimized;
+                // A maximized window stays maximized while it's minimized.
+                let maximized_changed = !minimized
+                    && mem::replace(&mut state.reported_maximized, maximized) != maximized;
+                drop(state);
+
+                if maximized_changed {
+                    userdata.send_window_event(window, MaximizedChanged(maximized));
+                }
+                if minimized_changed {
+                    userdata.send_window_event(window, MinimizedChanged(minimized));
+                }
+            }
+
             let mut state = userdata.window_state_lock();
             if (w, h) != (0, 0) && physical_size != state.surface_size {
                 // WM_SIZE is received with size (0, 0) when a window is minimized; ignore.
                 state.surface_size = physical_size;
+                // Drop the echo of the last `request_surface_size` if the application asked for
+                // it.
+                let is_echo = state.resize_echo.take() == Some(physical_size);
                 drop(state);
-                userdata.send_window_event(window, SurfaceResized(physical_size));
+                if !is_echo {
+                    userdata.send_window_event(window, SurfaceResized(physical_size));
+                }
             }
             result = ProcResult::Value(0);
         },
@@ -1538,11 +1659,11 @@ unsafe fn public_window_callback_inner(
         },
 
         WM_IME_STARTCOMPOSITION => {
-            let ime_allowed = userdata.window_state_lock().ime_capabilities.is_some();
-            if ime_allowed {
+            let ime_capabilities = userdata.window_state_lock().ime_capabilities;
+            if let Some(capabilities) = ime_capabilities {
                 userdata.window_state_lock().ime_state = ImeState::Enabled;
 
-                userdata.send_window_event(window, WindowEvent::Ime(Ime::Enabled));
+                userdata.send_window_event(window, WindowEvent::Ime(Ime::Enabled(capabilities)));
             }
 
             result = ProcResult::DefWindowProc(wparam);
@@ -1575,7 +1696,8 @@ unsafe fn public_window_callback_inner(
                             window,
                             WindowEvent::Ime(Ime::Preedit(String::new(), None)),
                         );
-                        userdata.send_window_event(window, WindowEvent::Ime(Ime::Commit(text)));
+                        userdata
+                            .send_window_event(window, WindowEvent::Ime(Ime::Commit(text, None)));
                     }
                 }
 
@@ -1589,7 +1711,7 @@ unsafe fn public_window_callback_inner(
 
                         userdata.send_window_event(
                             window,
-                            WindowEvent::Ime(Ime::Preedit(text, cursor_range)),
+                            WindowEvent::Ime(Ime::preedit(text, cursor_range)),
                         );
                     }
                 }
@@ -1614,7 +1736,8 @@ unsafe fn public_window_callback_inner(
                             window,
                             WindowEvent::Ime(Ime::Preedit(String::new(), None)),
                         );
-                        userdata.send_window_event(window, WindowEvent::Ime(Ime::Commit(text)));
+                        userdata
+                            .send_window_event(window, WindowEvent::Ime(Ime::Commit(text, None)));
                     }
                 }
 
@@ -1659,6 +1782,11 @@ unsafe fn public_window_callback_inner(
             }
             // Send `WindowEvent::Minimized` here if we decide to implement one
 
+            // The low four bits are used internally, e.g. `drag_window` moves with `HTCAPTION`.
+            if wparam & 0xfff0 == SC_MOVE as usize {
+                userdata.window_state_lock().moving = true;
+            }
+
             if wparam == SC_SCREENSAVE as usize {
                 let window_state = userdata.window_state_lock();
                 if window_state.fullscreen.is_some() {
@@ -1796,6 +1924,7 @@ unsafe fn public_window_callback_inner(
                 device_id: None,
                 delta: LineDelta(0.0, value * scroll_lines_multiplier as f32),
                 phase: TouchPhase::Moved,
+                momentum: false,
             });
 
             result = ProcResult::Value(0);
@@ -1829,6 +1958,7 @@ unsafe fn public_window_callback_inner(
                 device_id: None,
                 delta: LineDelta(value * scroll_characters_multiplier as f32, 0.0),
                 phase: TouchPhase::Moved,
+                momentum: false,
             });
 
             result = ProcResult::Value(0);
@@ -1848,7 +1978,8 @@ unsafe fn public_window_callback_inner(
             }
         },
 
-        WM_LBUTTONDOWN | WM_RBUTTONDOWN | WM_MBUTTONDOWN => {
+        WM_LBUTTONDOWN | WM_RBUTTONDOWN | WM_MBUTTONDOWN | WM_LBUTTONDBLCLK | WM_RBUTTONDBLCLK
+        | WM_MBUTTONDBLCLK => {
             use winit_core::event::ElementState::Pressed;
             use winit_core::event::MouseButton;
             use winit_core::event::WindowEvent::PointerButton;
@@ -1861,18 +1992,28 @@ unsafe fn public_window_callback_inner(
             let y = util::get_y_lparam(lparam as u32) as i32;
             let position = PhysicalPosition::new(x as f64, y as f64);
 
+            // The second press of a double-click is sent as `WM_*BUTTONDBLCLK`.
+            let click_count =
+                if matches!(msg, WM_LBUTTONDBLCLK | WM_RBUTTONDBLCLK | WM_MBUTTONDBLCLK) {
+                    2
+                } else {
+                    1
+                };
+            userdata.window_state_lock().mouse.click_count = click_count;
+
             userdata.send_window_event(window, PointerButton {
                 device_id: None,
                 primary: true,
                 state: Pressed,
                 position,
                 button: match msg {
-                    WM_LBUTTONDOWN => MouseButton::Left,
-                    WM_RBUTTONDOWN => MouseButton::Right,
-                    WM_MBUTTONDOWN => MouseButton::Middle,
+                    WM_LBUTTONDOWN | WM_LBUTTONDBLCLK => MouseButton::Left,
+                    WM_RBUTTONDOWN | WM_RBUTTONDBLCLK => MouseButton::Right,
+                    WM_MBUTTONDOWN | WM_MBUTTONDBLCLK => MouseButton::Middle,
                     _ => unreachable!(),
                 }
                 .into(),
+                click_count: Some(click_count),
             });
             result = ProcResult::Value(0);
         },
@@ -1889,6 +2030,7 @@ unsafe fn public_window_callback_inner(
             let x = util::get_x_lparam(lparam as u32) as i32;
             let y = util::get_y_lparam(lparam as u32) as i32;
             let position = PhysicalPosition::new(x as f64, y as f64);
+            let click_count = userdata.window_state_lock().mouse.click_count;
 
             userdata.send_window_event(window, PointerButton {
                 device_id: None,
@@ -1902,11 +2044,12 @@ unsafe fn public_window_callback_inner(
                     _ => unreachable!(),
                 }
                 .into(),
+                click_count: Some(click_count),
             });
             result = ProcResult::Value(0);
         },
 
-        WM_XBUTTONDOWN => {
+        WM_XBUTTONDOWN | WM_XBUTTONDBLCLK => {
             use winit_core::event::ElementState::Pressed;
             use winit_core::event::MouseButton;
             use winit_core::event::WindowEvent::PointerButton;
@@ -1923,6 +2066,9 @@ unsafe fn public_window_callback_inner(
             // 1 is defined as back, 2 as forward; other codes are unexpected.
             let b = xbutton as u8 + MouseButton::Back as u8 - 1;
 
+            let click_count = if msg == WM_XBUTTONDBLCLK { 2 } else { 1 };
+            userdata.window_state_lock().mouse.click_count = click_count;
+
             userdata.send_window_event(window, PointerButton {
                 device_id: None,
                 primary: true,
@@ -1930,6 +2076,7 @@ unsafe fn public_window_callback_inner(
                 position,
                 // 1 is defined as back, 2 as forward; other codes are unexpected.
                 button: MouseButton::try_from_u8(b).unwrap().into(),
+                click_count: Some(click_count),
             });
             result = ProcResult::Value(0);
         },
@@ -1951,6 +2098,7 @@ unsafe fn public_window_callback_inner(
 
             // 1 is defined as back, 2 as forward; other codes are unexpected.
             let b = xbutton as u8 + MouseButton::Back as u8 - 1;
+            let click_count = userdata.window_state_lock().mouse.click_count;
 
             userdata.send_window_event(window, PointerButton {
                 device_id: None,
@@ -1959,6 +2107,7 @@ unsafe fn public_window_callback_inner(
                 position,
                 // 1 is defined as back, 2 as forward; other codes are unexpected.
                 button: MouseButton::try_from_u8(b).unwrap().into(),
+                click_count: Some(click_count),
             });
             result = ProcResult::Value(0);
         },
@@ -2018,6 +2167,7 @@ unsafe fn public_window_callback_inner(
                             state: Pressed,
                             position,
                             button: Touch { finger_id, force: None },
+                            click_count: None,
                         });
                     } else if util::has_flag(input.dwFlags, TOUCHEVENTF_UP) {
                         userdata.send_window_event(window, WindowEvent::PointerButton {
@@ -2026,6 +2176,7 @@ unsafe fn public_window_callback_inner(
                             state: Released,
                             position,
                             button: Touch { finger_id, force: None },
+                            click_count: None,
                         });
                         userdata.send_window_event(window, WindowEvent::PointerLeft {
                             device_id: None,
@@ -2187,6 +2338,7 @@ unsafe fn public_window_callback_inner(
                                 state: Pressed,
                                 position,
                                 button,
+                                click_count: None,
                             });
                         } else {
                             userdata.send_window_event(window, WindowEvent::PointerButton {
@@ -2195,6 +2347,7 @@ unsafe fn public_window_callback_inner(
                                 state: Released,
                                 position,
                                 button,
+                                click_count: None,
                             });
                             userdata.send_window_event(window, WindowEvent::PointerLeft {
                                 device_id: None,
@@ -2232,6 +2385,13 @@ unsafe fn public_window_callback_inner(
             result = ProcResult::Value(0);
         },
 
+        WM_MOUSEACTIVATE => {
+            // Sent before the activation messages when clicking into an inactive window.
+            userdata.window_state_lock().pending_focus_source = Some(FocusSource::Pointer);
+            unsafe { PostMessageW(window, CLEAR_FOCUS_SOURCE_MSG_ID.get(), 0, 0) };
+            result = ProcResult::DefWindowProc(wparam);
+        },
+
         WM_NCACTIVATE => {
             let is_active = wparam != false.into();
             let active_focus_changed = userdata.window_state_lock().set_active(is_active);
@@ -2250,6 +2410,17 @@ unsafe fn public_window_callback_inner(
             if active_focus_changed {
                 unsafe { gain_active_focus(window, userdata) };
             }
+            // The cursor clip is global, so apply the grab again when getting the focus back.
+            userdata.window_state_lock().mouse.set_cursor_flags(window, |_| ()).ok();
+            let cursor_flags = userdata.window_state_lock().mouse.cursor_flags();
+            if active_focus_changed && cursor_flags.contains(CursorFlags::GRABBED) {
+                let mode = if cursor_flags.contains(CursorFlags::LOCKED) {
+                    CursorGrabMode::Locked
+                } else {
+                    CursorGrabMode::Confined
+                };
+                userdata.send_window_event(window, WindowEvent::CursorGrabChanged(mode));
+            }
             result = ProcResult::Value(0);
         },
 
@@ -2258,6 +2429,12 @@ unsafe fn public_window_callback_inner(
             if active_focus_changed {
                 unsafe { lose_active_focus(window, userdata) };
             }
+            // The cursor clip is released along with the focus.
+            let cursor_flags = userdata.window_state_lock().mouse.cursor_flags();
+            if active_focus_changed && cursor_flags.contains(CursorFlags::GRABBED) {
+                let event = WindowEvent::CursorGrabChanged(CursorGrabMode::None);
+                userdata.send_window_event(window, event);
+            }
             result = ProcResult::Value(0);
         },
 
@@ -2381,6 +2558,7 @@ unsafe fn public_window_callback_inner(
             let new_surface_size = Arc::new(Mutex::new(new_physical_surface_size));
             userdata.send_window_event(window, ScaleFactorChanged {
                 scale_factor: new_scale_factor,
+                old_scale_factor,
                 surface_size_writer: SurfaceSizeWriter::new(Arc::downgrade(&new_surface_size)),
             });
 
@@ -2461,6 +2639,9 @@ unsafe fn public_window_callback_inner(
                     f.set(WindowFlags::MARKER_RETAIN_STATE_ON_SIZE, wparam != 0)
                 });
                 result = ProcResult::Value(0);
+            } else if msg == CLEAR_FOCUS_SOURCE_MSG_ID.get() {
+                userdata.window_state_lock().pending_focus_source = None;
+                result = ProcResult::Value(0);
             } else if msg == TASKBAR_CREATED.get() {
                 let window_state = userdata.window_state_lock();
                 unsafe { set_skip_taskbar(window, window_state.skip_taskbar) };
@@ -2533,6 +2714,30 @@ unsafe extern "system" fn thread_event_target_callback(
             unsafe { DefWindowProcW(window, msg, wparam, lparam) }
         },
 
+        // The thread event target is a top-level window, so it receives the broadcast even when the
+        // application has no window.
+        WM_SETTINGCHANGE => {
+            let theme = crate::dark_mode::system_theme();
+            if userdata.system_theme.replace(theme) != theme {
+                userdata.event_loop_runner.send_event(Event::SystemThemeChanged(theme));
+            }
+
+            unsafe { DefWindowProcW(window, msg, wparam, lparam) }
+        },
+
+        WM_INPUT_DEVICE_CHANGE => {
+            let event = match wparam as u32 {
+                GIDC_ARRIVAL => Some(DeviceEvent::Added),
+                GIDC_REMOVAL => Some(DeviceEvent::Removed),
+                _ => None,
+            };
+            if let Some(event) = event {
+                userdata.send_device_event(wrap_device_id(lparam as _), event, None);
+            }
+
+            0
+        },
+
         _ if msg == USER_EVENT_MSG_ID.get() => {
             // synthesis a placeholder UserEvent, so that if the callback is
             // re-entered it can be buffered for later delivery. the real
@@ -2565,6 +2770,10 @@ unsafe fn handle_raw_input(userdata: &ThreadMsgTargetData, data: RAWINPUT) {
     use winit_core::event::MouseScrollDelta::LineDelta;
 
     let device_id = wrap_device_id(data.header.hDevice as _);
+    // The raw input is sent to the thread message target, so look up the window that has the
+    // keyboard focus on this thread as a hint of where the input is going.
+    let focus = unsafe { GetFocus() };
+    let window_id = (!focus.is_null()).then(|| WindowId::from_raw(focus as usize));
 
     if data.header.dwType == RIM_TYPEMOUSE {
         let mouse = unsafe { data.data.mouse };
@@ -2574,7 +2783,7 @@ unsafe fn handle_raw_input(userdata: &ThreadMsgTargetData, data: RAWINPUT) {
             let y = mouse.lLastY as f64;
 
             if x != 0.0 || y != 0.0 {
-                userdata.send_device_event(device_id, PointerMotion { delta: (x, y) });
+                userdata.send_device_event(device_id, PointerMotion { delta: (x, y) }, window_id);
             }
         }
 
@@ -2582,18 +2791,24 @@ unsafe fn handle_raw_input(userdata: &ThreadMsgTargetData, data: RAWINPUT) {
         if util::has_flag(button_flags as u32, RI_MOUSE_WHEEL) {
             let button_data = unsafe { mouse.Anonymous.Anonymous.usButtonData } as i16;
             let delta = button_data as f32 / WHEEL_DELTA as f32;
-            userdata.send_device_event(device_id, MouseWheel { delta: LineDelta(0.0, delta) });
+            let event = MouseWheel { delta: LineDelta(0.0, delta) };
+            userdata.send_device_event(device_id, event, window_id);
         }
         if util::has_flag(button_flags as u32, RI_MOUSE_HWHEEL) {
             let button_data = unsafe { mouse.Anonymous.Anonymous.usButtonData } as i16;
             let delta = -button_data as f32 / WHEEL_DELTA as f32;
-            userdata.send_device_event(device_id, MouseWheel { delta: LineDelta(delta, 0.0) });
+            let event = MouseWheel { delta: LineDelta(delta, 0.0) };
+            userdata.send_device_event(device_id, event, window_id);
         }
 
         let button_state = raw_input::get_raw_mouse_button_state(button_flags as u32);
         for (button, state) in button_state.iter().enumerate() {
             if let Some(state) = *state {
-                userdata.send_device_event(device_id, Button { button: button as _, state });
+                userdata.send_device_event(
+                    device_id,
+                    Button { button: button as _, state },
+                    window_id,
+                );
             }
         }
     } else if data.header.dwType == RIM_TYPEKEYBOARD {
@@ -2609,7 +2824,8 @@ unsafe fn handle_raw_input(userdata: &ThreadMsgTargetData, data: RAWINPUT) {
         if let Some(physical_key) = raw_input::get_keyboard_physical_key(keyboard) {
             let state = if pressed { Pressed } else { Released };
 
-            userdata.send_device_event(device_id, Key(RawKeyEvent { physical_key, state }));
+            let event = Key(RawKeyEvent { physical_key, state });
+            userdata.send_device_event(device_id, event, window_id);
         }
     }
 }
@@ -1,6 +1,6 @@
 use std::any::Any;
 use std::cell::{Cell, Ref, RefCell};
-use std::collections::VecDeque;
+use std::collections::{HashSet, VecDeque};
 use std::rc::Rc;
 use std::sync::{Arc, Mutex};
 use std::time::Instant;
@@ -13,9 +13,11 @@ use windows_sys::Win32::System::Ole::{
 };
 use winit_core::application::ApplicationHandler;
 use winit_core::data_transfer::DataTransferId;
-use winit_core::event::{DeviceEvent, DeviceId, StartCause, SurfaceSizeWriter, WindowEvent};
-use winit_core::event_loop::{ActiveEventLoop as RootActiveEventLoop, DndAction};
-use winit_core::window::WindowId;
+use winit_core::event::{
+    DeviceEvent, DeviceId, StartCause, SurfaceSizeWriter, WaitCancelReason, WindowEvent,
+};
+use winit_core::event_loop::{ActiveEventLoop as RootActiveEventLoop, DndAction, PendingWakeUps};
+use winit_core::window::{Theme, WindowId};
 
 use super::{ActiveEventLoop, ControlFlow, EventLoopThreadExecutor};
 use crate::dnd::{DataObject, DropEffect, DropSource, SourceDataObject, drop_effect_to_dnd_action};
@@ -91,6 +93,9 @@ pub(crate) struct EventLoopRunner {
     /// has had its chance to read the data.
     pending_source_drag_cleanup: Cell<Option<DataTransferId>>,
 
+    /// Keys passed to `wake_up_coalesced` that weren't delivered yet.
+    pub(super) pending_wake_ups: Arc<PendingWakeUps>,
+
     panic_error: Cell<Option<PanicError>>,
 }
 
@@ -120,12 +125,13 @@ pub(crate) enum RunnerState {
 
 #[derive(Debug, Clone)]
 pub(crate) enum Event {
-    Device { device_id: DeviceId, event: DeviceEvent },
+    Device { device_id: DeviceId, event: DeviceEvent, window_id: Option<WindowId> },
     Window { window_id: WindowId, event: WindowEvent },
-    BufferedScaleFactorChanged(HWND, f64, PhysicalSize<u32>),
+    BufferedScaleFactorChanged(HWND, f64, f64, PhysicalSize<u32>),
     // FIXME(madsmtm): Coalesce these into a flag (or similar) instead of handling them as events.
     // https://github.com/rust-windowing/winit/pull/3687
-    WakeUp,
+    WakeUp(HashSet<u64>),
+    SystemThemeChanged(Theme),
 }
 
 impl EventLoopRunner {
@@ -145,6 +151,7 @@ impl EventLoopRunner {
             source_drag: Cell::new(None),
             pending_drag: RefCell::new(None),
             pending_source_drag_cleanup: Cell::new(None),
+            pending_wake_ups: Arc::new(PendingWakeUps::new()),
         }
     }
 
@@ -537,18 +544,21 @@ impl EventLoopRunner {
     }
 
     fn call_new_events(self: &Rc<Self>, init: bool) {
-        let start_cause = match (init, self.control_flow(), self.exit.get()) {
+        let control_flow = self.control_flow().effective(self.last_events_cleared.get());
+        let start_cause = match (init, control_flow, self.exit.get()) {
             (true, ..) => StartCause::Init,
-            (false, ControlFlow::Poll, None) => StartCause::Poll,
+            (false, ControlFlow::Poll | ControlFlow::PollUntil(_), None) => StartCause::Poll,
             (false, _, Some(_)) | (false, ControlFlow::Wait, None) => StartCause::WaitCancelled {
                 requested_resume: None,
                 start: self.last_events_cleared.get(),
+                reason: WaitCancelReason::Unknown,
             },
             (false, ControlFlow::WaitUntil(requested_resume), None) => {
                 if Instant::now() < requested_resume {
                     StartCause::WaitCancelled {
                         requested_resume: Some(requested_resume),
                         start: self.last_events_cleared.get(),
+                        reason: WaitCancelReason::Unknown,
                     }
                 } else {
                     StartCause::ResumeTimeReached {
@@ -574,11 +584,17 @@ impl Event {
     pub fn buffer_scale_factor(self) -> Self {
         match self {
             Self::Window {
-                event: WindowEvent::ScaleFactorChanged { scale_factor, surface_size_writer },
+                event:
+                    WindowEvent::ScaleFactorChanged {
+                        scale_factor,
+                        old_scale_factor,
+                        surface_size_writer,
+                    },
                 window_id,
             } => Event::BufferedScaleFactorChanged(
                 window_id.into_raw() as HWND,
                 scale_factor,
+                old_scale_factor,
                 surface_size_writer.surface_size().unwrap(),
             ),
             event => event,
@@ -592,16 +608,22 @@ impl Event {
     ) {
         match self {
             Self::Window { window_id, event } => app.window_event(event_loop, window_id, event),
-            Self::Device { device_id, event } => {
-                app.device_event(event_loop, Some(device_id), event)
+            Self::Device { device_id, event, window_id } => {
+                app.device_event_with_window(event_loop, Some(device_id), event, window_id)
             },
-            Self::BufferedScaleFactorChanged(window, scale_factor, new_surface_size) => {
+            Self::BufferedScaleFactorChanged(
+                window,
+                scale_factor,
+                old_scale_factor,
+                new_surface_size,
+            ) => {
                 let user_new_surface_size = Arc::new(Mutex::new(new_surface_size));
                 app.window_event(
                     event_loop,
                     WindowId::from_raw(window as usize),
                     WindowEvent::ScaleFactorChanged {
                         scale_factor,
+                        old_scale_factor,
                         surface_size_writer: SurfaceSizeWriter::new(Arc::downgrade(
                             &user_new_surface_size,
                         )),
@@ -620,7 +642,13 @@ impl Event {
                     window_flags.set_size(window, surface_size);
                 }
             },
-            Self::WakeUp => app.proxy_wake_up(event_loop),
+            Self::WakeUp(keys) => {
+                app.proxy_wake_up(event_loop);
+                for key in keys {
+                    app.proxy_wake_up_coalesced(event_loop, key);
+                }
+            },
+            Self::SystemThemeChanged(theme) => app.system_theme_changed(event_loop, theme),
         }
     }
 }
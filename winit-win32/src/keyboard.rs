@@ -2,8 +2,8 @@ use std::char;
 use std::ffi::OsString;
 use std::mem::MaybeUninit;
 use std::os::windows::ffi::OsStringExt;
-use std::sync::atomic::AtomicU32;
 use std::sync::atomic::Ordering::Relaxed;
+use std::sync::atomic::{AtomicBool, AtomicU32};
 use std::sync::{Mutex, MutexGuard};
 
 use smol_str::SmolStr;
@@ -62,11 +62,17 @@ pub struct MessageAsKeyEvent {
 /// text input. The "sequence" only consists of one WM_KEYUP / WM_SYSKEYUP event.
 pub struct KeyEventBuilder {
     event_info: Mutex<Option<PartialKeyEventInfo>>,
+    /// Whether a dead key was pressed and not yet consumed by a character.
+    dead_key_pending: AtomicBool,
     pending: PendingEventQueue<MessageAsKeyEvent>,
 }
 impl Default for KeyEventBuilder {
     fn default() -> Self {
-        KeyEventBuilder { event_info: Mutex::new(None), pending: Default::default() }
+        KeyEventBuilder {
+            event_info: Mutex::new(None),
+            dead_key_pending: AtomicBool::new(false),
+            pending: Default::default(),
+        }
     }
 }
 impl KeyEventBuilder {
@@ -96,6 +102,9 @@ impl KeyEventBuilder {
                     MatchResult::MessagesToDispatch(self.pending.complete_multi(key_events))
                 },
                 WM_KILLFOCUS => {
+                    // A dead key pressed before losing focus doesn't compose with the characters
+                    // typed after regaining it.
+                    self.dead_key_pending.store(false, Relaxed);
                     // synthesize keyup events
                     let kbd_state = get_kbd_state();
                     let key_events = Self::synthesize_kbd_state(ElementState::Released, &kbd_state);
@@ -157,6 +166,8 @@ impl KeyEventBuilder {
                     // At this point, we know that there isn't going to be any more events related
                     // to this key press
                     let event_info = self.event_info.lock().unwrap().take().unwrap();
+                    // The dead key is committed with the text of the next character.
+                    self.dead_key_pending.store(true, Relaxed);
                     let ev = event_info.finalize();
                     MatchResult::MessagesToDispatch(self.pending.complete_pending(
                         pending_token,
@@ -249,6 +260,10 @@ impl KeyEventBuilder {
                             let key = layout.get_key(mod_no_ctrl, num_lock_on, vkey, physical_key);
                             event_info.text = PartialText::Text(key.to_text().map(SmolStr::new));
                         }
+                        // Whether combined or not, the pending dead key is part of this text.
+                        if self.dead_key_pending.swap(false, Relaxed) {
+                            event_info.composed_length = Some(1);
+                        }
                         let ev = event_info.finalize();
                         MatchResult::MessagesToDispatch(self.pending.complete_pending(
                             pending_token,
@@ -446,6 +461,7 @@ impl KeyEventBuilder {
             location: get_location(scancode, locale_id),
             utf16parts: Vec::with_capacity(8),
             text: PartialText::Text(text.clone()),
+            composed_length: None,
         };
 
         let mut event = event_info.finalize();
@@ -487,6 +503,9 @@ struct PartialKeyEventInfo {
     utf16parts: Vec<u16>,
 
     text: PartialText,
+
+    /// The number of code points of pending dead-key input committed by `text`.
+    composed_length: Option<usize>,
 }
 
 impl PartialKeyEventInfo {
@@ -580,6 +599,7 @@ impl PartialKeyEventInfo {
             location,
             utf16parts: Vec::with_capacity(8),
             text: PartialText::System(Vec::new()),
+            composed_length: None,
         }
     }
 
@@ -631,6 +651,7 @@ impl PartialKeyEventInfo {
             repeat: self.is_repeat,
             text_with_all_modifiers: char_with_all_modifiers,
             key_without_modifiers: self.key_without_modifiers,
+            composed_length: self.composed_length,
         }
     }
 }
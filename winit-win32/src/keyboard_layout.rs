@@ -40,7 +40,10 @@ use windows_sys::Win32::UI::Input::KeyboardAndMouse::{
     VK_SCROLL, VK_SELECT, VK_SEPARATOR, VK_SHIFT, VK_SLEEP, VK_SNAPSHOT, VK_SPACE, VK_SUBTRACT,
     VK_TAB, VK_UP, VK_VOLUME_DOWN, VK_VOLUME_MUTE, VK_VOLUME_UP, VK_XBUTTON1, VK_XBUTTON2, VK_ZOOM,
 };
-use winit_core::keyboard::{Key, KeyCode, ModifiersState, NamedKey, NativeKey, PhysicalKey};
+use winit_core::event::Modifiers;
+use winit_core::keyboard::{
+    Key, KeyCode, ModifiersKeys, ModifiersState, NamedKey, NativeKey, PhysicalKey,
+};
 
 use crate::keyboard::scancode_to_physicalkey;
 use crate::util::{loword, primarylangid};
@@ -283,6 +286,25 @@ impl LayoutCache {
         mods
     }
 
+    /// The modifiers along with the state of every modifier key.
+    pub fn get_mods(&mut self) -> Modifiers {
+        let state = self.get_agnostic_mods();
+        let mut pressed_mods = ModifiersKeys::empty();
+        for (key, vk) in [
+            (ModifiersKeys::LSHIFT, VK_LSHIFT),
+            (ModifiersKeys::RSHIFT, VK_RSHIFT),
+            (ModifiersKeys::LCONTROL, VK_LCONTROL),
+            (ModifiersKeys::RCONTROL, VK_RCONTROL),
+            (ModifiersKeys::LALT, VK_LMENU),
+            (ModifiersKeys::RALT, VK_RMENU),
+            (ModifiersKeys::LMETA, VK_LWIN),
+            (ModifiersKeys::RMETA, VK_RWIN),
+        ] {
+            pressed_mods.set(key, key_pressed(vk));
+        }
+        Modifiers::new(state, pressed_mods).with_other_mods_released()
+    }
+
     fn prepare_layout(locale_id: u64) -> Layout {
         let mut layout = Layout {
             hkl: locale_id,
@@ -4,12 +4,19 @@ use std::num::{NonZeroU16, NonZeroU32};
 use std::{io, iter, mem, ptr};
 
 use dpi::{PhysicalPosition, PhysicalSize};
-use windows_sys::Win32::Foundation::{HWND, LPARAM, POINT, RECT};
+use windows_sys::Win32::Devices::Display::{
+    DISPLAYCONFIG_DEVICE_INFO_GET_SOURCE_NAME, DISPLAYCONFIG_MODE_INFO,
+    DISPLAYCONFIG_OUTPUT_TECHNOLOGY_DISPLAYPORT_EMBEDDED, DISPLAYCONFIG_OUTPUT_TECHNOLOGY_INTERNAL,
+    DISPLAYCONFIG_OUTPUT_TECHNOLOGY_UDI_EMBEDDED, DISPLAYCONFIG_PATH_INFO,
+    DISPLAYCONFIG_SOURCE_DEVICE_NAME, DisplayConfigGetDeviceInfo, GetDisplayConfigBufferSizes,
+    QDC_ONLY_ACTIVE_PATHS, QueryDisplayConfig,
+};
+use windows_sys::Win32::Foundation::{ERROR_SUCCESS, HWND, LPARAM, POINT, RECT};
 use windows_sys::Win32::Graphics::Gdi::{
-    DEVMODEW, DM_BITSPERPEL, DM_DISPLAYFREQUENCY, DM_PELSHEIGHT, DM_PELSWIDTH,
-    ENUM_CURRENT_SETTINGS, EnumDisplayMonitors, EnumDisplaySettingsExW, GetMonitorInfoW, HDC,
-    HMONITOR, MONITOR_DEFAULTTONEAREST, MONITOR_DEFAULTTOPRIMARY, MONITORINFO, MONITORINFOEXW,
-    MonitorFromPoint, MonitorFromWindow,
+    CreateDCW, DEVMODEW, DM_BITSPERPEL, DM_DISPLAYFREQUENCY, DM_PELSHEIGHT, DM_PELSWIDTH, DeleteDC,
+    ENUM_CURRENT_SETTINGS, EnumDisplayMonitors, EnumDisplaySettingsExW, GetDeviceCaps,
+    GetMonitorInfoW, HDC, HMONITOR, HORZSIZE, MONITOR_DEFAULTTONEAREST, MONITOR_DEFAULTTOPRIMARY,
+    MONITORINFO, MONITORINFOEXW, MonitorFromPoint, MonitorFromWindow, VERTSIZE,
 };
 use windows_sys::core::BOOL;
 use winit_core::monitor::{MonitorHandleProvider, VideoMode};
@@ -205,4 +212,80 @@ impl MonitorHandleProvider for MonitorHandle {
     fn video_modes(&self) -> Box<dyn Iterator<Item = VideoMode>> {
         Box::new(self.video_mode_handles().map(|mode| mode.mode))
     }
+
+    fn physical_size_mm(&self) -> Option<(u32, u32)> {
+        let monitor_info = get_monitor_info(self.0).ok()?;
+        let device_name = monitor_info.szDevice.as_ptr();
+        unsafe {
+            let hdc = CreateDCW(ptr::null(), device_name, ptr::null(), ptr::null());
+            if hdc.is_null() {
+                return None;
+            }
+
+            let width = GetDeviceCaps(hdc, HORZSIZE as i32);
+            let height = GetDeviceCaps(hdc, VERTSIZE as i32);
+            DeleteDC(hdc);
+
+            (width > 0 && height > 0).then_some((width as u32, height as u32))
+        }
+    }
+
+    fn is_builtin(&self) -> Option<bool> {
+        let monitor_info = get_monitor_info(self.0).ok()?;
+        let device_name = &monitor_info.szDevice;
+        let device_name = &device_name[..device_name.iter().position(|&c| c == 0)?];
+
+        // Find the display path whose source is this monitor, its target describes the connector.
+        active_display_paths()?.into_iter().find_map(|path| {
+            let mut source_name = DISPLAYCONFIG_SOURCE_DEVICE_NAME::default();
+            source_name.header.r#type = DISPLAYCONFIG_DEVICE_INFO_GET_SOURCE_NAME;
+            source_name.header.size = mem::size_of_val(&source_name) as u32;
+            source_name.header.adapterId = path.sourceInfo.adapterId;
+            source_name.header.id = path.sourceInfo.id;
+            if unsafe { DisplayConfigGetDeviceInfo(&mut source_name.header) } != 0 {
+                return None;
+            }
+
+            let view_name = &source_name.viewGdiDeviceName;
+            let view_name = &view_name[..view_name.iter().position(|&c| c == 0)?];
+            (view_name == device_name).then(|| {
+                matches!(
+                    path.targetInfo.outputTechnology,
+                    DISPLAYCONFIG_OUTPUT_TECHNOLOGY_INTERNAL
+                        | DISPLAYCONFIG_OUTPUT_TECHNOLOGY_DISPLAYPORT_EMBEDDED
+                        | DISPLAYCONFIG_OUTPUT_TECHNOLOGY_UDI_EMBEDDED
+                )
+            })
+        })
+    }
+}
+
+fn active_display_paths() -> Option<Vec<DISPLAYCONFIG_PATH_INFO>> {
+    let mut path_count = 0;
+    let mut mode_count = 0;
+    let status = unsafe {
+        GetDisplayConfigBufferSizes(QDC_ONLY_ACTIVE_PATHS, &mut path_count, &mut mode_count)
+    };
+    if status != ERROR_SUCCESS {
+        return None;
+    }
+
+    let mut paths = vec![DISPLAYCONFIG_PATH_INFO::default(); path_count as usize];
+    let mut modes = vec![DISPLAYCONFIG_MODE_INFO::default(); mode_count as usize];
+    let status = unsafe {
+        QueryDisplayConfig(
+            QDC_ONLY_ACTIVE_PATHS,
+            &mut path_count,
+            paths.as_mut_ptr(),
+            &mut mode_count,
+            modes.as_mut_ptr(),
+            ptr::null_mut(),
+        )
+    };
+    if status != ERROR_SUCCESS {
+        return None;
+    }
+
+    paths.truncate(path_count as usize);
+    Some(paths)
 }
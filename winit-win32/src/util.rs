@@ -305,8 +305,10 @@ pub(crate) static GET_POINTER_TOUCH_INFO: LazyLock<Option<GetPointerTouchInfo>>
 pub(crate) static GET_POINTER_PEN_INFO: LazyLock<Option<GetPointerPenInfo>> =
     LazyLock::new(|| get_function!("user32.dll", GetPointerPenInfo));
 
+/// The id of a raw input device, which is a physical device unless the input was injected.
 pub(crate) fn wrap_device_id(id: u32) -> DeviceId {
-    DeviceId::from_raw(id as i64)
+    let device_id = DeviceId::from_raw(id as i64);
+    if id != 0 { device_id.with_virtual(false) } else { device_id }
 }
 
 #[inline(always)]
@@ -34,9 +34,10 @@ use windows_sys::Win32::UI::Input::KeyboardAndMouse::{
 };
 use windows_sys::Win32::UI::Input::Touch::{RegisterTouchWindow, TWF_WANTPALM};
 use windows_sys::Win32::UI::WindowsAndMessaging::{
-    CS_HREDRAW, CS_VREDRAW, CW_USEDEFAULT, CreateWindowExW, EnableMenuItem, FLASHW_ALL,
-    FLASHW_STOP, FLASHW_TIMERNOFG, FLASHW_TRAY, FLASHWINFO, FlashWindowEx, GWLP_HINSTANCE,
-    GetClientRect, GetCursorPos, GetForegroundWindow, GetSystemMenu, GetSystemMetrics,
+    CS_DBLCLKS, CS_HREDRAW, CS_VREDRAW, CW_USEDEFAULT, CreateWindowExW,
+    DisableProcessWindowsGhosting, EnableMenuItem, FLASHW_ALL, FLASHW_STOP, FLASHW_TIMERNOFG,
+    FLASHW_TRAY, FLASHWINFO, FlashWindowEx, GWLP_HINSTANCE, GetClientRect, GetCursorPos,
+    GetForegroundWindow, GetSystemMenu, GetSystemMetrics, GetWindowDisplayAffinity,
     GetWindowPlacement, GetWindowTextLengthW, GetWindowTextW, HTBOTTOM, HTBOTTOMLEFT,
     HTBOTTOMRIGHT, HTCAPTION, HTLEFT, HTRIGHT, HTTOP, HTTOPLEFT, HTTOPRIGHT, IsWindowVisible,
     LoadCursorW, MENU_ITEM_STATE, MF_BYCOMMAND, MFS_DISABLED, MFS_ENABLED, NID_READY, PM_NOREMOVE,
@@ -48,7 +49,8 @@ use windows_sys::Win32::UI::WindowsAndMessaging::{
     WM_SYSCOMMAND, WNDCLASSEXW,
 };
 use winit_core::cursor::Cursor;
-use winit_core::error::RequestError;
+use winit_core::error::{CreateWindowError, RequestError};
+use winit_core::event::FocusSource;
 use winit_core::icon::{Icon, RgbaIcon};
 use winit_core::monitor::{Fullscreen, MonitorHandle as CoreMonitorHandle, MonitorHandleProvider};
 use winit_core::window::{
@@ -106,7 +108,7 @@ impl Window {
     pub(crate) fn new(
         event_loop: &ActiveEventLoop,
         w_attr: WindowAttributes,
-    ) -> Result<Window, RequestError> {
+    ) -> Result<Window, CreateWindowError> {
         // We dispatch an `init` function because of code style.
         // First person to remove the need for cloning here gets a cookie!
         //
@@ -346,13 +348,14 @@ impl Window {
         self.window_state_lock().use_system_wheel_speed = should_use;
     }
 
-    fn set_icon(&self, mut new_icon: Icon, icon_type: IconType) {
+    /// Returns whether the icon was set.
+    fn set_icon(&self, mut new_icon: Icon, icon_type: IconType) -> bool {
         if let Some(icon) = new_icon.cast_ref::<RgbaIcon>() {
             let icon = match WinIcon::from_rgba(icon) {
                 Ok(icon) => icon,
                 Err(err) => {
                     warn!("{}", err);
-                    return;
+                    return false;
                 },
             };
             new_icon = Icon(Arc::new(icon));
@@ -372,6 +375,10 @@ impl Window {
                 IconType::Small => self.window_state_lock().window_icon = Some(new_icon),
                 IconType::Big => self.window_state_lock().taskbar_icon = Some(new_icon),
             }
+
+            true
+        } else {
+            false
         }
     }
 
@@ -471,6 +478,13 @@ impl CoreWindow for Window {
             )
     }
 
+    fn surface_outer_position(&self) -> Result<PhysicalPosition<i32>, RequestError> {
+        util::WindowArea::Inner
+            .get_rect(self.hwnd())
+            .map(|rect| PhysicalPosition::new(rect.left, rect.top))
+            .map_err(|err| os_error!(err).into())
+    }
+
     fn surface_position(&self) -> PhysicalPosition<i32> {
         let mut rect: RECT = unsafe { mem::zeroed() };
         if unsafe { GetClientRect(self.hwnd(), &mut rect) } == false.into() {
@@ -522,11 +536,22 @@ impl CoreWindow for Window {
     }
 
     fn request_surface_size(&self, size: Size) -> Option<PhysicalSize<u32>> {
+        let size = winit_core::window::clamp_surface_size(size);
         let scale_factor = self.scale_factor();
         let physical_size = size.to_physical::<u32>(scale_factor);
 
-        let window_flags = self.window_state_lock().window_flags;
+        let window_flags = {
+            let mut window_state = self.window_state_lock();
+            // No `WM_SIZE` is sent when the size doesn't change.
+            let expect_echo =
+                window_state.suppress_resize_echo && physical_size != window_state.surface_size;
+            window_state.resize_echo = expect_echo.then_some(physical_size);
+            window_state.window_flags
+        };
         window_flags.set_size(self.hwnd(), physical_size);
+        // `SetWindowPos` sends `WM_SIZE` before returning, so an echo that is still expected won't
+        // come, e.g. because the size was constrained.
+        self.window_state_lock().resize_echo = None;
 
         if physical_size != self.surface_size() {
             let window_state = Arc::clone(&self.window_state);
@@ -542,6 +567,20 @@ impl CoreWindow for Window {
         None
     }
 
+    fn set_close_confirmation_pending(&self, pending: bool) {
+        if pending {
+            unsafe { DisableProcessWindowsGhosting() };
+        }
+    }
+
+    fn set_suppress_resize_echo(&self, suppress: bool) {
+        let mut window_state = self.window_state_lock();
+        window_state.suppress_resize_echo = suppress;
+        if !suppress {
+            window_state.resize_echo = None;
+        }
+    }
+
     fn safe_area(&self) -> PhysicalInsets<u32> {
         PhysicalInsets::new(0, 0, 0, 0)
     }
@@ -596,7 +635,8 @@ impl CoreWindow for Window {
             WindowState::set_window_flags(window_state.lock().unwrap(), window.hwnd(), |f| {
                 f.set(WindowFlags::MINIMIZABLE, buttons.contains(WindowButtons::MINIMIZE));
                 f.set(WindowFlags::MAXIMIZABLE, buttons.contains(WindowButtons::MAXIMIZE));
-                f.set(WindowFlags::CLOSABLE, buttons.contains(WindowButtons::CLOSE))
+                f.set(WindowFlags::CLOSABLE, buttons.contains(WindowButtons::CLOSE));
+                f.set(WindowFlags::CONTEXT_HELP, buttons.contains(WindowButtons::HELP))
             });
         });
     }
@@ -607,36 +647,46 @@ impl CoreWindow for Window {
         if window_state.window_flags.contains(WindowFlags::MINIMIZABLE) {
             buttons |= WindowButtons::MINIMIZE;
         }
-        if window_state.window_flags.contains(WindowFlags::MAXIMIZABLE) {
+        if window_state.window_flags.contains(WindowFlags::MAXIMIZABLE | WindowFlags::RESIZABLE) {
             buttons |= WindowButtons::MAXIMIZE;
         }
         if window_state.window_flags.contains(WindowFlags::CLOSABLE) {
             buttons |= WindowButtons::CLOSE;
         }
+        if window_state.window_flags.contains(WindowFlags::CONTEXT_HELP) {
+            buttons |= WindowButtons::HELP;
+        }
         buttons
     }
 
     fn set_cursor(&self, cursor: Cursor) {
-        match cursor {
-            Cursor::Icon(icon) => {
-                self.window_state_lock().mouse.selected_cursor = SelectedCursor::Named(icon);
-                self.thread_executor.execute_in_thread(move || unsafe {
-                    let cursor = LoadCursorW(ptr::null_mut(), util::to_windows_cursor(icon));
-                    SetCursor(cursor);
-                });
-            },
-            Cursor::Custom(cursor) => {
-                let cursor = match cursor.cast_ref::<WinCursor>() {
-                    Some(cursor) => cursor,
-                    None => return,
-                };
-                self.window_state_lock().mouse.selected_cursor =
-                    SelectedCursor::Custom(cursor.0.clone());
-                let handle = cursor.0.clone();
-                self.thread_executor.execute_in_thread(move || unsafe {
-                    SetCursor(handle.as_raw_handle());
-                });
-            },
+        // Use the first cursor in the fallback chain that can be shown.
+        for candidate in cursor.candidates() {
+            match candidate {
+                Cursor::Icon(icon) => {
+                    let icon = *icon;
+                    self.window_state_lock().mouse.selected_cursor = SelectedCursor::Named(icon);
+                    self.thread_executor.execute_in_thread(move || unsafe {
+                        let cursor = LoadCursorW(ptr::null_mut(), util::to_windows_cursor(icon));
+                        SetCursor(cursor);
+                    });
+                },
+                Cursor::Custom(cursor) => {
+                    let cursor = match cursor.cast_ref::<WinCursor>() {
+                        Some(cursor) => cursor,
+                        None => continue,
+                    };
+                    self.window_state_lock().mouse.selected_cursor =
+                        SelectedCursor::Custom(cursor.0.clone());
+                    let handle = cursor.0.clone();
+                    self.thread_executor.execute_in_thread(move || unsafe {
+                        SetCursor(handle.as_raw_handle());
+                    });
+                },
+                Cursor::Fallback(_) => unreachable!("fallback chains are flattened"),
+            }
+
+            break;
         }
     }
 
@@ -725,6 +775,39 @@ impl CoreWindow for Window {
         Ok(())
     }
 
+    fn resize_by(&self, delta: PhysicalSize<i32>) -> Result<(), RequestError> {
+        let scale_factor = self.scale_factor();
+        let to_physical =
+            |size: Option<Size>| size.map(|size| size.to_physical::<u32>(scale_factor));
+        let (size, min_size, max_size, increments) = {
+            let window_state = self.window_state_lock();
+            (
+                window_state.surface_size,
+                to_physical(window_state.min_size),
+                to_physical(window_state.max_size),
+                to_physical(window_state.surface_resize_increments),
+            )
+        };
+
+        let width = winit_core::window::resize_dimension(
+            size.width,
+            delta.width,
+            min_size.map(|size| size.width),
+            max_size.map(|size| size.width),
+            increments.map(|size| size.width),
+        );
+        let height = winit_core::window::resize_dimension(
+            size.height,
+            delta.height,
+            min_size.map(|size| size.height),
+            max_size.map(|size| size.height),
+            increments.map(|size| size.height),
+        );
+
+        let _ = self.request_surface_size(PhysicalSize::new(width, height).into());
+        Ok(())
+    }
+
     fn show_window_menu(&self, position: Position) {
         unsafe {
             self.handle_showing_window_menu(position);
@@ -791,6 +874,10 @@ impl CoreWindow for Window {
     }
 
     fn set_fullscreen(&self, fullscreen: Option<Fullscreen>) {
+        if !winit_core::monitor::validate_fullscreen(fullscreen.as_ref()) {
+            return;
+        }
+
         let window = self.window;
         let window_state = Arc::clone(&self.window_state);
 
@@ -900,14 +987,14 @@ impl CoreWindow for Window {
             // Update window bounds
             match &fullscreen {
                 Some(fullscreen) => {
-                    // Save window bounds before entering fullscreen
+                    // Save window bounds before entering fullscreen, switching between
+                    // fullscreen modes keeps the ones from before the first.
                     let placement = unsafe {
                         let mut placement = mem::zeroed();
                         GetWindowPlacement(window.hwnd(), &mut placement);
                         placement
                     };
-
-                    window_state.lock().unwrap().saved_window = Some(SavedWindow { placement });
+                    window_state.lock().unwrap().saved_window.save(SavedWindow { placement });
 
                     let monitor = match &fullscreen {
                         Fullscreen::Exclusive(monitor, _)
@@ -937,9 +1024,8 @@ impl CoreWindow for Window {
                     }
                 },
                 None => {
-                    let mut window_state_lock = window_state.lock().unwrap();
-                    if let Some(SavedWindow { placement }) = window_state_lock.saved_window.take() {
-                        drop(window_state_lock);
+                    let saved_window = window_state.lock().unwrap().saved_window.take();
+                    if let Some(SavedWindow { placement }) = saved_window {
                         unsafe {
                             SetWindowPlacement(window.hwnd(), &placement);
                             InvalidateRgn(window.hwnd(), ptr::null_mut(), false.into());
@@ -998,12 +1084,19 @@ impl CoreWindow for Window {
 
     fn set_window_icon(&self, window_icon: Option<Icon>) {
         if let Some(window_icon) = window_icon {
-            self.set_icon(window_icon, IconType::Small);
+            if self.set_icon(window_icon.clone(), IconType::Small) {
+                self.window_state_lock().requested_window_icon = Some(window_icon);
+            }
         } else {
             self.unset_icon(IconType::Small);
+            self.window_state_lock().requested_window_icon = None;
         }
     }
 
+    fn window_icon(&self) -> Option<Icon> {
+        self.window_state_lock().requested_window_icon.clone()
+    }
+
     fn ime_capabilities(&self) -> Option<ImeCapabilities> {
         self.window_state.lock().unwrap().ime_capabilities
     }
@@ -1018,6 +1111,7 @@ impl CoreWindow for Window {
             _ => (),
         }
 
+        let request = request.to_physical(self.scale_factor());
         let window = self.window;
         let state = self.window_state.clone();
         self.thread_executor.execute_in_thread(move || unsafe {
@@ -1025,7 +1119,10 @@ impl CoreWindow for Window {
             let mut state = state.lock().unwrap();
             let (capabilities, request_data) = match &request {
                 ImeRequest::Enable(enable) => {
-                    let capabilities = *enable.capabilities();
+                    // Only the position of the candidate window is supported.
+                    let capabilities = enable
+                        .capabilities()
+                        .intersection(ImeCapabilities::new().with_cursor_area());
                     state.ime_capabilities = Some(capabilities);
                     ImeContext::set_ime_allowed(hwnd, true);
                     (capabilities, enable.request_data())
@@ -1117,7 +1214,11 @@ impl CoreWindow for Window {
         let is_foreground = self.window.hwnd() == unsafe { GetForegroundWindow() };
 
         if is_visible && !is_minimized && !is_foreground {
+            self.window_state_lock().pending_focus_source = Some(FocusSource::Programmatic);
             unsafe { force_window_active(self.window.hwnd()) };
+            // The activation messages are sent synchronously, don't attribute a later activation
+            // to this request if it failed.
+            self.window_state_lock().pending_focus_source = None;
         }
     }
 
@@ -1131,6 +1232,13 @@ impl CoreWindow for Window {
         };
     }
 
+    #[inline]
+    fn is_content_protected(&self) -> bool {
+        let mut affinity = WDA_NONE;
+        let success = unsafe { GetWindowDisplayAffinity(self.hwnd(), &mut affinity) };
+        success != false.into() && affinity != WDA_NONE
+    }
+
     #[inline]
     fn reset_dead_keys(&self) {
         // `ToUnicode` consumes the dead-key by default, so we are constructing a fake (but valid)
@@ -1313,7 +1421,11 @@ impl InitData<'_> {
 
         // Set visible before setting the size to ensure the
         // attribute is correctly applied.
-        win.set_visible(attributes.visible);
+        //
+        // Windows created in exclusive fullscreen are only shown once the video mode was set in
+        // `init`, so they don't flash in their windowed state.
+        let exclusive = matches!(attributes.fullscreen, Some(Fullscreen::Exclusive(..)));
+        win.set_visible(attributes.visible && !exclusive);
 
         win.set_enabled_buttons(attributes.enabled_buttons);
 
@@ -1357,7 +1469,7 @@ impl InitData<'_> {
 unsafe fn init(
     mut attributes: WindowAttributes,
     runner: &Rc<EventLoopRunner>,
-) -> Result<Window, RequestError> {
+) -> Result<Window, CreateWindowError> {
     let title = util::encode_wide(&attributes.title);
 
     let win_attributes = attributes
@@ -1405,13 +1517,14 @@ unsafe fn init(
             }
             Some(handle.hwnd.get() as HWND)
         },
-        Some(raw) => unreachable!("Invalid raw window handle {raw:?} on Windows"),
+        Some(_) => return Err(CreateWindowError::UnsupportedParentWindow),
         None => fallback_parent(),
     };
 
     let menu = win_attributes.menu;
     let fullscreen = attributes.fullscreen.clone();
     let maximized = attributes.maximized;
+    let visible = attributes.visible;
     let mut initdata = InitData { runner, attributes, win_attributes, window_flags, window: None };
 
     let (style, ex_style) = window_flags.to_window_styles();
@@ -1449,7 +1562,11 @@ unsafe fn init(
     // This is because if the size is changed in WM_CREATE, the restored size will be stored in that
     // size.
     if fullscreen.is_some() {
+        let exclusive = matches!(fullscreen, Some(Fullscreen::Exclusive(..)));
         win.set_fullscreen(fullscreen);
+        if exclusive && visible {
+            win.set_visible(true);
+        }
         unsafe { force_window_active(win.window.hwnd()) };
     } else if maximized {
         win.set_maximized(true);
@@ -1461,7 +1578,8 @@ unsafe fn init(
 unsafe fn register_window_class(class_name: &[u16]) {
     let class = WNDCLASSEXW {
         cbSize: mem::size_of::<WNDCLASSEXW>() as u32,
-        style: CS_HREDRAW | CS_VREDRAW,
+        // `CS_DBLCLKS` makes the system report double-clicks, see `PointerButton::click_count`.
+        style: CS_HREDRAW | CS_VREDRAW | CS_DBLCLKS,
         lpfnWndProc: Some(super::event_loop::public_window_callback),
         cbClsExtra: 0,
         cbWndExtra: 0,
@@ -12,14 +12,14 @@ use windows_sys::Win32::UI::WindowsAndMessaging::{
     SW_SHOWNOACTIVATE, SWP_ASYNCWINDOWPOS, SWP_FRAMECHANGED, SWP_NOACTIVATE, SWP_NOMOVE,
     SWP_NOREPOSITION, SWP_NOSIZE, SWP_NOZORDER, SendMessageW, SetWindowLongW, SetWindowPos,
     ShowWindow, WINDOW_EX_STYLE, WINDOW_STYLE, WINDOWPLACEMENT, WS_BORDER, WS_CAPTION, WS_CHILD,
-    WS_CLIPCHILDREN, WS_CLIPSIBLINGS, WS_EX_ACCEPTFILES, WS_EX_APPWINDOW, WS_EX_LAYERED,
-    WS_EX_NOREDIRECTIONBITMAP, WS_EX_TOPMOST, WS_EX_TRANSPARENT, WS_EX_WINDOWEDGE, WS_MAXIMIZE,
-    WS_MAXIMIZEBOX, WS_MINIMIZE, WS_MINIMIZEBOX, WS_OVERLAPPEDWINDOW, WS_POPUP, WS_SIZEBOX,
-    WS_SYSMENU, WS_VISIBLE,
+    WS_CLIPCHILDREN, WS_CLIPSIBLINGS, WS_EX_ACCEPTFILES, WS_EX_APPWINDOW, WS_EX_CONTEXTHELP,
+    WS_EX_LAYERED, WS_EX_NOREDIRECTIONBITMAP, WS_EX_TOPMOST, WS_EX_TRANSPARENT, WS_EX_WINDOWEDGE,
+    WS_MAXIMIZE, WS_MAXIMIZEBOX, WS_MINIMIZE, WS_MINIMIZEBOX, WS_OVERLAPPEDWINDOW, WS_POPUP,
+    WS_SIZEBOX, WS_SYSMENU, WS_VISIBLE,
 };
+use winit_core::event::{FocusSource, Modifiers};
 use winit_core::icon::Icon;
-use winit_core::keyboard::ModifiersState;
-use winit_core::monitor::Fullscreen;
+use winit_core::monitor::{Fullscreen, FullscreenRestore};
 use winit_core::window::{ImeCapabilities, Theme, WindowAttributes};
 
 use crate::{SelectedCursor, WindowAttributesWindows, event_loop, util};
@@ -40,11 +40,13 @@ pub(crate) struct WindowState {
 
     pub window_icon: Option<Icon>,
     pub taskbar_icon: Option<Icon>,
+    // The icon as last passed to `set_window_icon`, `window_icon` may be converted from it.
+    pub requested_window_icon: Option<Icon>,
 
-    pub saved_window: Option<SavedWindow>,
+    pub saved_window: FullscreenRestore<SavedWindow>,
     pub scale_factor: f64,
 
-    pub modifiers_state: ModifiersState,
+    pub modifiers: Modifiers,
     pub fullscreen: Option<Fullscreen>,
     pub current_theme: Theme,
     pub preferred_theme: Option<Theme>,
@@ -57,15 +59,30 @@ pub(crate) struct WindowState {
     // Used by WM_NCACTIVATE, WM_SETFOCUS and WM_KILLFOCUS
     pub is_active: bool,
     pub is_focused: bool,
+    /// How the next activation is caused, set by `WM_MOUSEACTIVATE` and `focus_window`.
+    pub pending_focus_source: Option<FocusSource>,
 
     // Flag whether redraw was requested.
     pub redraw_requested: bool,
 
     pub dragging: bool,
+    /// Whether the modal size/move loop moves the window, set by `SC_MOVE`.
+    pub moving: bool,
 
     pub skip_taskbar: bool,
 
     pub use_system_wheel_speed: bool,
+
+    /// The color painted on `WM_ERASEBKGND`, see `WindowAttributes::with_background`.
+    pub background: Option<[u8; 4]>,
+
+    pub suppress_resize_echo: bool,
+    /// The size requested by the last `request_surface_size` whose `WM_SIZE` should be dropped.
+    pub resize_echo: Option<PhysicalSize<u32>>,
+
+    /// The states last reported with `MaximizedChanged` and `MinimizedChanged`.
+    pub reported_maximized: bool,
+    pub reported_minimized: bool,
 }
 
 #[derive(Clone)]
@@ -85,6 +102,8 @@ pub struct MouseProperties {
     pub capture_count: u32,
     cursor_flags: CursorFlags,
     pub last_position: Option<PhysicalPosition<f64>>,
+    /// The click count of the last button press, reported again on its release.
+    pub click_count: u32,
 }
 
 bitflags! {
@@ -140,6 +159,8 @@ bitflags! {
 
         const CLIP_CHILDREN = 1 << 22;
 
+        const CONTEXT_HELP = 1 << 23;
+
         const EXCLUSIVE_FULLSCREEN_OR_MASK = WindowFlags::ALWAYS_ON_TOP.bits();
     }
 }
@@ -165,6 +186,7 @@ impl WindowState {
                 capture_count: 0,
                 cursor_flags: CursorFlags::empty(),
                 last_position: None,
+                click_count: 0,
             },
 
             min_size: attributes.min_surface_size,
@@ -176,11 +198,12 @@ impl WindowState {
 
             window_icon: attributes.window_icon.clone(),
             taskbar_icon: None,
+            requested_window_icon: None,
 
-            saved_window: None,
+            saved_window: FullscreenRestore::new(attributes.restore_on_unfullscreen),
             scale_factor,
 
-            modifiers_state: ModifiersState::default(),
+            modifiers: Modifiers::default(),
             fullscreen: None,
             current_theme,
             preferred_theme,
@@ -191,13 +214,27 @@ impl WindowState {
 
             is_active: false,
             is_focused: false,
+            pending_focus_source: None,
             redraw_requested: false,
 
             dragging: false,
+            moving: false,
 
             skip_taskbar: false,
 
             use_system_wheel_speed: true,
+
+            background: attributes.background.map(|[r, g, b, a]| {
+                // GDI has no alpha, DWM shows premultiplied black as transparent.
+                let a = if attributes.transparent { a } else { u8::MAX };
+                let premultiply = |c: u8| (c as u16 * a as u16 / 255) as u8;
+                [premultiply(r), premultiply(g), premultiply(b), a]
+            }),
+
+            suppress_resize_echo: false,
+            resize_echo: None,
+            reported_maximized: false,
+            reported_minimized: false,
         }
     }
 
@@ -282,7 +319,9 @@ impl WindowFlags {
         if self.contains(WindowFlags::RESIZABLE) {
             style |= WS_SIZEBOX;
         }
-        if self.contains(WindowFlags::MAXIMIZABLE) {
+        // A non-resizable window can't be maximized, `MAXIMIZABLE` is kept to restore the button
+        // when the window is made resizable again.
+        if self.contains(WindowFlags::MAXIMIZABLE | WindowFlags::RESIZABLE) {
             style |= WS_MAXIMIZEBOX;
         }
         if self.contains(WindowFlags::MINIMIZABLE) {
@@ -324,6 +363,9 @@ impl WindowFlags {
         if self.contains(WindowFlags::CLIP_CHILDREN) {
             style |= WS_CLIPCHILDREN;
         }
+        if self.contains(WindowFlags::CONTEXT_HELP) {
+            style_ex |= WS_EX_CONTEXTHELP;
+        }
 
         if self.intersects(
             WindowFlags::MARKER_EXCLUSIVE_FULLSCREEN | WindowFlags::MARKER_BORDERLESS_FULLSCREEN,
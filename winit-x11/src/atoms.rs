@@ -59,6 +59,8 @@ atom_manager! {
     _NET_WM_STATE_MAXIMIZED_HORZ,
     _NET_WM_STATE_MAXIMIZED_VERT,
     _NET_WM_WINDOW_TYPE,
+    _NET_WM_WINDOW_OPACITY,
+    _NET_WM_USER_TIME,
 
     // Activation atoms.
     _NET_STARTUP_INFO_BEGIN,
@@ -124,6 +126,7 @@ atom_manager! {
     None: b"None",
 
     // Miscellaneous Atoms
+    _GTK_FRAME_EXTENTS,
     _GTK_THEME_VARIANT,
     _MOTIF_WM_HINTS,
     _NET_ACTIVE_WINDOW,
@@ -20,13 +20,13 @@ use winit_common::xkb::Context;
 use winit_core::application::ApplicationHandler;
 use winit_core::cursor::{CustomCursor as CoreCustomCursor, CustomCursorSource};
 use winit_core::data_transfer::{DataTransfer, DataTransferId, TransferType};
-use winit_core::error::{EventLoopError, NotSupportedError, RequestError};
-use winit_core::event::{DeviceId, StartCause, WindowEvent};
+use winit_core::error::{CreateWindowError, EventLoopError, NotSupportedError, RequestError};
+use winit_core::event::{DeviceId, FingerId, StartCause, WaitCancelReason, WindowEvent};
 use winit_core::event_loop::pump_events::PumpStatus;
 use winit_core::event_loop::{
-    ActiveEventLoop as RootActiveEventLoop, AsyncRequestSerial, ControlFlow, DeviceEvents,
+    ActiveEventLoop as RootActiveEventLoop, AsyncRequestSerial, Backend, ControlFlow, DeviceEvents,
     DndAction, EventLoopProxy as CoreEventLoopProxy, EventLoopProxyProvider,
-    OwnedDisplayHandle as CoreOwnedDisplayHandle,
+    OwnedDisplayHandle as CoreOwnedDisplayHandle, PendingWakeUps, PointerConfig,
 };
 use winit_core::monitor::MonitorHandle as CoreMonitorHandle;
 use winit_core::window::{Theme, Window as CoreWindow, WindowAttributes, WindowId};
@@ -182,19 +182,29 @@ pub struct ActiveEventLoop {
     pub(crate) root: xproto::Window,
     pub(crate) ime: Option<RefCell<Ime>>,
     pub(crate) windows: RefCell<HashMap<WindowId, Weak<UnownedWindow>>>,
+    /// The fingers currently touching, for `ActiveEventLoop::is_finger_active`.
+    pub(crate) active_fingers: RefCell<HashSet<FingerId>>,
+    /// The connected devices, for `ActiveEventLoop::is_device_active`.
+    pub(crate) active_devices: RefCell<HashSet<DeviceId>>,
     pub(crate) redraw_sender: WakeSender<WindowId>,
+    pub(crate) redraw_throttle: Arc<Mutex<util::RedrawThrottle>>,
+    /// Set by `ActiveEventLoop::set_device_key_repeat`.
+    pub(crate) device_key_repeat: RefCell<Option<util::DeviceKeyRepeat>>,
     pub(crate) activation_sender: WakeSender<ActivationItem>,
+    pub(crate) title_sender: WakeSender<WindowId>,
     event_loop_proxy: CoreEventLoopProxy,
     device_events: Cell<DeviceEvents>,
 }
 
 #[derive(Debug)]
 pub struct EventLoop {
-    loop_running: bool,
+    pump_run: util::PumpRun,
     event_loop: Loop<'static, EventLoopState>,
     event_processor: EventProcessor,
     redraw_receiver: PeekableReceiver<WindowId>,
     activation_receiver: PeekableReceiver<ActivationItem>,
+    title_receiver: PeekableReceiver<WindowId>,
+    pending_wake_ups: Arc<PendingWakeUps>,
 
     /// The current state of the event loop.
     state: EventLoopState,
@@ -326,6 +336,9 @@ impl EventLoop {
         // Create a channel for sending activation tokens.
         let (activation_token_sender, activation_token_channel) = mpsc::channel();
 
+        // Create a channel for coalescing title changes.
+        let (title_sender, title_channel) = mpsc::channel();
+
         // Create a channel for sending user events.
         let (user_waker, user_waker_source) =
             calloop::ping::make_ping().expect("Failed to create user event loop waker.");
@@ -336,7 +349,8 @@ impl EventLoop {
                 state.proxy_wake_up = true;
             })
             .expect("Failed to register the event loop waker source");
-        let event_loop_proxy = EventLoopProxy::new(user_waker);
+        let pending_wake_ups = Arc::new(PendingWakeUps::new());
+        let event_loop_proxy = EventLoopProxy::new(user_waker, pending_wake_ups.clone());
 
         let xkb_context =
             Context::from_x11_xkb(xconn.xcb_connection().get_raw_xcb_connection()).unwrap();
@@ -351,6 +365,8 @@ impl EventLoop {
             control_flow: Cell::new(ControlFlow::default()),
             exit: Cell::new(None),
             windows: Default::default(),
+            active_fingers: Default::default(),
+            active_devices: Default::default(),
             ime_sender,
             xconn,
             wm_delete_window,
@@ -360,10 +376,16 @@ impl EventLoop {
                 sender: redraw_sender, // not used again so no clone
                 waker: waker.clone(),
             },
+            redraw_throttle: Default::default(),
+            device_key_repeat: Default::default(),
             activation_sender: WakeSender {
                 sender: activation_token_sender, // not used again so no clone
                 waker: waker.clone(),
             },
+            title_sender: WakeSender {
+                sender: title_sender, // not used again so no clone
+                waker: waker.clone(),
+            },
             event_loop_proxy: event_loop_proxy.into(),
             device_events: Default::default(),
         };
@@ -387,6 +409,7 @@ impl EventLoop {
             first_touch: None,
             active_window: None,
             modifiers: Default::default(),
+            modifiers_keys: Default::default(),
             is_composing: false,
         };
 
@@ -416,11 +439,13 @@ impl EventLoop {
         event_processor.init_device(ALL_DEVICES);
 
         let event_loop = EventLoop {
-            loop_running: false,
+            pump_run: util::PumpRun::default(),
             event_loop,
             event_processor,
             redraw_receiver: PeekableReceiver::from_recv(redraw_channel),
             activation_receiver: PeekableReceiver::from_recv(activation_token_channel),
+            title_receiver: PeekableReceiver::from_recv(title_channel),
+            pending_wake_ups,
             state: EventLoopState { x11_readiness: Readiness::EMPTY, proxy_wake_up: false },
         };
 
@@ -438,11 +463,8 @@ impl EventLoop {
         self.event_processor.target.clear_exit();
         let exit = loop {
             match self.pump_app_events(None, &mut app) {
-                PumpStatus::Exit(0) => {
-                    break Ok(());
-                },
                 PumpStatus::Exit(code) => {
-                    break Err(EventLoopError::ExitFailure(code));
+                    break EventLoopError::exit_result(code);
                 },
                 _ => {
                     continue;
@@ -468,9 +490,7 @@ impl EventLoop {
         timeout: Option<Duration>,
         mut app: A,
     ) -> PumpStatus {
-        if !self.loop_running {
-            self.loop_running = true;
-
+        if self.pump_run.start(&self.event_processor.target.exit) {
             // run the initial loop iteration
             self.single_iteration(&mut app, StartCause::Init);
         }
@@ -480,19 +500,40 @@ impl EventLoop {
         if !self.exiting() {
             self.poll_events_with_timeout(timeout, &mut app);
         }
-        if let Some(code) = self.exit_code() {
-            self.loop_running = false;
-
-            PumpStatus::Exit(code)
-        } else {
-            PumpStatus::Continue
-        }
+        self.pump_run.status(self.exit_code())
     }
 
     fn has_pending(&mut self) -> bool {
         self.event_processor.poll()
             || self.state.proxy_wake_up
             || self.redraw_receiver.has_incoming()
+            || self.has_due_redraws()
+            || self.has_due_key_repeat()
+    }
+
+    /// The work pending after waking up.
+    fn wake_up_sources(&mut self) -> util::WakeUpSources {
+        util::WakeUpSources {
+            os_event: self.event_processor.poll(),
+            key_repeat: self.has_due_key_repeat(),
+            proxy_wake_up: self.state.proxy_wake_up,
+            redraw: self.redraw_receiver.has_incoming() || self.has_due_redraws(),
+        }
+    }
+
+    /// Whether a redraw deferred by `Window::set_max_redraw_rate` can be dispatched.
+    fn has_due_redraws(&self) -> bool {
+        let redraw_throttle = self.event_processor.target.redraw_throttle.lock().unwrap();
+        redraw_throttle.due(Instant::now()).next().is_some()
+    }
+
+    /// Whether a raw key repeat synthesized for `set_device_key_repeat` can be dispatched.
+    fn has_due_key_repeat(&self) -> bool {
+        let device_key_repeat = self.event_processor.target.device_key_repeat.borrow();
+        device_key_repeat
+            .as_ref()
+            .and_then(|repeat| repeat.next_deadline())
+            .is_some_and(|deadline| deadline <= Instant::now())
     }
 
     fn poll_events_with_timeout<A: ApplicationHandler>(
@@ -508,15 +549,28 @@ impl EventLoop {
             // If we already have work to do then we don't want to block on the next poll.
             Some(Duration::ZERO)
         } else {
-            let control_flow_timeout = match self.control_flow() {
-                ControlFlow::Wait => None,
-                ControlFlow::Poll => Some(Duration::ZERO),
-                ControlFlow::WaitUntil(wait_deadline) => {
-                    Some(wait_deadline.saturating_duration_since(start))
-                },
+            let control_flow_timeout = self.control_flow().timeout(start);
+
+            // Wake up for the redraws deferred by `Window::set_max_redraw_rate`.
+            let redraw_timeout = {
+                let redraw_throttle = self.event_processor.target.redraw_throttle.lock().unwrap();
+                redraw_throttle
+                    .next_deadline()
+                    .map(|deadline| deadline.saturating_duration_since(start))
             };
 
-            min_timeout(control_flow_timeout, timeout)
+            // Wake up for the raw key repeats of `set_device_key_repeat`.
+            let key_repeat_timeout = self
+                .event_processor
+                .target
+                .device_key_repeat
+                .borrow()
+                .as_ref()
+                .and_then(|repeat| repeat.next_deadline())
+                .map(|deadline| deadline.saturating_duration_since(start));
+
+            let wake_up_timeout = min_timeout(redraw_timeout, key_repeat_timeout);
+            min_timeout(min_timeout(control_flow_timeout, wake_up_timeout), timeout)
         };
 
         self.state.x11_readiness = Readiness::EMPTY;
@@ -531,18 +585,39 @@ impl EventLoop {
 
         // NB: `StartCause::Init` is handled as a special case and doesn't need
         // to be considered here
-        let cause = match self.control_flow() {
-            ControlFlow::Poll => StartCause::Poll,
-            ControlFlow::Wait => StartCause::WaitCancelled { start, requested_resume: None },
+        let cause = match self.control_flow().effective(start) {
+            ControlFlow::Poll | ControlFlow::PollUntil(_) => StartCause::Poll,
+            ControlFlow::Wait => StartCause::WaitCancelled {
+                start,
+                requested_resume: None,
+                reason: WaitCancelReason::Unknown,
+            },
             ControlFlow::WaitUntil(deadline) => {
                 if Instant::now() < deadline {
-                    StartCause::WaitCancelled { start, requested_resume: Some(deadline) }
+                    StartCause::WaitCancelled {
+                        start,
+                        requested_resume: Some(deadline),
+                        reason: WaitCancelReason::Unknown,
+                    }
                 } else {
                     StartCause::ResumeTimeReached { start, requested_resume: deadline }
                 }
             },
         };
 
+        // Let the application know if it was only woken up to redraw.
+        let cause = match cause {
+            StartCause::WaitCancelled { start, requested_resume, .. } => {
+                let sources = self.wake_up_sources();
+                if sources.only_redraws() {
+                    StartCause::RedrawRequested { start, requested_resume }
+                } else {
+                    StartCause::WaitCancelled { start, requested_resume, reason: sources.reason() }
+                }
+            },
+            cause => cause,
+        };
+
         // False positive / spurious wake ups could lead to us spamming
         // redundant iterations of the event loop with no new events to
         // dispatch.
@@ -573,6 +648,9 @@ impl EventLoop {
         // Process all pending events
         self.drain_events(app);
 
+        // Synthesize the raw key repeats of `set_device_key_repeat`.
+        self.event_processor.dispatch_device_key_repeat(Instant::now(), app);
+
         // Empty activation tokens.
         while let Ok((window_id, serial)) = self.activation_receiver.try_recv() {
             let token = self
@@ -599,16 +677,24 @@ impl EventLoop {
         // Empty the user event buffer
         if mem::take(&mut self.state.proxy_wake_up) {
             app.proxy_wake_up(&self.event_processor.target);
+            for key in self.pending_wake_ups.take() {
+                app.proxy_wake_up_coalesced(&self.event_processor.target, key);
+            }
         }
 
         // Empty the redraw requests
         {
-            let mut windows = HashSet::new();
+            let now = Instant::now();
+            let mut redraw_throttle = self.event_processor.target.redraw_throttle.lock().unwrap();
+            let mut windows: HashSet<_> = redraw_throttle.due(now).collect();
 
             while let Ok(window_id) = self.redraw_receiver.try_recv() {
                 windows.insert(window_id);
             }
 
+            windows.retain(|&window_id| redraw_throttle.dispatch(window_id, now));
+            drop(redraw_throttle);
+
             for window_id in windows {
                 app.window_event(
                     &self.event_processor.target,
@@ -620,6 +706,12 @@ impl EventLoop {
 
         // This is always the last event we dispatch before poll again
         app.about_to_wait(&self.event_processor.target);
+
+        // Write the titles last, so every title set during this iteration is coalesced.
+        while let Ok(window_id) = self.title_receiver.try_recv() {
+            self.event_processor
+                .with_window(window_id.into_raw() as xproto::Window, |window| window.flush_title());
+        }
     }
 
     fn drain_events<A: ApplicationHandler>(&mut self, app: &mut A) {
@@ -685,6 +777,11 @@ impl ActiveEventLoop {
             .expect_then_ignore_error("Failed to update device event filter");
     }
 
+    /// The current device event filter.
+    pub(crate) fn device_events(&self) -> DeviceEvents {
+        self.device_events.get()
+    }
+
     pub(crate) fn clear_exit(&self) {
         self.exit.set(None)
     }
@@ -706,7 +803,8 @@ impl RootActiveEventLoop for ActiveEventLoop {
     fn create_window(
         &self,
         window_attributes: WindowAttributes,
-    ) -> Result<Box<dyn CoreWindow>, RequestError> {
+    ) -> Result<Box<dyn CoreWindow>, CreateWindowError> {
+        window_attributes.validate()?;
         Ok(Box::new(Window::new(self, window_attributes)?))
     }
 
@@ -739,6 +837,38 @@ impl RootActiveEventLoop for ActiveEventLoop {
         self.device_events.set(allowed);
     }
 
+    fn set_device_key_repeat(&self, enabled: bool) {
+        let mut device_key_repeat = self.device_key_repeat.borrow_mut();
+        if !enabled {
+            *device_key_repeat = None;
+        } else if device_key_repeat.is_none() {
+            let (delay, interval) = self.xconn.key_repeat_rate().unwrap_or_else(|| {
+                warn!("failed to query the XKB autorepeat rate, using the X server's defaults");
+                (Duration::from_millis(660), Duration::from_millis(40))
+            });
+            *device_key_repeat = Some(util::DeviceKeyRepeat::new(delay, interval));
+        }
+    }
+
+    fn system_pointer_config(&self) -> PointerConfig {
+        let Some(xsettings_screen) = self.xconn.xsettings_screen() else {
+            return PointerConfig::default();
+        };
+
+        self.xconn.xsettings_pointer_config(xsettings_screen).unwrap_or_else(|err| {
+            tracing::warn!("failed to fetch XSettings: {err}");
+            PointerConfig::default()
+        })
+    }
+
+    fn is_finger_active(&self, finger_id: FingerId) -> Option<bool> {
+        Some(self.active_fingers.borrow().contains(&finger_id))
+    }
+
+    fn is_device_active(&self, device_id: DeviceId) -> Option<bool> {
+        Some(self.active_devices.borrow().contains(&device_id))
+    }
+
     fn set_control_flow(&self, control_flow: ControlFlow) {
         self.control_flow.set(control_flow)
     }
@@ -751,6 +881,10 @@ impl RootActiveEventLoop for ActiveEventLoop {
         self.exit.set(Some(0))
     }
 
+    fn exit_with_code(&self, code: i32) {
+        self.exit.set(Some(code))
+    }
+
     fn exiting(&self) -> bool {
         self.exit.get().is_some()
     }
@@ -763,6 +897,10 @@ impl RootActiveEventLoop for ActiveEventLoop {
         self
     }
 
+    fn backend(&self) -> Option<Backend> {
+        Some(Backend::X11)
+    }
+
     fn data_transfer(&self, id: DataTransferId) -> Result<Box<dyn DataTransfer>, RequestError> {
         let dnd = self.dnd.borrow();
 
@@ -907,17 +1045,24 @@ impl Deref for DeviceInfo<'_> {
 #[derive(Clone, Debug)]
 pub struct EventLoopProxy {
     ping: Ping,
+    pending_wake_ups: Arc<PendingWakeUps>,
 }
 
 impl EventLoopProxyProvider for EventLoopProxy {
     fn wake_up(&self) {
         self.ping.ping();
     }
+
+    fn wake_up_coalesced(&self, key: u64) {
+        if self.pending_wake_ups.insert(key) {
+            self.ping.ping();
+        }
+    }
 }
 
 impl EventLoopProxy {
-    fn new(ping: Ping) -> Self {
-        Self { ping }
+    fn new(ping: Ping, pending_wake_ups: Arc<PendingWakeUps>) -> Self {
+        Self { ping, pending_wake_ups }
     }
 }
 
@@ -1095,6 +1240,21 @@ pub(crate) fn mkdid(w: xinput::DeviceId) -> DeviceId {
     DeviceId::from_raw(w as i64)
 }
 
+/// The id of an XInput2 master device, which aggregates the input of its slave devices.
+pub(crate) fn mkdid_master(w: xinput::DeviceId) -> DeviceId {
+    mkdid(w).with_virtual(true)
+}
+
+/// The id of an XInput2 slave device, which is a physical device.
+pub(crate) fn mkdid_slave(w: xinput::DeviceId) -> DeviceId {
+    mkdid(w).with_virtual(false)
+}
+
+/// The id of an XInput2 device, given its use.
+pub(crate) fn mkdid_with_use(w: xinput::DeviceId, r#use: c_int) -> DeviceId {
+    mkdid(w).with_virtual(matches!(r#use, ffi::XIMasterPointer | ffi::XIMasterKeyboard))
+}
+
 #[derive(Debug)]
 pub struct Device {
     _name: String,
@@ -1,22 +1,23 @@
 use std::cell::{Cell, RefCell};
 use std::collections::{HashMap, VecDeque};
-use std::mem::MaybeUninit;
+use std::mem::{self, MaybeUninit};
 use std::os::raw::{c_char, c_int, c_long, c_ulong};
 use std::slice;
 use std::sync::{Arc, Mutex};
+use std::time::Instant;
 
 use dpi::{PhysicalPosition, PhysicalSize};
 use tracing::warn;
-use winit_common::xkb::{self, Context, XkbState};
+use winit_common::xkb::{self, Context, ModifiersKeysState, XkbState};
 use winit_core::application::ApplicationHandler;
 use winit_core::event::{
-    ButtonSource, DeviceEvent, DeviceId, ElementState, FingerId, Ime, MouseButton,
-    MouseScrollDelta, PointerKind, PointerSource, RawKeyEvent, SurfaceSizeWriter, TouchPhase,
-    WindowEvent,
+    ButtonSource, DeviceEvent, DeviceId, ElementState, FingerId, FocusSource, Ime, Modifiers,
+    MouseButton, MouseScrollDelta, PointerKind, PointerSource, RawKeyEvent, SurfaceSizeWriter,
+    TouchPhase, WindowEvent,
 };
-use winit_core::event_loop::DndAction;
+use winit_core::event_loop::{DeviceEvents, DndAction};
 use winit_core::keyboard::ModifiersState;
-use winit_core::window::WindowId;
+use winit_core::window::{CursorGrabMode, WindowId};
 use x11_dl::xinput2::{
     self, XIDeviceEvent, XIEnterEvent, XIFocusInEvent, XIFocusOutEvent, XIHierarchyEvent,
     XILeaveEvent, XIModifierState, XIRawEvent,
@@ -24,7 +25,7 @@ use x11_dl::xinput2::{
 use x11_dl::xlib::{
     self, Display as XDisplay, Window as XWindow, XAnyEvent, XClientMessageEvent, XConfigureEvent,
     XDestroyWindowEvent, XEvent, XExposeEvent, XKeyEvent, XMapEvent, XPropertyEvent,
-    XReparentEvent, XSelectionEvent, XVisibilityEvent, XkbAnyEvent, XkbStateRec,
+    XReparentEvent, XSelectionEvent, XUnmapEvent, XVisibilityEvent, XkbAnyEvent, XkbStateRec,
 };
 use x11rb::protocol::sync::{ConnectionExt, Int64};
 use x11rb::protocol::xinput;
@@ -37,7 +38,7 @@ use crate::atoms::*;
 use crate::dnd::{DndState, SelectionType};
 use crate::event_loop::{
     ALL_DEVICES, ActiveEventLoop, CookieResultExt, Device, DeviceInfo, DeviceType,
-    ScrollOrientation, mkdid, mkwid,
+    ScrollOrientation, mkdid, mkdid_master, mkdid_slave, mkdid_with_use, mkwid,
 };
 use crate::ime::{ImeEvent, ImeEventReceiver, ImeReceiver, ImeRequest};
 use crate::util;
@@ -71,7 +72,9 @@ pub struct EventProcessor {
     // Currently focused window belonging to this process
     pub active_window: Option<xproto::Window>,
     /// Latest modifiers we've sent for the user to trigger change in event.
-    pub modifiers: Cell<ModifiersState>,
+    pub modifiers: Cell<Modifiers>,
+    /// The modifier keys held down in the active window.
+    pub modifiers_keys: ModifiersKeysState,
     // Track modifiers based on keycodes. NOTE: that serials generally don't work for tracking
     // since they are not unique and could be duplicated in case of sequence of key events is
     // delivered at near the same time.
@@ -93,8 +96,8 @@ impl EventProcessor {
 
             let ime = ime.get_mut();
             match request {
-                ImeRequest::Area(window_id, x, y, w, h) => {
-                    ime.send_xim_area(window_id, x, y, w, h);
+                ImeRequest::Area(window_id, x, y, w, h, rtl) => {
+                    ime.send_xim_area(window_id, x, y, w, h, rtl);
                 },
                 ImeRequest::Allow(window_id, allowed) => {
                     ime.set_ime_allowed(window_id, allowed);
@@ -106,13 +109,19 @@ impl EventProcessor {
         while let Ok((window, event)) = self.ime_event_receiver.try_recv() {
             let window_id = mkwid(window as xproto::Window);
             let event = match event {
-                ImeEvent::Enabled => WindowEvent::Ime(Ime::Enabled),
+                ImeEvent::Enabled => {
+                    let capabilities = self
+                        .with_window(window as xproto::Window, |window| window.ime_capabilities())
+                        .flatten()
+                        .unwrap_or_default();
+                    WindowEvent::Ime(Ime::Enabled(capabilities))
+                },
                 ImeEvent::Start => {
                     self.is_composing = true;
                     WindowEvent::Ime(Ime::Preedit("".to_owned(), None))
                 },
                 ImeEvent::Update(text, position) if self.is_composing => {
-                    WindowEvent::Ime(Ime::Preedit(text, Some((position, position))))
+                    WindowEvent::Ime(Ime::preedit(text, Some((position, position))))
                 },
                 ImeEvent::End => {
                     self.is_composing = false;
@@ -187,6 +196,7 @@ impl EventProcessor {
             xlib::ConfigureNotify => self.configure_notify(xev.as_ref(), app),
             xlib::ReparentNotify => self.reparent_notify(xev.as_ref()),
             xlib::MapNotify => self.map_notify(xev.as_ref(), app),
+            xlib::UnmapNotify => self.unmap_notify(xev.as_ref(), app),
             xlib::DestroyNotify => self.destroy_notify(xev.as_ref(), app),
             xlib::PropertyNotify => self.property_notify(xev.as_ref(), app),
             xlib::VisibilityNotify => self.visibility_notify(xev.as_ref(), app),
@@ -275,7 +285,7 @@ impl EventProcessor {
 
                     xinput2::XI_HierarchyChanged => {
                         let xev: &XIHierarchyEvent = unsafe { xev.as_event() };
-                        self.xinput2_hierarchy_changed(xev);
+                        self.xinput2_hierarchy_changed(xev, app);
                     },
                     _ => {},
                 }
@@ -331,8 +341,11 @@ impl EventProcessor {
         if let Some(info) = DeviceInfo::get(&self.target.xconn, device as _) {
             let atoms = self.target.x_connection().atoms();
 
+            let mut active_devices = self.target.active_devices.borrow_mut();
             for info in info.iter() {
-                devices.insert(mkdid(info.deviceid as xinput::DeviceId), Device::new(info, atoms));
+                let device_id = mkdid_with_use(info.deviceid as xinput::DeviceId, info._use);
+                devices.insert(device_id, Device::new(info, atoms));
+                active_devices.insert(device_id);
             }
         }
     }
@@ -807,6 +820,7 @@ impl EventProcessor {
                 let surface_size = Arc::new(Mutex::new(new_surface_size));
                 app.window_event(&self.target, window_id, WindowEvent::ScaleFactorChanged {
                     scale_factor: new_scale_factor,
+                    old_scale_factor: last_scale_factor,
                     surface_size_writer: SurfaceSizeWriter::new(Arc::downgrade(&surface_size)),
                 });
 
@@ -857,7 +871,38 @@ impl EventProcessor {
             let _ = window.set_cursor_hittest(true);
         }
 
-        if resized {
+        // Deliver the initial scale factor with the first configure if the application asked for
+        // it.
+        if resized && mem::take(&mut window.shared_state_lock().initial_scale_event) {
+            let old_surface_size = PhysicalSize::from(new_surface_size);
+            let surface_size = Arc::new(Mutex::new(old_surface_size));
+            let scale_factor = window.scale_factor();
+            app.window_event(&self.target, window_id, WindowEvent::ScaleFactorChanged {
+                scale_factor,
+                old_scale_factor: scale_factor,
+                surface_size_writer: SurfaceSizeWriter::new(Arc::downgrade(&surface_size)),
+            });
+
+            let requested_size = *surface_size.lock().unwrap();
+            drop(surface_size);
+
+            if requested_size != old_surface_size {
+                window.request_surface_size_physical(requested_size.width, requested_size.height);
+            }
+        }
+
+        // The grab may have been released while the window manager changed the fullscreen state.
+        if resized && mem::take(&mut window.shared_state_lock().regrab_pending) {
+            if let Some(mode) = window.reapply_cursor_grab() {
+                app.window_event(&self.target, window_id, WindowEvent::CursorGrabChanged(mode));
+            }
+        }
+
+        // Drop the echo of the last `request_surface_size` if the application asked for it.
+        let is_echo =
+            resized && window.shared_state_lock().resize_echo.take() == Some(new_surface_size);
+
+        if resized && !is_echo {
             let event = WindowEvent::SurfaceResized(new_surface_size.into());
             app.window_event(&self.target, window_id, event);
         }
@@ -885,8 +930,31 @@ impl EventProcessor {
         // The purpose of it is to deliver initial focused state of the newly created
         // window, given that we can't rely on `CreateNotify`, due to it being not
         // sent.
-        let focus = self.with_window(window, |window| window.has_focus()).unwrap_or_default();
-        app.window_event(&self.target, window_id, WindowEvent::Focused(focus));
+        let (focus, grab) = self
+            .with_window(window, |window| {
+                // The grab was released when the window was unmapped.
+                (window.has_focus(), window.reapply_cursor_grab())
+            })
+            .unwrap_or_default();
+        app.window_event(&self.target, window_id, WindowEvent::Focused {
+            focused: focus,
+            via: FocusSource::Unknown,
+        });
+
+        if let Some(mode) = grab {
+            app.window_event(&self.target, window_id, WindowEvent::CursorGrabChanged(mode));
+        }
+    }
+
+    fn unmap_notify(&self, xev: &XUnmapEvent, app: &mut dyn ApplicationHandler) {
+        let window = xev.window as xproto::Window;
+        let window_id = mkwid(window);
+
+        // The server releases the grab of windows that become unviewable.
+        if self.with_window(window, |window| window.cursor_grab_released()).unwrap_or_default() {
+            let event = WindowEvent::CursorGrabChanged(CursorGrabMode::None);
+            app.window_event(&self.target, window_id, event);
+        }
     }
 
     fn destroy_notify(&self, xev: &XDestroyWindowEvent, app: &mut dyn ApplicationHandler) {
@@ -896,6 +964,7 @@ impl EventProcessor {
         // In the event that the window's been destroyed without being dropped first, we
         // cleanup again here.
         self.target.windows.borrow_mut().remove(&WindowId::from_raw(window as _));
+        self.target.redraw_throttle.lock().unwrap().remove(window_id);
 
         // Since all XIM stuff needs to happen from the same thread, we destroy the input
         // context here instead of when dropping the window.
@@ -916,6 +985,21 @@ impl EventProcessor {
             || atom == atoms[_XSETTINGS_SETTINGS]
         {
             self.process_dpi_change(app);
+        } else if atom == atoms[_NET_WM_STATE] {
+            let xwindow = xev.window as xproto::Window;
+            let Some((maximized, minimized)) =
+                self.with_window(xwindow, |window| window.wm_state_changes())
+            else {
+                return;
+            };
+
+            let window_id = mkwid(xwindow);
+            if let Some(maximized) = maximized {
+                app.window_event(&self.target, window_id, WindowEvent::MaximizedChanged(maximized));
+            }
+            if let Some(minimized) = minimized {
+                app.window_event(&self.target, window_id, WindowEvent::MinimizedChanged(minimized));
+            }
         }
     }
 
@@ -1004,6 +1088,8 @@ impl EventProcessor {
             false
         };
 
+        self.modifiers_keys.update(keycode, state);
+
         // Always update the modifiers when we're not replaying.
         if !replay {
             self.update_mods_from_core_event(window_id, xev.state as u16, app);
@@ -1024,7 +1110,7 @@ impl EventProcessor {
 
             // Restore the client's modifiers state after replay.
             if replay {
-                self.send_modifiers(window_id, self.modifiers.get(), true, app);
+                self.send_modifiers(window_id, self.modifiers.get().state(), true, app);
             }
 
             return;
@@ -1038,7 +1124,7 @@ impl EventProcessor {
                 let event = WindowEvent::Ime(Ime::Preedit(String::new(), None));
                 app.window_event(&self.target, window_id, event);
 
-                let event = WindowEvent::Ime(Ime::Commit(written));
+                let event = WindowEvent::Ime(Ime::Commit(written, None));
                 self.is_composing = false;
                 app.window_event(&self.target, window_id, event);
             }
@@ -1080,7 +1166,7 @@ impl EventProcessor {
         app: &mut dyn ApplicationHandler,
     ) {
         let window_id = mkwid(event.event as xproto::Window);
-        let device_id = Some(mkdid(event.deviceid as xinput::DeviceId));
+        let device_id = Some(mkdid_master(event.deviceid as xinput::DeviceId));
 
         // Set the timestamp.
         self.target.xconn.set_timestamp(event.time as xproto::Timestamp);
@@ -1099,6 +1185,12 @@ impl EventProcessor {
             return;
         }
 
+        let window = event.event as xproto::Window;
+        let released = state == ElementState::Released;
+        if self.with_window(window, |window| window.drag_move_ended(released)) == Some(true) {
+            app.window_event(&self.target, window_id, WindowEvent::MoveEnded);
+        }
+
         let position = PhysicalPosition::new(event.event_x, event.event_y);
 
         let event = match event.detail as u32 {
@@ -1108,6 +1200,7 @@ impl EventProcessor {
                 state,
                 position,
                 button: MouseButton::Left.into(),
+                click_count: None,
             },
             xlib::Button2 => WindowEvent::PointerButton {
                 device_id,
@@ -1115,6 +1208,7 @@ impl EventProcessor {
                 state,
                 position,
                 button: MouseButton::Middle.into(),
+                click_count: None,
             },
             xlib::Button3 => WindowEvent::PointerButton {
                 device_id,
@@ -1122,23 +1216,19 @@ impl EventProcessor {
                 state,
                 position,
                 button: MouseButton::Right.into(),
+                click_count: None,
             },
 
             // Suppress emulated scroll wheel clicks, since we handle the real motion events for
             // those. In practice, even clicky scroll wheels appear to be reported by
             // evdev (and XInput2 in turn) as axis motion, so we don't otherwise
             // special-case these button presses.
-            4..=7 => match state {
+            x @ 4..=7 => match state {
                 ElementState::Pressed => WindowEvent::MouseWheel {
                     device_id,
-                    delta: match event.detail {
-                        4 => MouseScrollDelta::LineDelta(0.0, 1.0),
-                        5 => MouseScrollDelta::LineDelta(0.0, -1.0),
-                        6 => MouseScrollDelta::LineDelta(1.0, 0.0),
-                        7 => MouseScrollDelta::LineDelta(-1.0, 0.0),
-                        _ => unreachable!(),
-                    },
+                    delta: scroll_button_delta(x).unwrap(),
                     phase: TouchPhase::Moved,
+                    momentum: false,
                 },
                 ElementState::Released => return,
             },
@@ -1151,6 +1241,7 @@ impl EventProcessor {
                 // Button 8 maps to MouseButton::BACK = 3; 36 maps to MouseButton::Button32.
                 // 255 is the largest code yielded on X11 (tested).
                 button: MouseButton::try_from_u8((x - 5) as u8).unwrap().into(),
+                click_count: None,
             },
             x @ 37..=0xff => WindowEvent::PointerButton {
                 device_id,
@@ -1159,6 +1250,7 @@ impl EventProcessor {
                 position,
                 // 255 is the largest code yielded on X11 (tested).
                 button: ButtonSource::Unknown(x as u16),
+                click_count: None,
             },
             _ => return,
         };
@@ -1179,11 +1271,16 @@ impl EventProcessor {
             return;
         };
 
-        let device_id = Some(mkdid(event.deviceid as xinput::DeviceId));
+        let device_id = Some(mkdid_master(event.deviceid as xinput::DeviceId));
         let window = event.event as xproto::Window;
         let window_id = mkwid(window);
         let new_cursor_pos = (event.event_x, event.event_y);
 
+        // The window manager releases its grab once it's done moving the window.
+        if self.with_window(window, |window| window.drag_move_ended(false)) == Some(true) {
+            app.window_event(&self.target, window_id, WindowEvent::MoveEnded);
+        }
+
         let cursor_moved = self.with_window(window, |window| {
             let mut shared_state_lock = window.shared_state_lock();
             util::maybe_change(&mut shared_state_lock.cursor_pos, new_cursor_pos)
@@ -1235,7 +1332,12 @@ impl EventProcessor {
                     ScrollOrientation::Vertical => MouseScrollDelta::LineDelta(0.0, -delta as f32),
                 };
 
-                let event = WindowEvent::MouseWheel { device_id, delta, phase: TouchPhase::Moved };
+                let event = WindowEvent::MouseWheel {
+                    device_id,
+                    delta,
+                    phase: TouchPhase::Moved,
+                    momentum: false,
+                };
                 events.push(event);
             }
 
@@ -1253,7 +1355,7 @@ impl EventProcessor {
 
         let window = event.event as xproto::Window;
         let window_id = mkwid(window);
-        let device_id = mkdid(event.deviceid as xinput::DeviceId);
+        let device_id = mkdid_master(event.deviceid as xinput::DeviceId);
 
         if let Some(all_info) = DeviceInfo::get(&self.target.xconn, ALL_DEVICES.into()) {
             let mut devices = self.devices.borrow_mut();
@@ -1283,11 +1385,16 @@ impl EventProcessor {
                 kind: PointerKind::Mouse,
             };
             app.window_event(&self.target, window_id, event);
+
+            if self.with_window(window, |window| window.drag_move_ended(false)) == Some(true) {
+                app.window_event(&self.target, window_id, WindowEvent::MoveEnded);
+            }
         }
     }
 
     fn xinput2_mouse_left(&self, event: &XILeaveEvent, app: &mut dyn ApplicationHandler) {
         let window = event.event as xproto::Window;
+        let event_mode = event.mode;
 
         // Set the timestamp.
         self.target.xconn.set_timestamp(event.time as xproto::Timestamp);
@@ -1297,12 +1404,19 @@ impl EventProcessor {
         if self.window_exists(window) {
             let window_id = mkwid(window);
             let event = WindowEvent::PointerLeft {
-                device_id: Some(mkdid(event.deviceid as xinput::DeviceId)),
+                device_id: Some(mkdid_master(event.deviceid as xinput::DeviceId)),
                 primary: true,
                 position: Some(PhysicalPosition::new(event.event_x, event.event_y)),
                 kind: PointerKind::Mouse,
             };
             app.window_event(&self.target, window_id, event);
+
+            // The window manager grabs the pointer to move the window.
+            if event_mode == xinput2::XINotifyGrab
+                && self.with_window(window, |window| window.drag_move_started()) == Some(true)
+            {
+                app.window_event(&self.target, window_id, WindowEvent::MoveStarted);
+            }
         }
     }
 
@@ -1327,11 +1441,19 @@ impl EventProcessor {
         let window_id = mkwid(window);
         let position = PhysicalPosition::new(xev.event_x, xev.event_y);
 
-        if let Some(window) = self.with_window(window, Arc::clone) {
+        let grab = self.with_window(window, |window| {
             window.shared_state_lock().has_focus = true;
-        }
+            window.reapply_cursor_grab()
+        });
+
+        app.window_event(&self.target, window_id, WindowEvent::Focused {
+            focused: true,
+            via: FocusSource::Unknown,
+        });
 
-        app.window_event(&self.target, window_id, WindowEvent::Focused(true));
+        if let Some(Some(mode)) = grab {
+            app.window_event(&self.target, window_id, WindowEvent::CursorGrabChanged(mode));
+        }
 
         // Issue key press events for all pressed keys
         Self::handle_pressed_keys(
@@ -1339,6 +1461,7 @@ impl EventProcessor {
             window_id,
             ElementState::Pressed,
             &mut self.xkb_context,
+            &mut self.modifiers_keys,
             app,
         );
 
@@ -1350,7 +1473,7 @@ impl EventProcessor {
             .devices
             .borrow()
             .get(&mkdid(xev.deviceid as xinput::DeviceId))
-            .map(|device| mkdid(device.attachment as xinput::DeviceId));
+            .map(|device| mkdid_master(device.attachment as xinput::DeviceId));
 
         let event = WindowEvent::PointerMoved {
             device_id,
@@ -1381,6 +1504,7 @@ impl EventProcessor {
             self.target.update_listen_device_events(false);
 
             // Clear the modifiers when unfocusing the window.
+            self.modifiers_keys.clear();
             if let Some(xkb_state) = self.xkb_context.state_mut() {
                 xkb_state.update_modifiers(0, 0, 0, 0, 0, 0);
                 let mods = xkb_state.modifiers();
@@ -1393,6 +1517,7 @@ impl EventProcessor {
                 window_id,
                 ElementState::Released,
                 &mut self.xkb_context,
+                &mut self.modifiers_keys,
                 app,
             );
 
@@ -1404,7 +1529,10 @@ impl EventProcessor {
                 window.shared_state_lock().has_focus = false;
             }
 
-            app.window_event(&self.target, window_id, WindowEvent::Focused(false));
+            app.window_event(&self.target, window_id, WindowEvent::Focused {
+                focused: false,
+                via: FocusSource::Unknown,
+            });
         }
     }
 
@@ -1432,11 +1560,12 @@ impl EventProcessor {
                 app.window_event(&self.target, window_id, event);
             }
 
-            let device_id = Some(mkdid(xev.deviceid as xinput::DeviceId));
+            let device_id = Some(mkdid_master(xev.deviceid as xinput::DeviceId));
             let finger_id = FingerId::from_raw(id as usize);
 
             match phase {
                 xinput2::XI_TouchBegin => {
+                    self.target.active_fingers.borrow_mut().insert(finger_id);
                     let event = WindowEvent::PointerEntered {
                         device_id,
                         primary: is_first_touch,
@@ -1450,6 +1579,7 @@ impl EventProcessor {
                         state: ElementState::Pressed,
                         position,
                         button: ButtonSource::Touch { finger_id, force: None },
+                        click_count: None,
                     };
                     app.window_event(&self.target, window_id, event);
                 },
@@ -1469,6 +1599,7 @@ impl EventProcessor {
                         state: ElementState::Released,
                         position,
                         button: ButtonSource::Touch { finger_id, force: None },
+                        click_count: None,
                     };
                     app.window_event(&self.target, window_id, event);
                     let event = WindowEvent::PointerLeft {
@@ -1478,12 +1609,27 @@ impl EventProcessor {
                         kind: PointerKind::Touch(finger_id),
                     };
                     app.window_event(&self.target, window_id, event);
+                    self.target.active_fingers.borrow_mut().remove(&finger_id);
                 },
                 _ => unreachable!(),
             }
         }
     }
 
+    /// Whether the focused window opted out of device events with
+    /// `Window::set_receive_device_events`.
+    fn device_events_suppressed(&self) -> bool {
+        self.target.device_events() != DeviceEvents::Always
+            && self.active_window.is_some_and(|window| {
+                self.with_window(window, |window| !window.receives_device_events()).unwrap_or(false)
+            })
+    }
+
+    /// The focused window, reported as a hint with raw device events.
+    fn focused_window_id(&self) -> Option<WindowId> {
+        self.active_window.map(mkwid)
+    }
+
     fn xinput2_raw_button_input(
         &self,
         xev: &XIRawEvent,
@@ -1493,17 +1639,34 @@ impl EventProcessor {
         // Set the timestamp.
         self.target.xconn.set_timestamp(xev.time as xproto::Timestamp);
 
-        if xev.flags & xinput2::XIPointerEmulated == 0 {
-            let event = DeviceEvent::Button { state, button: xev.detail as u32 };
-            app.device_event(&self.target, Some(mkdid(xev.deviceid as xinput::DeviceId)), event);
+        if self.device_events_suppressed() {
+            return;
+        }
+
+        if xev.flags & xinput2::XIPointerEmulated != 0 {
+            return;
         }
+
+        // Buttons 4 to 7 are wheel clicks from devices without scroll valuators, e.g. tilt
+        // wheels, which would otherwise never show up as scrolling.
+        let event = match scroll_button_delta(xev.detail as u32) {
+            Some(delta) if state == ElementState::Pressed => DeviceEvent::MouseWheel { delta },
+            Some(_) => return,
+            None => DeviceEvent::Button { state, button: xev.detail as u32 },
+        };
+        let device_id = Some(mkdid_master(xev.deviceid as xinput::DeviceId));
+        app.device_event_with_window(&self.target, device_id, event, self.focused_window_id());
     }
 
     fn xinput2_raw_mouse_motion(&self, xev: &XIRawEvent, app: &mut dyn ApplicationHandler) {
         // Set the timestamp.
         self.target.xconn.set_timestamp(xev.time as xproto::Timestamp);
 
-        let did = Some(mkdid(xev.deviceid as xinput::DeviceId));
+        if self.device_events_suppressed() {
+            return;
+        }
+
+        let did = Some(mkdid_master(xev.deviceid as xinput::DeviceId));
         let mask =
             unsafe { slice::from_raw_parts(xev.valuators.mask, xev.valuators.mask_len as usize) };
         let mut value = xev.raw_values;
@@ -1538,14 +1701,15 @@ impl EventProcessor {
         };
 
         if let Some(mouse_delta) = mouse_delta.consume() {
-            app.device_event(&self.target, did, DeviceEvent::PointerMotion { delta: mouse_delta });
+            let event = DeviceEvent::PointerMotion { delta: mouse_delta };
+            app.device_event_with_window(&self.target, did, event, self.focused_window_id());
         }
 
         if let Some(scroll_delta) = scroll_delta.consume() {
             let event = DeviceEvent::MouseWheel {
                 delta: MouseScrollDelta::LineDelta(scroll_delta.0, scroll_delta.1),
             };
-            app.device_event(&self.target, did, event);
+            app.device_event_with_window(&self.target, did, event, self.focused_window_id());
         }
     }
 
@@ -1558,27 +1722,83 @@ impl EventProcessor {
         // Set the timestamp.
         self.target.xconn.set_timestamp(xev.time as xproto::Timestamp);
 
-        let device_id = Some(mkdid(xev.sourceid as xinput::DeviceId));
+        let device_id = Some(mkdid_slave(xev.sourceid as xinput::DeviceId));
         let keycode = xev.detail as u32;
         if keycode < KEYCODE_OFFSET as u32 {
             return;
         }
+
+        // Track the held key even while device events are suppressed, so a key released in the
+        // meantime doesn't keep repeating.
+        if let Some(device_key_repeat) = self.target.device_key_repeat.borrow_mut().as_mut() {
+            match state {
+                ElementState::Pressed => {
+                    let key_repeats = self
+                        .xkb_context
+                        .keymap_mut()
+                        .is_some_and(|keymap| keymap.key_repeats(keycode));
+                    if key_repeats {
+                        device_key_repeat.press(device_id, keycode, Instant::now());
+                    }
+                },
+                ElementState::Released => device_key_repeat.release(keycode),
+            }
+        }
+
+        if self.device_events_suppressed() {
+            return;
+        }
+
         let physical_key = xkb::raw_keycode_to_physicalkey(keycode);
 
         let event = DeviceEvent::Key(RawKeyEvent { physical_key, state });
-        app.device_event(&self.target, device_id, event);
+        app.device_event_with_window(&self.target, device_id, event, self.focused_window_id());
+    }
+
+    /// Dispatch the raw key repeat of `ActiveEventLoop::set_device_key_repeat` if it's due.
+    pub(crate) fn dispatch_device_key_repeat(
+        &mut self,
+        now: Instant,
+        app: &mut dyn ApplicationHandler,
+    ) {
+        let Some((device_id, keycode)) =
+            self.target.device_key_repeat.borrow_mut().as_mut().and_then(|repeat| repeat.due(now))
+        else {
+            return;
+        };
+
+        if self.device_events_suppressed() {
+            return;
+        }
+
+        let physical_key = xkb::raw_keycode_to_physicalkey(keycode);
+        let event = DeviceEvent::Key(RawKeyEvent { physical_key, state: ElementState::Pressed });
+        app.device_event_with_window(&self.target, device_id, event, self.focused_window_id());
     }
 
-    fn xinput2_hierarchy_changed(&mut self, xev: &XIHierarchyEvent) {
+    fn xinput2_hierarchy_changed(
+        &mut self,
+        xev: &XIHierarchyEvent,
+        app: &mut dyn ApplicationHandler,
+    ) {
         // Set the timestamp.
         self.target.xconn.set_timestamp(xev.time as xproto::Timestamp);
         let infos = unsafe { slice::from_raw_parts(xev.info, xev.num_info as usize) };
         for info in infos {
+            let device_id = mkdid_with_use(info.deviceid as xinput::DeviceId, info._use);
             if 0 != info.flags & (xinput2::XISlaveAdded | xinput2::XIMasterAdded) {
                 self.init_device(info.deviceid as xinput::DeviceId);
+                app.device_event_with_window(
+                    &self.target,
+                    Some(device_id),
+                    DeviceEvent::Added,
+                    None,
+                );
             } else if 0 != info.flags & (xinput2::XISlaveRemoved | xinput2::XIMasterRemoved) {
-                let mut devices = self.devices.borrow_mut();
-                devices.remove(&mkdid(info.deviceid as xinput::DeviceId));
+                self.devices.borrow_mut().remove(&device_id);
+                self.target.active_devices.borrow_mut().remove(&device_id);
+                let event = DeviceEvent::Removed;
+                app.device_event_with_window(&self.target, Some(device_id), event, None);
             }
         }
     }
@@ -1806,8 +2026,9 @@ impl EventProcessor {
     ) {
         // NOTE: Always update the modifiers to account for case when they've changed
         // and forced was `true`.
+        let modifiers = self.modifiers_keys.modifiers(modifiers);
         if self.modifiers.replace(modifiers) != modifiers || force {
-            let event = WindowEvent::ModifiersChanged(self.modifiers.get().into());
+            let event = WindowEvent::ModifiersChanged(modifiers);
             app.window_event(&self.target, window_id, event);
         }
     }
@@ -1817,6 +2038,7 @@ impl EventProcessor {
         window_id: winit_core::window::WindowId,
         state: ElementState,
         xkb_context: &mut Context,
+        modifiers_keys: &mut ModifiersKeysState,
         app: &mut dyn ApplicationHandler,
     ) {
         // Update modifiers state and emit key events based on which keys are currently pressed.
@@ -1838,6 +2060,7 @@ impl EventProcessor {
         };
 
         for keycode in target.xconn.query_keymap().into_iter().filter(|k| *k >= KEYCODE_OFFSET) {
+            modifiers_keys.update(keycode as u32, state);
             let event = key_processor.process_key_event(keycode as u32, state, false);
             let event = WindowEvent::KeyboardInput { device_id: None, event, is_synthetic: true };
             app.window_event(target, window_id, event);
@@ -1883,6 +2106,19 @@ impl EventProcessor {
     }
 }
 
+/// The scroll delta of a press of the core wheel buttons 4 to 7.
+///
+/// Buttons 4 and 5 scroll up and down, buttons 6 and 7 scroll left and right.
+fn scroll_button_delta(button: u32) -> Option<MouseScrollDelta> {
+    match button {
+        4 => Some(MouseScrollDelta::LineDelta(0.0, 1.0)),
+        5 => Some(MouseScrollDelta::LineDelta(0.0, -1.0)),
+        6 => Some(MouseScrollDelta::LineDelta(1.0, 0.0)),
+        7 => Some(MouseScrollDelta::LineDelta(-1.0, 0.0)),
+        _ => None,
+    }
+}
+
 fn is_first_touch(first: &mut Option<u32>, num: &mut u32, id: u32, phase: i32) -> bool {
     match phase {
         xinput2::XI_TouchBegin => {
@@ -1902,3 +2138,20 @@ fn is_first_touch(first: &mut Option<u32>, num: &mut u32, id: u32, phase: i32) -
 
     *first == Some(id)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scroll_buttons_map_to_line_deltas() {
+        assert_eq!(scroll_button_delta(4), Some(MouseScrollDelta::LineDelta(0.0, 1.0)));
+        assert_eq!(scroll_button_delta(5), Some(MouseScrollDelta::LineDelta(0.0, -1.0)));
+        assert_eq!(scroll_button_delta(6), Some(MouseScrollDelta::LineDelta(1.0, 0.0)));
+        assert_eq!(scroll_button_delta(7), Some(MouseScrollDelta::LineDelta(-1.0, 0.0)));
+
+        for button in [1, 2, 3, 8, 9] {
+            assert_eq!(scroll_button_delta(button), None);
+        }
+    }
+}
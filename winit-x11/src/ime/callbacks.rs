@@ -117,7 +117,8 @@ unsafe fn replace_im(inner: *mut ImeInner) -> Result<(), ReplaceImError> {
 
     let mut new_contexts = HashMap::new();
     for (window, old_context) in unsafe { (*inner).contexts.iter() } {
-        let area = old_context.as_ref().map(|old_context| old_context.ic_area);
+        let area =
+            old_context.as_ref().map(|old_context| (old_context.ic_area, old_context.ic_rtl));
 
         // Check if the IME was allowed on that context.
         let is_allowed =
@@ -196,6 +196,8 @@ struct ImeContextClientData {
 pub struct ImeContext {
     pub(crate) ic: ffi::XIC,
     pub(crate) ic_area: ffi::XRectangle,
+    // Whether the text in `ic_area` is right-to-left.
+    pub(crate) ic_rtl: bool,
     pub(crate) allowed: bool,
     // Since the data is passed shared between X11 XIM callbacks, but couldn't be directly free
     // from there we keep the pointer to automatically deallocate it.
@@ -207,7 +209,7 @@ impl ImeContext {
         xconn: &Arc<XConnection>,
         im: &InputMethod,
         window: ffi::Window,
-        ic_area: Option<ffi::XRectangle>,
+        ic_area: Option<(ffi::XRectangle, bool)>,
         event_sender: ImeEventSender,
         allowed: bool,
     ) -> Result<Self, ImeContextCreationError> {
@@ -244,13 +246,14 @@ impl ImeContext {
         let mut context = ImeContext {
             ic,
             ic_area: ffi::XRectangle { x: 0, y: 0, width: 0, height: 0 },
+            ic_rtl: false,
             allowed,
             _client_data: unsafe { Box::from_raw(client_data) },
         };
 
         // Set the preedit cursor area, if it's present.
-        if let Some(ic_area) = ic_area {
-            context.set_area(xconn, ic_area.x, ic_area.y, ic_area.width, ic_area.height);
+        if let Some((ic_area, rtl)) = ic_area {
+            context.set_area(xconn, ic_area.x, ic_area.y, ic_area.width, ic_area.height, rtl);
         }
 
         Ok(context)
@@ -362,6 +365,9 @@ impl ImeContext {
     ///
     /// Use of this information is discretionary by input method servers,
     /// and some may not use it by default, even if they have support.
+    ///
+    /// The spot is placed at the bottom corner of the area where the text ends, which is the left
+    /// one for right-to-left text.
     pub(crate) fn set_area(
         &mut self,
         xconn: &Arc<XConnection>,
@@ -369,16 +375,17 @@ impl ImeContext {
         y: i16,
         width: u16,
         height: u16,
+        rtl: bool,
     ) {
         let ic_area = ffi::XRectangle { x, y, width, height };
 
-        if !self.is_allowed() || self.ic_area == ic_area {
+        if !self.is_allowed() || (self.ic_area == ic_area && self.ic_rtl == rtl) {
             return;
         }
 
         self.ic_area = ic_area;
-        let ic_spot =
-            ffi::XPoint { x: x.saturating_add(width as i16), y: y.saturating_add(height as i16) };
+        self.ic_rtl = rtl;
+        let ic_spot = spot_location(&ic_area, rtl);
 
         unsafe {
             let preedit_attr = util::memory::XSmartPointer::new(
@@ -403,3 +410,25 @@ impl ImeContext {
         }
     }
 }
+
+/// The spot location for the area, at the bottom corner where the text ends.
+fn spot_location(area: &ffi::XRectangle, rtl: bool) -> ffi::XPoint {
+    let x = if rtl { area.x } else { area.x.saturating_add(area.width as i16) };
+    ffi::XPoint { x, y: area.y.saturating_add(area.height as i16) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spot_is_where_the_text_ends() {
+        let area = ffi::XRectangle { x: 10, y: 20, width: 30, height: 40 };
+
+        let ltr = spot_location(&area, false);
+        assert_eq!((ltr.x, ltr.y), (40, 60));
+
+        let rtl = spot_location(&area, true);
+        assert_eq!((rtl.x, rtl.y), (10, 60));
+    }
+}
@@ -37,8 +37,9 @@ pub type ImeEventSender = Sender<(ffi::Window, ImeEvent)>;
 
 /// Request to control XIM handler from the window.
 pub enum ImeRequest {
-    /// Set IME preedit area for given `window_id`.
-    Area(ffi::Window, i16, i16, u16, u16),
+    /// Set IME preedit area for given `window_id`, the last field is `true` for right-to-left
+    /// text.
+    Area(ffi::Window, i16, i16, u16, u16, bool),
 
     /// Allow IME input for the given `window_id`.
     Allow(ffi::Window, bool),
@@ -193,12 +194,20 @@ impl Ime {
         }
     }
 
-    pub fn send_xim_area(&mut self, window: ffi::Window, x: i16, y: i16, w: u16, h: u16) {
+    pub fn send_xim_area(
+        &mut self,
+        window: ffi::Window,
+        x: i16,
+        y: i16,
+        w: u16,
+        h: u16,
+        rtl: bool,
+    ) {
         if self.is_destroyed() {
             return;
         }
         if let Some(&mut Some(ref mut context)) = self.inner.contexts.get_mut(&window) {
-            context.set_area(&self.xconn, x as _, y as _, w as _, h as _);
+            context.set_area(&self.xconn, x as _, y as _, w as _, h as _, rtl);
         }
     }
 
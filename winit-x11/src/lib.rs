@@ -4,6 +4,7 @@ use dpi::Size;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use winit_core::event_loop::ActiveEventLoop as CoreActiveEventLoop;
+use winit_core::monitor::MonitorHandle as CoreMonitorHandle;
 use winit_core::window::{ActivationToken, PlatformWindowAttributes, Window as CoreWindow};
 
 pub use crate::event_loop::{ActiveEventLoop, EventLoop};
@@ -110,6 +111,16 @@ pub fn register_xlib_error_hook(hook: XlibErrorHook) {
 pub trait ActiveEventLoopExtX11 {
     /// True if the event loop uses X11.
     fn is_x11(&self) -> bool;
+
+    /// Enable or disable caching of the monitor list.
+    ///
+    /// Winit caches the list of monitors and only re-queries it from the server when RandR
+    /// reports a change. Applications that frequently reconfigure displays may want to disable
+    /// the cache to always get a fresh list, at the cost of extra round-trips to the X server for
+    /// every monitor query.
+    ///
+    /// The cache is enabled by default. Does nothing if the event loop doesn't use X11.
+    fn set_monitor_list_caching(&self, enabled: bool);
 }
 
 impl ActiveEventLoopExtX11 for dyn CoreActiveEventLoop + '_ {
@@ -117,6 +128,37 @@ impl ActiveEventLoopExtX11 for dyn CoreActiveEventLoop + '_ {
     fn is_x11(&self) -> bool {
         self.cast_ref::<ActiveEventLoop>().is_some()
     }
+
+    #[inline]
+    fn set_monitor_list_caching(&self, enabled: bool) {
+        if let Some(event_loop) = self.cast_ref::<ActiveEventLoop>() {
+            event_loop.xconn.set_monitor_list_caching(enabled);
+        }
+    }
+}
+
+/// Additional methods on [`MonitorHandle`] that are specific to X11.
+///
+/// [`MonitorHandle`]: winit_core::monitor::MonitorHandle
+pub trait MonitorHandleExtX11 {
+    /// Returns the XID of the RandR output shown on this monitor.
+    ///
+    /// Unlike [`MonitorHandleProvider::native_id`], which is the XID of the CRTC, this is what
+    /// e.g. `vkGetRandROutputDisplayEXT` expects. When the CRTC drives several outputs, i.e. the
+    /// monitors are mirrored, the first one is returned.
+    ///
+    /// Returns `None` if the monitor is not an X11 one, or winit couldn't query any monitor.
+    ///
+    /// [`MonitorHandleProvider::native_id`]: winit_core::monitor::MonitorHandleProvider::native_id
+    fn randr_output(&self) -> Option<u32>;
+}
+
+impl MonitorHandleExtX11 for CoreMonitorHandle {
+    #[inline]
+    fn randr_output(&self) -> Option<u32> {
+        let monitor = self.cast_ref::<monitor::MonitorHandle>()?;
+        (!monitor.is_dummy()).then_some(monitor.output)
+    }
 }
 
 /// Additional methods on [`EventLoop`] that are specific to X11.
@@ -128,6 +170,9 @@ pub trait EventLoopExtX11 {
 /// Additional methods when building event loop that are specific to X11.
 pub trait EventLoopBuilderExtX11 {
     /// Force using X11.
+    ///
+    /// Building the event loop returns an error if X11 is unavailable, instead of falling back to
+    /// another backend.
     fn with_x11(&mut self) -> &mut Self;
 
     /// Whether to allow the event loop to be created off of the main thread.
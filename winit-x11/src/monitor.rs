@@ -1,4 +1,8 @@
+use std::cmp;
 use std::num::NonZeroU32;
+use std::ops::Deref;
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
 
 use dpi::PhysicalPosition;
 use winit_core::monitor::{MonitorHandleProvider, VideoMode};
@@ -10,14 +14,26 @@ use crate::event_loop::X11Error;
 use crate::util;
 use crate::xdisplay::XConnection;
 
-// Used for testing. This should always be committed as false.
-const DISABLE_MONITOR_LIST_CACHING: bool = false;
-
 impl XConnection {
     pub fn invalidate_cached_monitor_list(&self) -> Option<Vec<MonitorHandle>> {
         // We update this lazily.
         self.monitor_handles.lock().unwrap().take()
     }
+
+    /// Enable or disable caching of the monitor list.
+    ///
+    /// Disabling the cache drops the currently cached list, so the next query goes to the server.
+    pub fn set_monitor_list_caching(&self, enabled: bool) {
+        self.monitor_list_caching.store(enabled, Ordering::Relaxed);
+        if !enabled {
+            self.invalidate_cached_monitor_list();
+        }
+    }
+
+    #[inline]
+    pub fn monitor_list_caching(&self) -> bool {
+        self.monitor_list_caching.load(Ordering::Relaxed)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -33,10 +49,27 @@ impl From<VideoModeHandle> for VideoMode {
     }
 }
 
+/// Information about a RandR output, as returned by `XConnection::get_output_info`.
+pub(crate) struct OutputInfo {
+    pub(crate) name: String,
+    pub(crate) scale_factor: f64,
+    pub(crate) physical_size_mm: Option<(u32, u32)>,
+    pub(crate) video_modes: Vec<VideoModeHandle>,
+}
+
+/// Handle to a monitor.
+///
+/// The monitor information is shared, so handles are cheap to clone, which matters as the whole
+/// monitor list is cloned whenever it's queried.
 #[derive(Debug, Clone)]
-pub struct MonitorHandle {
+pub struct MonitorHandle(Arc<MonitorInfo>);
+
+#[derive(Debug)]
+pub struct MonitorInfo {
     /// The actual id
     pub(crate) id: randr::Crtc,
+    /// The RandR output driven by the CRTC
+    pub(crate) output: randr::Output,
     /// The name of the monitor
     pub(crate) name: String,
     /// The position of the monitor in the X screen
@@ -45,6 +78,8 @@ pub struct MonitorHandle {
     primary: bool,
     /// The DPI scale factor
     pub(crate) scale_factor: f64,
+    /// The physical size reported by the output, in millimeters
+    pub(crate) physical_size_mm: Option<(u32, u32)>,
     /// Used to determine which windows are on this monitor
     pub(crate) rect: util::AaRect,
     /// Supported video modes on this monitor
@@ -79,6 +114,36 @@ impl MonitorHandleProvider for MonitorHandle {
     fn video_modes(&self) -> Box<dyn Iterator<Item = VideoMode>> {
         Box::new(self.video_modes.clone().into_iter().map(|mode| mode.into()))
     }
+
+    fn physical_size_mm(&self) -> Option<(u32, u32)> {
+        self.physical_size_mm
+    }
+
+    fn is_builtin(&self) -> Option<bool> {
+        Some(is_builtin_output_name(&self.name))
+    }
+
+    fn is_placeholder(&self) -> bool {
+        self.is_dummy()
+    }
+}
+
+/// Whether the name of a RandR output is one conventionally used for built-in panels.
+///
+/// There is no RandR property for this, so it's a heuristic based on the connector names of the
+/// kernel and the proprietary drivers, e.g. `eDP-1`, `eDP1` or `LVDS-0`.
+fn is_builtin_output_name(name: &str) -> bool {
+    ["eDP", "LVDS", "DSI"].iter().any(|prefix| {
+        name.get(..prefix.len()).is_some_and(|start| start.eq_ignore_ascii_case(prefix))
+    })
+}
+
+impl Deref for MonitorHandle {
+    type Target = MonitorInfo;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
 }
 
 impl PartialEq for MonitorHandle {
@@ -119,7 +184,7 @@ pub fn mode_refresh_rate_millihertz(mode: &randr::ModeInfo) -> Option<NonZeroU32
     }
 }
 
-impl MonitorHandle {
+impl MonitorInfo {
     fn new(
         xconn: &XConnection,
         resources: &ScreenResources,
@@ -127,25 +192,40 @@ impl MonitorHandle {
         crtc: &randr::GetCrtcInfoReply,
         primary: bool,
     ) -> Option<Self> {
-        let (name, scale_factor, video_modes) = xconn.get_output_info(resources, crtc)?;
+        let OutputInfo { name, scale_factor, physical_size_mm, video_modes } =
+            xconn.get_output_info(resources, crtc)?;
         let dimensions = (crtc.width as u32, crtc.height as u32);
         let position = (crtc.x as i32, crtc.y as i32);
 
         let rect = util::AaRect::new(position, dimensions);
 
-        Some(MonitorHandle { id, name, scale_factor, position, primary, rect, video_modes })
+        Some(MonitorInfo {
+            id,
+            output: crtc.outputs[0],
+            name,
+            scale_factor,
+            physical_size_mm,
+            position,
+            primary,
+            rect,
+            video_modes,
+        })
     }
+}
 
+impl MonitorHandle {
     pub fn dummy() -> Self {
-        MonitorHandle {
+        MonitorHandle(Arc::new(MonitorInfo {
             id: 0,
+            output: 0,
             name: "<dummy monitor>".into(),
             scale_factor: 1.0,
+            physical_size_mm: None,
             position: (0, 0),
             primary: true,
             rect: util::AaRect::new((0, 0), (1, 1)),
             video_modes: Vec::new(),
-        }
+        }))
     }
 
     pub(crate) fn is_dummy(&self) -> bool {
@@ -154,6 +234,27 @@ impl MonitorHandle {
     }
 }
 
+/// Pick the monitor a window with the given rectangle is on.
+///
+/// That's the monitor with the largest overlap with the window, or the one nearest to the window
+/// center when the window is off all of them. Ties go to the primary monitor, then to the monitor
+/// with the smallest id, so the result doesn't depend on the order of the monitor list.
+fn monitor_for_rect<'a>(
+    monitors: &'a [MonitorHandle],
+    window_rect: &util::AaRect,
+) -> Option<&'a MonitorHandle> {
+    let (center_x, center_y) = window_rect.center();
+    monitors.iter().min_by_key(|monitor| {
+        let overlap = window_rect.get_overlapping_area(&monitor.rect);
+        let distance = if overlap > 0 {
+            0
+        } else {
+            monitor.rect.distance_squared_to_point(center_x, center_y)
+        };
+        (cmp::Reverse(overlap), distance, !monitor.primary, monitor.id)
+    })
+}
+
 impl XConnection {
     pub fn get_monitor_for_window(
         &self,
@@ -161,29 +262,13 @@ impl XConnection {
     ) -> Result<MonitorHandle, X11Error> {
         let monitors = self.available_monitors()?;
 
-        if monitors.is_empty() {
-            // Return a dummy monitor to avoid panicking
-            return Ok(MonitorHandle::dummy());
-        }
-
-        let default = monitors.first().unwrap();
-
-        let window_rect = match window_rect {
-            Some(rect) => rect,
-            None => return Ok(default.to_owned()),
+        let monitor = match window_rect {
+            Some(window_rect) => monitor_for_rect(&monitors, &window_rect),
+            None => monitors.first(),
         };
 
-        let mut largest_overlap = 0;
-        let mut matched_monitor = default;
-        for monitor in &monitors {
-            let overlapping_area = window_rect.get_overlapping_area(&monitor.rect);
-            if overlapping_area > largest_overlap {
-                largest_overlap = overlapping_area;
-                matched_monitor = monitor;
-            }
-        }
-
-        Ok(matched_monitor.to_owned())
+        // Return a dummy monitor to avoid panicking
+        Ok(monitor.cloned().unwrap_or_else(MonitorHandle::dummy))
     }
 
     fn query_monitor_list(&self) -> Result<Vec<MonitorHandle>, X11Error> {
@@ -216,7 +301,7 @@ impl XConnection {
 
             let is_primary = crtc.outputs[0] == primary;
             has_primary |= is_primary;
-            let monitor = MonitorHandle::new(self, &resources, *crtc_id, crtc, is_primary);
+            let monitor = MonitorInfo::new(self, &resources, *crtc_id, crtc, is_primary);
             available_monitors.extend(monitor);
         }
 
@@ -228,7 +313,7 @@ impl XConnection {
             }
         }
 
-        Ok(available_monitors)
+        Ok(available_monitors.into_iter().map(|monitor| MonitorHandle(Arc::new(monitor))).collect())
     }
 
     pub fn available_monitors(&self) -> Result<Vec<MonitorHandle>, X11Error> {
@@ -237,7 +322,7 @@ impl XConnection {
             Some(ref monitors) => Ok(monitors.clone()),
             None => {
                 let monitors = self.query_monitor_list()?;
-                if !DISABLE_MONITOR_LIST_CACHING {
+                if self.monitor_list_caching() {
                     *monitors_lock = Some(monitors.clone());
                 }
                 Ok(monitors)
@@ -313,3 +398,93 @@ impl ScreenResources {
         Self { modes: reply.modes, crtcs: reply.crtcs }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monitor(id: randr::Crtc, primary: bool, position: (i32, i32)) -> MonitorHandle {
+        MonitorHandle(Arc::new(MonitorInfo {
+            id,
+            output: id,
+            name: format!("monitor {id}"),
+            scale_factor: 1.0,
+            physical_size_mm: None,
+            position,
+            primary,
+            rect: util::AaRect::new(position, (1920, 1080)),
+            video_modes: Vec::new(),
+        }))
+    }
+
+    #[test]
+    fn cloned_monitor_list_shares_data() {
+        // The cached monitor list is cloned whenever it's queried.
+        let monitors = vec![monitor(1, true, (0, 0)), monitor(2, false, (1920, 0))];
+        let queried = monitors.clone();
+
+        for (cached, queried) in monitors.iter().zip(&queried) {
+            assert!(Arc::ptr_eq(&cached.0, &queried.0));
+            assert_eq!(cached.video_modes.as_ptr(), queried.video_modes.as_ptr());
+        }
+    }
+
+    #[test]
+    fn builtin_output_names() {
+        for name in ["eDP-1", "eDP1", "LVDS-0", "lvds", "DSI-1"] {
+            assert!(is_builtin_output_name(name), "{name}");
+        }
+        for name in ["DP-1", "HDMI-A-1", "DVI-D-0", "Virtual-1", "", "ed"] {
+            assert!(!is_builtin_output_name(name), "{name}");
+        }
+    }
+
+    #[test]
+    fn overlap_ties_prefer_primary_then_smallest_id() {
+        // The window straddles the edge between the left and the right monitors.
+        let window_rect = util::AaRect::new((1820, 100), (200, 200));
+        let left = monitor(2, false, (0, 0));
+        let right = monitor(1, false, (1920, 0));
+
+        let monitors = [left.clone(), right.clone()];
+        assert_eq!(monitor_for_rect(&monitors, &window_rect), Some(&right));
+        let monitors = [right.clone(), left.clone()];
+        assert_eq!(monitor_for_rect(&monitors, &window_rect), Some(&right));
+
+        let primary_left = monitor(2, true, (0, 0));
+        let monitors = [right.clone(), primary_left.clone()];
+        assert_eq!(monitor_for_rect(&monitors, &window_rect), Some(&primary_left));
+    }
+
+    #[test]
+    fn off_screen_window_picks_nearest_monitor() {
+        let left = monitor(1, true, (0, 0));
+        let right = monitor(2, false, (1920, 0));
+        let monitors = [left.clone(), right.clone()];
+
+        // Right of the right monitor.
+        let window_rect = util::AaRect::new((4000, 100), (200, 200));
+        assert_eq!(monitor_for_rect(&monitors, &window_rect), Some(&right));
+
+        // Below the left monitor.
+        let window_rect = util::AaRect::new((100, 2000), (200, 200));
+        assert_eq!(monitor_for_rect(&monitors, &window_rect), Some(&left));
+    }
+
+    #[test]
+    fn largest_overlap_wins() {
+        let left = monitor(1, true, (0, 0));
+        let right = monitor(2, false, (1920, 0));
+        let monitors = [left, right.clone()];
+
+        let window_rect = util::AaRect::new((1900, 100), (200, 200));
+        assert_eq!(monitor_for_rect(&monitors, &window_rect), Some(&right));
+        assert_eq!(monitor_for_rect(&[], &window_rect), None);
+    }
+
+    #[test]
+    fn dummy_is_placeholder() {
+        assert!(MonitorHandle::dummy().is_placeholder());
+        assert!(!monitor(1, true, (0, 0)).is_placeholder());
+    }
+}
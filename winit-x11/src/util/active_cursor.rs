@@ -0,0 +1,58 @@
+use winit_core::cursor::Cursor;
+
+/// The cursor last applied with `Window::set_cursor`.
+///
+/// Setting the same cursor again is skipped, so applications that set the cursor on every pointer
+/// motion don't redefine it on the server each time.
+#[derive(Debug, Default)]
+pub struct ActiveCursor {
+    cursor: Option<Cursor>,
+}
+
+impl ActiveCursor {
+    /// Whether `cursor` differs from the active one and has to be applied.
+    pub fn is_changed(&self, cursor: &Cursor) -> bool {
+        self.cursor.as_ref() != Some(cursor)
+    }
+
+    /// Record the cursor that was applied, or `None` when applying it failed.
+    pub fn set(&mut self, cursor: Option<Cursor>) {
+        self.cursor = cursor;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use winit_core::cursor::CursorIcon;
+
+    use super::*;
+
+    #[test]
+    fn identical_cursors_apply_once() {
+        let mut active = ActiveCursor::default();
+        let mut set_cursor = |cursor: Cursor| {
+            let changed = active.is_changed(&cursor);
+            if changed {
+                active.set(Some(cursor));
+            }
+            changed
+        };
+
+        let updates = (0..10).filter(|_| set_cursor(Cursor::Icon(CursorIcon::Pointer))).count();
+        assert_eq!(updates, 1);
+
+        assert!(set_cursor(Cursor::Icon(CursorIcon::Text)));
+        assert!(set_cursor(Cursor::Icon(CursorIcon::Pointer)));
+    }
+
+    #[test]
+    fn failed_cursor_is_retried() {
+        let mut active = ActiveCursor::default();
+        let cursor = Cursor::Icon(CursorIcon::Wait);
+        active.set(Some(cursor.clone()));
+        assert!(!active.is_changed(&cursor));
+
+        active.set(None);
+        assert!(active.is_changed(&cursor));
+    }
+}
@@ -0,0 +1,87 @@
+use std::mem;
+
+use winit_core::window::CursorGrabMode;
+
+/// The cursor grab requested by the application and the one the server applied.
+///
+/// The server releases the grab when the window becomes unviewable, so the requested mode is kept
+/// to grab the cursor again afterwards.
+#[derive(Debug)]
+pub struct CursorGrab {
+    requested: CursorGrabMode,
+    grabbed: CursorGrabMode,
+}
+
+impl Default for CursorGrab {
+    fn default() -> Self {
+        Self { requested: CursorGrabMode::None, grabbed: CursorGrabMode::None }
+    }
+}
+
+impl CursorGrab {
+    /// Record the mode requested by the application, even if grabbing fails.
+    ///
+    /// Returns whether the cursor has to be grabbed for it.
+    pub fn request(&mut self, mode: CursorGrabMode) -> bool {
+        self.requested = mode;
+        self.grabbed != mode
+    }
+
+    /// Record the mode the server applied.
+    pub fn set_grabbed(&mut self, mode: CursorGrabMode) {
+        self.grabbed = mode;
+    }
+
+    /// The server released the grab.
+    ///
+    /// Returns whether the cursor was grabbed.
+    pub fn release(&mut self) -> bool {
+        mem::replace(&mut self.grabbed, CursorGrabMode::None) != CursorGrabMode::None
+    }
+
+    /// Forget the applied grab, which may be stale, to grab the cursor again.
+    ///
+    /// Returns the requested mode and the mode that was grabbed, or `None` if no grab is requested.
+    pub fn regrab(&mut self) -> Option<(CursorGrabMode, CursorGrabMode)> {
+        if self.requested == CursorGrabMode::None {
+            return None;
+        }
+
+        Some((self.requested, mem::replace(&mut self.grabbed, CursorGrabMode::None)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn released_grab_is_restored() {
+        let mut grab = CursorGrab::default();
+        assert!(grab.regrab().is_none());
+
+        assert!(grab.request(CursorGrabMode::Confined));
+        grab.set_grabbed(CursorGrabMode::Confined);
+        assert!(!grab.request(CursorGrabMode::Confined));
+
+        // The window was unmapped, e.g. during a fullscreen transition.
+        assert!(grab.release());
+        assert!(!grab.release());
+        assert_eq!(grab.regrab(), Some((CursorGrabMode::Confined, CursorGrabMode::None)));
+
+        // A grab that wasn't released is applied again without a change.
+        grab.set_grabbed(CursorGrabMode::Confined);
+        assert_eq!(grab.regrab(), Some((CursorGrabMode::Confined, CursorGrabMode::Confined)));
+    }
+
+    #[test]
+    fn failed_grab_is_retried() {
+        let mut grab = CursorGrab::default();
+        assert!(grab.request(CursorGrabMode::Confined));
+        assert_eq!(grab.regrab(), Some((CursorGrabMode::Confined, CursorGrabMode::None)));
+
+        // Releasing the grab clears the request.
+        assert!(!grab.request(CursorGrabMode::None));
+        assert!(grab.regrab().is_none());
+    }
+}
@@ -2,6 +2,13 @@ use std::cmp;
 
 use super::*;
 
+/// The geometry of a window, in physical pixels.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Geometry {
+    pub position: (i32, i32),
+    pub surface_size: (u32, u32),
+}
+
 // Friendly neighborhood axis-aligned rectangle
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct AaRect {
@@ -33,6 +40,18 @@ impl AaRect {
         );
         x_overlap * y_overlap
     }
+
+    pub fn center(&self) -> (i64, i64) {
+        (self.x + self.width / 2, self.y + self.height / 2)
+    }
+
+    /// The squared distance from the point to the nearest point of the rectangle, zero if the
+    /// point is inside of it.
+    pub fn distance_squared_to_point(&self, x: i64, y: i64) -> i64 {
+        let dx = cmp::max(0, cmp::max(self.x - x, x - (self.x + self.width)));
+        let dy = cmp::max(0, cmp::max(self.y - y, y - (self.y + self.height)));
+        dx * dx + dy * dy
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -81,11 +81,23 @@ impl MotifHints {
         self.hints.decorations = decorations as u32;
     }
 
+    pub fn set_minimizable(&mut self, minimizable: bool) {
+        self.set_func(mwm::MWM_FUNC_MINIMIZE, minimizable);
+    }
+
     pub fn set_maximizable(&mut self, maximizable: bool) {
-        if maximizable {
-            self.add_func(mwm::MWM_FUNC_MAXIMIZE);
+        self.set_func(mwm::MWM_FUNC_MAXIMIZE, maximizable);
+    }
+
+    pub fn set_closable(&mut self, closable: bool) {
+        self.set_func(mwm::MWM_FUNC_CLOSE, closable);
+    }
+
+    fn set_func(&mut self, func: u32, enabled: bool) {
+        if enabled {
+            self.add_func(func);
         } else {
-            self.remove_func(mwm::MWM_FUNC_MAXIMIZE);
+            self.remove_func(func);
         }
     }
 
@@ -1,3 +1,4 @@
+use std::time::Duration;
 use std::{slice, str};
 
 use x11rb::protocol::xinput::{self, ConnectionExt as _};
@@ -45,6 +46,22 @@ impl XConnection {
         }
     }
 
+    /// The autorepeat delay and interval of the core keyboard.
+    pub fn key_repeat_rate(&self) -> Option<(Duration, Duration)> {
+        let (mut delay, mut interval) = (0, 0);
+        let status = unsafe {
+            (self.xlib.XkbGetAutoRepeatRate)(
+                self.display,
+                xkb::ID::USE_CORE_KBD.into(),
+                &mut delay,
+                &mut interval,
+            )
+        };
+
+        (status == ffi::True)
+            .then(|| (Duration::from_millis(delay.into()), Duration::from_millis(interval.into())))
+    }
+
     pub fn query_pointer(
         &self,
         window: xproto::Window,
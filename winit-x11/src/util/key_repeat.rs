@@ -0,0 +1,125 @@
+use std::time::{Duration, Instant};
+
+use winit_core::event::DeviceId;
+
+/// Synthesizes repeated raw key presses for `ActiveEventLoop::set_device_key_repeat`.
+///
+/// Like the X server's autorepeat, only the most recently pressed key repeats, first after the
+/// delay and then at every interval.
+#[derive(Debug)]
+pub struct DeviceKeyRepeat {
+    delay: Duration,
+    interval: Duration,
+    held: Option<HeldKey>,
+}
+
+#[derive(Debug)]
+struct HeldKey {
+    device_id: Option<DeviceId>,
+    keycode: u32,
+    next_repeat: Instant,
+}
+
+impl DeviceKeyRepeat {
+    pub fn new(delay: Duration, interval: Duration) -> Self {
+        // A zero interval would repeat on every event loop iteration.
+        let interval = interval.max(Duration::from_millis(1));
+        Self { delay, interval, held: None }
+    }
+
+    /// The key was pressed at `now`, it starts repeating after the delay.
+    ///
+    /// Presses of the key that is already repeating don't restart the delay.
+    pub fn press(&mut self, device_id: Option<DeviceId>, keycode: u32, now: Instant) {
+        if self.held.as_ref().is_some_and(|held| held.keycode == keycode) {
+            return;
+        }
+
+        self.held = Some(HeldKey { device_id, keycode, next_repeat: now + self.delay });
+    }
+
+    /// The key was released, which stops repeating it if it's the repeating key.
+    pub fn release(&mut self, keycode: u32) {
+        if self.held.as_ref().is_some_and(|held| held.keycode == keycode) {
+            self.held = None;
+        }
+    }
+
+    /// Returns the repeating key if a repeat is due at `now`, and schedules the next repeat.
+    ///
+    /// Repeats missed while the event loop was busy are dropped instead of being delivered in a
+    /// burst.
+    pub fn due(&mut self, now: Instant) -> Option<(Option<DeviceId>, u32)> {
+        let interval = self.interval;
+        let held = self.held.as_mut().filter(|held| held.next_repeat <= now)?;
+
+        held.next_repeat += interval;
+        if held.next_repeat <= now {
+            held.next_repeat = now + interval;
+        }
+
+        Some((held.device_id, held.keycode))
+    }
+
+    /// The time of the next repeat.
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.held.as_ref().map(|held| held.next_repeat)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DELAY: Duration = Duration::from_millis(500);
+    const INTERVAL: Duration = Duration::from_millis(40);
+
+    #[test]
+    fn held_key_repeats_after_delay_at_interval() {
+        let mut repeat = DeviceKeyRepeat::new(DELAY, INTERVAL);
+        let start = Instant::now();
+        repeat.press(None, 38, start);
+
+        assert_eq!(repeat.next_deadline(), Some(start + DELAY));
+        assert_eq!(repeat.due(start + DELAY - Duration::from_millis(1)), None);
+
+        // Poll every millisecond for a second, like a busy event loop would.
+        let repeats = (0..1000)
+            .filter_map(|ms| repeat.due(start + Duration::from_millis(ms)))
+            .inspect(|&(_, keycode)| assert_eq!(keycode, 38))
+            .count();
+        assert_eq!(repeats, 13);
+
+        repeat.release(38);
+        assert_eq!(repeat.next_deadline(), None);
+        assert_eq!(repeat.due(start + Duration::from_secs(2)), None);
+    }
+
+    #[test]
+    fn only_the_latest_key_repeats() {
+        let mut repeat = DeviceKeyRepeat::new(DELAY, INTERVAL);
+        let start = Instant::now();
+        repeat.press(None, 38, start);
+        repeat.press(None, 39, start + Duration::from_millis(100));
+
+        // The repeated press of the repeating key doesn't restart the delay.
+        repeat.press(None, 39, start + Duration::from_millis(200));
+        assert_eq!(repeat.next_deadline(), Some(start + Duration::from_millis(100) + DELAY));
+
+        // Releasing an older key keeps the latest one repeating.
+        repeat.release(38);
+        assert_eq!(repeat.due(start + Duration::from_millis(600)), Some((None, 39)));
+    }
+
+    #[test]
+    fn missed_repeats_are_dropped() {
+        let mut repeat = DeviceKeyRepeat::new(DELAY, INTERVAL);
+        let start = Instant::now();
+        repeat.press(None, 38, start);
+
+        let late = start + Duration::from_secs(5);
+        assert!(repeat.due(late).is_some());
+        assert_eq!(repeat.due(late), None);
+        assert_eq!(repeat.next_deadline(), Some(late + INTERVAL));
+    }
+}
@@ -5,29 +5,43 @@ use std::mem::{self, MaybeUninit};
 use std::ops::BitAnd;
 use std::os::raw::*;
 
+mod active_cursor;
 mod client_msg;
 pub mod cookie;
 mod cursor;
+mod cursor_grab;
 mod geometry;
 mod hint;
 mod icon;
 mod input;
+mod key_repeat;
 pub mod keys;
 pub(crate) mod memory;
 mod mouse;
+mod pending_title;
+mod pump_run;
 mod randr;
+mod redraw_throttle;
+mod wake_up;
 mod window_property;
 mod wm;
 mod xmodmap;
 
 use x11rb::protocol::xproto::{self, ConnectionExt as _};
 
+pub use self::active_cursor::ActiveCursor;
 pub use self::cursor::*;
+pub use self::cursor_grab::CursorGrab;
 pub use self::geometry::*;
 pub use self::hint::*;
 pub(crate) use self::icon::rgba_to_cardinals;
 pub use self::input::*;
+pub use self::key_repeat::DeviceKeyRepeat;
 pub use self::mouse::*;
+pub use self::pending_title::PendingTitle;
+pub use self::pump_run::PumpRun;
+pub use self::redraw_throttle::RedrawThrottle;
+pub use self::wake_up::WakeUpSources;
 pub use self::window_property::*;
 pub use self::wm::*;
 pub use self::xmodmap::ModifierKeymap;
@@ -0,0 +1,54 @@
+/// The title of a window, coalescing rapid changes into a single property write.
+///
+/// Only the last title set before the event loop flushes it is written to the window.
+#[derive(Debug, Default)]
+pub struct PendingTitle {
+    title: String,
+    pending: bool,
+}
+
+impl PendingTitle {
+    pub fn new(title: &str) -> Self {
+        Self { title: title.to_owned(), pending: false }
+    }
+
+    /// The most recently set title, whether it was written yet or not.
+    pub fn get(&self) -> &str {
+        &self.title
+    }
+
+    /// Set the title, returning `true` when a flush must be scheduled.
+    pub fn set(&mut self, title: &str) -> bool {
+        self.title.clear();
+        self.title.push_str(title);
+        !std::mem::replace(&mut self.pending, true)
+    }
+
+    /// Take the title to write, if it changed since the last flush.
+    pub fn take(&mut self) -> Option<&str> {
+        std::mem::take(&mut self.pending).then_some(&self.title)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rapid_changes_flush_once() {
+        let mut title = PendingTitle::new("winit");
+        assert_eq!(title.take(), None);
+
+        let scheduled = (0..100).filter(|fps| title.set(&format!("{fps} FPS"))).count();
+        assert_eq!(scheduled, 1);
+        assert_eq!(title.get(), "99 FPS");
+
+        assert_eq!(title.take(), Some("99 FPS"));
+        assert_eq!(title.take(), None);
+        assert_eq!(title.get(), "99 FPS");
+
+        // Changes after a flush schedule another one.
+        assert!(title.set("100 FPS"));
+        assert_eq!(title.take(), Some("100 FPS"));
+    }
+}
@@ -0,0 +1,59 @@
+use std::cell::Cell;
+
+use winit_core::event_loop::pump_events::PumpStatus;
+
+/// The run of the event loop driven by `pump_app_events`.
+#[derive(Debug, Default)]
+pub struct PumpRun {
+    running: bool,
+}
+
+impl PumpRun {
+    /// Start a new run unless one is in progress, returning whether it started and is due for
+    /// `StartCause::Init`.
+    ///
+    /// The exit code of the previous run doesn't carry over, so `exit` is cleared.
+    pub fn start(&mut self, exit: &Cell<Option<i32>>) -> bool {
+        if self.running {
+            return false;
+        }
+
+        self.running = true;
+        exit.set(None);
+        true
+    }
+
+    /// The status after an iteration, the run ends once an exit was requested.
+    pub fn status(&mut self, exit: Option<i32>) -> PumpStatus {
+        match exit {
+            Some(code) => {
+                self.running = false;
+                PumpStatus::Exit(code)
+            },
+            None => PumpStatus::Continue,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pumping_after_exit_starts_a_new_run() {
+        let exit = Cell::new(None);
+        let mut run = PumpRun::default();
+
+        assert!(run.start(&exit));
+        assert_eq!(run.status(exit.get()), PumpStatus::Continue);
+        assert!(!run.start(&exit));
+
+        exit.set(Some(3));
+        assert_eq!(run.status(exit.get()), PumpStatus::Exit(3));
+
+        // The next pump starts over, without the exit code of the previous run.
+        assert!(run.start(&exit));
+        assert_eq!(exit.get(), None);
+        assert_eq!(run.status(exit.get()), PumpStatus::Continue);
+    }
+}
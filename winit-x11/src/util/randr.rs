@@ -8,7 +8,7 @@ use winit_core::monitor::VideoMode;
 use x11rb::protocol::randr::{self, ConnectionExt as _};
 
 use super::*;
-use crate::monitor::{self, VideoModeHandle};
+use crate::monitor::{self, OutputInfo, VideoModeHandle};
 
 /// Represents values of `WINIT_HIDPI_FACTOR`.
 pub enum EnvVarDPI {
@@ -55,7 +55,7 @@ impl XConnection {
         &self,
         resources: &monitor::ScreenResources,
         crtc: &randr::GetCrtcInfoReply,
-    ) -> Option<(String, f64, Vec<VideoModeHandle>)> {
+    ) -> Option<OutputInfo> {
         let output_info = match self
             .xcb_connection()
             .randr_get_output_info(crtc.outputs[0], x11rb::CURRENT_TIME)
@@ -149,7 +149,10 @@ impl XConnection {
             },
         };
 
-        Some((name, scale_factor, modes))
+        let physical_size_mm = (output_info.mm_width > 0 && output_info.mm_height > 0)
+            .then_some((output_info.mm_width, output_info.mm_height));
+
+        Some(OutputInfo { name, scale_factor, physical_size_mm, video_modes: modes })
     }
 
     pub fn set_crtc_config(
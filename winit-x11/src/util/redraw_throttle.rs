@@ -0,0 +1,130 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use winit_core::window::WindowId;
+
+/// Caps the rate of `RedrawRequested` for windows with a maximum redraw rate.
+///
+/// Redraws requested before the window's interval elapsed are deferred, and coalesced, until the
+/// interval elapsed.
+#[derive(Debug, Default)]
+pub struct RedrawThrottle {
+    windows: HashMap<WindowId, ThrottledWindow>,
+}
+
+#[derive(Debug)]
+struct ThrottledWindow {
+    interval: Duration,
+    last_redraw: Option<Instant>,
+    pending: bool,
+}
+
+impl ThrottledWindow {
+    fn next_redraw(&self) -> Option<Instant> {
+        self.last_redraw.map(|last_redraw| last_redraw + self.interval)
+    }
+}
+
+impl RedrawThrottle {
+    /// Set the maximum redraw rate of the window in frames per second, `None` or `Some(0)` remove
+    /// the cap.
+    ///
+    /// Returns `true` when a deferred redraw was dropped and must be requested again.
+    pub fn set_max_rate(&mut self, window_id: WindowId, rate: Option<u32>) -> bool {
+        match rate.filter(|&rate| rate != 0) {
+            Some(rate) => {
+                let interval = Duration::from_secs(1) / rate;
+                self.windows
+                    .entry(window_id)
+                    .and_modify(|window| window.interval = interval)
+                    .or_insert(ThrottledWindow { interval, last_redraw: None, pending: false });
+                false
+            },
+            None => self.remove(window_id),
+        }
+    }
+
+    /// Forget about the window, returning whether it had a deferred redraw.
+    pub fn remove(&mut self, window_id: WindowId) -> bool {
+        self.windows.remove(&window_id).is_some_and(|window| window.pending)
+    }
+
+    /// Returns whether the redraw of the window can be dispatched at `now`, deferring it otherwise.
+    pub fn dispatch(&mut self, window_id: WindowId, now: Instant) -> bool {
+        let Some(window) = self.windows.get_mut(&window_id) else {
+            return true;
+        };
+
+        if window.next_redraw().is_some_and(|next_redraw| now < next_redraw) {
+            window.pending = true;
+            return false;
+        }
+
+        window.last_redraw = Some(now);
+        window.pending = false;
+        true
+    }
+
+    /// The windows with a deferred redraw which can be dispatched at `now`.
+    pub fn due(&self, now: Instant) -> impl Iterator<Item = WindowId> + '_ {
+        self.windows.iter().filter_map(move |(&window_id, window)| {
+            (window.pending && window.next_redraw().is_some_and(|next_redraw| next_redraw <= now))
+                .then_some(window_id)
+        })
+    }
+
+    /// The earliest time a deferred redraw can be dispatched.
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.windows.values().filter(|window| window.pending).filter_map(|w| w.next_redraw()).min()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn rapid_requests_collapse_to_the_cap() {
+        let window_id = WindowId::from_raw(1);
+        let mut throttle = RedrawThrottle::default();
+        throttle.set_max_rate(window_id, Some(10));
+
+        // Request a redraw every millisecond for a second, and dispatch like the event loop does.
+        let start = Instant::now();
+        let mut dispatched = 0;
+        for ms in 0..1000 {
+            let now = start + Duration::from_millis(ms);
+            let mut windows: HashSet<_> = throttle.due(now).collect();
+            windows.insert(window_id);
+            for window_id in windows {
+                if throttle.dispatch(window_id, now) {
+                    dispatched += 1;
+                }
+            }
+        }
+
+        assert_eq!(dispatched, 10);
+        assert_eq!(throttle.next_deadline(), Some(start + Duration::from_millis(1000)));
+    }
+
+    #[test]
+    fn unthrottled_windows_always_dispatch() {
+        let window_id = WindowId::from_raw(1);
+        let mut throttle = RedrawThrottle::default();
+        let now = Instant::now();
+
+        assert!(throttle.dispatch(window_id, now));
+        assert!(throttle.dispatch(window_id, now));
+
+        throttle.set_max_rate(window_id, Some(60));
+        assert!(throttle.dispatch(window_id, now));
+        assert!(!throttle.dispatch(window_id, now));
+
+        // Removing the cap hands the deferred redraw back.
+        assert!(throttle.set_max_rate(window_id, None));
+        assert!(throttle.dispatch(window_id, now));
+        assert_eq!(throttle.next_deadline(), None);
+    }
+}
@@ -0,0 +1,75 @@
+use winit_core::event::WaitCancelReason;
+
+/// The work pending when the event loop wakes up from a wait.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct WakeUpSources {
+    /// The X server sent events.
+    pub os_event: bool,
+    /// A raw key repeat synthesized for `set_device_key_repeat` is due.
+    pub key_repeat: bool,
+    /// `EventLoopProxy::wake_up` was called.
+    pub proxy_wake_up: bool,
+    /// A window requested a redraw, or a throttled redraw is due.
+    pub redraw: bool,
+}
+
+impl WakeUpSources {
+    /// Whether the event loop was only woken up to redraw.
+    pub fn only_redraws(self) -> bool {
+        self.redraw && !self.os_event && !self.key_repeat && !self.proxy_wake_up
+    }
+
+    /// What woke up the event loop, by order of precedence.
+    ///
+    /// Key repeats stand in for the repeats of the X server, so they count as its events.
+    pub fn reason(self) -> WaitCancelReason {
+        if self.os_event || self.key_repeat {
+            WaitCancelReason::OsEvent
+        } else if self.proxy_wake_up {
+            WaitCancelReason::UserWakeUp
+        } else if self.redraw {
+            WaitCancelReason::Redraw
+        } else {
+            WaitCancelReason::Unknown
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn proxy_wake_up_cancels_wait_with_user_reason() {
+        let sources = WakeUpSources { proxy_wake_up: true, ..Default::default() };
+        assert!(!sources.only_redraws());
+        assert_eq!(sources.reason(), WaitCancelReason::UserWakeUp);
+
+        // Events from the server take precedence.
+        let sources = WakeUpSources { os_event: true, ..sources };
+        assert_eq!(sources.reason(), WaitCancelReason::OsEvent);
+
+        assert_eq!(WakeUpSources::default().reason(), WaitCancelReason::Unknown);
+    }
+
+    #[test]
+    fn redraw_only_wake_up() {
+        let sources = WakeUpSources { redraw: true, ..Default::default() };
+        assert!(sources.only_redraws());
+        assert_eq!(sources.reason(), WaitCancelReason::Redraw);
+
+        let sources = WakeUpSources { proxy_wake_up: true, ..sources };
+        assert!(!sources.only_redraws());
+        assert_eq!(sources.reason(), WaitCancelReason::UserWakeUp);
+    }
+
+    #[test]
+    fn key_repeat_is_an_os_event() {
+        let sources = WakeUpSources { key_repeat: true, redraw: true, ..Default::default() };
+        assert!(!sources.only_redraws());
+        assert_eq!(sources.reason(), WaitCancelReason::OsEvent);
+
+        let sources = WakeUpSources { proxy_wake_up: true, ..sources };
+        assert_eq!(sources.reason(), WaitCancelReason::OsEvent);
+    }
+}
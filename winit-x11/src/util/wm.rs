@@ -12,6 +12,7 @@ pub const MOVERESIZE_BOTTOM: isize = 5;
 pub const MOVERESIZE_BOTTOMLEFT: isize = 6;
 pub const MOVERESIZE_LEFT: isize = 7;
 pub const MOVERESIZE_MOVE: isize = 8;
+pub const MOVERESIZE_SIZE_KEYBOARD: isize = 9;
 
 // This info is global to the window manager.
 static SUPPORTED_HINTS: Mutex<Vec<xproto::Atom>> = Mutex::new(Vec::new());
@@ -1,10 +1,11 @@
 use std::borrow::Cow;
 use std::ffi::CString;
-use std::mem::replace;
+use std::mem::{replace, take};
 use std::num::NonZeroU32;
 use std::ops::Deref;
 use std::os::raw::*;
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex, MutexGuard};
 use std::{cmp, env};
 
@@ -12,17 +13,18 @@ use dpi::{PhysicalInsets, PhysicalPosition, PhysicalSize, Position, Size};
 use tracing::{debug, info, warn};
 use winit_core::application::ApplicationHandler;
 use winit_core::cursor::Cursor;
-use winit_core::error::{NotSupportedError, RequestError};
+use winit_core::error::{CreateWindowError, NotSupportedError, RequestError};
 use winit_core::event::{SurfaceSizeWriter, WindowEvent};
 use winit_core::event_loop::AsyncRequestSerial;
 use winit_core::icon::RgbaIcon;
 use winit_core::monitor::{
-    Fullscreen, MonitorHandle as CoreMonitorHandle, MonitorHandleProvider, VideoMode,
+    Fullscreen, FullscreenRestore, MonitorHandle as CoreMonitorHandle, MonitorHandleProvider,
+    VideoMode, primary_or_current_monitor,
 };
 use winit_core::window::{
     CursorGrabMode, ImeCapabilities, ImeRequest as CoreImeRequest, ImeRequestError,
-    ResizeDirection, Theme, UserAttentionType, Window as CoreWindow, WindowAttributes,
-    WindowButtons, WindowId, WindowLevel,
+    ResizeDirection, TextDirection, Theme, UserAttentionType, Window as CoreWindow,
+    WindowAttributes, WindowButtons, WindowId, WindowLevel,
 };
 use x11rb::connection::{Connection, RequestConnection};
 use x11rb::properties::{WmHints, WmSizeHints, WmSizeHintsSpecification};
@@ -32,12 +34,13 @@ use x11rb::protocol::xproto::{self, ClipOrdering, ConnectionExt as _, Rectangle}
 use x11rb::protocol::{randr, xinput};
 
 use crate::atoms::{
-    _GTK_THEME_VARIANT, _NET_ACTIVE_WINDOW, _NET_WM_ICON, _NET_WM_MOVERESIZE, _NET_WM_NAME,
-    _NET_WM_PID, _NET_WM_PING, _NET_WM_STATE, _NET_WM_STATE_ABOVE, _NET_WM_STATE_BELOW,
-    _NET_WM_STATE_FULLSCREEN, _NET_WM_STATE_HIDDEN, _NET_WM_STATE_MAXIMIZED_HORZ,
-    _NET_WM_STATE_MAXIMIZED_VERT, _NET_WM_SYNC_REQUEST, _NET_WM_SYNC_REQUEST_COUNTER,
-    _NET_WM_WINDOW_TYPE, _XEMBED, AtomName, CARD32, UTF8_STRING, WM_CHANGE_STATE,
-    WM_CLIENT_MACHINE, WM_DELETE_WINDOW, WM_PROTOCOLS, WM_STATE, XdndAware,
+    _GTK_FRAME_EXTENTS, _GTK_THEME_VARIANT, _NET_ACTIVE_WINDOW, _NET_WM_ICON, _NET_WM_MOVERESIZE,
+    _NET_WM_NAME, _NET_WM_PID, _NET_WM_PING, _NET_WM_STATE, _NET_WM_STATE_ABOVE,
+    _NET_WM_STATE_BELOW, _NET_WM_STATE_FULLSCREEN, _NET_WM_STATE_HIDDEN,
+    _NET_WM_STATE_MAXIMIZED_HORZ, _NET_WM_STATE_MAXIMIZED_VERT, _NET_WM_SYNC_REQUEST,
+    _NET_WM_SYNC_REQUEST_COUNTER, _NET_WM_USER_TIME, _NET_WM_WINDOW_OPACITY, _NET_WM_WINDOW_TYPE,
+    _XEMBED, AtomName, CARD32, UTF8_STRING, WM_CHANGE_STATE, WM_CLIENT_MACHINE, WM_DELETE_WINDOW,
+    WM_PROTOCOLS, WM_STATE, XdndAware,
 };
 use crate::event_loop::{
     ALL_MASTER_DEVICES, ActivationItem, ActiveEventLoop, CookieResultExt, ICONIC_STATE, VoidCookie,
@@ -65,7 +68,7 @@ impl Window {
     pub(crate) fn new(
         event_loop: &ActiveEventLoop,
         attribs: WindowAttributes,
-    ) -> Result<Self, RequestError> {
+    ) -> Result<Self, CreateWindowError> {
         let window = Arc::new(UnownedWindow::new(event_loop, attribs)?);
         event_loop.windows.borrow_mut().insert(window.id(), Arc::downgrade(&window));
         Ok(Window(window))
@@ -89,6 +92,10 @@ impl CoreWindow for Window {
         self.0.pre_present_notify()
     }
 
+    fn set_max_redraw_rate(&self, rate: Option<u32>) {
+        self.0.set_max_redraw_rate(rate)
+    }
+
     fn reset_dead_keys(&self) {
         winit_common::xkb::reset_dead_keys();
     }
@@ -113,6 +120,10 @@ impl CoreWindow for Window {
         self.0.request_surface_size(size)
     }
 
+    fn set_suppress_resize_echo(&self, suppress: bool) {
+        self.0.set_suppress_resize_echo(suppress);
+    }
+
     fn outer_size(&self) -> PhysicalSize<u32> {
         self.0.outer_size()
     }
@@ -121,6 +132,10 @@ impl CoreWindow for Window {
         self.0.safe_area()
     }
 
+    fn set_surface_margins(&self, margins: Option<PhysicalInsets<u32>>) {
+        self.0.set_surface_margins(margins)
+    }
+
     fn set_min_surface_size(&self, min_size: Option<Size>) {
         self.0.set_min_surface_size(min_size)
     }
@@ -157,6 +172,10 @@ impl CoreWindow for Window {
         self.0.is_visible()
     }
 
+    fn realize(&self) {
+        self.0.realize();
+    }
+
     fn set_resizable(&self, resizable: bool) {
         self.0.set_resizable(resizable);
     }
@@ -189,6 +208,14 @@ impl CoreWindow for Window {
         self.0.is_maximized()
     }
 
+    fn set_maximized_axes(&self, horizontal: bool, vertical: bool) {
+        self.0.set_maximized_axes(horizontal, vertical)
+    }
+
+    fn maximized_axes(&self) -> (bool, bool) {
+        self.0.maximized_axes()
+    }
+
     fn set_fullscreen(&self, fullscreen: Option<Fullscreen>) {
         self.0.set_fullscreen(fullscreen)
     }
@@ -210,11 +237,12 @@ impl CoreWindow for Window {
     }
 
     fn set_window_icon(&self, window_icon: Option<winit_core::icon::Icon>) {
-        let icon = match window_icon.as_ref() {
-            Some(icon) => icon.cast_ref::<RgbaIcon>(),
-            None => None,
-        };
-        self.0.set_window_icon(icon)
+        let mut shared_state = self.0.shared_state_lock();
+        self.0.set_window_icon(shared_state.set_window_icon(window_icon));
+    }
+
+    fn window_icon(&self) -> Option<winit_core::icon::Icon> {
+        self.0.shared_state_lock().window_icon.clone()
     }
 
     fn request_ime_update(&self, action: CoreImeRequest) -> Result<(), ImeRequestError> {
@@ -249,6 +277,10 @@ impl CoreWindow for Window {
         self.0.set_content_protected(protected);
     }
 
+    fn is_content_protected(&self) -> bool {
+        false
+    }
+
     fn title(&self) -> String {
         self.0.title()
     }
@@ -269,6 +301,10 @@ impl CoreWindow for Window {
         self.0.set_cursor_visible(visible);
     }
 
+    fn set_receive_device_events(&self, receive: bool) {
+        self.0.set_receive_device_events(receive);
+    }
+
     fn drag_window(&self) -> Result<(), RequestError> {
         self.0.drag_window()
     }
@@ -277,6 +313,10 @@ impl CoreWindow for Window {
         self.0.drag_resize_window(direction)
     }
 
+    fn resize_by(&self, delta: PhysicalSize<i32>) -> Result<(), RequestError> {
+        self.0.resize_by(delta)
+    }
+
     fn show_window_menu(&self, position: Position) {
         self.0.show_window_menu(position);
     }
@@ -289,17 +329,24 @@ impl CoreWindow for Window {
         self.0.current_monitor().map(|monitor| CoreMonitorHandle(Arc::new(monitor)))
     }
 
+    // Same as the event loop, both are served by the monitor cache of the connection.
     fn available_monitors(&self) -> Box<dyn Iterator<Item = CoreMonitorHandle>> {
         Box::new(
             self.0
+                .xconn
                 .available_monitors()
                 .into_iter()
+                .flatten()
                 .map(|monitor| CoreMonitorHandle(Arc::new(monitor))),
         )
     }
 
     fn primary_monitor(&self) -> Option<CoreMonitorHandle> {
-        self.0.primary_monitor().map(|monitor| CoreMonitorHandle(Arc::new(monitor)))
+        let primary = self.0.xconn.primary_monitor().ok();
+        primary_or_current_monitor(
+            primary.map(|monitor| CoreMonitorHandle(Arc::new(monitor))),
+            || self.current_monitor(),
+        )
     }
 
     fn rwh_06_display_handle(&self) -> &dyn rwh_06::HasDisplayHandle {
@@ -351,15 +398,26 @@ pub struct SharedState {
     pub inner_position: Option<(i32, i32)>,
     pub inner_position_rel_parent: Option<(i32, i32)>,
     pub is_resizable: bool,
+    // The buttons requested with `set_enabled_buttons`, see `SharedState::enabled_buttons`.
+    pub requested_buttons: WindowButtons,
     pub is_decorated: bool,
     pub ime_capabilities: Option<ImeCapabilities>,
+    // The last text direction requested for the IME cursor area
+    pub ime_direction: TextDirection,
     pub last_monitor: X11MonitorHandle,
     pub dpi_adjusted: Option<(u32, u32)>,
     pub(crate) fullscreen: Option<Fullscreen>,
+    // The states last reported with `MaximizedChanged` and `MinimizedChanged`
+    pub reported_maximized: bool,
+    pub reported_minimized: bool,
     // Set when application calls `set_fullscreen` when window is not visible
     pub(crate) desired_fullscreen: Option<Option<Fullscreen>>,
-    // Used to restore position after exiting fullscreen
-    pub restore_position: Option<(i32, i32)>,
+    // Set when application calls `set_cursor_position` when window is not visible
+    pub desired_cursor_position: Option<(i32, i32)>,
+    // The last icon set, the server isn't asked for it
+    pub window_icon: Option<winit_core::icon::Icon>,
+    // Used to restore the position and size after exiting fullscreen
+    pub restore_geometry: FullscreenRestore<util::Geometry>,
     // Used to restore video mode after exiting fullscreen
     pub desktop_video_mode: Option<(randr::Crtc, randr::Mode)>,
     pub frame_extents: Option<util::FrameExtentsHeuristic>,
@@ -371,6 +429,18 @@ pub struct SharedState {
     pub has_focus: bool,
     // Use `Option` to not apply hittest logic when it was never requested.
     pub cursor_hittest: Option<bool>,
+    pub suppress_resize_echo: bool,
+    // Deliver `ScaleFactorChanged` with the first `ConfigureNotify`.
+    pub initial_scale_event: bool,
+    // The size requested by the last `request_surface_size` whose echo should be dropped.
+    pub resize_echo: Option<(u32, u32)>,
+    // Re-apply the cursor grab with the next resize, set when the fullscreen state changes.
+    pub regrab_pending: bool,
+    // Map the window on `realize`, set for visible windows whose realization was deferred.
+    pub realize_pending: bool,
+    pub drag_move: winit_core::window::DragMove,
+    // The cursor last set with `set_cursor`, identical requests are skipped.
+    pub active_cursor: util::ActiveCursor,
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -382,26 +452,105 @@ pub enum Visibility {
 }
 
 impl SharedState {
+    /// Remember `window_icon`, unless it isn't RGBA, which unsets the icon instead.
+    ///
+    /// Returns the icon to set on the window.
+    fn set_window_icon(
+        &mut self,
+        window_icon: Option<winit_core::icon::Icon>,
+    ) -> Option<&RgbaIcon> {
+        self.window_icon = window_icon.filter(|icon| icon.cast_ref::<RgbaIcon>().is_some());
+        self.window_icon.as_ref().and_then(|icon| icon.cast_ref::<RgbaIcon>())
+    }
+
+    /// Queue the cursor position if the window isn't mapped, there's nothing to warp within yet.
+    ///
+    /// Returns whether the position was queued.
+    fn queue_cursor_position(&mut self, position: (i32, i32)) -> bool {
+        if self.visibility == Visibility::Yes {
+            return false;
+        }
+
+        self.desired_cursor_position = Some(position);
+        true
+    }
+
+    /// The window was mapped, returns the fullscreen state and the cursor position requested
+    /// before.
+    fn mapped(&mut self) -> (Option<Option<Fullscreen>>, Option<(i32, i32)>) {
+        self.visibility = Visibility::Yes;
+        (self.desired_fullscreen.take(), self.desired_cursor_position.take())
+    }
+
+    /// The enabled buttons, a non-resizable window can't be maximized.
+    fn enabled_buttons(&self) -> WindowButtons {
+        // There's no help button on X11.
+        let mut buttons = self.requested_buttons - WindowButtons::HELP;
+        buttons.set(
+            WindowButtons::MAXIMIZE,
+            buttons.contains(WindowButtons::MAXIMIZE) && self.is_resizable,
+        );
+        buttons
+    }
+
+    /// The min and max size hints, a non-resizable window is pinned to its `surface_size`.
+    fn size_limits(&self, surface_size: Size) -> (Option<Size>, Option<Size>) {
+        if self.is_resizable {
+            (self.min_surface_size, self.max_surface_size)
+        } else {
+            (Some(surface_size), Some(surface_size))
+        }
+    }
+
+    /// Record the fullscreen the window is mapped in, bypassing `set_fullscreen_inner`.
+    ///
+    /// `set_fullscreen_inner` ignores requests for the current fullscreen state, so the windowed
+    /// geometry must be saved here for leaving fullscreen to restore it.
+    fn set_initial_fullscreen(
+        &mut self,
+        fullscreen: Fullscreen,
+        desktop_video_mode: (randr::Crtc, randr::Mode),
+        windowed: util::Geometry,
+    ) {
+        self.desktop_video_mode = Some(desktop_video_mode);
+        self.fullscreen = Some(fullscreen);
+        self.regrab_pending = true;
+        self.restore_geometry.save(windowed);
+    }
+
     fn new(last_monitor: X11MonitorHandle, window_attributes: &WindowAttributes) -> Mutex<Self> {
-        let visibility =
-            if window_attributes.visible { Visibility::YesWait } else { Visibility::No };
+        let visibility = if window_attributes.visible && !window_attributes.realize_deferred {
+            Visibility::YesWait
+        } else {
+            Visibility::No
+        };
 
         Mutex::new(SharedState {
             last_monitor,
             visibility,
 
             is_resizable: window_attributes.resizable,
+            initial_scale_event: window_attributes.initial_scale_event,
+            requested_buttons: window_attributes.enabled_buttons,
             is_decorated: window_attributes.decorations,
             cursor_pos: None,
             size: None,
             position: None,
             inner_position: None,
             ime_capabilities: None,
+            ime_direction: TextDirection::default(),
             inner_position_rel_parent: None,
             dpi_adjusted: None,
             fullscreen: None,
+            reported_maximized: false,
+            reported_minimized: false,
             desired_fullscreen: None,
-            restore_position: None,
+            desired_cursor_position: None,
+            window_icon: window_attributes
+                .window_icon
+                .clone()
+                .filter(|icon| icon.cast_ref::<RgbaIcon>().is_some()),
+            restore_geometry: FullscreenRestore::new(window_attributes.restore_on_unfullscreen),
             desktop_video_mode: None,
             frame_extents: None,
             min_surface_size: None,
@@ -410,6 +559,12 @@ impl SharedState {
             base_size: None,
             has_focus: false,
             cursor_hittest: None,
+            suppress_resize_echo: false,
+            resize_echo: None,
+            regrab_pending: false,
+            drag_move: Default::default(),
+            active_cursor: Default::default(),
+            realize_pending: window_attributes.visible && window_attributes.realize_deferred,
         })
     }
 }
@@ -423,18 +578,25 @@ pub struct UnownedWindow {
     xwindow: xproto::Window,            // never changes
     #[allow(dead_code)]
     visual: u32, // never changes
+    argb: bool,                         // never changes
     root: xproto::Window,               // never changes
     #[allow(dead_code)]
     screen_id: i32, // never changes
     sync_counter_id: Option<NonZeroU32>, // never changes
     selected_cursor: Mutex<SelectedCursor>,
-    cursor_grabbed_mode: Mutex<CursorGrabMode>,
+    cursor_grab: Mutex<util::CursorGrab>,
     #[allow(clippy::mutex_atomic)]
     cursor_visible: Mutex<bool>,
+    receive_device_events: AtomicBool,
+    background: Option<[u8; 4]>, // never changes
     ime_sender: Mutex<ImeSender>,
     pub shared_state: Mutex<SharedState>,
+    // Written to the window by the event loop, see `UnownedWindow::flush_title`.
+    title: Mutex<util::PendingTitle>,
     redraw_sender: WakeSender<WindowId>,
+    redraw_throttle: Arc<Mutex<util::RedrawThrottle>>,
     activation_sender: WakeSender<ActivationItem>,
+    title_sender: WakeSender<WindowId>,
 }
 macro_rules! leap {
     ($e:expr) => {
@@ -442,12 +604,22 @@ macro_rules! leap {
     };
 }
 
+/// The background pixel for a `[r, g, b, a]` color.
+///
+/// With `alpha`, the pixel is premultiplied ARGB as expected by compositors for 32-bit visuals,
+/// otherwise the color is opaque.
+fn background_pixel([r, g, b, a]: [u8; 4], alpha: bool) -> u32 {
+    let a = if alpha { a } else { u8::MAX };
+    let premultiply = |c: u8| (c as u32 * a as u32 + 127) / 255;
+    (a as u32) << 24 | premultiply(r) << 16 | premultiply(g) << 8 | premultiply(b)
+}
+
 impl UnownedWindow {
     #[allow(clippy::unnecessary_cast)]
     pub(crate) fn new(
         event_loop: &ActiveEventLoop,
         mut window_attrs: WindowAttributes,
-    ) -> Result<UnownedWindow, RequestError> {
+    ) -> Result<UnownedWindow, CreateWindowError> {
         let xconn = &event_loop.xconn;
         let atoms = xconn.atoms();
 
@@ -473,11 +645,23 @@ impl UnownedWindow {
         let root = match window_attrs.parent_window() {
             Some(rwh_06::RawWindowHandle::Xlib(handle)) => handle.window as xproto::Window,
             Some(rwh_06::RawWindowHandle::Xcb(handle)) => handle.window.get(),
-            Some(raw) => unreachable!("Invalid raw window handle {raw:?} on X11"),
+            Some(_) => return Err(CreateWindowError::UnsupportedParentWindow),
             None => screen.root,
         };
 
         let mut monitors = leap!(xconn.available_monitors());
+        if let Some(
+            fullscreen
+            @ (Fullscreen::Exclusive(monitor, _) | Fullscreen::Borderless(Some(monitor))),
+        ) = &window_attrs.fullscreen
+        {
+            let available = monitor
+                .cast_ref::<X11MonitorHandle>()
+                .is_some_and(|monitor| monitors.contains(monitor));
+            if !available {
+                return Err(CreateWindowError::MonitorUnavailable(fullscreen.clone()));
+            }
+        }
         let guessed_monitor = if monitors.is_empty() {
             X11MonitorHandle::dummy()
         } else {
@@ -582,6 +766,10 @@ impl UnownedWindow {
 
             aux = aux.event_mask(event_mask).border_pixel(0);
 
+            if let Some(background) = window_attrs.background {
+                aux = aux.background_pixel(background_pixel(background, depth == 32));
+            }
+
             if x11_attributes.override_redirect {
                 aux = aux.override_redirect(true as u32);
             }
@@ -650,16 +838,22 @@ impl UnownedWindow {
             xconn: Arc::clone(xconn),
             xwindow: xwindow as xproto::Window,
             visual,
+            argb: depth == 32,
             root,
             screen_id,
             sync_counter_id: None,
             selected_cursor: Default::default(),
-            cursor_grabbed_mode: Mutex::new(CursorGrabMode::None),
+            cursor_grab: Default::default(),
             cursor_visible: Mutex::new(true),
+            receive_device_events: AtomicBool::new(true),
+            background: window_attrs.background,
             ime_sender: Mutex::new(event_loop.ime_sender.clone()),
             shared_state: SharedState::new(guessed_monitor, &window_attrs),
+            title: Mutex::new(util::PendingTitle::new(&window_attrs.title)),
             redraw_sender: event_loop.redraw_sender.clone(),
+            redraw_throttle: event_loop.redraw_throttle.clone(),
             activation_sender: event_loop.activation_sender.clone(),
+            title_sender: event_loop.title_sender.clone(),
         };
 
         // Title must be set before mapping. Some tiling window managers (i.e. i3) use the window
@@ -667,6 +861,10 @@ impl UnownedWindow {
         // act on the wrong title state.
         leap!(window.set_title_inner(&window_attrs.title)).ignore_error();
         leap!(window.set_decorations_inner(window_attrs.decorations)).ignore_error();
+        let enabled_buttons = window.shared_state_lock().enabled_buttons();
+        if enabled_buttons != WindowButtons::default() {
+            leap!(window.set_enabled_buttons_inner(enabled_buttons)).ignore_error();
+        }
 
         if let Some(theme) = window_attrs.preferred_theme {
             leap!(window.set_theme_inner(Some(theme))).ignore_error();
@@ -733,18 +931,21 @@ impl UnownedWindow {
             let mut max_surface_size =
                 window_attrs.max_surface_size.map(|size| size.to_physical::<u32>(scale_factor));
 
+            // Keep the requested limits, so `set_resizable(true)` can restore them.
+            let shared_state = window.shared_state.get_mut().unwrap();
+            shared_state.min_surface_size = min_surface_size.map(Into::into);
+            shared_state.max_surface_size = max_surface_size.map(Into::into);
+
             if !window_attrs.resizable {
                 if util::wm_name_is_one_of(&["Xfwm4"]) {
                     warn!("To avoid a WM bug, disabling resizing has no effect on Xfwm4");
+                    shared_state.is_resizable = true;
                 } else {
                     max_surface_size = Some(dimensions.into());
                     min_surface_size = Some(dimensions.into());
                 }
             }
 
-            let shared_state = window.shared_state.get_mut().unwrap();
-            shared_state.min_surface_size = min_surface_size.map(Into::into);
-            shared_state.max_surface_size = max_surface_size.map(Into::into);
             shared_state.surface_resize_increments = window_attrs.surface_resize_increments;
             shared_state.base_size = x11_attributes.base_size;
 
@@ -836,16 +1037,40 @@ impl UnownedWindow {
             leap!(xconn.select_xinput_events(window.xwindow, ALL_MASTER_DEVICES, mask))
                 .ignore_error();
 
-            // Set visibility (map window)
-            if window_attrs.visible {
-                leap!(xconn.xcb_connection().map_window(window.xwindow)).ignore_error();
-                leap!(xconn.xcb_connection().configure_window(
-                    xwindow,
-                    &xproto::ConfigureWindowAux::new().stack_mode(xproto::StackMode::ABOVE)
+            // A user time of zero asks the WM not to focus the window when it's mapped.
+            if !window_attrs.active {
+                leap!(xconn.change_property(
+                    window.xwindow,
+                    atoms[_NET_WM_USER_TIME],
+                    xproto::Atom::from(xproto::AtomEnum::CARDINAL),
+                    xproto::PropMode::REPLACE,
+                    &[0u32],
                 ))
                 .ignore_error();
             }
 
+            // Set visibility (map window)
+            if window_attrs.visible && !window_attrs.realize_deferred {
+                // Switch the video mode before mapping, so the window doesn't show up windowed
+                // while the mode switch is in progress.
+                if let Some(fullscreen @ Fullscreen::Exclusive(..)) = &window_attrs.fullscreen {
+                    let windowed = util::Geometry {
+                        position: position.map_or((0, 0), Into::into),
+                        surface_size: dimensions,
+                    };
+                    leap!(window.set_initial_exclusive_fullscreen(fullscreen.clone(), windowed));
+                }
+
+                leap!(xconn.xcb_connection().map_window(window.xwindow)).ignore_error();
+                if window_attrs.active {
+                    leap!(xconn.xcb_connection().configure_window(
+                        xwindow,
+                        &xproto::ConfigureWindowAux::new().stack_mode(xproto::StackMode::ABOVE)
+                    ))
+                    .ignore_error();
+                }
+            }
+
             // Attempt to make keyboard input repeat detectable
             unsafe {
                 let mut supported_ptr = ffi::False;
@@ -881,7 +1106,9 @@ impl UnownedWindow {
                 if let Some(PhysicalPosition { x, y }) = position {
                     let shared_state = window.shared_state.get_mut().unwrap();
 
-                    shared_state.restore_position = Some((x, y));
+                    shared_state
+                        .restore_geometry
+                        .set(util::Geometry { position: (x, y), surface_size: dimensions });
                 }
             }
 
@@ -969,9 +1196,8 @@ impl UnownedWindow {
         let hint_atom = atoms[_GTK_THEME_VARIANT];
         let utf8_atom = atoms[UTF8_STRING];
         let variant = match theme {
-            Some(Theme::Dark) => "dark",
-            Some(Theme::Light) => "light",
-            None => "dark",
+            Some(theme) if !theme.is_dark() => "light",
+            _ => "dark",
         };
         let variant = CString::new(variant).expect("`_GTK_THEME_VARIANT` contained null byte");
         self.xconn.change_property(
@@ -1023,6 +1249,60 @@ impl UnownedWindow {
         flusher
     }
 
+    /// Enter exclusive fullscreen on a window that isn't mapped yet.
+    ///
+    /// Window managers ignore `_NET_WM_STATE` requests for unmapped windows, so the property is
+    /// set directly instead, and the window is mapped fullscreen in the requested video mode.
+    ///
+    /// `windowed` is the geometry the window was created with, restored when leaving fullscreen.
+    fn set_initial_exclusive_fullscreen(
+        &self,
+        fullscreen: Fullscreen,
+        windowed: util::Geometry,
+    ) -> Result<(), X11Error> {
+        let Fullscreen::Exclusive(monitor, video_mode) = &fullscreen else {
+            return Ok(());
+        };
+        let monitor = monitor.cast_ref::<X11MonitorHandle>().unwrap();
+        let Some(native_mode) = monitor
+            .video_modes
+            .iter()
+            .find_map(|mode| (mode.mode == *video_mode).then_some(mode.native_mode))
+        else {
+            // Leave it to `set_fullscreen` to report the invalid mode once the window is mapped.
+            return Ok(());
+        };
+        if monitor.is_dummy() {
+            return Ok(());
+        }
+
+        let crtc = monitor.native_id() as _;
+        let desktop_video_mode = self.xconn.get_crtc_mode(crtc)?;
+        self.xconn.set_crtc_config(crtc, native_mode)?;
+
+        let (x, y) = monitor.position;
+        self.set_position_inner(x, y)?.ignore_error();
+
+        let atoms = self.xconn.atoms();
+        self.xconn
+            .change_property(
+                self.xwindow,
+                atoms[_NET_WM_STATE],
+                xproto::AtomEnum::ATOM.into(),
+                xproto::PropMode::APPEND,
+                &[atoms[_NET_WM_STATE_FULLSCREEN]],
+            )?
+            .ignore_error();
+
+        self.shared_state_lock().set_initial_fullscreen(
+            fullscreen,
+            (crtc, desktop_video_mode),
+            windowed,
+        );
+
+        Ok(())
+    }
+
     fn set_fullscreen_inner(
         &self,
         fullscreen: Option<Fullscreen>,
@@ -1043,6 +1323,7 @@ impl UnownedWindow {
             return Ok(None);
         }
         shared_state_lock.fullscreen.clone_from(&fullscreen);
+        shared_state_lock.regrab_pending = true;
 
         match (&old_fullscreen, &fullscreen) {
             // Store the desktop video mode before entering exclusive
@@ -1073,11 +1354,13 @@ impl UnownedWindow {
         match fullscreen {
             None => {
                 let flusher = self.set_fullscreen_hint(false);
-                let mut shared_state_lock = self.shared_state_lock();
-                if let Some(position) = shared_state_lock.restore_position.take() {
-                    drop(shared_state_lock);
-                    self.set_position_inner(position.0, position.1)
+                let geometry = self.shared_state_lock().restore_geometry.take();
+                if let Some(geometry) = geometry {
+                    let (x, y) = geometry.position;
+                    self.set_position_inner(x, y)
                         .expect_then_ignore_error("Failed to restore window position");
+                    let (width, height) = geometry.surface_size;
+                    self.request_surface_size_physical(width, height);
                 }
                 flusher.map(Some)
             },
@@ -1137,8 +1420,12 @@ impl UnownedWindow {
                         .expect("failed to set video mode");
                 }
 
-                let window_position = self.outer_position_physical();
-                self.shared_state_lock().restore_position = Some(window_position);
+                // Query the geometry before locking the shared state, which the queries lock too.
+                let geometry = util::Geometry {
+                    position: self.outer_position_physical(),
+                    surface_size: self.surface_size_physical(),
+                };
+                self.shared_state_lock().restore_geometry.save(geometry);
                 let monitor_origin: (i32, i32) = monitor.position;
                 self.set_position_inner(monitor_origin.0, monitor_origin.1)
                     .expect_then_ignore_error("Failed to set window position");
@@ -1156,6 +1443,10 @@ impl UnownedWindow {
 
     #[inline]
     pub(crate) fn set_fullscreen(&self, fullscreen: Option<Fullscreen>) {
+        if !winit_core::monitor::validate_fullscreen(fullscreen.as_ref()) {
+            return;
+        }
+
         if let Some(flusher) =
             self.set_fullscreen_inner(fullscreen).expect("Failed to change window fullscreen state")
         {
@@ -1176,12 +1467,18 @@ impl UnownedWindow {
                 .expect_then_ignore_error("Failed to unmap window"),
             Visibility::Yes => (),
             Visibility::YesWait => {
-                shared_state.visibility = Visibility::Yes;
+                let (fullscreen, cursor_position) = shared_state.mapped();
+                drop(shared_state);
 
-                if let Some(fullscreen) = shared_state.desired_fullscreen.take() {
-                    drop(shared_state);
+                if let Some(fullscreen) = fullscreen {
                     self.set_fullscreen(fullscreen);
                 }
+
+                if let Some((x, y)) = cursor_position {
+                    if let Err(err) = self.set_cursor_position_physical(x, y) {
+                        warn!("Failed to apply the queued cursor position: {err}");
+                    }
+                }
             },
         }
     }
@@ -1190,12 +1487,18 @@ impl UnownedWindow {
         Some(self.shared_state_lock().last_monitor.clone())
     }
 
-    pub fn available_monitors(&self) -> Vec<X11MonitorHandle> {
-        self.xconn.available_monitors().expect("Failed to get available monitors")
-    }
+    /// Returns the maximized and minimized states that changed since they were last reported,
+    /// called when `_NET_WM_STATE` changes.
+    pub(crate) fn wm_state_changes(&self) -> (Option<bool>, Option<bool>) {
+        let maximized = self.is_maximized();
+        let minimized = self.is_minimized().unwrap_or(false);
 
-    pub fn primary_monitor(&self) -> Option<X11MonitorHandle> {
-        Some(self.xconn.primary_monitor().expect("Failed to get primary monitor"))
+        let mut shared_state = self.shared_state_lock();
+        let maximized_changed =
+            replace(&mut shared_state.reported_maximized, maximized) != maximized;
+        let minimized_changed =
+            replace(&mut shared_state.reported_minimized, minimized) != minimized;
+        (maximized_changed.then_some(maximized), minimized_changed.then_some(minimized))
     }
 
     #[inline]
@@ -1230,12 +1533,11 @@ impl UnownedWindow {
         let monitor = self.shared_state_lock().last_monitor.clone();
         if monitor.name == new_monitor.name {
             let (width, height) = self.surface_size_physical();
+            // If we couldn't determine the previous scale factor (e.g., because all monitors were
+            // closed before), just pick whatever the current monitor has set as a baseline.
+            let old_scale_factor = maybe_prev_scale_factor.unwrap_or(monitor.scale_factor);
             let (new_width, new_height) = self.adjust_for_dpi(
-                // If we couldn't determine the previous scale
-                // factor (e.g., because all monitors were closed
-                // before), just pick whatever the current monitor
-                // has set as a baseline.
-                maybe_prev_scale_factor.unwrap_or(monitor.scale_factor),
+                old_scale_factor,
                 new_monitor.scale_factor,
                 width,
                 height,
@@ -1246,6 +1548,7 @@ impl UnownedWindow {
             let surface_size = Arc::new(Mutex::new(PhysicalSize::new(new_width, new_height)));
             app.window_event(event_loop, self.id(), WindowEvent::ScaleFactorChanged {
                 scale_factor: new_monitor.scale_factor,
+                old_scale_factor,
                 surface_size_writer: SurfaceSizeWriter::new(Arc::downgrade(&surface_size)),
             });
 
@@ -1299,6 +1602,11 @@ impl UnownedWindow {
 
     #[inline]
     pub fn is_maximized(&self) -> bool {
+        let (horizontal, vertical) = self.maximized_axes();
+        horizontal && vertical
+    }
+
+    pub fn maximized_axes(&self) -> (bool, bool) {
         let atoms = self.xconn.atoms();
         let state_atom = atoms[_NET_WM_STATE];
         let state = self.xconn.get_property(
@@ -1309,26 +1617,41 @@ impl UnownedWindow {
         let horz_atom = atoms[_NET_WM_STATE_MAXIMIZED_HORZ];
         let vert_atom = atoms[_NET_WM_STATE_MAXIMIZED_VERT];
         match state {
-            Ok(atoms) => {
-                let horz_maximized = atoms.contains(&horz_atom);
-                let vert_maximized = atoms.contains(&vert_atom);
-                horz_maximized && vert_maximized
-            },
-            _ => false,
+            Ok(atoms) => (atoms.contains(&horz_atom), atoms.contains(&vert_atom)),
+            _ => (false, false),
         }
     }
 
     fn set_maximized_inner(&self, maximized: bool) -> Result<VoidCookie<'_>, X11Error> {
+        self.set_maximized_axes_inner(maximized, maximized)
+    }
+
+    fn set_maximized_axes_inner(
+        &self,
+        horizontal: bool,
+        vertical: bool,
+    ) -> Result<VoidCookie<'_>, X11Error> {
         let atoms = self.xconn.atoms();
         let horz_atom = atoms[_NET_WM_STATE_MAXIMIZED_HORZ];
         let vert_atom = atoms[_NET_WM_STATE_MAXIMIZED_VERT];
 
-        self.set_netwm(maximized.into(), (horz_atom, vert_atom, 0, 0))
+        // Change both states in a single request when possible, so the window manager doesn't
+        // resize the window twice.
+        if horizontal == vertical {
+            return self.set_netwm(horizontal.into(), (horz_atom, vert_atom, 0, 0));
+        }
+
+        self.set_netwm(horizontal.into(), (horz_atom, 0, 0, 0))?.ignore_error();
+        self.set_netwm(vertical.into(), (vert_atom, 0, 0, 0))
     }
 
     #[inline]
     pub fn set_maximized(&self, maximized: bool) {
-        self.set_maximized_inner(maximized)
+        self.set_maximized_axes(maximized, maximized);
+    }
+
+    pub fn set_maximized_axes(&self, horizontal: bool, vertical: bool) {
+        self.set_maximized_axes_inner(horizontal, vertical)
             .expect_then_ignore_error("Failed to change window maximization");
         self.xconn.flush_requests().expect("Failed to change window maximization");
         self.invalidate_cached_frame_extents();
@@ -1356,15 +1679,84 @@ impl UnownedWindow {
         )
     }
 
+    /// Stash the title, the event loop writes the last one set once per iteration.
     #[inline]
     pub fn set_title(&self, title: &str) {
-        self.set_title_inner(title).expect_then_ignore_error("Failed to set window title");
+        if self.title.lock().unwrap().set(title) {
+            self.title_sender.send(self.id());
+        }
+    }
+
+    /// Write the title set since the last flush, if any.
+    pub(crate) fn flush_title(&self) {
+        let mut title = self.title.lock().unwrap();
+        let Some(title) = title.take() else {
+            return;
+        };
 
+        self.set_title_inner(title).expect_then_ignore_error("Failed to set window title");
         self.xconn.flush_requests().expect("Failed to set window title");
     }
 
     #[inline]
-    pub fn set_transparent(&self, _transparent: bool) {}
+    pub fn set_transparent(&self, transparent: bool) {
+        // The visual can't be changed after the window was created, so without an alpha channel
+        // the window can only be opaque.
+        if !self.argb {
+            if !transparent {
+                return;
+            }
+
+            warn!(
+                "`set_transparent` has no effect, because the window was created without an ARGB \
+                 visual; use `WindowAttributes::with_transparent` to request one"
+            );
+            return;
+        }
+
+        self.set_transparent_inner(transparent)
+            .expect_then_ignore_error("Failed to set window transparency");
+        self.xconn.flush_requests().expect("Failed to set window transparency");
+    }
+
+    fn set_transparent_inner(&self, transparent: bool) -> Result<VoidCookie<'_>, X11Error> {
+        let atoms = self.xconn.atoms();
+        let opacity_atom = atoms[_NET_WM_WINDOW_OPACITY];
+
+        // Fill the exposed areas with either fully transparent or opaque black, or the requested
+        // background, so the server doesn't leave garbage in the alpha channel behind.
+        let background_pixel = match self.background {
+            Some(background) => background_pixel(background, transparent),
+            None if transparent => 0x0000_0000,
+            None => 0xff00_0000,
+        };
+        self.xconn
+            .xcb_connection()
+            .change_window_attributes(
+                self.xwindow,
+                &xproto::ChangeWindowAttributesAux::new().background_pixel(background_pixel),
+            )?
+            .ignore_error();
+
+        // Let the compositor know whether it has to blend the window, a full opacity makes it
+        // ignore the alpha channel on compositors honoring the hint.
+        if transparent {
+            self.xconn.xcb_connection().delete_property(self.xwindow, opacity_atom)?.ignore_error();
+        } else {
+            self.xconn
+                .change_property(
+                    self.xwindow,
+                    opacity_atom,
+                    u32::from(xproto::AtomEnum::CARDINAL),
+                    xproto::PropMode::REPLACE,
+                    &[u32::MAX],
+                )?
+                .ignore_error();
+        }
+
+        // Repaint the background and let the application redraw with the new hint.
+        self.xconn.xcb_connection().clear_area(true, self.xwindow, 0, 0, 0, 0).map_err(Into::into)
+    }
 
     #[inline]
     pub fn set_blur(&self, _blur: bool) {}
@@ -1391,10 +1783,15 @@ impl UnownedWindow {
         self.shared_state_lock().is_decorated
     }
 
-    fn set_maximizable_inner(&self, maximizable: bool) -> Result<VoidCookie<'_>, X11Error> {
+    fn set_enabled_buttons_inner(
+        &self,
+        buttons: WindowButtons,
+    ) -> Result<VoidCookie<'_>, X11Error> {
         let mut hints = self.xconn.get_motif_hints(self.xwindow);
 
-        hints.set_maximizable(maximizable);
+        hints.set_minimizable(buttons.contains(WindowButtons::MINIMIZE));
+        hints.set_maximizable(buttons.contains(WindowButtons::MAXIMIZE));
+        hints.set_closable(buttons.contains(WindowButtons::CLOSE));
 
         self.xconn.set_motif_hints(self.xwindow, &hints)
     }
@@ -1457,6 +1854,7 @@ impl UnownedWindow {
     #[inline]
     pub fn set_visible(&self, visible: bool) {
         let mut shared_state = self.shared_state_lock();
+        shared_state.realize_pending = false;
 
         match (visible, shared_state.visibility) {
             (true, Visibility::Yes) | (true, Visibility::YesWait) | (false, Visibility::No) => {
@@ -1489,6 +1887,14 @@ impl UnownedWindow {
         }
     }
 
+    #[inline]
+    pub fn realize(&self) {
+        let realize_pending = take(&mut self.shared_state_lock().realize_pending);
+        if realize_pending {
+            self.set_visible(true);
+        }
+    }
+
     #[inline]
     pub fn is_visible(&self) -> Option<bool> {
         Some(self.shared_state_lock().visibility == Visibility::Yes)
@@ -1610,6 +2016,35 @@ impl UnownedWindow {
         PhysicalInsets::new(0, 0, 0, 0)
     }
 
+    fn set_surface_margins_inner(
+        &self,
+        margins: Option<PhysicalInsets<u32>>,
+    ) -> Result<VoidCookie<'_>, X11Error> {
+        let frame_extents_atom = self.xconn.atoms()[_GTK_FRAME_EXTENTS];
+        match margins {
+            Some(margins) => self.xconn.change_property(
+                self.xwindow,
+                frame_extents_atom,
+                u32::from(xproto::AtomEnum::CARDINAL),
+                xproto::PropMode::REPLACE,
+                &[margins.left, margins.right, margins.top, margins.bottom],
+            ),
+            None => self
+                .xconn
+                .xcb_connection()
+                .delete_property(self.xwindow, frame_extents_atom)
+                .map_err(Into::into),
+        }
+    }
+
+    pub fn set_surface_margins(&self, margins: Option<PhysicalInsets<u32>>) {
+        self.set_surface_margins_inner(margins)
+            .expect_then_ignore_error("Failed to set `_GTK_FRAME_EXTENTS`");
+        self.xconn.flush_requests().expect("Failed to set `_GTK_FRAME_EXTENTS`");
+        // The window manager may update `_NET_FRAME_EXTENTS` accordingly.
+        self.invalidate_cached_frame_extents();
+    }
+
     pub(crate) fn request_surface_size_physical(&self, width: u32, height: u32) {
         self.xconn
             .xcb_connection()
@@ -1627,9 +2062,19 @@ impl UnownedWindow {
 
     #[inline]
     pub fn request_surface_size(&self, size: Size) -> Option<PhysicalSize<u32>> {
+        let size = winit_core::window::clamp_surface_size(size);
         let scale_factor = self.scale_factor();
-        let size = size.to_physical::<u32>(scale_factor).into();
-        if !self.shared_state_lock().is_resizable {
+        let size: (i32, i32) = size.to_physical::<u32>(scale_factor).into();
+        let mut shared_state_lock = self.shared_state_lock();
+        let physical_size = (size.0 as u32, size.1 as u32);
+        // No echo is sent when the size doesn't change, and the echo of a previous request is
+        // superseded by this one.
+        let expect_echo =
+            shared_state_lock.suppress_resize_echo && shared_state_lock.size != Some(physical_size);
+        shared_state_lock.resize_echo = expect_echo.then_some(physical_size);
+        let is_resizable = shared_state_lock.is_resizable;
+        drop(shared_state_lock);
+        if !is_resizable {
             self.update_normal_hints(|normal_hints| {
                 normal_hints.min_size = Some(size);
                 normal_hints.max_size = Some(size);
@@ -1641,6 +2086,49 @@ impl UnownedWindow {
         None
     }
 
+    #[inline]
+    pub fn resize_by(&self, delta: PhysicalSize<i32>) -> Result<(), RequestError> {
+        let scale_factor = self.scale_factor();
+        let to_physical =
+            |size: Option<Size>| size.map(|size| size.to_physical::<u32>(scale_factor));
+        let (min_size, max_size, increments) = {
+            let shared_state_lock = self.shared_state_lock();
+            (
+                to_physical(shared_state_lock.min_surface_size),
+                to_physical(shared_state_lock.max_surface_size),
+                to_physical(shared_state_lock.surface_resize_increments),
+            )
+        };
+
+        let size = self.surface_size();
+        let width = winit_core::window::resize_dimension(
+            size.width,
+            delta.width,
+            min_size.map(|size| size.width),
+            max_size.map(|size| size.width),
+            increments.map(|size| size.width),
+        );
+        let height = winit_core::window::resize_dimension(
+            size.height,
+            delta.height,
+            min_size.map(|size| size.height),
+            max_size.map(|size| size.height),
+            increments.map(|size| size.height),
+        );
+
+        let _ = self.request_surface_size(PhysicalSize::new(width, height).into());
+        Ok(())
+    }
+
+    #[inline]
+    pub fn set_suppress_resize_echo(&self, suppress: bool) {
+        let mut shared_state_lock = self.shared_state_lock();
+        shared_state_lock.suppress_resize_echo = suppress;
+        if !suppress {
+            shared_state_lock.resize_echo = None;
+        }
+    }
+
     fn update_normal_hints<F>(&self, callback: F) -> Result<(), X11Error>
     where
         F: FnOnce(&mut WmSizeHints),
@@ -1673,7 +2161,14 @@ impl UnownedWindow {
 
     #[inline]
     pub fn set_min_surface_size(&self, dimensions: Option<Size>) {
-        self.shared_state_lock().min_surface_size = dimensions;
+        let mut shared_state_lock = self.shared_state_lock();
+        shared_state_lock.min_surface_size = dimensions;
+        // The hints of a non-resizable window are pinned to its size, the limit is applied when
+        // it's made resizable again.
+        if !shared_state_lock.is_resizable {
+            return;
+        }
+        drop(shared_state_lock);
         let physical_dimensions =
             dimensions.map(|dimensions| dimensions.to_physical::<u32>(self.scale_factor()).into());
         self.set_min_surface_size_physical(physical_dimensions);
@@ -1689,7 +2184,14 @@ impl UnownedWindow {
 
     #[inline]
     pub fn set_max_surface_size(&self, dimensions: Option<Size>) {
-        self.shared_state_lock().max_surface_size = dimensions;
+        let mut shared_state_lock = self.shared_state_lock();
+        shared_state_lock.max_surface_size = dimensions;
+        // The hints of a non-resizable window are pinned to its size, the limit is applied when
+        // it's made resizable again.
+        if !shared_state_lock.is_resizable {
+            return;
+        }
+        drop(shared_state_lock);
         let physical_dimensions =
             dimensions.map(|dimensions| dimensions.to_physical::<u32>(self.scale_factor()).into());
         self.set_max_surface_size_physical(physical_dimensions);
@@ -1753,22 +2255,22 @@ impl UnownedWindow {
             // Making the window unresizable on Xfwm prevents further changes to `WM_NORMAL_HINTS`
             // from being detected. This makes it impossible for resizing to be
             // re-enabled, and also breaks DPI scaling. As such, we choose the lesser of
-            // two evils and do nothing.
+            // two evils and do nothing, the window stays resizable.
             warn!("To avoid a WM bug, disabling resizing has no effect on Xfwm4");
             return;
         }
 
-        let (min_size, max_size) = if resizable {
-            let shared_state_lock = self.shared_state_lock();
-            (shared_state_lock.min_surface_size, shared_state_lock.max_surface_size)
-        } else {
-            let window_size = Some(Size::from(self.surface_size()));
-            (window_size, window_size)
+        let enabled_buttons = {
+            let mut shared_state_lock = self.shared_state_lock();
+            shared_state_lock.is_resizable = resizable;
+            shared_state_lock.enabled_buttons()
         };
-        self.shared_state_lock().is_resizable = resizable;
 
-        self.set_maximizable_inner(resizable)
-            .expect_then_ignore_error("Failed to call `XSetWMNormalHints`");
+        self.set_enabled_buttons_inner(enabled_buttons)
+            .expect_then_ignore_error("Failed to set enabled buttons");
+
+        let surface_size = Size::from(self.surface_size());
+        let (min_size, max_size) = self.shared_state_lock().size_limits(surface_size);
 
         let scale_factor = self.scale_factor();
         let min_surface_size = min_size.map(|size| cast_size_to_hint(size, scale_factor));
@@ -1778,6 +2280,7 @@ impl UnownedWindow {
             normal_hints.max_size = max_surface_size;
         })
         .expect("Failed to call `XSetWMNormalHints`");
+        self.xconn.flush_requests().expect("Failed to call `XSetWMNormalHints`");
     }
 
     #[inline]
@@ -1786,11 +2289,21 @@ impl UnownedWindow {
     }
 
     #[inline]
-    pub fn set_enabled_buttons(&self, _buttons: WindowButtons) {}
+    pub fn set_enabled_buttons(&self, buttons: WindowButtons) {
+        let enabled_buttons = {
+            let mut shared_state_lock = self.shared_state_lock();
+            shared_state_lock.requested_buttons = buttons;
+            shared_state_lock.enabled_buttons()
+        };
+
+        self.set_enabled_buttons_inner(enabled_buttons)
+            .expect_then_ignore_error("Failed to set enabled buttons");
+        self.xconn.flush_requests().expect("Failed to set enabled buttons");
+    }
 
     #[inline]
     pub fn enabled_buttons(&self) -> WindowButtons {
-        WindowButtons::all()
+        self.shared_state_lock().enabled_buttons()
     }
 
     #[allow(dead_code)]
@@ -1807,8 +2320,20 @@ impl UnownedWindow {
 
     #[inline]
     pub fn set_cursor(&self, cursor: Cursor) {
+        if !self.shared_state_lock().active_cursor.is_changed(&cursor) {
+            return;
+        }
+
+        // Use the first cursor in the fallback chain that can be shown.
+        let applied = cursor.candidates().any(|candidate| self.apply_cursor(candidate));
+        self.shared_state_lock().active_cursor.set(applied.then_some(cursor));
+    }
+
+    /// Applies a single cursor, returning whether it could be shown.
+    fn apply_cursor(&self, cursor: &Cursor) -> bool {
         match cursor {
             Cursor::Icon(icon) => {
+                let icon = *icon;
                 let old_cursor = replace(
                     &mut *self.selected_cursor.lock().unwrap(),
                     SelectedCursor::Named(icon),
@@ -1819,15 +2344,19 @@ impl UnownedWindow {
                 {
                     if let Err(err) = self.xconn.set_cursor_icon(self.xwindow, Some(icon)) {
                         tracing::error!("failed to set cursor icon: {err}");
+                        *self.selected_cursor.lock().unwrap() = old_cursor;
+                        return false;
                     }
                 }
+
+                true
             },
             Cursor::Custom(cursor) => {
                 let cursor = match cursor.cast_ref::<CustomCursor>() {
                     Some(cursor) => cursor,
                     None => {
                         tracing::error!("unrecognized cursor passed to X11 backend");
-                        return;
+                        return false;
                     },
                 };
 
@@ -1835,11 +2364,14 @@ impl UnownedWindow {
                 if *self.cursor_visible.lock().unwrap() {
                     if let Err(err) = self.xconn.set_custom_cursor(self.xwindow, cursor) {
                         tracing::error!("failed to set window icon: {err}");
+                        return false;
                     }
                 }
 
                 *self.selected_cursor.lock().unwrap() = SelectedCursor::Custom(cursor.clone());
+                true
             },
+            Cursor::Fallback(_) => unreachable!("fallback chains are flattened"),
         }
     }
 
@@ -1850,8 +2382,9 @@ impl UnownedWindow {
             return Err(NotSupportedError::new("locked cursor is not implemented on X11").into());
         }
 
-        let mut grabbed_lock = self.cursor_grabbed_mode.lock().unwrap();
-        if mode == *grabbed_lock {
+        // Track the request even if grabbing fails, so it's retried on the next transition.
+        let mut grab_lock = self.cursor_grab.lock().unwrap();
+        if !grab_lock.request(mode) {
             return Ok(());
         }
 
@@ -1861,7 +2394,7 @@ impl UnownedWindow {
             .xcb_connection()
             .ungrab_pointer(x11rb::CURRENT_TIME)
             .expect_then_ignore_error("Failed to call `xcb_ungrab_pointer`");
-        *grabbed_lock = CursorGrabMode::None;
+        grab_lock.set_grabbed(CursorGrabMode::None);
 
         let result = match mode {
             CursorGrabMode::None => self
@@ -1919,12 +2452,36 @@ impl UnownedWindow {
         };
 
         if result.is_ok() {
-            *grabbed_lock = mode;
+            grab_lock.set_grabbed(mode);
         }
 
         result
     }
 
+    /// Grab the cursor again with the last requested mode.
+    ///
+    /// The server releases the grab when the window becomes unviewable, which some window managers
+    /// do around fullscreen transitions, and the window manager may take it over on focus changes.
+    ///
+    /// Returns the new grab mode if the grab was restored.
+    pub(crate) fn reapply_cursor_grab(&self) -> Option<CursorGrabMode> {
+        let (mode, old_mode) = self.cursor_grab.lock().unwrap().regrab()?;
+        match self.set_cursor_grab(mode) {
+            Ok(()) => (old_mode != mode).then_some(mode),
+            Err(err) => {
+                debug!("Failed to re-apply the cursor grab: {err}");
+                None
+            },
+        }
+    }
+
+    /// Forget the grab released by the server when the window was unmapped.
+    ///
+    /// Returns `true` if the window had grabbed the cursor.
+    pub(crate) fn cursor_grab_released(&self) -> bool {
+        self.cursor_grab.lock().unwrap().release()
+    }
+
     #[inline]
     pub fn set_cursor_visible(&self, visible: bool) {
         #[allow(clippy::mutex_atomic)]
@@ -1951,12 +2508,26 @@ impl UnownedWindow {
         }
     }
 
+    #[inline]
+    pub fn set_receive_device_events(&self, receive: bool) {
+        self.receive_device_events.store(receive, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub(crate) fn receives_device_events(&self) -> bool {
+        self.receive_device_events.load(Ordering::Relaxed)
+    }
+
     #[inline]
     pub fn scale_factor(&self) -> f64 {
         self.shared_state_lock().last_monitor.scale_factor
     }
 
     pub fn set_cursor_position_physical(&self, x: i32, y: i32) -> Result<(), RequestError> {
+        if self.shared_state_lock().queue_cursor_position((x, y)) {
+            return Ok(());
+        }
+
         self.xconn
             .xcb_connection()
             .warp_pointer(x11rb::NONE, self.xwindow, 0, 0, 0, 0, x as _, y as _)
@@ -2010,33 +2581,50 @@ impl UnownedWindow {
 
     /// Moves the window while it is being dragged.
     pub fn drag_window(&self) -> Result<(), RequestError> {
-        self.drag_initiate(util::MOVERESIZE_MOVE)
+        self.drag_initiate(util::MOVERESIZE_MOVE, None)
     }
 
     #[inline]
     pub fn show_window_menu(&self, _position: Position) {}
 
-    /// Resizes the window while it is being dragged.
+    /// Resizes the window while it is being dragged, or with the keyboard if the left mouse
+    /// button isn't pressed.
     pub fn drag_resize_window(&self, direction: ResizeDirection) -> Result<(), RequestError> {
-        self.drag_initiate(match direction {
-            ResizeDirection::East => util::MOVERESIZE_RIGHT,
-            ResizeDirection::North => util::MOVERESIZE_TOP,
-            ResizeDirection::NorthEast => util::MOVERESIZE_TOPRIGHT,
-            ResizeDirection::NorthWest => util::MOVERESIZE_TOPLEFT,
-            ResizeDirection::South => util::MOVERESIZE_BOTTOM,
-            ResizeDirection::SouthEast => util::MOVERESIZE_BOTTOMRIGHT,
-            ResizeDirection::SouthWest => util::MOVERESIZE_BOTTOMLEFT,
-            ResizeDirection::West => util::MOVERESIZE_LEFT,
-        })
+        self.drag_initiate(
+            match direction {
+                ResizeDirection::East => util::MOVERESIZE_RIGHT,
+                ResizeDirection::North => util::MOVERESIZE_TOP,
+                ResizeDirection::NorthEast => util::MOVERESIZE_TOPRIGHT,
+                ResizeDirection::NorthWest => util::MOVERESIZE_TOPLEFT,
+                ResizeDirection::South => util::MOVERESIZE_BOTTOM,
+                ResizeDirection::SouthEast => util::MOVERESIZE_BOTTOMRIGHT,
+                ResizeDirection::SouthWest => util::MOVERESIZE_BOTTOMLEFT,
+                ResizeDirection::West => util::MOVERESIZE_LEFT,
+            },
+            Some(util::MOVERESIZE_SIZE_KEYBOARD),
+        )
     }
 
     /// Initiates a drag operation while the left mouse button is pressed.
-    fn drag_initiate(&self, action: isize) -> Result<(), RequestError> {
+    ///
+    /// If the button isn't pressed, `keyboard_action` is initiated instead when provided.
+    fn drag_initiate(
+        &self,
+        action: isize,
+        keyboard_action: Option<isize>,
+    ) -> Result<(), RequestError> {
         let pointer = self
             .xconn
             .query_pointer(self.xwindow, util::VIRTUAL_CORE_POINTER)
             .map_err(|err| os_error!(err))?;
 
+        // The button mask has a bit for each button, starting with the (unused) button 0.
+        let left_button_pressed = pointer.buttons.first().is_some_and(|mask| mask & (1 << 1) != 0);
+        let (action, button) = match keyboard_action {
+            Some(keyboard_action) if !left_button_pressed => (keyboard_action, 0),
+            _ => (action, 1),
+        };
+
         let window_position = self.inner_position_physical();
 
         let atoms = self.xconn.atoms();
@@ -2044,14 +2632,14 @@ impl UnownedWindow {
 
         // we can't use `set_cursor_grab(false)` here because it doesn't run `XUngrabPointer`
         // if the cursor isn't currently grabbed
-        let mut grabbed_lock = self.cursor_grabbed_mode.lock().unwrap();
+        let mut grab_lock = self.cursor_grab.lock().unwrap();
         self.xconn
             .xcb_connection()
             .ungrab_pointer(x11rb::CURRENT_TIME)
             .map_err(|err| os_error!(X11Error::from(err)))?
             .ignore_error();
         self.xconn.flush_requests().map_err(|err| os_error!(X11Error::Xlib(err)))?;
-        *grabbed_lock = CursorGrabMode::None;
+        grab_lock.set_grabbed(CursorGrabMode::None);
 
         // we keep the lock until we are done
         self.xconn
@@ -2067,7 +2655,7 @@ impl UnownedWindow {
                     (window_position.0 + xinput_fp1616_to_float(pointer.win_x) as i32) as u32,
                     (window_position.1 + xinput_fp1616_to_float(pointer.win_y) as i32) as u32,
                     action.try_into().unwrap(),
-                    1, // Button 1
+                    button,
                     1,
                 ],
             )
@@ -2075,11 +2663,29 @@ impl UnownedWindow {
 
         self.xconn.flush_requests().map_err(|err| os_error!(X11Error::Xlib(err)))?;
 
+        // The window manager only moves the window while the button is held.
+        if action == util::MOVERESIZE_MOVE && left_button_pressed {
+            self.shared_state_lock().drag_move.request();
+        }
+
         Ok(())
     }
 
+    /// Marks the move requested by `drag_window` as started once the window manager grabbed the
+    /// pointer, returning whether a move was requested.
+    pub(crate) fn drag_move_started(&self) -> bool {
+        self.shared_state_lock().drag_move.pointer_grabbed()
+    }
+
+    /// Marks the move started by `drag_window` as done, returning whether a move was active.
+    ///
+    /// A `released` button also drops a requested move the window manager never started.
+    pub(crate) fn drag_move_ended(&self, released: bool) -> bool {
+        self.shared_state_lock().drag_move.pointer_input(released)
+    }
+
     #[inline]
-    pub fn set_ime_cursor_area(&self, spot: Position, size: Size) {
+    pub fn set_ime_cursor_area(&self, spot: Position, size: Size, direction: TextDirection) {
         let PhysicalPosition { x, y } = spot.to_physical::<i16>(self.scale_factor());
         let PhysicalSize { width, height } = size.to_physical::<u16>(self.scale_factor());
         let _ = self.ime_sender.lock().unwrap().send(ImeRequest::Area(
@@ -2088,6 +2694,7 @@ impl UnownedWindow {
             y,
             width,
             height,
+            direction == TextDirection::Rtl,
         ));
     }
 
@@ -2102,6 +2709,7 @@ impl UnownedWindow {
 
     #[inline]
     pub fn request_ime_update(&self, request: CoreImeRequest) -> Result<(), ImeRequestError> {
+        let request = request.to_physical(self.scale_factor());
         let mut shared_state = self.shared_state_lock();
         let (capabilities, state) = match request {
             CoreImeRequest::Enable(enable) => {
@@ -2111,7 +2719,11 @@ impl UnownedWindow {
                     return Err(ImeRequestError::AlreadyEnabled);
                 }
 
+                // XIM can only position the candidate box.
+                let capabilities =
+                    capabilities.intersection(ImeCapabilities::new().with_cursor_area());
                 shared_state.ime_capabilities = Some(capabilities);
+                shared_state.ime_direction = TextDirection::default();
                 drop(shared_state);
                 self.set_ime_allowed(true);
                 (capabilities, request_data)
@@ -2133,9 +2745,18 @@ impl UnownedWindow {
             },
         };
 
+        // The direction is kept until it's changed, like the other IME state.
+        let mut shared_state = self.shared_state_lock();
+        if let Some(direction) = state.direction {
+            shared_state.ime_direction = direction;
+        }
+        let direction = shared_state.ime_direction;
+        drop(shared_state);
+
+        let state = state.clamp_cursor_area(self.surface_size(), self.scale_factor());
         if let Some((position, size)) = state.cursor_area {
             if capabilities.cursor_area() {
-                self.set_ime_cursor_area(position, size);
+                self.set_ime_cursor_area(position, size, direction);
             } else {
                 warn!("discarding IME cursor area update without capability enabled.");
             }
@@ -2246,6 +2867,14 @@ impl UnownedWindow {
         self.redraw_sender.send(WindowId::from_raw(self.xwindow as _));
     }
 
+    pub fn set_max_redraw_rate(&self, rate: Option<u32>) {
+        let window_id = WindowId::from_raw(self.xwindow as _);
+        if self.redraw_throttle.lock().unwrap().set_max_rate(window_id, rate) {
+            // Don't lose the redraw deferred by the previous cap.
+            self.request_redraw();
+        }
+    }
+
     #[inline]
     pub fn pre_present_notify(&self) {
         // TODO timer
@@ -2285,7 +2914,7 @@ impl UnownedWindow {
     }
 
     pub fn title(&self) -> String {
-        String::new()
+        self.title.lock().unwrap().get().to_owned()
     }
 }
 
@@ -2307,3 +2936,143 @@ fn cast_size_to_hint(size: Size, scale_factor: f64) -> (i32, i32) {
         Size::Logical(size) => size.to_physical::<i32>(scale_factor).into(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn initial_fullscreen_restores_the_created_geometry() {
+        let shared_state =
+            SharedState::new(X11MonitorHandle::dummy(), &WindowAttributes::default());
+        let mut shared_state = shared_state.into_inner().unwrap();
+
+        let windowed = util::Geometry { position: (100, 50), surface_size: (800, 600) };
+        let monitor = CoreMonitorHandle(Arc::new(X11MonitorHandle::dummy()));
+        let video_mode = VideoMode::new(PhysicalSize::new(1024, 768), None, None);
+        let fullscreen = Fullscreen::Exclusive(monitor, video_mode);
+        shared_state.set_initial_fullscreen(fullscreen.clone(), (1, 2), windowed);
+
+        assert_eq!(shared_state.fullscreen, Some(fullscreen));
+        assert_eq!(shared_state.desktop_video_mode, Some((1, 2)));
+        assert_eq!(shared_state.restore_geometry.take(), Some(windowed));
+    }
+
+    #[test]
+    fn initial_window_icon_is_kept_only_when_set() {
+        #[derive(Debug)]
+        struct NativeIcon;
+        impl winit_core::icon::IconProvider for NativeIcon {}
+
+        let icon = winit_core::icon::Icon::from_rgba(vec![0xff; 4 * 4 * 4], 4, 4).unwrap();
+        let attributes = WindowAttributes::default().with_window_icon(Some(icon.clone()));
+        let shared_state = SharedState::new(X11MonitorHandle::dummy(), &attributes);
+        let window_icon = shared_state.into_inner().unwrap().window_icon.unwrap();
+        assert!(Arc::ptr_eq(&window_icon.0, &icon.0));
+
+        // X11 only supports RGBA icons, others are never set.
+        let icon = winit_core::icon::Icon(Arc::new(NativeIcon));
+        let attributes = WindowAttributes::default().with_window_icon(Some(icon));
+        let shared_state = SharedState::new(X11MonitorHandle::dummy(), &attributes);
+        assert!(shared_state.into_inner().unwrap().window_icon.is_none());
+    }
+
+    #[test]
+    fn window_icon_is_the_last_one_set() {
+        #[derive(Debug)]
+        struct NativeIcon;
+        impl winit_core::icon::IconProvider for NativeIcon {}
+
+        let shared_state =
+            SharedState::new(X11MonitorHandle::dummy(), &WindowAttributes::default());
+        let mut shared_state = shared_state.into_inner().unwrap();
+
+        let icon = winit_core::icon::Icon::from_rgba(vec![0xff; 4 * 4 * 4], 4, 4).unwrap();
+        assert!(shared_state.set_window_icon(Some(icon.clone())).is_some());
+        assert!(Arc::ptr_eq(&shared_state.window_icon.as_ref().unwrap().0, &icon.0));
+
+        // An icon that can't be set unsets the previous one.
+        let native = winit_core::icon::Icon(Arc::new(NativeIcon));
+        assert!(shared_state.set_window_icon(Some(native)).is_none());
+        assert!(shared_state.window_icon.is_none());
+
+        shared_state.set_window_icon(Some(icon));
+        assert!(shared_state.set_window_icon(None).is_none());
+        assert!(shared_state.window_icon.is_none());
+    }
+
+    #[test]
+    fn size_limits_are_kept_while_not_resizable() {
+        let attributes = WindowAttributes::default().with_resizable(false);
+        let shared_state = SharedState::new(X11MonitorHandle::dummy(), &attributes);
+        let mut shared_state = shared_state.into_inner().unwrap();
+        let surface_size = Size::from(PhysicalSize::new(800, 600));
+        let min = Some(Size::from(PhysicalSize::new(50, 50)));
+        let max = Some(Size::from(PhysicalSize::new(1000, 1000)));
+
+        shared_state.min_surface_size = min;
+        shared_state.max_surface_size = max;
+        assert_eq!(
+            shared_state.size_limits(surface_size),
+            (Some(surface_size), Some(surface_size))
+        );
+
+        shared_state.is_resizable = true;
+        assert_eq!(shared_state.size_limits(surface_size), (min, max));
+    }
+
+    #[test]
+    fn non_resizable_window_has_no_maximize_button() {
+        let shared_state =
+            SharedState::new(X11MonitorHandle::dummy(), &WindowAttributes::default());
+        let mut shared_state = shared_state.into_inner().unwrap();
+        assert_eq!(shared_state.enabled_buttons(), WindowButtons::default());
+
+        shared_state.is_resizable = false;
+        assert_eq!(
+            shared_state.enabled_buttons(),
+            WindowButtons::default() - WindowButtons::MAXIMIZE
+        );
+        shared_state.is_resizable = true;
+        assert_eq!(shared_state.enabled_buttons(), WindowButtons::default());
+
+        // Making the window resizable doesn't bring back a maximize button that wasn't requested.
+        shared_state.requested_buttons = WindowButtons::CLOSE;
+        shared_state.is_resizable = false;
+        assert_eq!(shared_state.enabled_buttons(), WindowButtons::CLOSE);
+        shared_state.is_resizable = true;
+        assert_eq!(shared_state.enabled_buttons(), WindowButtons::CLOSE);
+    }
+
+    #[test]
+    fn deferred_window_is_mapped_on_realize() {
+        let initial_state = |visible, realize_deferred| {
+            let attributes = WindowAttributes::default()
+                .with_visible(visible)
+                .with_realize_deferred(realize_deferred);
+            let shared_state = SharedState::new(X11MonitorHandle::dummy(), &attributes);
+            let shared_state = shared_state.into_inner().unwrap();
+            (shared_state.visibility, shared_state.realize_pending)
+        };
+
+        assert_eq!(initial_state(true, false), (Visibility::YesWait, false));
+        assert_eq!(initial_state(true, true), (Visibility::No, true));
+        // A hidden window stays hidden when it's realized.
+        assert_eq!(initial_state(false, true), (Visibility::No, false));
+    }
+
+    #[test]
+    fn cursor_position_is_applied_on_map() {
+        let attributes = WindowAttributes::default().with_visible(false);
+        let shared_state = SharedState::new(X11MonitorHandle::dummy(), &attributes);
+        let mut shared_state = shared_state.into_inner().unwrap();
+
+        assert!(shared_state.queue_cursor_position((10, 10)));
+        assert!(shared_state.queue_cursor_position((20, 20)));
+        assert_eq!(shared_state.mapped(), (None, Some((20, 20))));
+
+        // The position is warped right away once the window is mapped.
+        assert!(!shared_state.queue_cursor_position((30, 30)));
+        assert_eq!(shared_state.mapped(), (None, None));
+    }
+}
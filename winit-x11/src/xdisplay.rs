@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 use std::error::Error;
 use std::ffi::c_int;
-use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 use std::sync::{Arc, Mutex, RwLock, RwLockReadGuard};
 use std::{fmt, ptr};
 
@@ -49,6 +49,9 @@ pub struct XConnection {
     /// List of monitor handles.
     pub monitor_handles: Mutex<Option<Vec<MonitorHandle>>>,
 
+    /// Whether the monitor list is cached in `monitor_handles`.
+    pub(crate) monitor_list_caching: AtomicBool,
+
     /// The resource database.
     database: RwLock<resource_manager::Database>,
 
@@ -154,6 +157,7 @@ impl XConnection {
             timestamp: AtomicU32::new(0),
             latest_error: Mutex::new(None),
             monitor_handles: Mutex::new(None),
+            monitor_list_caching: AtomicBool::new(true),
             database: RwLock::new(database),
             cursor_cache: Default::default(),
             randr_version: (randr_version.major_version, randr_version.minor_version),
@@ -6,7 +6,10 @@
 
 use std::iter;
 use std::num::NonZeroUsize;
+use std::time::Duration;
 
+use dpi::PhysicalSize;
+use winit_core::event_loop::PointerConfig;
 use x11rb::protocol::xproto::{self, ConnectionExt};
 
 use super::atoms::*;
@@ -17,6 +20,9 @@ type Result<T> = core::result::Result<T, ParserError>;
 
 const DPI_NAME: &[u8] = b"Xft/DPI";
 const DPI_MULTIPLIER: f64 = 1024.0;
+const DOUBLE_CLICK_TIME_NAME: &[u8] = b"Net/DoubleClickTime";
+const DOUBLE_CLICK_DISTANCE_NAME: &[u8] = b"Net/DoubleClickDistance";
+const DRAG_THRESHOLD_NAME: &[u8] = b"Net/DndDragThreshold";
 const LITTLE_ENDIAN: u8 = b'l';
 const BIG_ENDIAN: u8 = b'B';
 
@@ -26,14 +32,7 @@ impl XConnection {
         &self,
         xsettings_screen: xproto::Atom,
     ) -> core::result::Result<Option<f64>, X11Error> {
-        let atoms = self.atoms();
-
-        // Get the current owner of the screen's settings.
-        let owner = self.xcb_connection().get_selection_owner(xsettings_screen)?.reply()?;
-
-        // Read the _XSETTINGS_SETTINGS property.
-        let data: Vec<u8> =
-            self.get_property(owner.owner, atoms[_XSETTINGS_SETTINGS], atoms[_XSETTINGS_SETTINGS])?;
+        let data = self.xsettings_data(xsettings_screen)?;
 
         // Parse the property.
         let dpi_setting = read_settings(&data)?
@@ -55,6 +54,57 @@ impl XConnection {
             Ok(None)
         }
     }
+
+    /// Get the pointer configuration from XSettings.
+    ///
+    /// Settings which are not present keep their default value.
+    pub(crate) fn xsettings_pointer_config(
+        &self,
+        xsettings_screen: xproto::Atom,
+    ) -> core::result::Result<PointerConfig, X11Error> {
+        let data = self.xsettings_data(xsettings_screen)?;
+        Ok(read_pointer_config(&data)?)
+    }
+
+    /// Read the raw `_XSETTINGS_SETTINGS` property of the screen's settings owner.
+    fn xsettings_data(
+        &self,
+        xsettings_screen: xproto::Atom,
+    ) -> core::result::Result<Vec<u8>, X11Error> {
+        let atoms = self.atoms();
+
+        // Get the current owner of the screen's settings.
+        let owner = self.xcb_connection().get_selection_owner(xsettings_screen)?.reply()?;
+
+        // Read the _XSETTINGS_SETTINGS property.
+        self.get_property(owner.owner, atoms[_XSETTINGS_SETTINGS], atoms[_XSETTINGS_SETTINGS])
+            .map_err(Into::into)
+    }
+}
+
+/// Read the pointer configuration from the block of data.
+fn read_pointer_config(data: &[u8]) -> Result<PointerConfig> {
+    let mut config = PointerConfig::default();
+    for setting in read_settings(data)? {
+        let setting = setting?;
+        let value = match setting.data {
+            SettingData::Integer(value) => value.max(0) as u32,
+            _ => continue,
+        };
+
+        match setting.name {
+            DOUBLE_CLICK_TIME_NAME => {
+                config.double_click_interval = Duration::from_millis(value as u64)
+            },
+            DOUBLE_CLICK_DISTANCE_NAME => {
+                config.double_click_distance = PhysicalSize::new(value, value)
+            },
+            DRAG_THRESHOLD_NAME => config.drag_threshold = PhysicalSize::new(value, value),
+            _ => (),
+        }
+    }
+
+    Ok(config)
 }
 
 /// Read over the settings in the block of data.
@@ -310,6 +360,23 @@ mod tests {
         assert_string(&lcd.data, "lcddefault");
     }
 
+    #[test]
+    fn parse_pointer_config() {
+        let data = XSETTINGS
+            .trim()
+            .split(',')
+            .map(|tok| {
+                let val = tok.strip_prefix("0x").unwrap();
+                u8::from_str_radix(val, 16).unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        let config = read_pointer_config(&data).unwrap();
+        assert_eq!(config.double_click_interval, Duration::from_millis(400));
+        assert_eq!(config.double_click_distance, PhysicalSize::new(5, 5));
+        assert_eq!(config.drag_threshold, PhysicalSize::new(8, 8));
+    }
+
     fn assert_string(dat: &SettingData<'_>, s: &str) {
         match dat {
             SettingData::String(left) => assert_eq!(*left, s.as_bytes()),
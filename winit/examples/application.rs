@@ -270,6 +270,16 @@ impl Application {
                 let actual_theme = theme.or_else(|| window.window.theme()).unwrap_or(Theme::Dark);
                 window.set_draw_theme(actual_theme);
             },
+            Action::ToggleThemeRapidly => {
+                // Flip the theme several times in a row, the decorations must end up matching the
+                // last theme set.
+                let mut theme = window.window.theme().unwrap_or(Theme::Dark);
+                for _ in 0..9 {
+                    theme = if theme.is_dark() { Theme::Light } else { Theme::Dark };
+                    window.window.set_theme(Some(theme));
+                }
+                window.set_draw_theme(theme);
+            },
             #[cfg(macos_platform)]
             Action::CreateNewTab => {
                 let tab_id = window.window.tabbing_identifier();
@@ -417,15 +427,17 @@ impl ApplicationHandler for Application {
             WindowEvent::SurfaceResized(size) => {
                 window.resize(size);
             },
-            WindowEvent::Focused(focused) => {
+            WindowEvent::Focused { focused, via } => {
                 if focused {
-                    info!("Window={window_id:?} focused");
+                    info!("Window={window_id:?} focused via {via:?}");
                 } else {
-                    info!("Window={window_id:?} unfocused");
+                    info!("Window={window_id:?} unfocused via {via:?}");
                 }
             },
-            WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
-                info!("Window={window_id:?} changed scale to {scale_factor}");
+            WindowEvent::ScaleFactorChanged { scale_factor, old_scale_factor, .. } => {
+                info!(
+                    "Window={window_id:?} changed scale from {old_scale_factor} to {scale_factor}"
+                );
             },
             WindowEvent::ThemeChanged(theme) => {
                 info!("Theme changed to {theme:?}");
@@ -935,15 +947,17 @@ impl WindowState {
                         && y <= (surface_size.height - insets.bottom)
                     {
                         // In safe area
-                        buffer[index] = match self.theme {
-                            Theme::Light => 0xffe8e8e8, // Light gray
-                            Theme::Dark => 0xff525252,  // Medium gray
+                        buffer[index] = if self.theme.is_dark() {
+                            0xff525252 // Medium gray
+                        } else {
+                            0xffe8e8e8 // Light gray
                         };
                     } else {
                         // Outside safe area
-                        buffer[index] = match self.theme {
-                            Theme::Light => 0xffffffff, // White
-                            Theme::Dark => 0xff181818,  // Dark gray
+                        buffer[index] = if self.theme.is_dark() {
+                            0xff181818 // Dark gray
+                        } else {
+                            0xffffffff // White
                         };
                     }
                 }
@@ -1003,6 +1017,7 @@ enum Action {
     #[cfg(macos_platform)]
     CycleOptionAsAlt,
     SetTheme(Option<Theme>),
+    ToggleThemeRapidly,
     #[cfg(macos_platform)]
     CreateNewTab,
     RequestResize,
@@ -1045,6 +1060,13 @@ impl Action {
             Action::SetTheme(None) => "Change to the system theme",
             Action::SetTheme(Some(Theme::Light)) => "Change to a light theme",
             Action::SetTheme(Some(Theme::Dark)) => "Change to a dark theme",
+            Action::SetTheme(Some(Theme::HighContrastLight)) => {
+                "Change to a high contrast light theme"
+            },
+            Action::SetTheme(Some(Theme::HighContrastDark)) => {
+                "Change to a high contrast dark theme"
+            },
+            Action::ToggleThemeRapidly => "Toggle the theme several times in a row",
             #[cfg(macos_platform)]
             Action::CreateNewTab => "Create new tab",
             Action::RequestResize => "Request a resize",
@@ -1273,6 +1295,7 @@ const KEY_BINDINGS: &[Binding<&'static str>] = &[
     Binding::new("K", ModifiersState::empty(), Action::SetTheme(None)),
     Binding::new("K", ModifiersState::META, Action::SetTheme(Some(Theme::Light))),
     Binding::new("K", ModifiersState::CONTROL, Action::SetTheme(Some(Theme::Dark))),
+    Binding::new("K", ModifiersState::ALT, Action::ToggleThemeRapidly),
     #[cfg(macos_platform)]
     Binding::new("T", ModifiersState::META, Action::CreateNewTab),
     #[cfg(macos_platform)]
@@ -61,7 +61,9 @@ impl ApplicationHandler for ControlFlowDemo {
         info!("new_events: {cause:?}");
 
         self.wait_cancelled = match cause {
-            StartCause::WaitCancelled { .. } => self.mode == Mode::WaitUntil,
+            StartCause::WaitCancelled { .. } | StartCause::RedrawRequested { .. } => {
+                self.mode == Mode::WaitUntil
+            },
             _ => false,
         }
     }
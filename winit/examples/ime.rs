@@ -217,9 +217,11 @@ impl App {
     fn handle_ime_event(&mut self, event: Ime) {
         let surface = self.surface.as_ref().expect("IME request without a window");
         match event {
-            Ime::Enabled => info!("IME enabled for Window={:?}", surface.window().id()),
+            Ime::Enabled(capabilities) => {
+                info!("IME enabled for Window={:?} with {capabilities:?}", surface.window().id())
+            },
             Ime::Preedit(text, caret_pos) => info!("Preedit: {text}, with caret at {caret_pos:?}"),
-            Ime::Commit(text) => {
+            Ime::Commit(text, _) => {
                 self.input_state.append_text(&text);
                 let request_data = self.get_ime_update();
                 surface.window().request_ime_update(ImeRequest::Update(request_data)).unwrap();
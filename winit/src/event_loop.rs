@@ -256,6 +256,13 @@ impl EventLoop {
         self.event_loop.window_target().owned_display_handle()
     }
 
+    /// Returns the [`Backend`] the event loop is running on.
+    ///
+    /// See [`ActiveEventLoop::backend`] for details.
+    pub fn backend(&self) -> Option<Backend> {
+        self.event_loop.window_target().backend()
+    }
+
     /// Change if or when [`DeviceEvent`]s are captured.
     ///
     /// See [`ActiveEventLoop::listen_device_events`] for details.
@@ -195,6 +195,8 @@
 //! * `rwh_06`: Implement `raw-window-handle v0.6` traits.
 //! * `serde`: Enables serialization/deserialization of certain types with [Serde](https://crates.io/crates/serde).
 //! * `mint`: Enables mint (math interoperability standard types) conversions.
+//! * `image`: Enables `Icon::from_png` and `Icon::from_ico` to create window icons from PNG and ICO
+//!   images.
 //! * `private-apple-apis`: Enables private APIs whose usage might cause rejections from the App
 //!   Store. Currently enables the use of `CGSSetWindowBackgroundBlurRadius`, commonly used for
 //!   terminal emulators.
@@ -22,6 +22,7 @@
 //! [`here`]: https://specifications.freedesktop.org/startup-notification-spec/startup-notification-latest.txt
 
 use std::env;
+use std::time::Duration;
 
 use crate::error::{NotSupportedError, RequestError};
 use crate::event_loop::{ActiveEventLoop, AsyncRequestSerial};
@@ -40,6 +41,25 @@ pub trait EventLoopExtStartupNotify {
     ///
     /// It's recommended **to unset** this environment variable for child processes.
     fn read_token_from_env(&self) -> Option<ActivationToken>;
+
+    /// Request a new activation token for the window, giving up after `timeout`.
+    ///
+    /// Like [`WindowExtStartupNotify::request_activation_token`], but when the system doesn't
+    /// deliver the token within `timeout`, [`WindowEvent::ActivationTokenDone`] is sent to the
+    /// window with an empty token instead, so the application doesn't wait forever on a
+    /// misbehaving compositor. An empty token is harmless to pass on, it just won't transfer the
+    /// focus.
+    ///
+    /// ## Platform-specific
+    ///
+    /// - **X11:** The token is generated by winit, so the timeout never elapses.
+    ///
+    /// [`WindowEvent::ActivationTokenDone`]: crate::event::WindowEvent::ActivationTokenDone
+    fn request_activation_token_with_timeout(
+        &self,
+        window: &dyn Window,
+        timeout: Duration,
+    ) -> Result<AsyncRequestSerial, RequestError>;
 }
 
 pub trait WindowExtStartupNotify {
@@ -70,6 +90,28 @@ impl EventLoopExtStartupNotify for dyn ActiveEventLoop + '_ {
             env::var(X11_VAR).ok().map(ActivationToken::from_raw)
         }
     }
+
+    fn request_activation_token_with_timeout(
+        &self,
+        window: &dyn Window,
+        timeout: Duration,
+    ) -> Result<AsyncRequestSerial, RequestError> {
+        #[cfg(wayland_platform)]
+        if let (Some(event_loop), Some(window)) = (
+            self.cast_ref::<crate::platform_impl::wayland::ActiveEventLoop>(),
+            window.cast_ref::<crate::platform_impl::wayland::Window>(),
+        ) {
+            return event_loop.request_activation_token_with_timeout(window, timeout);
+        }
+
+        #[cfg(x11_platform)]
+        if let Some(window) = window.cast_ref::<crate::platform_impl::x11::Window>() {
+            return window.request_activation_token();
+        }
+
+        let _ = (window, timeout);
+        Err(NotSupportedError::new("startup notify is not supported").into())
+    }
 }
 
 impl WindowExtStartupNotify for dyn Window + '_ {
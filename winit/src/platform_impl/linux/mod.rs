@@ -94,6 +94,14 @@ impl EventLoop {
                 .is_some(),
             env::var("DISPLAY").map(|var| !var.is_empty()).unwrap_or(false),
         ) {
+            // User is forcing X11, but there's no display to connect to.
+            #[cfg(x11_platform)]
+            (Some(Backend::X), _, false) => {
+                return Err(NotSupportedError::new(
+                    "the X11 backend was requested, but DISPLAY is not set",
+                )
+                .into());
+            },
             // User is forcing a backend.
             (Some(backend), ..) => backend,
             // Wayland is present.
@@ -0,0 +1,41 @@
+#![cfg(windows_platform)]
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use winit::application::ApplicationHandler;
+use winit::event::WindowEvent;
+use winit::event_loop::{ActiveEventLoop, EventLoop};
+use winit::platform::windows::EventLoopBuilderExtWindows;
+use winit::window::{WindowAttributes, WindowId};
+
+struct App {
+    checked: Rc<Cell<bool>>,
+}
+
+impl ApplicationHandler for App {
+    fn can_create_surfaces(&mut self, event_loop: &dyn ActiveEventLoop) {
+        let attributes = WindowAttributes::default().with_visible(false);
+        let window = event_loop.create_window(attributes).expect("failed to create window");
+        assert!(!window.is_content_protected());
+
+        window.set_content_protected(true);
+        assert!(window.is_content_protected());
+        window.set_content_protected(false);
+        assert!(!window.is_content_protected());
+
+        self.checked.set(true);
+        event_loop.exit();
+    }
+
+    fn window_event(&mut self, _: &dyn ActiveEventLoop, _: WindowId, _: WindowEvent) {}
+}
+
+#[test]
+fn is_content_protected_follows_set_content_protected() {
+    let event_loop = EventLoop::builder().with_any_thread(true).build().unwrap();
+
+    let checked = Rc::new(Cell::new(false));
+    event_loop.run_app(App { checked: checked.clone() }).unwrap();
+    assert!(checked.get());
+}
@@ -0,0 +1,60 @@
+#![cfg(any(x11_platform, wayland_platform, windows_platform))]
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use winit::application::ApplicationHandler;
+use winit::event::{DeviceEvent, DeviceId, StartCause, WindowEvent};
+use winit::event_loop::{ActiveEventLoop, EventLoop};
+use winit::window::{Window, WindowAttributes, WindowId};
+
+/// Records the callbacks it receives, exiting once the window got an event.
+struct Recorder {
+    calls: Rc<RefCell<Vec<&'static str>>>,
+    window: Option<Box<dyn Window>>,
+}
+
+impl ApplicationHandler for Recorder {
+    fn new_events(&mut self, _: &dyn ActiveEventLoop, cause: StartCause) {
+        let call = if cause == StartCause::Init { "init" } else { "new_events" };
+        self.calls.borrow_mut().push(call);
+    }
+
+    fn can_create_surfaces(&mut self, event_loop: &dyn ActiveEventLoop) {
+        self.calls.borrow_mut().push("can_create_surfaces");
+        let window = event_loop.create_window(WindowAttributes::default()).unwrap();
+        window.request_redraw();
+        self.window = Some(window);
+    }
+
+    fn window_event(&mut self, event_loop: &dyn ActiveEventLoop, _: WindowId, _: WindowEvent) {
+        self.calls.borrow_mut().push("window_event");
+        event_loop.exit();
+    }
+
+    fn device_event(&mut self, _: &dyn ActiveEventLoop, _: Option<DeviceId>, _: DeviceEvent) {
+        self.calls.borrow_mut().push("device_event");
+    }
+}
+
+#[test]
+#[cfg_attr(any(x11_platform, wayland_platform), ignore = "needs an X11 or Wayland display server")]
+fn init_is_the_first_callback() {
+    let mut builder = EventLoop::builder();
+    #[cfg(x11_platform)]
+    winit::platform::x11::EventLoopBuilderExtX11::with_any_thread(&mut builder, true);
+    #[cfg(all(wayland_platform, not(x11_platform)))]
+    winit::platform::wayland::EventLoopBuilderExtWayland::with_any_thread(&mut builder, true);
+    #[cfg(windows_platform)]
+    winit::platform::windows::EventLoopBuilderExtWindows::with_any_thread(&mut builder, true);
+
+    let event_loop = builder.build().expect("failed to connect to the display server");
+
+    let calls = Rc::new(RefCell::new(Vec::new()));
+    event_loop.run_app(Recorder { calls: calls.clone(), window: None }).unwrap();
+
+    let calls = calls.borrow();
+    assert_eq!(calls[..2], ["init", "can_create_surfaces"], "in {calls:?}");
+    assert_eq!(calls.iter().filter(|&&call| call == "init").count(), 1, "in {calls:?}");
+    assert!(calls.contains(&"window_event"), "in {calls:?}");
+}
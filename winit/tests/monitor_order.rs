@@ -0,0 +1,48 @@
+#![cfg(any(x11_platform, wayland_platform))]
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use winit::application::ApplicationHandler;
+use winit::event::WindowEvent;
+use winit::event_loop::{ActiveEventLoop, EventLoop};
+use winit::monitor::MonitorHandle;
+use winit::window::{WindowAttributes, WindowId};
+
+struct App {
+    checked: Rc<Cell<bool>>,
+}
+
+impl ApplicationHandler for App {
+    fn can_create_surfaces(&mut self, event_loop: &dyn ActiveEventLoop) {
+        let window = event_loop
+            .create_window(WindowAttributes::default().with_visible(false))
+            .expect("failed to create window");
+
+        let from_loop: Vec<MonitorHandle> = event_loop.available_monitors().collect();
+        let from_window: Vec<MonitorHandle> = window.available_monitors().collect();
+        assert_eq!(from_loop, from_window);
+        assert_eq!(event_loop.primary_monitor(), window.primary_monitor());
+
+        self.checked.set(true);
+        event_loop.exit();
+    }
+
+    fn window_event(&mut self, _: &dyn ActiveEventLoop, _: WindowId, _: WindowEvent) {}
+}
+
+#[test]
+#[ignore = "needs an X11 or Wayland display server"]
+fn window_and_event_loop_list_monitors_in_the_same_order() {
+    let mut builder = EventLoop::builder();
+    #[cfg(x11_platform)]
+    winit::platform::x11::EventLoopBuilderExtX11::with_any_thread(&mut builder, true);
+    #[cfg(all(wayland_platform, not(x11_platform)))]
+    winit::platform::wayland::EventLoopBuilderExtWayland::with_any_thread(&mut builder, true);
+
+    let event_loop = builder.build().expect("failed to connect to the display server");
+
+    let checked = Rc::new(Cell::new(false));
+    event_loop.run_app(App { checked: checked.clone() }).unwrap();
+    assert!(checked.get());
+}
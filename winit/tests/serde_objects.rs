@@ -3,8 +3,9 @@
 use serde::{Deserialize, Serialize};
 use winit::cursor::CursorIcon;
 use winit::dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize};
-use winit::event::{ElementState, MouseButton, MouseScrollDelta, TouchPhase};
+use winit::event::{ElementState, Ime, KeyEvent, MouseButton, MouseScrollDelta, TouchPhase};
 use winit::keyboard::{Key, KeyCode, KeyLocation, ModifiersState, NamedKey, PhysicalKey};
+use winit::window::TextDirection;
 
 #[allow(dead_code)]
 fn needs_serde<S: Serialize + Deserialize<'static>>() {}
@@ -12,6 +13,7 @@ fn needs_serde<S: Serialize + Deserialize<'static>>() {}
 #[test]
 fn window_serde() {
     needs_serde::<CursorIcon>();
+    needs_serde::<TextDirection>();
 }
 
 #[test]
@@ -20,6 +22,8 @@ fn events_serde() {
     needs_serde::<ElementState>();
     needs_serde::<MouseButton>();
     needs_serde::<MouseScrollDelta>();
+    needs_serde::<Ime>();
+    needs_serde::<KeyEvent>();
     needs_serde::<Key>();
     needs_serde::<NamedKey>();
     needs_serde::<KeyCode>();
@@ -36,3 +40,36 @@ fn dpi_serde() {
     needs_serde::<LogicalSize<f64>>();
     needs_serde::<PhysicalSize<u32>>();
 }
+
+#[test]
+fn key_event_round_trip() {
+    let event = KeyEvent {
+        physical_key: PhysicalKey::Code(KeyCode::KeyE),
+        logical_key: Key::Character("é".into()),
+        text: Some("é".into()),
+        location: KeyLocation::Standard,
+        state: ElementState::Pressed,
+        repeat: false,
+        text_with_all_modifiers: Some("é".into()),
+        key_without_modifiers: Key::Character("e".into()),
+        composed_length: Some(1),
+    };
+
+    let json = serde_json::to_string(&event).unwrap();
+    assert_eq!(serde_json::from_str::<KeyEvent>(&json).unwrap(), event);
+
+    let event = KeyEvent {
+        physical_key: PhysicalKey::Code(KeyCode::Enter),
+        logical_key: Key::Named(NamedKey::Enter),
+        text: None,
+        location: KeyLocation::Numpad,
+        state: ElementState::Released,
+        repeat: true,
+        text_with_all_modifiers: None,
+        key_without_modifiers: Key::Named(NamedKey::Enter),
+        composed_length: None,
+    };
+
+    let json = serde_json::to_string(&event).unwrap();
+    assert_eq!(serde_json::from_str::<KeyEvent>(&json).unwrap(), event);
+}